@@ -14,8 +14,8 @@
 //! Make sure to set up your .env file with AMP_USERNAME and AMP_PASSWORD
 
 use amp_rs::signer::LwkSoftwareSigner;
-use amp_rs::ApiClient;
 use amp_rs::ElementsRpc;
+use amp_rs::{Amount, ApiClient};
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
     execute,
@@ -1679,8 +1679,11 @@ async fn run_distribution_flow_with_channel(
     }
 
     // 7. DRY RUN: Distribution (commented out to prevent UI freeze)
-    // Convert smallest_units back to base unit (like BTC) using asset precision
-    let amount_for_distribution = smallest_units as f64 / 10f64.powi(asset.precision as i32);
+    // smallest_units is the on-chain satoshi amount; precision only affects display
+    let amount_for_distribution = Amount::from_sat(smallest_units);
+    let display_amount = asset
+        .format_amount(amount_for_distribution)
+        .unwrap_or_else(|_| amount_for_distribution.to_string());
 
     // 7. Execute distribution (LIVE) - Now working with async background task!
     let distribution_assignments = vec![amp_rs::model::AssetDistributionAssignment {
@@ -1691,7 +1694,7 @@ async fn run_distribution_flow_with_channel(
 
     let _ = tx.send(DistributionMessage::Info(format!(
         "Distributing {} base units ({} smallest units)...",
-        amount_for_distribution, smallest_units
+        display_amount, smallest_units
     )));
     let wallet_name = "amp_elements_wallet_static_for_funding";
 
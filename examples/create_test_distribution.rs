@@ -3,7 +3,7 @@
 //! This example creates a test distribution from existing assignments
 //! to demonstrate the cancellation functionality.
 
-use amp_rs::ApiClient;
+use amp_rs::{Amount, ApiClient};
 use dotenvy;
 use std::env;
 
@@ -79,7 +79,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     Some(amp_rs::model::AssetDistributionAssignment {
                         user_id: assignment.registered_user.to_string(),
                         address: address.clone(),
-                        amount: assignment.amount as f64,
+                        amount: Amount::from_sat(assignment.amount),
                     })
                 } else {
                     println!(
@@ -20,7 +20,7 @@
 //! cargo run --example descriptor_wallet_setup
 //! ```
 
-use amp_rs::signer::LwkSoftwareSigner;
+use amp_rs::signer::{KeyChain, LwkSoftwareSigner};
 use amp_rs::ElementsRpc;
 use chrono;
 use dotenvy;
@@ -75,8 +75,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("   Mnemonic: {}", mnemonic);
     println!("   Testnet: {}", signer.is_testnet());
 
-    // Derive a treasury address to demonstrate address generation
-    let treasury_address = signer.derive_address(Some(0))?;
+    // Derive a treasury address from the account the descriptor below covers
+    let treasury_address = signer.derive_address_on_chain(KeyChain::External, 0)?;
     println!("   Treasury address (index 0): {}", treasury_address);
 
     println!("\n📝 Generating Descriptors");
@@ -1,5 +1,7 @@
 // Example demonstrating ElementsRpc UTXO and transaction management methods
-use amp_rs::{AmpError, DistributionResponse, ElementsRpc, TransactionDetail, TxInput, Unspent};
+use amp_rs::{
+    Amount, AmpError, DistributionResponse, ElementsRpc, TransactionDetail, TxInput, Unspent,
+};
 use std::collections::HashMap;
 
 #[tokio::main]
@@ -35,7 +37,7 @@ async fn main() -> Result<(), AmpError> {
     let unspent = Unspent {
         txid: "abc123def456789".to_string(),
         vout: 0,
-        amount: 100.0,
+        amount: Amount::from_btc(100.0).unwrap(),
         asset: "6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d".to_string(),
         address: "lq1qq2xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq".to_string(),
         spendable: true,
@@ -80,11 +82,11 @@ async fn main() -> Result<(), AmpError> {
     let mut map_address_amount = HashMap::new();
     map_address_amount.insert(
         "lq1qq2xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq".to_string(),
-        100.0,
+        Amount::from_btc(100.0).unwrap(),
     );
     map_address_amount.insert(
         "lq1qq3xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq".to_string(),
-        50.0,
+        Amount::from_btc(50.0).unwrap(),
     );
 
     let mut map_address_asset = HashMap::new();
@@ -34,7 +34,7 @@
 //! - Testnet configuration for safe testing
 
 use amp_rs::signer::LwkSoftwareSigner;
-use amp_rs::{ApiClient, ElementsRpc};
+use amp_rs::{Amount, ApiClient, ElementsRpc};
 use dotenvy;
 use std::env;
 
@@ -525,7 +525,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let distribution_assignments = vec![amp_rs::model::AssetDistributionAssignment {
         user_id: user_id.to_string(),
         address: user_address.clone(),
-        amount: assignment_amount as f64 / 100_000_000.0, // Convert satoshis to BTC
+        amount: Amount::from_sat(assignment_amount),
     }];
 
     println!("✅ Assignment vector created");
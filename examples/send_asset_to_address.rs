@@ -17,7 +17,7 @@
 //! - `ELEMENTS_RPC_USER`: Elements node RPC username
 //! - `ELEMENTS_RPC_PASSWORD`: Elements node RPC password
 
-use amp_rs::{ApiClient, ElementsRpc};
+use amp_rs::{Amount, ApiClient, ElementsRpc};
 use dotenvy;
 use std::collections::HashMap;
 use std::env;
@@ -125,7 +125,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     println!("✅ Found {} UTXOs at source address:", source_utxos.len());
-    let mut total_amount = 0.0;
+    let mut total_amount = Amount::ZERO;
     for (i, utxo) in source_utxos.iter().enumerate() {
         println!("   {}. TXID: {}:{}", i + 1, utxo.txid, utxo.vout);
         println!(
@@ -143,7 +143,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     println!("\n💵 Total amount to send: {}", total_amount);
 
-    if total_amount == 0.0 {
+    if !total_amount.is_positive() {
         println!("❌ No funds available to send");
         return Err("No funds available".into());
     }
@@ -19,11 +19,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("  AMP_API_BASE_URL: {:?}", env::var("AMP_API_BASE_URL"));
 
     // Check if token file exists
-    let token_exists = tokio::fs::try_exists("token.json").await.unwrap_or(false);
-    println!("  Token file exists: {}", token_exists);
+    let token_file = amp_rs::TokenAccountKey::from_env()?.token_file();
+    let token_exists = tokio::fs::try_exists(&token_file).await.unwrap_or(false);
+    println!(
+        "  Token file {} exists: {}",
+        token_file.display(),
+        token_exists
+    );
 
     if token_exists {
-        let token_content = tokio::fs::read_to_string("token.json").await?;
+        let token_content = tokio::fs::read_to_string(&token_file).await?;
         println!("  Token file content: {}", token_content);
     }
 
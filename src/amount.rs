@@ -0,0 +1,417 @@
+//! # Exact Asset Amounts
+//!
+//! On-chain Liquid amounts are integers (satoshis). Elements RPC and the AMP distribution,
+//! reissuance and burn endpoints present them as "BTC-style" decimals with 8 places, while
+//! AMP assignments and activities use the raw satoshi integer. [`Amount`] stores the
+//! satoshi value and converts exactly between these representations, so summing and
+//! splitting large distributions never accumulates floating point drift.
+//!
+//! An asset's `precision` only affects how its amounts are displayed to people:
+//! `1250` satoshis of an asset with precision `2` is shown as `12.50`. Use
+//! [`Amount::from_asset_units`] and [`Amount::to_asset_units_string`] (or the helpers on
+//! [`Asset`](crate::model::Asset)) for those conversions.
+//!
+//! ## Example
+//!
+//! ```
+//! use amp_rs::Amount;
+//!
+//! let amount = Amount::from_btc_str("0.00000100").unwrap();
+//! assert_eq!(amount.to_sat(), 100);
+//! assert_eq!(amount.to_string(), "0.00000100");
+//!
+//! let display = Amount::from_asset_units("12.5", 2).unwrap();
+//! assert_eq!(display.to_sat(), 1250);
+//! assert_eq!(display.to_asset_units_string(2).unwrap(), "12.50");
+//! ```
+
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::str::FromStr;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+/// Number of decimal places used by Elements RPC and AMP for BTC-style amounts
+pub const BTC_DECIMALS: u32 = 8;
+
+const SATS_PER_BTC: i64 = 100_000_000;
+
+/// Errors produced when parsing or converting amounts
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AmountError {
+    #[error("Invalid amount '{0}'")]
+    Invalid(String),
+    #[error("Amount '{amount}' has more than {decimals} decimal places")]
+    TooPrecise { amount: String, decimals: u32 },
+    #[error("Amount is out of range")]
+    OutOfRange,
+    #[error("Invalid asset precision {0} (must be between 0 and 8)")]
+    InvalidPrecision(i64),
+}
+
+/// An exact asset amount in satoshis
+///
+/// Serializes as a BTC-style JSON number, which is the format used by Elements RPC and
+/// the AMP distribution endpoints. Deserializes from a JSON number or a decimal string.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Amount(i64);
+
+impl Amount {
+    /// Zero amount
+    pub const ZERO: Self = Self(0);
+
+    /// Maximum amount of a single asset that can exist on Liquid (21 million BTC-style units)
+    pub const MAX_MONEY: Self = Self(21_000_000 * SATS_PER_BTC);
+
+    /// Creates an amount from satoshis
+    #[must_use]
+    pub const fn from_sat(sats: i64) -> Self {
+        Self(sats)
+    }
+
+    /// Returns the amount in satoshis
+    #[must_use]
+    pub const fn to_sat(self) -> i64 {
+        self.0
+    }
+
+    /// Creates an amount from a BTC-style floating point value
+    ///
+    /// The value is rounded to the nearest satoshi, so every value with at most
+    /// 8 decimal places converts exactly.
+    ///
+    /// # Errors
+    /// Returns an error if the value is not finite or does not fit in satoshis
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn from_btc(btc: f64) -> Result<Self, AmountError> {
+        if !btc.is_finite() {
+            return Err(AmountError::Invalid(btc.to_string()));
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let sats = (btc * SATS_PER_BTC as f64).round();
+        #[allow(clippy::cast_precision_loss)]
+        if sats.abs() >= i64::MAX as f64 {
+            return Err(AmountError::OutOfRange);
+        }
+        Ok(Self(sats as i64))
+    }
+
+    /// Returns the amount as a BTC-style floating point value
+    ///
+    /// Only use this for display or for APIs that require a float; arithmetic should be
+    /// done on `Amount` directly.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn to_btc(self) -> f64 {
+        self.0 as f64 / SATS_PER_BTC as f64
+    }
+
+    /// Parses a BTC-style decimal string such as `"0.00000100"`
+    ///
+    /// # Errors
+    /// Returns an error if the string is not a decimal number, has more than 8 decimal
+    /// places, or does not fit in satoshis
+    pub fn from_btc_str(s: &str) -> Result<Self, AmountError> {
+        parse_decimal(s, BTC_DECIMALS).map(Self)
+    }
+
+    /// Formats the amount as a BTC-style decimal string with 8 decimal places
+    #[must_use]
+    pub fn to_btc_string(self) -> String {
+        format_decimal(self.0, BTC_DECIMALS)
+    }
+
+    /// Parses an amount expressed in display units of an asset with the given precision
+    ///
+    /// For an asset with precision `2`, `"12.5"` is `1250` satoshis.
+    ///
+    /// # Errors
+    /// Returns an error if the precision is not between 0 and 8, or if the string is not
+    /// a decimal number with at most `precision` decimal places that fits in satoshis
+    pub fn from_asset_units(s: &str, precision: i64) -> Result<Self, AmountError> {
+        parse_decimal(s, checked_precision(precision)?).map(Self)
+    }
+
+    /// Formats the amount in display units of an asset with the given precision
+    ///
+    /// # Errors
+    /// Returns an error if the precision is not between 0 and 8
+    pub fn to_asset_units_string(self, precision: i64) -> Result<String, AmountError> {
+        Ok(format_decimal(self.0, checked_precision(precision)?))
+    }
+
+    /// Returns `true` if the amount is greater than zero
+    #[must_use]
+    pub const fn is_positive(self) -> bool {
+        self.0 > 0
+    }
+
+    /// Adds two amounts, returning `None` on overflow
+    #[must_use]
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_add(rhs.0) {
+            Some(sats) => Some(Self(sats)),
+            None => None,
+        }
+    }
+
+    /// Subtracts two amounts, returning `None` on overflow
+    #[must_use]
+    pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_sub(rhs.0) {
+            Some(sats) => Some(Self(sats)),
+            None => None,
+        }
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_btc_string())
+    }
+}
+
+impl FromStr for Amount {
+    type Err = AmountError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_btc_str(s)
+    }
+}
+
+impl Add for Amount {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for Amount {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sub for Amount {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl SubAssign for Amount {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Sum for Amount {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Self> for Amount {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl Serialize for Amount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.to_btc())
+    }
+}
+
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct AmountVisitor;
+
+        impl Visitor<'_> for AmountVisitor {
+            type Value = Amount;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a BTC-style amount as a number or decimal string")
+            }
+
+            fn visit_f64<E: de::Error>(self, value: f64) -> Result<Amount, E> {
+                Amount::from_btc(value).map_err(E::custom)
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Amount, E> {
+                value
+                    .checked_mul(SATS_PER_BTC)
+                    .map(Amount)
+                    .ok_or_else(|| E::custom(AmountError::OutOfRange))
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Amount, E> {
+                i64::try_from(value)
+                    .map_err(|_| E::custom(AmountError::OutOfRange))
+                    .and_then(|value| self.visit_i64(value))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Amount, E> {
+                Amount::from_btc_str(value).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(AmountVisitor)
+    }
+}
+
+fn checked_precision(precision: i64) -> Result<u32, AmountError> {
+    u32::try_from(precision)
+        .ok()
+        .filter(|precision| *precision <= BTC_DECIMALS)
+        .ok_or(AmountError::InvalidPrecision(precision))
+}
+
+/// Parses a decimal string into an integer scaled by `10^decimals`
+fn parse_decimal(s: &str, decimals: u32) -> Result<i64, AmountError> {
+    let invalid = || AmountError::Invalid(s.to_string());
+
+    let trimmed = s.trim();
+    let (negative, digits) = match trimmed.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, trimmed),
+    };
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+
+    if (whole.is_empty() && fraction.is_empty())
+        || !whole.bytes().all(|b| b.is_ascii_digit())
+        || !fraction.bytes().all(|b| b.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+
+    let fraction = fraction.trim_end_matches('0');
+    if u32::try_from(fraction.len()).map_or(true, |len| len > decimals) {
+        return Err(AmountError::TooPrecise {
+            amount: s.to_string(),
+            decimals,
+        });
+    }
+
+    let scale = 10_i128.pow(decimals);
+    let whole_value: i128 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().map_err(|_| AmountError::OutOfRange)?
+    };
+    let fraction_value: i128 = if fraction.is_empty() {
+        0
+    } else {
+        let padding = decimals - u32::try_from(fraction.len()).unwrap_or(decimals);
+        fraction.parse::<i128>().map_err(|_| invalid())? * 10_i128.pow(padding)
+    };
+
+    let value = whole_value
+        .checked_mul(scale)
+        .and_then(|value| value.checked_add(fraction_value))
+        .ok_or(AmountError::OutOfRange)?;
+    let value = if negative { -value } else { value };
+
+    i64::try_from(value).map_err(|_| AmountError::OutOfRange)
+}
+
+/// Formats an integer scaled by `10^decimals` as a decimal string
+fn format_decimal(value: i64, decimals: u32) -> String {
+    let sign = if value < 0 { "-" } else { "" };
+    let magnitude = value.unsigned_abs();
+    if decimals == 0 {
+        return format!("{sign}{magnitude}");
+    }
+
+    let scale = 10_u64.pow(decimals);
+    let width = decimals as usize;
+    format!("{sign}{}.{:0width$}", magnitude / scale, magnitude % scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_btc_string_round_trip() {
+        let amount = Amount::from_btc_str("1234.56789012").unwrap();
+        assert_eq!(amount.to_sat(), 123_456_789_012);
+        assert_eq!(amount.to_btc_string(), "1234.56789012");
+        assert_eq!(Amount::from_btc_str(".5").unwrap().to_sat(), 50_000_000);
+        assert_eq!(
+            Amount::from_btc_str("-0.1").unwrap().to_string(),
+            "-0.10000000"
+        );
+    }
+
+    #[test]
+    fn test_btc_string_rejects_invalid_input() {
+        assert!(matches!(
+            Amount::from_btc_str("0.000000001"),
+            Err(AmountError::TooPrecise { .. })
+        ));
+        assert!(matches!(
+            Amount::from_btc_str("1e-8"),
+            Err(AmountError::Invalid(_))
+        ));
+        assert!(matches!(
+            Amount::from_btc_str(""),
+            Err(AmountError::Invalid(_))
+        ));
+        assert!(matches!(
+            Amount::from_btc_str("100000000000000"),
+            Err(AmountError::OutOfRange)
+        ));
+    }
+
+    #[test]
+    fn test_asset_units_use_precision() {
+        assert_eq!(Amount::from_asset_units("12.5", 2).unwrap().to_sat(), 1250);
+        assert_eq!(Amount::from_asset_units("7", 0).unwrap().to_sat(), 7);
+        assert_eq!(
+            Amount::from_sat(1250).to_asset_units_string(2).unwrap(),
+            "12.50"
+        );
+        assert_eq!(Amount::from_sat(7).to_asset_units_string(0).unwrap(), "7");
+        assert!(matches!(
+            Amount::from_asset_units("1.234", 2),
+            Err(AmountError::TooPrecise { .. })
+        ));
+        assert!(matches!(
+            Amount::from_asset_units("1", 9),
+            Err(AmountError::InvalidPrecision(9))
+        ));
+    }
+
+    #[test]
+    fn test_summing_many_small_amounts_is_exact() {
+        let amounts = vec![Amount::from_btc(0.1).unwrap(); 1000];
+        let total: Amount = amounts.iter().sum();
+        assert_eq!(total, Amount::from_btc_str("100").unwrap());
+    }
+
+    #[test]
+    fn test_serde_uses_btc_units() {
+        let amount = Amount::from_sat(150);
+        let json = serde_json::to_string(&amount).unwrap();
+        assert_eq!(serde_json::from_str::<Amount>(&json).unwrap(), amount);
+
+        assert_eq!(
+            serde_json::from_str::<Amount>("0.00000150").unwrap(),
+            amount
+        );
+        assert_eq!(
+            serde_json::from_str::<Amount>("\"0.00000150\"").unwrap(),
+            amount
+        );
+        assert_eq!(
+            serde_json::from_str::<Amount>("2").unwrap().to_sat(),
+            200_000_000
+        );
+    }
+}
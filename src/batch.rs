@@ -0,0 +1,612 @@
+//! # Batch Distributions
+//!
+//! [`ApiClient::distribute_asset`](crate::ApiClient::distribute_asset) pays every
+//! assignment from a single transaction. Each confidential output adds a range proof
+//! and a surjection proof of several kilobytes, so a distribution to a few hundred
+//! investors already exceeds the standard transaction weight and is not relayed.
+//!
+//! A [`BatchPlanner`] splits the assignments into [`DistributionBatch`]es that each
+//! stay within an output count and an estimated virtual size. Assignments to the same
+//! address always end up in the same batch, since they are paid by a single output.
+//! [`ApiClient::distribute_asset_in_batches`](crate::ApiClient::distribute_asset_in_batches)
+//! then runs every batch as its own AMP distribution and transaction and returns a
+//! [`BatchDistributionReport`] describing which batches completed, which failed and
+//! which were not attempted.
+//!
+//! ## Fee UTXOs
+//!
+//! Each batch pays its fee in L-BTC. The node wallet only spends confirmed outputs, so
+//! a batch can only reuse the L-BTC change of the previous batch once that batch has
+//! confirmed. With one L-BTC UTXO per batch, as reported by
+//! [`BatchPlan::fee_utxos_needed`], no batch waits on the change of another.
+//! [`BatchPlan::check_fee_funding`] compares the plan with the wallet's UTXOs before
+//! anything is sent.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use amp_rs::{ApiClient, AssetDistributionAssignment, ElementsRpc, LwkSoftwareSigner};
+//! # use amp_rs::batch::BatchPlanner;
+//! # use amp_rs::fee::FeeRate;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let assignments: Vec<AssetDistributionAssignment> = Vec::new();
+//! let client = ApiClient::new().await?;
+//! let node_rpc = ElementsRpc::from_env()?;
+//! let (_, signer) = LwkSoftwareSigner::generate_new()?;
+//!
+//! let plan = BatchPlanner::new()
+//!     .with_fee_rate(FeeRate::from_sat_per_vb(1))
+//!     .plan(assignments)?;
+//! println!(
+//!     "{} batches, {} fee UTXOs, estimated fee {}",
+//!     plan.batches().len(),
+//!     plan.fee_utxos_needed(),
+//!     plan.total_fee()
+//! );
+//!
+//! let report = client
+//!     .distribute_asset_in_batches("asset-uuid", plan, &node_rpc, "wallet_name", &signer, None)
+//!     .await?;
+//! for failed in report.failed() {
+//!     println!("Batch {} failed: {:?}", failed.batch.index, failed.status);
+//! }
+//! let skipped = report.skipped_assignments();
+//! println!("{} assignments were not attempted", skipped.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::amount::Amount;
+use crate::fee::{self, FeeRate};
+use crate::model::{AssetDistributionAssignment, Unspent};
+
+/// Default maximum number of recipient outputs in one batch
+pub const DEFAULT_MAX_OUTPUTS_PER_BATCH: usize = 250;
+
+/// Largest standard transaction (400,000 weight units) in virtual bytes
+pub const MAX_STANDARD_TX_VSIZE: usize = 100_000;
+
+/// Asset UTXOs each batch is assumed to spend when no other estimate is given
+pub const DEFAULT_ASSET_INPUTS_PER_BATCH: usize = 2;
+
+/// Errors produced while planning batches
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum BatchPlanError {
+    #[error("No assignments to plan")]
+    NoAssignments,
+    #[error("Invalid batch limits: {0}")]
+    InvalidLimits(String),
+    #[error("A transaction paying address {address} alone is estimated at {vsize} vB, above the limit of {max_vsize} vB")]
+    OutputTooLarge {
+        address: String,
+        vsize: usize,
+        max_vsize: usize,
+    },
+}
+
+/// Splits distribution assignments into transactions of bounded size
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchPlanner {
+    max_outputs: usize,
+    max_vsize: usize,
+    asset_inputs: usize,
+    fee_rate: FeeRate,
+}
+
+impl Default for BatchPlanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BatchPlanner {
+    /// Creates a planner with the default limits and the minimum relay fee rate
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            max_outputs: DEFAULT_MAX_OUTPUTS_PER_BATCH,
+            max_vsize: MAX_STANDARD_TX_VSIZE,
+            asset_inputs: DEFAULT_ASSET_INPUTS_PER_BATCH,
+            fee_rate: FeeRate::MIN_RELAY,
+        }
+    }
+
+    /// Sets the maximum number of recipient outputs in one batch
+    #[must_use]
+    pub const fn with_max_outputs(mut self, max_outputs: usize) -> Self {
+        self.max_outputs = max_outputs;
+        self
+    }
+
+    /// Sets the maximum estimated virtual size of one batch transaction
+    #[must_use]
+    pub const fn with_max_vsize(mut self, max_vsize: usize) -> Self {
+        self.max_vsize = max_vsize;
+        self
+    }
+
+    /// Sets how many asset UTXOs each batch is expected to spend
+    ///
+    /// The estimate only affects the size and fee of each batch. Wallets holding the
+    /// asset in many small UTXOs should raise it.
+    #[must_use]
+    pub const fn with_asset_inputs(mut self, asset_inputs: usize) -> Self {
+        self.asset_inputs = asset_inputs;
+        self
+    }
+
+    /// Sets the fee rate used to estimate the fee of each batch
+    #[must_use]
+    pub const fn with_fee_rate(mut self, fee_rate: FeeRate) -> Self {
+        self.fee_rate = fee_rate;
+        self
+    }
+
+    /// Splits `assignments` into batches, keeping their order
+    ///
+    /// # Errors
+    /// Returns an error if there are no assignments, the limits allow no outputs, or a
+    /// single recipient does not fit in a transaction of the maximum size
+    pub fn plan(
+        &self,
+        assignments: Vec<AssetDistributionAssignment>,
+    ) -> Result<BatchPlan, BatchPlanError> {
+        if assignments.is_empty() {
+            return Err(BatchPlanError::NoAssignments);
+        }
+        if self.max_outputs == 0 {
+            return Err(BatchPlanError::InvalidLimits(
+                "max_outputs must be at least 1".to_string(),
+            ));
+        }
+
+        // Assignments to the same address are paid by one output, so they are planned
+        // as one unit
+        let mut groups: Vec<Vec<AssetDistributionAssignment>> = Vec::new();
+        let mut group_by_address: HashMap<String, usize> = HashMap::new();
+        for assignment in assignments {
+            if let Some(&index) = group_by_address.get(&assignment.address) {
+                groups[index].push(assignment);
+            } else {
+                group_by_address.insert(assignment.address.clone(), groups.len());
+                groups.push(vec![assignment]);
+            }
+        }
+
+        let mut batches = Vec::new();
+        let mut current: Vec<AssetDistributionAssignment> = Vec::new();
+        let mut outputs = 0;
+        for group in groups {
+            if outputs > 0
+                && (outputs + 1 > self.max_outputs
+                    || self.estimate_vsize(outputs + 1) > self.max_vsize)
+            {
+                batches.push(self.batch(batches.len(), std::mem::take(&mut current), outputs));
+                outputs = 0;
+            }
+
+            let vsize = self.estimate_vsize(1);
+            if outputs == 0 && vsize > self.max_vsize {
+                return Err(BatchPlanError::OutputTooLarge {
+                    address: group[0].address.clone(),
+                    vsize,
+                    max_vsize: self.max_vsize,
+                });
+            }
+
+            current.extend(group);
+            outputs += 1;
+        }
+        batches.push(self.batch(batches.len(), current, outputs));
+
+        tracing::info!(
+            "Planned {} distribution batches at {}",
+            batches.len(),
+            self.fee_rate
+        );
+
+        Ok(BatchPlan {
+            batches,
+            fee_rate: self.fee_rate,
+        })
+    }
+
+    /// Estimates the size of a batch transaction paying `recipients` outputs
+    ///
+    /// Each batch spends its asset inputs and one L-BTC input, and returns asset and
+    /// L-BTC change.
+    fn estimate_vsize(&self, recipients: usize) -> usize {
+        fee::estimate_vsize(self.asset_inputs + 1, recipients + 2)
+    }
+
+    fn batch(
+        &self,
+        index: usize,
+        assignments: Vec<AssetDistributionAssignment>,
+        outputs: usize,
+    ) -> DistributionBatch {
+        let estimated_vsize = self.estimate_vsize(outputs);
+        DistributionBatch {
+            index,
+            total: assignments.iter().map(|assignment| assignment.amount).sum(),
+            assignments,
+            outputs,
+            estimated_vsize,
+            estimated_fee: self.fee_rate.fee_for_vsize(estimated_vsize),
+        }
+    }
+}
+
+/// One transaction of a batch distribution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DistributionBatch {
+    /// Position of the batch in the plan, starting at 0
+    pub index: usize,
+    /// Assignments paid by the batch
+    pub assignments: Vec<AssetDistributionAssignment>,
+    /// Total amount of the asset distributed by the batch
+    pub total: Amount,
+    /// Number of recipient outputs (distinct addresses)
+    pub outputs: usize,
+    /// Estimated virtual size of the signed transaction
+    pub estimated_vsize: usize,
+    /// Estimated L-BTC fee of the transaction
+    pub estimated_fee: Amount,
+}
+
+/// Assignments split into batches by a [`BatchPlanner`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchPlan {
+    batches: Vec<DistributionBatch>,
+    fee_rate: FeeRate,
+}
+
+impl BatchPlan {
+    /// Returns the batches, in the order they are distributed
+    #[must_use]
+    pub fn batches(&self) -> &[DistributionBatch] {
+        &self.batches
+    }
+
+    /// Returns the fee rate the batch fees were estimated at
+    #[must_use]
+    pub const fn fee_rate(&self) -> FeeRate {
+        self.fee_rate
+    }
+
+    /// Returns the total number of assignments in the plan
+    #[must_use]
+    pub fn assignment_count(&self) -> usize {
+        self.batches
+            .iter()
+            .map(|batch| batch.assignments.len())
+            .sum()
+    }
+
+    /// Returns the total amount of the asset distributed by all batches
+    #[must_use]
+    pub fn total_amount(&self) -> Amount {
+        self.batches.iter().map(|batch| batch.total).sum()
+    }
+
+    /// Returns the estimated L-BTC fee of all batches
+    #[must_use]
+    pub fn total_fee(&self) -> Amount {
+        self.batches.iter().map(|batch| batch.estimated_fee).sum()
+    }
+
+    /// Returns how many L-BTC UTXOs let every batch pay its fee without waiting for the
+    /// change of another batch to confirm
+    #[must_use]
+    pub fn fee_utxos_needed(&self) -> usize {
+        self.batches.len()
+    }
+
+    /// Matches the batch fees against the L-BTC UTXOs of a wallet
+    ///
+    /// UTXOs of other assets in `utxos` are ignored. The largest fees are matched with
+    /// the largest UTXOs, each UTXO paying for at most one batch.
+    #[must_use]
+    pub fn check_fee_funding(&self, utxos: &[Unspent], policy_asset: &str) -> FeeFunding {
+        let mut amounts: Vec<Amount> = utxos
+            .iter()
+            .filter(|utxo| utxo.asset == policy_asset && utxo.spendable)
+            .map(|utxo| utxo.amount)
+            .collect();
+        amounts.sort_unstable_by_key(|amount| Reverse(*amount));
+
+        let mut batches: Vec<&DistributionBatch> = self.batches.iter().collect();
+        batches.sort_by_key(|batch| Reverse(batch.estimated_fee));
+
+        let mut uncovered_batches: Vec<usize> = batches
+            .iter()
+            .enumerate()
+            .filter(|(rank, batch)| {
+                amounts
+                    .get(*rank)
+                    .is_none_or(|amount| *amount < batch.estimated_fee)
+            })
+            .map(|(_, batch)| batch.index)
+            .collect();
+        uncovered_batches.sort_unstable();
+
+        FeeFunding {
+            needed_utxos: self.fee_utxos_needed(),
+            available_utxos: amounts.len(),
+            needed: self.total_fee(),
+            available: amounts.iter().copied().sum(),
+            uncovered_batches,
+        }
+    }
+}
+
+/// L-BTC available to pay the fees of a [`BatchPlan`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeFunding {
+    /// L-BTC UTXOs needed for every batch to have its own
+    pub needed_utxos: usize,
+    /// Spendable L-BTC UTXOs in the wallet
+    pub available_utxos: usize,
+    /// Estimated fee of all batches
+    pub needed: Amount,
+    /// Total of the spendable L-BTC UTXOs
+    pub available: Amount,
+    /// Batches without a UTXO of their own that covers their fee
+    pub uncovered_batches: Vec<usize>,
+}
+
+impl FeeFunding {
+    /// Returns `true` if the wallet holds enough L-BTC for all batch fees
+    #[must_use]
+    pub fn is_sufficient(&self) -> bool {
+        self.available >= self.needed
+    }
+
+    /// Returns `true` if every batch has its own L-BTC UTXO covering its fee
+    #[must_use]
+    pub fn is_fully_covered(&self) -> bool {
+        self.uncovered_batches.is_empty()
+    }
+}
+
+/// Outcome of one batch of a batch distribution
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum BatchStatus {
+    /// The distribution was confirmed with the AMP API
+    Completed { txid: String },
+    /// The batch failed; its distribution may have to be resumed or cancelled
+    Failed {
+        error: String,
+        /// AMP distribution created for the batch, `None` if it failed before one was
+        /// created. Resume or cancel it before distributing the batch again.
+        distribution_uuid: Option<String>,
+    },
+    /// The batch was not attempted because an earlier batch failed
+    Skipped,
+}
+
+/// A batch and its outcome
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchResult {
+    pub batch: DistributionBatch,
+    pub status: BatchStatus,
+}
+
+/// Result of [`ApiClient::distribute_asset_in_batches`](crate::ApiClient::distribute_asset_in_batches)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchDistributionReport {
+    pub asset_uuid: String,
+    pub results: Vec<BatchResult>,
+}
+
+impl BatchDistributionReport {
+    /// Returns `true` if every batch completed
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.results
+            .iter()
+            .all(|result| matches!(result.status, BatchStatus::Completed { .. }))
+    }
+
+    /// Returns the transaction IDs of the completed batches, in batch order
+    #[must_use]
+    pub fn txids(&self) -> Vec<&str> {
+        self.results
+            .iter()
+            .filter_map(|result| match &result.status {
+                BatchStatus::Completed { txid } => Some(txid.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the batches that failed
+    ///
+    /// A failed batch may have been broadcast or even confirmed on chain, so its
+    /// assignments must not simply be distributed again. Resume its distribution with
+    /// `resume_distribution`, or cancel it, first.
+    #[must_use]
+    pub fn failed(&self) -> Vec<&BatchResult> {
+        self.results
+            .iter()
+            .filter(|result| matches!(result.status, BatchStatus::Failed { .. }))
+            .collect()
+    }
+
+    /// Returns the assignments of the batches that were not attempted
+    ///
+    /// These can safely be planned and distributed again. Assignments of
+    /// [`failed`](Self::failed) batches are not included.
+    #[must_use]
+    pub fn skipped_assignments(&self) -> Vec<AssetDistributionAssignment> {
+        self.results
+            .iter()
+            .filter(|result| result.status == BatchStatus::Skipped)
+            .flat_map(|result| result.batch.assignments.iter().cloned())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assignments(count: usize) -> Vec<AssetDistributionAssignment> {
+        (0..count)
+            .map(|i| AssetDistributionAssignment {
+                user_id: format!("user{i}"),
+                address: format!("vjaddress{i}"),
+                amount: Amount::from_sat(1000),
+            })
+            .collect()
+    }
+
+    fn lbtc(amount: i64) -> Unspent {
+        Unspent {
+            txid: format!("{amount:064x}"),
+            vout: 0,
+            amount: Amount::from_sat(amount),
+            asset: "policy".to_string(),
+            address: "address".to_string(),
+            spendable: true,
+            confirmations: Some(1),
+            scriptpubkey: None,
+            redeemscript: None,
+            witnessscript: None,
+            amountblinder: None,
+            assetblinder: None,
+        }
+    }
+
+    #[test]
+    fn test_plan_respects_output_and_size_limits() {
+        let plan = BatchPlanner::new()
+            .with_max_outputs(40)
+            .plan(assignments(100))
+            .unwrap();
+        let sizes: Vec<usize> = plan.batches().iter().map(|b| b.outputs).collect();
+        assert_eq!(sizes, vec![40, 40, 20]);
+        assert_eq!(plan.assignment_count(), 100);
+        assert_eq!(plan.total_amount(), Amount::from_sat(100_000));
+        assert_eq!(plan.fee_utxos_needed(), 3);
+
+        // Without an output limit the standard size decides
+        let plan = BatchPlanner::new()
+            .with_max_outputs(usize::MAX)
+            .plan(assignments(1000))
+            .unwrap();
+        assert!(plan.batches().len() > 1);
+        for batch in plan.batches() {
+            assert!(batch.estimated_vsize <= MAX_STANDARD_TX_VSIZE);
+        }
+        assert_eq!(plan.assignment_count(), 1000);
+        assert_eq!(
+            plan.batches()[0].assignments[0].user_id,
+            "user0",
+            "batches keep the order of the assignments"
+        );
+
+        assert_eq!(
+            BatchPlanner::new().plan(Vec::new()).unwrap_err(),
+            BatchPlanError::NoAssignments
+        );
+        assert!(matches!(
+            BatchPlanner::new()
+                .with_max_vsize(1000)
+                .plan(assignments(1))
+                .unwrap_err(),
+            BatchPlanError::OutputTooLarge { .. }
+        ));
+    }
+
+    #[test]
+    fn test_plan_keeps_same_address_in_one_batch() {
+        let mut assignments = assignments(3);
+        assignments.push(AssetDistributionAssignment {
+            user_id: "user3".to_string(),
+            address: "vjaddress0".to_string(),
+            amount: Amount::from_sat(500),
+        });
+
+        let plan = BatchPlanner::new()
+            .with_max_outputs(1)
+            .plan(assignments)
+            .unwrap();
+        assert_eq!(plan.batches().len(), 3);
+        let first = &plan.batches()[0];
+        assert_eq!(first.outputs, 1);
+        assert_eq!(first.assignments.len(), 2);
+        assert_eq!(first.total, Amount::from_sat(1500));
+    }
+
+    #[test]
+    fn test_fee_funding_matches_batches_to_utxos() {
+        let plan = BatchPlanner::new()
+            .with_max_outputs(2)
+            .with_fee_rate(FeeRate::from_sat_per_vb(1))
+            .plan(assignments(5))
+            .unwrap();
+        let fee = plan.batches()[0].estimated_fee.to_sat();
+
+        let mut utxos = vec![lbtc(fee * 10), lbtc(fee * 10), lbtc(fee * 10)];
+        utxos[2].asset = "other".to_string();
+        let funding = plan.check_fee_funding(&utxos, "policy");
+        assert_eq!(funding.needed_utxos, 3);
+        assert_eq!(funding.available_utxos, 2);
+        assert!(funding.is_sufficient());
+        assert!(!funding.is_fully_covered());
+        assert_eq!(funding.uncovered_batches.len(), 1);
+
+        let funding = plan.check_fee_funding(&[lbtc(fee)], "policy");
+        assert!(!funding.is_sufficient());
+
+        utxos[2].asset = "policy".to_string();
+        assert!(plan.check_fee_funding(&utxos, "policy").is_fully_covered());
+    }
+
+    #[test]
+    fn test_report_lists_skipped_assignments() {
+        let plan = BatchPlanner::new()
+            .with_max_outputs(2)
+            .plan(assignments(5))
+            .unwrap();
+        let statuses = [
+            BatchStatus::Completed {
+                txid: "tx".to_string(),
+            },
+            BatchStatus::Failed {
+                error: "boom".to_string(),
+                distribution_uuid: Some("dist".to_string()),
+            },
+            BatchStatus::Skipped,
+        ];
+        let report = BatchDistributionReport {
+            asset_uuid: "asset".to_string(),
+            results: plan
+                .batches()
+                .iter()
+                .cloned()
+                .zip(statuses)
+                .map(|(batch, status)| BatchResult { batch, status })
+                .collect(),
+        };
+
+        assert!(!report.is_complete());
+        assert_eq!(report.txids(), vec!["tx"]);
+        assert_eq!(report.failed().len(), 1);
+        let skipped: Vec<String> = report
+            .skipped_assignments()
+            .into_iter()
+            .map(|assignment| assignment.user_id)
+            .collect();
+        assert_eq!(skipped, vec!["user4"]);
+    }
+}
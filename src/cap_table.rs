@@ -0,0 +1,269 @@
+//! # Cap Tables
+//!
+//! A [`CapTable`] is the list of holders of an asset and their balances at one block
+//! height, built from the AMP ownerships at that height. Dividends and other corporate
+//! actions are usually defined by a record date, so a cap table can also be taken at a
+//! point in time: [`ApiClient::cap_table_at`](crate::ApiClient::cap_table_at) maps the
+//! datetime to the last block at or before it with
+//! [`ElementsRpc::height_at`](crate::ElementsRpc::height_at).
+//!
+//! [`CapTable::diff`] compares two snapshots and lists the holders who entered, exited or
+//! changed balance in between.
+//!
+//! Holders are identified by their GAID, or by the owner name AMP reports when they have
+//! none. Amounts are in the asset's base units, as in the AMP API.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use amp_rs::{ApiClient, ElementsRpc};
+//! # use chrono::{TimeZone, Utc};
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = ApiClient::new().await?;
+//! let node_rpc = ElementsRpc::from_env()?;
+//!
+//! let record_date = Utc.with_ymd_and_hms(2025, 6, 30, 23, 59, 59).unwrap();
+//! let previous = client.cap_table_at_height("asset-uuid", 3_000_000).await?;
+//! let current = client.cap_table_at("asset-uuid", record_date, &node_rpc).await?;
+//!
+//! println!("{} holders at height {}", current.entries.len(), current.height);
+//! let diff = previous.diff(&current);
+//! for entry in &diff.entered {
+//!     println!("{} entered with {}", entry.holder(), entry.amount);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::model::Ownership;
+
+/// One holder's balance in a [`CapTable`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapTableEntry {
+    #[serde(rename = "GAID")]
+    pub gaid: Option<String>,
+    pub owner: Option<String>,
+    pub amount: i64,
+}
+
+impl CapTableEntry {
+    /// Returns the identifier the holder is matched by: the GAID, or else the owner
+    #[must_use]
+    pub fn holder(&self) -> &str {
+        self.gaid
+            .as_deref()
+            .or(self.owner.as_deref())
+            .unwrap_or_default()
+    }
+}
+
+/// Holders of an asset and their balances at a block height
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapTable {
+    pub asset_uuid: String,
+    pub height: i64,
+    /// The datetime the snapshot was requested for, when taken by date
+    pub record_time: Option<DateTime<Utc>>,
+    /// Holders with a positive balance, largest first
+    pub entries: Vec<CapTableEntry>,
+}
+
+impl CapTable {
+    /// Builds a cap table from the AMP ownerships at `height`
+    ///
+    /// Ownerships of the same holder are added up and holders without a balance are
+    /// left out.
+    #[must_use]
+    pub fn from_ownerships(asset_uuid: &str, height: i64, ownerships: Vec<Ownership>) -> Self {
+        let mut holders: BTreeMap<String, CapTableEntry> = BTreeMap::new();
+        for ownership in ownerships {
+            let entry = CapTableEntry {
+                gaid: ownership.gaid,
+                owner: ownership.owner,
+                amount: ownership.amount,
+            };
+            holders
+                .entry(entry.holder().to_string())
+                .and_modify(|existing| existing.amount += entry.amount)
+                .or_insert(entry);
+        }
+        let mut entries: Vec<CapTableEntry> = holders
+            .into_values()
+            .filter(|entry| entry.amount > 0)
+            .collect();
+        // Stable, so equal balances stay ordered by holder
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.amount));
+        Self {
+            asset_uuid: asset_uuid.to_string(),
+            height,
+            record_time: None,
+            entries,
+        }
+    }
+
+    /// Returns the total held across all holders
+    #[must_use]
+    pub fn total(&self) -> i64 {
+        self.entries.iter().map(|entry| entry.amount).sum()
+    }
+
+    /// Returns the entry of a holder, matched by GAID or owner
+    #[must_use]
+    pub fn get(&self, holder: &str) -> Option<&CapTableEntry> {
+        self.entries.iter().find(|entry| entry.holder() == holder)
+    }
+
+    /// Compares this snapshot with a later one
+    #[must_use]
+    pub fn diff(&self, later: &Self) -> CapTableDiff {
+        let before: BTreeMap<&str, &CapTableEntry> =
+            self.entries.iter().map(|e| (e.holder(), e)).collect();
+        let after: BTreeMap<&str, &CapTableEntry> =
+            later.entries.iter().map(|e| (e.holder(), e)).collect();
+
+        let mut diff = CapTableDiff {
+            from_height: self.height,
+            to_height: later.height,
+            entered: Vec::new(),
+            exited: Vec::new(),
+            changed: Vec::new(),
+        };
+        for (holder, entry) in &after {
+            match before.get(holder) {
+                None => diff.entered.push((*entry).clone()),
+                Some(previous) if previous.amount != entry.amount => {
+                    diff.changed.push(BalanceChange {
+                        gaid: entry.gaid.clone(),
+                        owner: entry.owner.clone(),
+                        before: previous.amount,
+                        after: entry.amount,
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+        diff.exited = before
+            .iter()
+            .filter(|(holder, _)| !after.contains_key(*holder))
+            .map(|(_, entry)| (*entry).clone())
+            .collect();
+        diff
+    }
+}
+
+/// A holder whose balance differs between two snapshots
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceChange {
+    #[serde(rename = "GAID")]
+    pub gaid: Option<String>,
+    pub owner: Option<String>,
+    pub before: i64,
+    pub after: i64,
+}
+
+impl BalanceChange {
+    /// Returns the change in balance, negative when the holder's balance fell
+    #[must_use]
+    pub const fn delta(&self) -> i64 {
+        self.after - self.before
+    }
+}
+
+/// Differences between two [`CapTable`] snapshots, ordered by holder
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapTableDiff {
+    pub from_height: i64,
+    pub to_height: i64,
+    /// Holders present only in the later snapshot, with their new balance
+    pub entered: Vec<CapTableEntry>,
+    /// Holders present only in the earlier snapshot, with their former balance
+    pub exited: Vec<CapTableEntry>,
+    /// Holders present in both snapshots whose balance changed
+    pub changed: Vec<BalanceChange>,
+}
+
+impl CapTableDiff {
+    /// Returns `true` if no holder entered, exited or changed balance
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entered.is_empty() && self.exited.is_empty() && self.changed.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ownership(gaid: Option<&str>, owner: &str, amount: i64) -> Ownership {
+        Ownership {
+            owner: Some(owner.to_string()),
+            amount,
+            gaid: gaid.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_from_ownerships_merges_and_orders_holders() {
+        let table = CapTable::from_ownerships(
+            "uuid",
+            100,
+            vec![
+                ownership(Some("GA1"), "Alice", 300),
+                ownership(None, "Treasury", 500),
+                ownership(Some("GA2"), "Bob", 0),
+                ownership(Some("GA1"), "Alice", 200),
+                ownership(Some("GA3"), "Carol", 100),
+            ],
+        );
+
+        let holders: Vec<(&str, i64)> = table
+            .entries
+            .iter()
+            .map(|e| (e.holder(), e.amount))
+            .collect();
+        assert_eq!(holders, vec![("GA1", 500), ("Treasury", 500), ("GA3", 100)]);
+        assert_eq!(table.total(), 1_100);
+        assert_eq!(table.get("GA3").unwrap().owner.as_deref(), Some("Carol"));
+        assert!(table.get("GA2").is_none());
+    }
+
+    #[test]
+    fn test_diff_lists_entered_exited_and_changed_holders() {
+        let before = CapTable::from_ownerships(
+            "uuid",
+            100,
+            vec![
+                ownership(Some("GA1"), "Alice", 500),
+                ownership(Some("GA2"), "Bob", 200),
+                ownership(Some("GA3"), "Carol", 100),
+            ],
+        );
+        let after = CapTable::from_ownerships(
+            "uuid",
+            200,
+            vec![
+                ownership(Some("GA1"), "Alice", 400),
+                ownership(Some("GA3"), "Carol", 100),
+                ownership(Some("GA4"), "Dave", 300),
+            ],
+        );
+
+        let diff = before.diff(&after);
+        assert_eq!((diff.from_height, diff.to_height), (100, 200));
+        assert_eq!(diff.entered.len(), 1);
+        assert_eq!(diff.entered[0].holder(), "GA4");
+        assert_eq!(diff.exited.len(), 1);
+        assert_eq!(diff.exited[0].amount, 200);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].gaid.as_deref(), Some("GA1"));
+        assert_eq!(diff.changed[0].delta(), -100);
+
+        assert!(after.diff(&after).is_empty());
+    }
+}
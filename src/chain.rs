@@ -0,0 +1,652 @@
+//! # Chain Backends
+//!
+//! Chain operations (UTXO lookup, transaction lookup, broadcast and the chain tip) behind
+//! one [`ChainBackend`] trait. Two implementations are provided:
+//! - [`ElementsRpc`] - the node's bitcoind-style JSON-RPC interface
+//! - [`EsploraBackend`] - an Esplora HTTP API, such as `blockstream.info/liquid/api` or
+//!   a self-hosted instance, which needs no node at all
+//!
+//! [`ApiClientBuilder::chain_backend`](crate::client::ApiClientBuilder::chain_backend)
+//! selects the backend used to broadcast and track the confirmations of distribution,
+//! reissuance and burn transactions. When
+//! [`ApiClientBuilder::funding_addresses`](crate::client::ApiClientBuilder::funding_addresses)
+//! is set as well, those workflows stop using the node wallet to build transactions:
+//! UTXOs of the [`FundingAddresses`] are looked up with [`ChainBackend::list_unspent`],
+//! the transaction is built with a [`PsetBuilder`](crate::pset_builder::PsetBuilder),
+//! signed with the workflow's [`Signer`](crate::signer::Signer) and broadcast through
+//! the backend. The AMP API still receives the node wallet's view of the transaction
+//! when a workflow is confirmed, so the node must watch the funding addresses.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use amp_rs::chain::{ChainBackend, EsploraBackend};
+//! use amp_rs::fee::FeeRate;
+//! use amp_rs::pset_builder::PsetBuilder;
+//! use amp_rs::{Amount, Network};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let esplora = EsploraBackend::new("https://blockstream.info/liquidtestnet/api");
+//! println!("Tip height: {}", esplora.tip_height().await?);
+//!
+//! let utxos = esplora.list_unspent(&["tlq1qq...".to_string()]).await?;
+//! let pset = PsetBuilder::new(Network::LiquidTestnet)
+//!     .add_inputs(utxos)
+//!     .add_recipient("tlq1qq...", "asset_id_hex", Amount::from_sat(1_000))
+//!     .change_address("tlq1qq...")
+//!     .fee_rate(FeeRate::MIN_RELAY)
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use elements::encode::deserialize;
+use elements::secp256k1_zkp::{Secp256k1, SecretKey};
+use elements::{Address, Transaction};
+use serde::Deserialize;
+
+use crate::amount::Amount;
+use crate::client::{AmpError, ElementsRpc};
+use crate::model::{ProgressUpdate, Unspent};
+
+/// Default interval between confirmation polls
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// A transaction as seen by a chain backend
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainTransaction {
+    pub txid: String,
+    /// Raw transaction hex
+    pub hex: String,
+    /// Number of confirmations, 0 while the transaction is in the mempool
+    pub confirmations: u32,
+    /// Height of the block that includes the transaction, if confirmed
+    pub block_height: Option<u64>,
+}
+
+/// Addresses that fund workflow transactions built without the node wallet
+///
+/// The chain backend must be able to unblind the outputs of these addresses, for example
+/// an [`EsploraBackend`] with their blinding keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FundingAddresses {
+    /// Addresses whose UTXOs are spent
+    pub addresses: Vec<String>,
+    /// Confidential address that receives change and reissued amounts
+    pub change_address: String,
+}
+
+impl FundingAddresses {
+    /// Spends the UTXOs of `addresses` and returns change to `change_address`
+    #[must_use]
+    pub fn new(addresses: Vec<String>, change_address: impl Into<String>) -> Self {
+        Self {
+            addresses,
+            change_address: change_address.into(),
+        }
+    }
+}
+
+/// Source of chain data and transaction broadcast
+///
+/// Implementations must be thread-safe; a single backend may be shared by several clients.
+#[async_trait]
+pub trait ChainBackend: Send + Sync + fmt::Debug {
+    /// Returns the height of the current chain tip
+    async fn tip_height(&self) -> Result<u64, AmpError>;
+
+    /// Lists the unspent outputs paying to any of `addresses`
+    ///
+    /// The `ApiClient` workflows fund their transactions from these outputs when
+    /// [`FundingAddresses`] are configured.
+    async fn list_unspent(&self, addresses: &[String]) -> Result<Vec<Unspent>, AmpError>;
+
+    /// Fetches a transaction and its confirmation status
+    ///
+    /// Returns `AmpError::NotFound` only if the backend definitely does not know the
+    /// transaction.
+    async fn get_transaction(&self, txid: &str) -> Result<ChainTransaction, AmpError>;
+
+    /// Broadcasts a signed transaction and returns its txid
+    async fn broadcast(&self, tx_hex: &str) -> Result<String, AmpError>;
+
+    /// Polls until `txid` has at least `min_confirmations` confirmations
+    ///
+    /// Lookup failures are retried until the timeout, since a transaction may not be
+    /// visible immediately after broadcast. Confirmation counts are reported on
+    /// `progress_tx` whenever they change.
+    ///
+    /// # Errors
+    /// Returns `AmpError::Timeout` if the transaction is not confirmed within `timeout`
+    async fn wait_for_confirmations(
+        &self,
+        txid: &str,
+        min_confirmations: u32,
+        timeout: Duration,
+        poll_interval: Duration,
+        progress_tx: Option<&tokio::sync::mpsc::UnboundedSender<ProgressUpdate>>,
+    ) -> Result<ChainTransaction, AmpError> {
+        let start_time = Instant::now();
+        let mut last_confirmations = None;
+
+        loop {
+            match self.get_transaction(txid).await {
+                Ok(transaction) => {
+                    if last_confirmations != Some(transaction.confirmations) {
+                        last_confirmations = Some(transaction.confirmations);
+                        if let Some(tx) = progress_tx {
+                            let _ = tx.send(ProgressUpdate::Confirmation {
+                                current: transaction.confirmations,
+                                required: min_confirmations,
+                                txid: txid.to_string(),
+                            });
+                        }
+                    }
+                    if transaction.confirmations >= min_confirmations {
+                        tracing::info!(
+                            "Transaction {} confirmed with {} confirmations",
+                            txid,
+                            transaction.confirmations
+                        );
+                        return Ok(transaction);
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to look up transaction {}: {}. Retrying...", txid, e);
+                }
+            }
+
+            if start_time.elapsed() + poll_interval > timeout {
+                return Err(AmpError::timeout(format!(
+                    "Transaction {txid} did not reach {min_confirmations} confirmations within {}s",
+                    timeout.as_secs()
+                )));
+            }
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+}
+
+/// Uses the node's default wallet for UTXO and transaction lookups
+#[async_trait]
+impl ChainBackend for ElementsRpc {
+    async fn tip_height(&self) -> Result<u64, AmpError> {
+        let info = self.get_blockchain_info().await?;
+        u64::try_from(info.blocks)
+            .map_err(|_| AmpError::rpc(format!("Invalid block height {}", info.blocks)))
+    }
+
+    async fn list_unspent(&self, addresses: &[String]) -> Result<Vec<Unspent>, AmpError> {
+        self.list_unspent_for_addresses(addresses).await
+    }
+
+    async fn get_transaction(&self, txid: &str) -> Result<ChainTransaction, AmpError> {
+        let detail = Self::get_transaction(self, txid).await?;
+        Ok(ChainTransaction {
+            txid: detail.txid,
+            hex: detail.hex,
+            confirmations: detail.confirmations,
+            block_height: detail.blockheight,
+        })
+    }
+
+    async fn broadcast(&self, tx_hex: &str) -> Result<String, AmpError> {
+        self.send_raw_transaction(tx_hex).await
+    }
+}
+
+/// Confirmation status of an Esplora transaction or output
+#[derive(Debug, Deserialize)]
+struct EsploraStatus {
+    confirmed: bool,
+    #[serde(default)]
+    block_height: Option<u64>,
+}
+
+/// Unspent output as returned by `GET /address/:address/utxo`
+#[derive(Debug, Deserialize)]
+struct EsploraUtxo {
+    txid: String,
+    vout: u32,
+    status: EsploraStatus,
+    /// Explicit value, absent for confidential outputs
+    #[serde(default)]
+    value: Option<u64>,
+    /// Explicit asset, absent for confidential outputs
+    #[serde(default)]
+    asset: Option<String>,
+}
+
+/// Chain backend for the Esplora HTTP API
+///
+/// Esplora only sees commitments for confidential outputs. Blinding keys registered
+/// with [`with_blinding_key`](Self::with_blinding_key) are used to unblind them, which
+/// also fills in the `amountblinder` and `assetblinder` needed by
+/// [`PsetBuilder`](crate::pset_builder::PsetBuilder). Confidential outputs that none of
+/// the keys unblind are left out of [`list_unspent`](ChainBackend::list_unspent).
+#[derive(Clone)]
+pub struct EsploraBackend {
+    client: reqwest::Client,
+    base_url: String,
+    blinding_keys: Vec<SecretKey>,
+}
+
+impl fmt::Debug for EsploraBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EsploraBackend")
+            .field("base_url", &self.base_url)
+            .field("blinding_keys", &self.blinding_keys.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl EsploraBackend {
+    /// Creates a backend for the Esplora API at `base_url`
+    #[must_use]
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            blinding_keys: Vec::new(),
+        }
+    }
+
+    /// Uses the given HTTP client, for example to configure timeouts or a proxy
+    #[must_use]
+    pub fn with_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Adds a private blinding key used to unblind confidential outputs
+    #[must_use]
+    pub fn with_blinding_key(mut self, blinding_key: SecretKey) -> Self {
+        self.blinding_keys.push(blinding_key);
+        self
+    }
+
+    /// Returns the API base URL
+    #[must_use]
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    async fn get_text(&self, path: &str) -> Result<String, AmpError> {
+        let url = format!("{}{path}", self.base_url);
+        let response = self.client.get(&url).send().await?;
+        let status = response.status();
+        let body = response.text().await?;
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Err(AmpError::NotFound(format!(
+                "Esplora request GET {path} failed with status {status}: {body}"
+            )));
+        }
+        if !status.is_success() {
+            return Err(AmpError::rpc(format!(
+                "Esplora request GET {path} failed with status {status}: {body}"
+            )));
+        }
+        Ok(body)
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T, AmpError> {
+        let body = self.get_text(path).await?;
+        serde_json::from_str(&body)
+            .map_err(|e| AmpError::rpc(format!("Failed to parse Esplora response for {path}: {e}")))
+    }
+
+    async fn get_raw_transaction(&self, txid: &str) -> Result<Transaction, AmpError> {
+        let hex = self.get_text(&format!("/tx/{txid}/hex")).await?;
+        let bytes = hex::decode(hex.trim())
+            .map_err(|e| AmpError::rpc(format!("Invalid transaction hex for {txid}: {e}")))?;
+        deserialize(&bytes).map_err(|e| AmpError::rpc(format!("Invalid transaction {txid}: {e}")))
+    }
+
+    /// Converts an Esplora UTXO into an [`Unspent`], unblinding it if necessary
+    async fn to_unspent(
+        &self,
+        address: &str,
+        script_pubkey: &elements::Script,
+        utxo: EsploraUtxo,
+        tip_height: u64,
+    ) -> Result<Option<Unspent>, AmpError> {
+        let confirmations = utxo
+            .status
+            .block_height
+            .filter(|_| utxo.status.confirmed)
+            .map_or(0, |height| {
+                u32::try_from(tip_height.saturating_sub(height) + 1).unwrap_or(u32::MAX)
+            });
+
+        let (value, asset, blinders) = if let (Some(value), Some(asset)) = (utxo.value, utxo.asset)
+        {
+            (value, asset, None)
+        } else {
+            let transaction = self.get_raw_transaction(&utxo.txid).await?;
+            let Some(output) = usize::try_from(utxo.vout)
+                .ok()
+                .and_then(|vout| transaction.output.get(vout))
+            else {
+                return Err(AmpError::rpc(format!(
+                    "Transaction {} has no output {}",
+                    utxo.txid, utxo.vout
+                )));
+            };
+            let secp = Secp256k1::new();
+            let Some(secrets) = self
+                .blinding_keys
+                .iter()
+                .find_map(|key| output.unblind(&secp, *key).ok())
+            else {
+                tracing::debug!(
+                    "Skipping confidential output {}:{} that no blinding key unblinds",
+                    utxo.txid,
+                    utxo.vout
+                );
+                return Ok(None);
+            };
+            (
+                secrets.value,
+                secrets.asset.to_string(),
+                Some((secrets.value_bf.to_string(), secrets.asset_bf.to_string())),
+            )
+        };
+
+        let amount = i64::try_from(value)
+            .map(Amount::from_sat)
+            .map_err(|_| AmpError::rpc(format!("Output value {value} is out of range")))?;
+        let (amountblinder, assetblinder) = blinders.unzip();
+        Ok(Some(Unspent {
+            txid: utxo.txid,
+            vout: utxo.vout,
+            amount,
+            asset,
+            address: address.to_string(),
+            spendable: true,
+            confirmations: Some(confirmations),
+            scriptpubkey: Some(hex::encode(script_pubkey.as_bytes())),
+            redeemscript: None,
+            witnessscript: None,
+            amountblinder,
+            assetblinder,
+        }))
+    }
+}
+
+#[async_trait]
+impl ChainBackend for EsploraBackend {
+    async fn tip_height(&self) -> Result<u64, AmpError> {
+        let height = self.get_text("/blocks/tip/height").await?;
+        height
+            .trim()
+            .parse()
+            .map_err(|e| AmpError::rpc(format!("Invalid tip height '{height}': {e}")))
+    }
+
+    async fn list_unspent(&self, addresses: &[String]) -> Result<Vec<Unspent>, AmpError> {
+        let tip_height = self.tip_height().await?;
+        let mut unspent = Vec::new();
+        for address in addresses {
+            let script_pubkey = Address::from_str(address)
+                .map_err(|e| AmpError::validation(format!("Invalid address '{address}': {e}")))?
+                .script_pubkey();
+            let utxos: Vec<EsploraUtxo> =
+                self.get_json(&format!("/address/{address}/utxo")).await?;
+            for utxo in utxos {
+                if let Some(utxo) = self
+                    .to_unspent(address, &script_pubkey, utxo, tip_height)
+                    .await?
+                {
+                    unspent.push(utxo);
+                }
+            }
+        }
+        tracing::debug!(
+            "Found {} unspent outputs for {} addresses",
+            unspent.len(),
+            addresses.len()
+        );
+        Ok(unspent)
+    }
+
+    async fn get_transaction(&self, txid: &str) -> Result<ChainTransaction, AmpError> {
+        let status: EsploraStatus = self.get_json(&format!("/tx/{txid}/status")).await?;
+        let hex = self.get_text(&format!("/tx/{txid}/hex")).await?;
+        let confirmations = match status.block_height.filter(|_| status.confirmed) {
+            Some(height) => {
+                let tip_height = self.tip_height().await?;
+                u32::try_from(tip_height.saturating_sub(height) + 1).unwrap_or(u32::MAX)
+            }
+            None => 0,
+        };
+        Ok(ChainTransaction {
+            txid: txid.to_string(),
+            hex: hex.trim().to_string(),
+            confirmations,
+            block_height: status.block_height,
+        })
+    }
+
+    async fn broadcast(&self, tx_hex: &str) -> Result<String, AmpError> {
+        let response = self
+            .client
+            .post(format!("{}/tx", self.base_url))
+            .body(tx_hex.to_string())
+            .send()
+            .await?;
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            return Err(AmpError::rpc(format!(
+                "Esplora broadcast failed with status {status}: {body}"
+            )));
+        }
+        let txid = body.trim().to_string();
+        tracing::info!("Broadcast transaction {} through Esplora", txid);
+        Ok(txid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::prelude::*;
+
+    const TXID: &str = "d0c5e7a5f6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3";
+
+    #[tokio::test]
+    async fn test_esplora_transaction_confirmations() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/blocks/tip/height");
+            then.status(200).body("1001");
+        });
+        server.mock(|when, then| {
+            when.method(GET).path(format!("/tx/{TXID}/status"));
+            then.status(200)
+                .json_body(serde_json::json!({"confirmed": true, "block_height": 1000}));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path(format!("/tx/{TXID}/hex"));
+            then.status(200).body("0200");
+        });
+
+        let esplora = EsploraBackend::new(server.base_url());
+        let transaction = esplora
+            .wait_for_confirmations(
+                TXID,
+                2,
+                Duration::from_secs(1),
+                Duration::from_millis(10),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(transaction.confirmations, 2);
+        assert_eq!(transaction.block_height, Some(1000));
+        assert_eq!(transaction.hex, "0200");
+
+        let err = esplora
+            .wait_for_confirmations(
+                TXID,
+                3,
+                Duration::from_millis(50),
+                Duration::from_millis(10),
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AmpError::Timeout(_)));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_transactions_are_not_found() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path(format!("/tx/{TXID}/status"));
+            then.status(404).body("Transaction not found");
+        });
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .json_body_partial(r#"{"method": "gettransaction"}"#);
+            then.status(500).json_body(serde_json::json!({
+                "result": null,
+                "error": {"code": -5, "message": "Invalid or non-wallet transaction id"},
+                "id": "amp-client"
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .json_body_partial(r#"{"method": "getblockchaininfo"}"#);
+            then.status(500).body("Work queue depth exceeded");
+        });
+
+        let esplora = EsploraBackend::new(server.base_url());
+        let err = ChainBackend::get_transaction(&esplora, TXID)
+            .await
+            .unwrap_err();
+        assert!(err.is_not_found());
+
+        let rpc = ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string());
+        let err = ChainBackend::get_transaction(&rpc, TXID).await.unwrap_err();
+        assert!(err.is_not_found());
+        assert!(err.to_string().contains("-5"));
+        assert!(!rpc.tip_height().await.unwrap_err().is_not_found());
+    }
+
+    #[tokio::test]
+    async fn test_esplora_list_unspent_unblinds_confidential_outputs() {
+        use elements::confidential::{AssetBlindingFactor, ValueBlindingFactor};
+        use elements::{AddressParams, AssetId, TxOutSecrets};
+
+        let secp = Secp256k1::new();
+        let blinding_key = SecretKey::from_slice(&[7; 32]).unwrap();
+        let spend_key = elements::bitcoin::PublicKey::new(
+            SecretKey::from_slice(&[8; 32]).unwrap().public_key(&secp),
+        );
+        let address = Address::p2wpkh(
+            &spend_key,
+            Some(blinding_key.public_key(&secp)),
+            &AddressParams::LIQUID_TESTNET,
+        );
+        let asset = AssetId::from_slice(&[9; 32]).unwrap();
+        let input_secrets = TxOutSecrets::new(
+            asset,
+            AssetBlindingFactor::zero(),
+            5_000,
+            ValueBlindingFactor::zero(),
+        );
+        let (confidential, ..) = elements::TxOut::new_not_last_confidential(
+            &mut rand::thread_rng(),
+            &secp,
+            5_000,
+            address.clone(),
+            asset,
+            &[input_secrets],
+        )
+        .unwrap();
+        let transaction = Transaction {
+            version: 2,
+            lock_time: elements::LockTime::ZERO,
+            input: vec![],
+            output: vec![confidential],
+        };
+
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/blocks/tip/height");
+            then.status(200).body("110");
+        });
+        server.mock(|when, then| {
+            when.method(GET).path(format!("/address/{address}/utxo"));
+            then.status(200).json_body(serde_json::json!([
+                {
+                    "txid": TXID,
+                    "vout": 0,
+                    "status": {"confirmed": true, "block_height": 101},
+                    "valuecommitment": "08aa",
+                    "assetcommitment": "0abb"
+                },
+                {
+                    "txid": "ee".repeat(32),
+                    "vout": 1,
+                    "status": {"confirmed": false},
+                    "value": 700,
+                    "asset": asset.to_string()
+                }
+            ]));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path(format!("/tx/{TXID}/hex"));
+            then.status(200)
+                .body(elements::encode::serialize_hex(&transaction));
+        });
+
+        let addresses = [address.to_string()];
+        let esplora = EsploraBackend::new(server.base_url()).with_blinding_key(blinding_key);
+        let utxos = esplora.list_unspent(&addresses).await.unwrap();
+        assert_eq!(utxos.len(), 2);
+        assert_eq!(utxos[0].amount, Amount::from_sat(5_000));
+        assert_eq!(utxos[0].asset, asset.to_string());
+        assert_eq!(utxos[0].confirmations, Some(10));
+        assert!(utxos[0].amountblinder.is_some() && utxos[0].assetblinder.is_some());
+        assert_eq!(
+            utxos[0].scriptpubkey,
+            Some(hex::encode(address.script_pubkey().as_bytes()))
+        );
+        assert_eq!(utxos[1].amount, Amount::from_sat(700));
+        assert_eq!(utxos[1].confirmations, Some(0));
+        assert_eq!(utxos[1].amountblinder, None);
+
+        // Without the blinding key only the explicit output is returned
+        let utxos = EsploraBackend::new(server.base_url())
+            .list_unspent(&addresses)
+            .await
+            .unwrap();
+        assert_eq!(utxos.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_esplora_broadcast_reports_rejection() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(POST).path("/tx").body("00ff");
+            then.status(400)
+                .body("sendrawtransaction RPC error: bad-txns-inputs-missingorspent");
+        });
+
+        let err = EsploraBackend::new(server.base_url())
+            .broadcast("00ff")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("missingorspent"));
+    }
+}
@@ -1,15 +1,20 @@
+use std::collections::HashMap;
 use std::env;
+use std::future::Future;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration as StdDuration;
 
 use async_trait::async_trait;
 use chrono::{Duration, Utc};
 
+use futures::stream::{self, Stream, TryStreamExt};
+use once_cell::sync::Lazy;
 use reqwest::header::AUTHORIZATION;
 use reqwest::{Client, Method, Url};
 use serde::de::DeserializeOwned;
 use thiserror::Error;
-use tokio::sync::{Mutex, OnceCell, Semaphore};
+use tokio::sync::{Mutex, Semaphore};
 use tokio::time::sleep;
 
 use elements::encode::Decodable;
@@ -17,17 +22,34 @@ use secrecy::ExposeSecret;
 use secrecy::Secret;
 use std::str::FromStr;
 
+use crate::amount::Amount;
+use crate::batch::{BatchDistributionReport, BatchPlan, BatchResult, BatchStatus};
+use crate::chain::{self, ChainBackend, FundingAddresses};
+use crate::coin_selection::{CoinSelector, LargestFirst};
+use crate::confirmation::{ConfirmedOperation, ReorgMonitor};
+use crate::distribution::{
+    CheckpointError, CheckpointStore, DistributionCheckpoint, DistributionFunding,
+    DistributionState, InMemoryCheckpointStore,
+};
+use crate::dry_run::{
+    DryRunOperation, DryRunPlan, OutputKind, PlannedAmpCall, PlannedOutput, PlannedRpcCall,
+};
+use crate::fee::{self, FeeRate, FeeStrategy};
 use crate::model::{
     Activity, AddressGaidResponse, Asset, AssetActivityParams, AssetDistributionAssignment,
     AssetLostOutputs, AssetSummary, AssetTransaction, AssetTransactionParams, Assignment, Balance,
     BroadcastResponse, CategoriesRequest, CategoryAdd, CategoryEdit, CategoryResponse,
-    ChangePasswordRequest, ChangePasswordResponse, CreateAssetAssignmentRequest, EditAssetRequest,
-    GaidBalanceEntry, IssuanceRequest, IssuanceResponse, Outpoint, Ownership, Password,
-    ReceivedByAddress, RegisterAssetResponse, RegisteredUserResponse, Reissuance, TokenData,
-    TokenInfo, TokenRequest, TokenResponse, TransactionDetail, TxInput, Unspent,
-    UpdateBlindersRequest, Utxo, ValidateGaidResponse,
+    ChangePasswordRequest, ChangePasswordResponse, CreateAssetAssignmentRequest,
+    DistributionTransaction, EditAssetRequest, GaidBalanceEntry, IssuanceRequest, IssuanceResponse,
+    Outpoint, Ownership, Password, ReceivedByAddress, RegisterAssetResponse,
+    RegisteredUserResponse, Reissuance, ReissueRequestResponse, TokenData, TokenInfo, TokenRequest,
+    TokenResponse, TransactionDetail, TxInput, Unspent, UpdateBlindersRequest, Utxo,
+    ValidateGaidResponse, WalletTransaction,
 };
+use crate::network::Network;
+use crate::pset_builder::{PsetBuilder, PsetBuilderError};
 use crate::signer::{Signer, SignerError};
+use crate::token_store::{FileTokenStore, TokenStore, DEFAULT_TOKEN_FILE};
 
 /// Environment variables used for token environment detection
 #[derive(Debug)]
@@ -356,6 +378,17 @@ impl LiveTokenStrategy {
     /// Returns an error if the `TokenManager` cannot be initialized
     pub async fn with_config(config: RetryConfig) -> Result<Self, Error> {
         let base_url = get_amp_api_base_url()?;
+        Self::with_config_and_base_url(config, base_url).await
+    }
+
+    /// Creates a live token strategy with custom retry configuration and base URL
+    ///
+    /// # Errors
+    /// Returns an error if the `TokenManager` cannot be initialized
+    pub async fn with_config_and_base_url(
+        config: RetryConfig,
+        base_url: Url,
+    ) -> Result<Self, Error> {
         let token_manager =
             Arc::new(TokenManager::with_config_and_base_url(config, base_url).await?);
         Ok(Self { token_manager })
@@ -482,9 +515,15 @@ pub enum AmpError {
     #[error("Signer error: {0}")]
     Signer(#[from] SignerError),
 
+    #[error("Distribution checkpoint error: {0}")]
+    Checkpoint(#[from] CheckpointError),
+
     #[error("Timeout waiting for confirmations: {0}")]
     Timeout(String),
 
+    #[error("Not found: {0}")]
+    NotFound(String),
+
     #[error("Validation error: {0}")]
     Validation(String),
 
@@ -558,11 +597,21 @@ impl AmpError {
                 raw_response,
             },
             Self::Timeout(msg) => Self::Timeout(format!("{context_str}: {msg}")),
+            Self::NotFound(msg) => Self::NotFound(format!("{context_str}: {msg}")),
             Self::Validation(msg) => Self::Validation(format!("{context_str}: {msg}")),
             other => other, // Don't modify other error types
         }
     }
 
+    /// Returns true if the requested item definitely does not exist
+    ///
+    /// Lookup failures such as connection errors are not "not found"; the item may still
+    /// exist.
+    #[must_use]
+    pub const fn is_not_found(&self) -> bool {
+        matches!(self, Self::NotFound(_))
+    }
+
     /// Returns true if this error indicates a retryable condition
     #[must_use]
     pub const fn is_retryable(&self) -> bool {
@@ -814,6 +863,7 @@ pub struct ElementsRpc {
     base_url: String,
     username: String,
     password: String,
+    network: Arc<tokio::sync::OnceCell<Network>>,
 }
 
 /// Network information from Elements node
@@ -861,6 +911,20 @@ pub struct BlockchainInfo {
     pub warnings: Option<String>,
 }
 
+/// Block header from Elements node (`getblockheader`)
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct BlockHeader {
+    pub hash: String,
+    pub height: i64,
+    pub confirmations: i64,
+    /// Block timestamp in seconds since the Unix epoch
+    pub time: i64,
+    #[serde(default)]
+    pub mediantime: Option<i64>,
+    #[serde(default)]
+    pub previousblockhash: Option<String>,
+}
+
 /// RPC request structure for Elements node
 #[derive(Debug, serde::Serialize)]
 struct RpcRequest {
@@ -888,6 +952,21 @@ struct RpcError {
     message: String,
 }
 
+/// JSON-RPC error code for an unknown address, key or transaction id
+const RPC_INVALID_ADDRESS_OR_KEY: i32 = -5;
+
+/// L-BTC change below this amount is not worth an output of its own and goes to the fee
+const DISTRIBUTION_DUST_THRESHOLD: Amount = Amount::from_sat(1000);
+
+/// L-BTC inputs paying the fee of a transaction, with the fee and L-BTC change
+#[derive(Debug)]
+struct FeePayment {
+    inputs: Vec<Unspent>,
+    fee: Amount,
+    change: Amount,
+    vsize: usize,
+}
+
 impl ElementsRpc {
     /// Creates a new `ElementsRpc` client with connection parameters
     ///
@@ -921,9 +1000,93 @@ impl ElementsRpc {
             base_url: url,
             username,
             password,
+            network: Arc::new(tokio::sync::OnceCell::new()),
         }
     }
 
+    /// Sets the network of the node instead of detecting it
+    ///
+    /// Without an explicit network, [`network`](Self::network) asks the node on first use.
+    ///
+    /// # Examples
+    /// ```
+    /// use amp_rs::ElementsRpc;
+    /// use amp_rs::network::Network;
+    ///
+    /// let rpc = ElementsRpc::new(
+    ///     "http://localhost:18884".to_string(),
+    ///     "user".to_string(),
+    ///     "pass".to_string()
+    /// )
+    /// .with_network(Network::LiquidTestnet);
+    /// ```
+    #[must_use]
+    pub fn with_network(mut self, network: Network) -> Self {
+        self.network = Arc::new(tokio::sync::OnceCell::new_with(Some(network)));
+        self
+    }
+
+    /// Returns the network of the node
+    ///
+    /// Uses the network set with [`with_network`](Self::with_network). Otherwise the
+    /// network is detected from the `chain` reported by `getblockchaininfo` and cached;
+    /// on regtest chains the policy asset is read from `dumpassetlabels`.
+    ///
+    /// # Errors
+    /// Returns an error if the node cannot be queried or reports an unknown chain
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use amp_rs::ElementsRpc;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let rpc = ElementsRpc::from_env()?;
+    /// let network = rpc.network().await?;
+    /// println!("Fees are paid in {}", network.policy_asset());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn network(&self) -> Result<Network, AmpError> {
+        self.network
+            .get_or_try_init(|| self.detect_network())
+            .await
+            .cloned()
+    }
+
+    /// Detects the network from the node's chain and asset labels
+    async fn detect_network(&self) -> Result<Network, AmpError> {
+        let chain = self.get_blockchain_info().await?.chain;
+
+        let policy_asset = if Network::from_chain(&chain, None)
+            .is_ok_and(|network| matches!(network, Network::ElementsRegtest { .. }))
+        {
+            match self
+                .rpc_call::<HashMap<String, String>>("dumpassetlabels", serde_json::json!([]))
+                .await
+            {
+                Ok(mut labels) => labels.remove("bitcoin"),
+                Err(e) => {
+                    tracing::warn!(
+                        "Could not read the regtest policy asset, assuming the default: {}",
+                        e
+                    );
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let network = Network::from_chain(&chain, policy_asset.as_deref())
+            .map_err(|e| AmpError::validation(e.to_string()))?;
+        tracing::info!(
+            "Detected Elements network {} with policy asset {}",
+            network,
+            network.policy_asset()
+        );
+        Ok(network)
+    }
+
     /// Creates a new `ElementsRpc` client from environment variables
     ///
     /// Expected environment variables:
@@ -989,6 +1152,13 @@ impl ElementsRpc {
                 .text()
                 .await
                 .unwrap_or_else(|_| "Unable to read error body".to_string());
+            // The node reports RPC errors with an error status and a JSON-RPC error body
+            if let Some(error) = serde_json::from_str::<RpcResponse<serde_json::Value>>(&error_body)
+                .ok()
+                .and_then(|rpc_response| rpc_response.error)
+            {
+                return Err(Self::rpc_error(method, error));
+            }
             return Err(AmpError::rpc(format!(
                 "RPC request failed with status: {status} - Body: {error_body}"
             )));
@@ -1000,10 +1170,7 @@ impl ElementsRpc {
             .map_err(|e| AmpError::rpc(format!("Failed to parse RPC response: {e}")))?;
 
         if let Some(error) = rpc_response.error {
-            return Err(AmpError::rpc(format!(
-                "RPC error {}: {}",
-                error.code, error.message
-            )));
+            return Err(Self::rpc_error(method, error));
         }
 
         rpc_response
@@ -1011,6 +1178,21 @@ impl ElementsRpc {
             .ok_or_else(|| AmpError::rpc("RPC response missing result field".to_string()))
     }
 
+    /// Converts a JSON-RPC error of `method` into an `AmpError`
+    ///
+    /// Transaction lookups failing with `RPC_INVALID_ADDRESS_OR_KEY` mean the node does
+    /// not know the transaction and become `AmpError::NotFound`.
+    fn rpc_error(method: &str, error: RpcError) -> AmpError {
+        let message = format!("RPC error {}: {}", error.code, error.message);
+        if error.code == RPC_INVALID_ADDRESS_OR_KEY
+            && matches!(method, "gettransaction" | "getrawtransaction")
+        {
+            AmpError::NotFound(message)
+        } else {
+            AmpError::rpc(message)
+        }
+    }
+
     /// Retrieves network information from the Elements node
     ///
     /// # Errors
@@ -1053,6 +1235,73 @@ impl ElementsRpc {
             .await
     }
 
+    /// Returns the hash of the block at `height` in the active chain
+    ///
+    /// # Errors
+    /// Returns an error if the RPC call fails or the height is beyond the tip
+    pub async fn get_block_hash(&self, height: i64) -> Result<String, AmpError> {
+        self.rpc_call("getblockhash", serde_json::json!([height]))
+            .await
+    }
+
+    /// Returns the header of the block with hash `block_hash`
+    ///
+    /// # Errors
+    /// Returns an error if the RPC call fails or the block is unknown
+    pub async fn get_block_header(&self, block_hash: &str) -> Result<BlockHeader, AmpError> {
+        self.rpc_call("getblockheader", serde_json::json!([block_hash, true]))
+            .await
+    }
+
+    /// Returns the height of the last block with a timestamp at or before `at`
+    ///
+    /// Binary searches the active chain by block timestamp. Liquid blocks are signed by
+    /// the federation at fixed intervals, so their timestamps increase with height.
+    ///
+    /// # Errors
+    /// Returns an error if an RPC call fails or `at` is before the genesis block
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use amp_rs::ElementsRpc;
+    /// # use chrono::{TimeZone, Utc};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let rpc = ElementsRpc::from_env()?;
+    /// let record_date = Utc.with_ymd_and_hms(2025, 6, 30, 23, 59, 59).unwrap();
+    /// println!("Record height: {}", rpc.height_at(record_date).await?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn height_at(&self, at: chrono::DateTime<Utc>) -> Result<i64, AmpError> {
+        let target = at.timestamp();
+        let mut high = self.get_blockchain_info().await?.blocks;
+        if self.block_time(high).await? <= target {
+            return Ok(high);
+        }
+        let mut low = 0;
+        if self.block_time(low).await? > target {
+            return Err(AmpError::validation(format!(
+                "{at} is before the genesis block"
+            )));
+        }
+        // Invariant: block `low` is at or before the target, block `high` is after it
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            if self.block_time(mid).await? <= target {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(low)
+    }
+
+    async fn block_time(&self, height: i64) -> Result<i64, AmpError> {
+        let hash = self.get_block_hash(height).await?;
+        Ok(self.get_block_header(&hash).await?.time)
+    }
+
     /// Unlocks the wallet with a passphrase for the specified timeout
     ///
     /// # Arguments
@@ -1259,13 +1508,13 @@ impl ElementsRpc {
             .map_err(|e| AmpError::rpc(format!("Failed to parse listunspent RPC response: {e}")))?;
 
         if let Some(error) = rpc_response.error {
-            let error_msg = if asset_id.is_some() {
+            let error_msg = if let Some(asset_id) = asset_id {
                 format!(
                     "Failed to list unspent outputs for asset {}. \
                     This may indicate that the treasury address is not imported in the Elements node. \
                     Ensure the treasury address is properly imported as a watch-only address. \
                     RPC error: {} (code: {})",
-                    asset_id.unwrap(),
+                    asset_id,
                     error.message,
                     error.code
                 )
@@ -1283,19 +1532,38 @@ impl ElementsRpc {
         tracing::debug!("Found {} unspent outputs", utxos.len());
 
         // If we're looking for a specific asset and found no UTXOs, provide helpful context
-        if utxos.is_empty() && asset_id.is_some() {
-            tracing::warn!(
-                "No UTXOs found for asset {}. This may indicate:\n\
-                1. The treasury address is not imported in the Elements node\n\
-                2. The asset issuance transaction hasn't been confirmed yet\n\
-                3. The UTXOs have already been spent",
-                asset_id.unwrap()
-            );
+        if utxos.is_empty() {
+            if let Some(asset_id) = asset_id {
+                tracing::warn!(
+                    "No UTXOs found for asset {}. This may indicate:\n\
+                    1. The treasury address is not imported in the Elements node\n\
+                    2. The asset issuance transaction hasn't been confirmed yet\n\
+                    3. The UTXOs have already been spent",
+                    asset_id
+                );
+            }
         }
 
         Ok(utxos)
     }
 
+    /// Lists the unspent outputs paying to any of `addresses`
+    ///
+    /// The query goes through the node's default wallet, which must track the addresses
+    /// (for example as watch-only imports) and holds their blinding keys.
+    ///
+    /// # Errors
+    /// Returns an error if the RPC call fails
+    pub async fn list_unspent_for_addresses(
+        &self,
+        addresses: &[String],
+    ) -> Result<Vec<Unspent>, AmpError> {
+        tracing::debug!("Listing unspent outputs for {} addresses", addresses.len());
+        self.rpc_call("listunspent", serde_json::json!([0, 9_999_999, addresses]))
+            .await
+            .map_err(|e| e.with_context("Failed to list unspent outputs for addresses"))
+    }
+
     /// List unspent outputs for a specific wallet
     ///
     /// This method lists unspent transaction outputs (UTXOs) for a specific wallet,
@@ -1491,15 +1759,17 @@ impl ElementsRpc {
         );
 
         // If we're looking for a specific asset and found no UTXOs, provide helpful context
-        if utxos.is_empty() && asset_id.is_some() {
-            tracing::warn!(
-                "No UTXOs found for asset {} in wallet {}. This may indicate:\n\
-                1. The asset issuance transaction hasn't been confirmed yet\n\
-                2. The UTXOs have already been spent\n\
-                3. The wallet doesn't contain the expected addresses",
-                asset_id.unwrap(),
-                wallet_name
-            );
+        if utxos.is_empty() {
+            if let Some(asset_id) = asset_id {
+                tracing::warn!(
+                    "No UTXOs found for asset {} in wallet {}. This may indicate:\n\
+                    1. The asset issuance transaction hasn't been confirmed yet\n\
+                    2. The UTXOs have already been spent\n\
+                    3. The wallet doesn't contain the expected addresses",
+                    asset_id,
+                    wallet_name
+                );
+            }
         }
 
         Ok(utxos)
@@ -1517,7 +1787,7 @@ impl ElementsRpc {
     ///
     /// # Examples
     /// ```no_run
-    /// # use amp_rs::{ElementsRpc, model::{TxInput}};
+    /// # use amp_rs::{Amount, ElementsRpc, model::{TxInput}};
     /// # use std::collections::HashMap;
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -1528,7 +1798,7 @@ impl ElementsRpc {
     ///     sequence: None,
     /// }];
     /// let mut outputs = HashMap::new();
-    /// outputs.insert("address1".to_string(), 100.0);
+    /// outputs.insert("address1".to_string(), Amount::from_btc_str("100")?);
     /// let mut assets = HashMap::new();
     /// assets.insert("address1".to_string(), "asset_id".to_string());
     /// let raw_tx = rpc.create_raw_transaction(inputs, outputs, assets).await?;
@@ -1539,7 +1809,7 @@ impl ElementsRpc {
     pub async fn create_raw_transaction(
         &self,
         inputs: Vec<TxInput>,
-        outputs: std::collections::HashMap<String, f64>,
+        outputs: std::collections::HashMap<String, Amount>,
         assets: std::collections::HashMap<String, String>,
     ) -> Result<String, AmpError> {
         tracing::debug!(
@@ -1548,6 +1818,12 @@ impl ElementsRpc {
             outputs.len()
         );
 
+        // Send amounts as exact decimal strings rather than floats
+        let outputs: std::collections::HashMap<String, String> = outputs
+            .into_iter()
+            .map(|(address, amount)| (address, amount.to_btc_string()))
+            .collect();
+
         // Elements RPC createrawtransaction expects:
         // createrawtransaction inputs outputs locktime replaceable assets
         let params = serde_json::json!([
@@ -1683,7 +1959,7 @@ impl ElementsRpc {
         &self,
         wallet_name: &str,
         inputs: Vec<TxInput>,
-        outputs: std::collections::HashMap<String, f64>,
+        outputs: std::collections::HashMap<String, Amount>,
         assets: std::collections::HashMap<String, String>,
     ) -> Result<String, AmpError> {
         tracing::debug!(
@@ -1705,11 +1981,8 @@ impl ElementsRpc {
                 AmpError::validation(format!("No asset ID found for address {address}"))
             })?;
 
-            // Convert amount to string with proper precision for Elements
-            let amount_str = format!("{amount:.8}");
-
             outputs_array.push(serde_json::json!({
-                address.clone(): amount_str,
+                address.clone(): amount.to_btc_string(),
                 "asset": asset_id
             }));
         }
@@ -2530,6 +2803,80 @@ impl ElementsRpc {
         Ok(tx_detail)
     }
 
+    /// Lists the most recent transactions of a wallet using Elements' listtransactions RPC
+    ///
+    /// Each output a transaction sends or receives is a separate entry.
+    ///
+    /// # Arguments
+    /// * `wallet_name` - Name of the Elements wallet to query
+    /// * `count` - Maximum number of entries to return, newest last
+    ///
+    /// # Errors
+    /// Returns an error if the wallet cannot be loaded or the RPC call fails
+    pub async fn list_transactions(
+        &self,
+        wallet_name: &str,
+        count: u32,
+    ) -> Result<Vec<WalletTransaction>, AmpError> {
+        tracing::debug!(
+            "Listing the last {} transactions of wallet {}",
+            count,
+            wallet_name
+        );
+
+        // First load the wallet to ensure it's available
+        self.load_wallet(wallet_name).await?;
+
+        let request = RpcRequest {
+            jsonrpc: "1.0".to_string(),
+            id: "amp-client".to_string(),
+            method: "listtransactions".to_string(),
+            params: serde_json::json!(["*", count, 0]),
+        };
+
+        // Use the wallet-specific RPC endpoint
+        let base = self.base_url.trim_end_matches('/');
+        let wallet_url = format!("{base}/wallet/{wallet_name}");
+
+        let response = self
+            .client
+            .post(&wallet_url)
+            .basic_auth(&self.username, Some(&self.password))
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| {
+                AmpError::rpc(format!("Failed to send listtransactions RPC request: {e}"))
+            })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unable to read error body".to_string());
+            return Err(AmpError::rpc(format!(
+                "Listtransactions RPC request failed with status: {status} - Body: {error_body}"
+            )));
+        }
+
+        let rpc_response: RpcResponse<Vec<WalletTransaction>> =
+            response.json().await.map_err(|e| {
+                AmpError::rpc(format!(
+                    "Failed to parse listtransactions RPC response: {e}"
+                ))
+            })?;
+
+        if let Some(error) = rpc_response.error {
+            return Err(AmpError::rpc(format!(
+                "Listtransactions RPC error: {} (code: {})",
+                error.message, error.code
+            )));
+        }
+
+        Ok(rpc_response.result.unwrap_or_default())
+    }
+
     /// Sends multiple outputs to multiple addresses using Elements' sendmany RPC
     ///
     /// This method uses Elements' built-in sendmany command which properly handles
@@ -2555,15 +2902,15 @@ impl ElementsRpc {
     ///
     /// # Examples
     /// ```no_run
-    /// # use amp_rs::ElementsRpc;
+    /// # use amp_rs::{Amount, ElementsRpc};
     /// # use std::collections::HashMap;
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let rpc = ElementsRpc::from_env()?;
     ///
     /// let mut address_amounts = HashMap::new();
-    /// address_amounts.insert("address1".to_string(), 100.0);
-    /// address_amounts.insert("address2".to_string(), 50.0);
+    /// address_amounts.insert("address1".to_string(), Amount::from_btc_str("100")?);
+    /// address_amounts.insert("address2".to_string(), Amount::from_btc_str("50")?);
     ///
     /// let mut asset_amounts = HashMap::new();
     /// asset_amounts.insert("address1".to_string(), "asset_id_hex".to_string());
@@ -2578,7 +2925,7 @@ impl ElementsRpc {
     pub async fn sendmany(
         &self,
         wallet_name: &str,
-        address_amounts: std::collections::HashMap<String, f64>,
+        address_amounts: std::collections::HashMap<String, Amount>,
         asset_amounts: std::collections::HashMap<String, String>,
         min_conf: Option<u32>,
         comment: Option<&str>,
@@ -2596,6 +2943,12 @@ impl ElementsRpc {
         // First load the wallet to ensure it's available
         self.load_wallet(wallet_name).await?;
 
+        // Send amounts as exact decimal strings rather than floats
+        let address_amounts: std::collections::HashMap<String, String> = address_amounts
+            .into_iter()
+            .map(|(address, amount)| (address, amount.to_btc_string()))
+            .collect();
+
         // Elements sendmany parameters:
         // 1. dummy (empty string for compatibility)
         // 2. amounts (map of address -> amount)
@@ -2963,12 +3316,12 @@ impl ElementsRpc {
     ///
     /// # Examples
     /// ```no_run
-    /// # use amp_rs::ElementsRpc;
+    /// # use amp_rs::{Amount, ElementsRpc};
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let rpc = ElementsRpc::from_env()?;
     /// let asset_id = "6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d";
-    /// let amount = 1000000.0; // 0.01 of an asset with 8 decimals
+    /// let amount = Amount::from_btc_str("0.01")?;
     /// let result = rpc.reissueasset("test_wallet", asset_id, amount).await?;
     /// println!("Reissuance txid: {}, vin: {}", result["txid"], result["vin"]);
     /// # Ok(())
@@ -2978,7 +3331,7 @@ impl ElementsRpc {
         &self,
         wallet_name: &str,
         asset_id: &str,
-        amount: f64,
+        amount: Amount,
     ) -> Result<serde_json::Value, AmpError> {
         tracing::debug!(
             "Reissuing asset {} with amount {} in wallet {}",
@@ -2990,7 +3343,7 @@ impl ElementsRpc {
         // First load the wallet to ensure it's available
         self.load_wallet(wallet_name).await?;
 
-        let params = serde_json::json!([asset_id, amount]);
+        let params = serde_json::json!([asset_id, amount.to_btc_string()]);
 
         let request = RpcRequest {
             jsonrpc: "1.0".to_string(),
@@ -3126,12 +3479,12 @@ impl ElementsRpc {
     ///
     /// # Examples
     /// ```no_run
-    /// # use amp_rs::ElementsRpc;
+    /// # use amp_rs::{Amount, ElementsRpc};
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let rpc = ElementsRpc::from_env()?;
     /// let asset_id = "6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d";
-    /// let amount = 1000.0; // Burn 1000 units
+    /// let amount = Amount::from_sat(1000); // Burn 1000 satoshis
     ///
     /// let txid = rpc.destroyamount("test_wallet", asset_id, amount).await?;
     /// println!("Burn transaction created: {}", txid);
@@ -3142,7 +3495,7 @@ impl ElementsRpc {
         &self,
         wallet_name: &str,
         asset_id: &str,
-        amount: f64,
+        amount: Amount,
     ) -> Result<String, AmpError> {
         tracing::debug!(
             "Burning asset {} with amount {} in wallet {}",
@@ -3154,7 +3507,7 @@ impl ElementsRpc {
         // First load the wallet to ensure it's available
         self.load_wallet(wallet_name).await?;
 
-        let params = serde_json::json!([asset_id, amount]);
+        let params = serde_json::json!([asset_id, amount.to_btc_string()]);
 
         let request = RpcRequest {
             jsonrpc: "1.0".to_string(),
@@ -3428,6 +3781,9 @@ impl ElementsRpc {
     /// 2. Sorts UTXOs by amount (largest first) for efficiency
     /// 3. Selects UTXOs until the target amount plus estimated fees is covered
     ///
+    /// Use [`select_utxos_with_selector`](Self::select_utxos_with_selector) to choose a
+    /// different [`CoinSelector`] strategy.
+    ///
     /// # Arguments
     /// * `asset_id` - The asset ID to select UTXOs for
     /// * `target_amount` - The total amount needed for distribution
@@ -3441,15 +3797,15 @@ impl ElementsRpc {
     ///
     /// # Examples
     /// ```no_run
-    /// # use amp_rs::ElementsRpc;
+    /// # use amp_rs::{Amount, ElementsRpc};
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let rpc = ElementsRpc::from_env()?;
     /// let (selected_utxos, total_amount) = rpc.select_utxos_for_amount(
     ///     "wallet_name",
     ///     "asset_id_hex",
-    ///     150.0,
-    ///     0.001
+    ///     Amount::from_btc_str("150")?,
+    ///     Amount::from_btc_str("0.001")?
     /// ).await?;
     /// println!("Selected {} UTXOs totaling {}", selected_utxos.len(), total_amount);
     /// # Ok(())
@@ -3459,13 +3815,68 @@ impl ElementsRpc {
         &self,
         wallet_name: &str,
         asset_id: &str,
-        target_amount: f64,
-        estimated_fee: f64,
-    ) -> Result<(Vec<Unspent>, f64), AmpError> {
+        target_amount: Amount,
+        estimated_fee: Amount,
+    ) -> Result<(Vec<Unspent>, Amount), AmpError> {
+        self.select_utxos_with_selector(
+            wallet_name,
+            asset_id,
+            target_amount,
+            estimated_fee,
+            &LargestFirst,
+        )
+        .await
+    }
+
+    /// Selects UTXOs to cover the required amount plus fees using the given strategy
+    ///
+    /// The wallet's spendable UTXOs for `asset_id` are fetched with `listunspent` and
+    /// passed to `selector`, which decides which of them to spend.
+    ///
+    /// # Arguments
+    /// * `asset_id` - The asset ID to select UTXOs for
+    /// * `target_amount` - The total amount needed for distribution
+    /// * `estimated_fee` - Estimated transaction fee in the same asset
+    /// * `selector` - The coin selection strategy
+    ///
+    /// # Returns
+    /// Returns a tuple of (`selected_utxos`, `total_selected_amount`)
+    ///
+    /// # Errors
+    /// Returns an error if the RPC calls fail, no spendable UTXOs exist, or the selector
+    /// cannot cover the required amount
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use amp_rs::{Amount, ElementsRpc};
+    /// # use amp_rs::coin_selection::BranchAndBound;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let rpc = ElementsRpc::from_env()?;
+    /// let (selected_utxos, total_amount) = rpc.select_utxos_with_selector(
+    ///     "wallet_name",
+    ///     "asset_id_hex",
+    ///     Amount::from_btc_str("150")?,
+    ///     Amount::ZERO,
+    ///     &BranchAndBound::default(),
+    /// ).await?;
+    /// println!("Selected {} UTXOs totaling {}", selected_utxos.len(), total_amount);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn select_utxos_with_selector(
+        &self,
+        wallet_name: &str,
+        asset_id: &str,
+        target_amount: Amount,
+        estimated_fee: Amount,
+        selector: &dyn CoinSelector,
+    ) -> Result<(Vec<Unspent>, Amount), AmpError> {
         tracing::debug!(
-            "Selecting UTXOs for asset {} from wallet {} - target: {}, fee: {}",
+            "Selecting UTXOs for asset {} from wallet {} with {:?} - target: {}, fee: {}",
             asset_id,
             wallet_name,
+            selector,
             target_amount,
             estimated_fee
         );
@@ -3493,33 +3904,14 @@ impl ElementsRpc {
             )));
         }
 
-        // Sort UTXOs by amount (largest first) for efficient selection
-        utxos.sort_by(|a, b| {
-            b.amount
-                .partial_cmp(&a.amount)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
-
         let required_amount = target_amount + estimated_fee;
-        let mut selected_utxos = Vec::new();
-        let mut total_selected = 0.0;
-
-        // Select UTXOs until we have enough to cover the required amount
-        for utxo in utxos {
-            selected_utxos.push(utxo.clone());
-            total_selected += utxo.amount;
-
-            if total_selected >= required_amount {
-                break;
-            }
-        }
-
-        // Check if we have sufficient funds
-        if total_selected < required_amount {
-            return Err(AmpError::validation(format!(
-                "Insufficient UTXOs: need {required_amount}, have {total_selected} (target: {target_amount}, fee: {estimated_fee})"
-            )));
-        }
+        let selection = selector.select(&utxos, required_amount).map_err(|e| {
+            AmpError::validation(format!(
+                "{e} (target: {target_amount}, fee: {estimated_fee})"
+            ))
+        })?;
+        let selected_utxos = selection.selected;
+        let total_selected = selection.total;
 
         tracing::info!(
             "Selected {} UTXOs totaling {} for target {} + fee {}",
@@ -3534,194 +3926,530 @@ impl ElementsRpc {
 
     /// Builds a raw transaction for asset distribution with proper change handling
     ///
+    /// Asset UTXOs are chosen largest first; use
+    /// [`build_distribution_transaction_with_selector`](Self::build_distribution_transaction_with_selector)
+    /// to choose a different [`CoinSelector`] strategy and to get the fee details needed
+    /// for fee bumping.
+    ///
     /// This method orchestrates the complete transaction building process:
     /// 1. Selects appropriate UTXOs using `select_utxos_for_amount`
-    /// 2. Creates transaction inputs from selected UTXOs
-    /// 3. Creates outputs for distribution addresses
-    /// 4. Calculates and creates change output if necessary
-    /// 5. Builds the raw transaction using `create_raw_transaction`
+    /// 2. Resolves the fee rate and selects L-BTC UTXOs covering the fee
+    /// 3. Creates outputs for distribution addresses, asset and L-BTC change and the fee
+    /// 4. Builds the raw transaction and blinds it
+    ///
+    /// The fee is the fee rate applied to the estimated size of the blinded, signed
+    /// transaction (see [`fee::estimate_vsize`]).
     ///
     /// # Arguments
     /// * `asset_id` - The asset ID being distributed
     /// * `address_amounts` - Map of recipient addresses to amounts
-    /// * `change_address` - Address to send change to (if any)
-    /// * `estimated_fee` - Estimated transaction fee
+    /// * `change_address` - Address to send asset and L-BTC change to
+    /// * `fee` - Fee rate to pay, or a confirmation target passed to `estimatesmartfee`
     ///
     /// # Returns
-    /// Returns a tuple of (`raw_transaction_hex`, `selected_utxos`, `change_amount`)
+    /// Returns a tuple of (`raw_transaction_hex`, `selected_utxos`, `change_amount`), where
+    /// `selected_utxos` includes the L-BTC fee inputs and `change_amount` is the asset change
     ///
     /// # Errors
-    /// Returns an error if UTXO selection fails or transaction building fails
+    /// Returns an error if UTXO selection fails, the wallet lacks L-BTC for the fee, or
+    /// transaction building fails
     ///
     /// # Examples
     /// ```no_run
-    /// # use amp_rs::ElementsRpc;
+    /// # use amp_rs::{Amount, ElementsRpc};
+    /// # use amp_rs::fee::{FeeRate, FeeStrategy};
     /// # use std::collections::HashMap;
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let rpc = ElementsRpc::from_env()?;
     /// let mut address_amounts = HashMap::new();
-    /// address_amounts.insert("address1".to_string(), 100.0);
-    /// address_amounts.insert("address2".to_string(), 50.0);
+    /// address_amounts.insert("address1".to_string(), Amount::from_btc_str("100")?);
+    /// address_amounts.insert("address2".to_string(), Amount::from_btc_str("50")?);
     ///
     /// let (raw_tx, utxos, change) = rpc.build_distribution_transaction(
     ///     "wallet_name",
     ///     "asset_id_hex",
     ///     address_amounts,
     ///     "change_address",
-    ///     0.001
+    ///     FeeStrategy::Rate(FeeRate::from_sat_per_vb(1)),
     /// ).await?;
     /// println!("Built transaction with {} inputs, change: {}", utxos.len(), change);
     /// # Ok(())
     /// # }
     /// ```
-    #[allow(clippy::cognitive_complexity)]
-    #[allow(clippy::too_many_lines)]
     pub async fn build_distribution_transaction(
         &self,
         wallet_name: &str,
         asset_id: &str,
-        address_amounts: std::collections::HashMap<String, f64>,
+        address_amounts: std::collections::HashMap<String, Amount>,
         change_address: &str,
-        _estimated_fee: f64,
-    ) -> Result<(String, Vec<Unspent>, f64), AmpError> {
-        const DUST_THRESHOLD: f64 = 0.00001;
-        const LBTC_ASSET_ID: &str =
-            "144c654344aa716d6f3abcc1ca90e5641e4e2a7f633bc09fe3baf64585819a49"; // L-BTC on Liquid testnet
+        fee: FeeStrategy,
+    ) -> Result<(String, Vec<Unspent>, Amount), AmpError> {
+        let transaction = self
+            .build_distribution_transaction_with_selector(
+                wallet_name,
+                asset_id,
+                address_amounts,
+                change_address,
+                fee,
+                &LargestFirst,
+            )
+            .await?;
+        let inputs = transaction.inputs();
+
+        Ok((
+            transaction.raw_transaction,
+            inputs,
+            transaction.asset_change,
+        ))
+    }
 
+    /// Builds a raw transaction for asset distribution, choosing asset UTXOs with `selector`
+    ///
+    /// Behaves like [`build_distribution_transaction`](Self::build_distribution_transaction)
+    /// except that the asset inputs are picked by the given [`CoinSelector`] and the full
+    /// [`DistributionTransaction`] is returned. L-BTC inputs for the fee are picked largest
+    /// first. All inputs signal replace-by-fee, so a transaction that gets stuck can be
+    /// replaced with [`bump_distribution_fee`](Self::bump_distribution_fee).
+    ///
+    /// # Errors
+    /// Returns an error if UTXO selection fails, the wallet lacks L-BTC for the fee, or
+    /// transaction building fails
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use amp_rs::{Amount, ElementsRpc};
+    /// # use amp_rs::coin_selection::OldestFirst;
+    /// # use amp_rs::fee::FeeStrategy;
+    /// # use std::collections::HashMap;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let rpc = ElementsRpc::from_env()?;
+    /// let mut address_amounts = HashMap::new();
+    /// address_amounts.insert("address1".to_string(), Amount::from_btc_str("100")?);
+    ///
+    /// let transaction = rpc.build_distribution_transaction_with_selector(
+    ///     "wallet_name",
+    ///     "asset_id_hex",
+    ///     address_amounts,
+    ///     "change_address",
+    ///     FeeStrategy::ConfTarget(2),
+    ///     &OldestFirst,
+    /// ).await?;
+    /// println!("Fee: {} ({} vB at {})", transaction.fee, transaction.vsize, transaction.fee_rate);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn build_distribution_transaction_with_selector(
+        &self,
+        wallet_name: &str,
+        asset_id: &str,
+        address_amounts: std::collections::HashMap<String, Amount>,
+        change_address: &str,
+        fee: FeeStrategy,
+        selector: &dyn CoinSelector,
+    ) -> Result<DistributionTransaction, AmpError> {
         tracing::debug!(
             "Building distribution transaction for asset {} with {} outputs",
             asset_id,
             address_amounts.len()
         );
 
-        // Calculate total distribution amount
-        let total_distribution: f64 = address_amounts.values().sum();
+        let total_distribution = Self::distribution_total(&address_amounts)?;
+
+        // Select UTXOs to cover the distribution (custom asset)
+        let (asset_inputs, _) = self
+            .select_utxos_with_selector(
+                wallet_name,
+                asset_id,
+                total_distribution,
+                Amount::ZERO,
+                selector,
+            )
+            .await?;
+
+        let fee_rate = self.estimate_fee_rate(fee).await?;
+        let network = self.network().await?;
+
+        self.assemble_distribution_transaction(
+            wallet_name,
+            &network,
+            asset_id,
+            &address_amounts,
+            change_address,
+            asset_inputs,
+            Vec::new(),
+            fee_rate,
+            Amount::ZERO,
+        )
+        .await
+    }
+
+    /// Replaces a stuck distribution transaction with one paying a higher fee
+    ///
+    /// The replacement spends the same asset inputs and pays the same recipients, so it
+    /// conflicts with the original and only one of them can confirm. The L-BTC inputs of
+    /// the original are reused, and more are added if the higher fee needs them. As
+    /// required by BIP 125, the new fee is at least the original fee plus the minimum relay
+    /// fee for the replacement's size.
+    ///
+    /// The replacement has to be signed and broadcast like the original.
+    ///
+    /// # Arguments
+    /// * `address_amounts` - The recipients of the original transaction
+    /// * `change_address` - Address to send asset and L-BTC change to
+    /// * `previous` - The transaction being replaced
+    /// * `fee` - The new fee rate, or a confirmation target passed to `estimatesmartfee`
+    ///
+    /// # Errors
+    /// Returns an error if the new fee rate is not higher than the original, the wallet
+    /// lacks L-BTC for the higher fee, or transaction building fails
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use amp_rs::{Amount, ElementsRpc};
+    /// # use amp_rs::coin_selection::LargestFirst;
+    /// # use amp_rs::fee::{FeeRate, FeeStrategy};
+    /// # use std::collections::HashMap;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let rpc = ElementsRpc::from_env()?;
+    /// let mut address_amounts = HashMap::new();
+    /// address_amounts.insert("address1".to_string(), Amount::from_btc_str("100")?);
+    ///
+    /// let original = rpc.build_distribution_transaction_with_selector(
+    ///     "wallet_name",
+    ///     "asset_id_hex",
+    ///     address_amounts.clone(),
+    ///     "change_address",
+    ///     FeeStrategy::Rate(FeeRate::MIN_RELAY),
+    ///     &LargestFirst,
+    /// ).await?;
+    ///
+    /// // ... sign and broadcast, then later, if it does not confirm:
+    /// let replacement = rpc.bump_distribution_fee(
+    ///     "wallet_name",
+    ///     "asset_id_hex",
+    ///     address_amounts,
+    ///     "change_address",
+    ///     &original,
+    ///     FeeStrategy::Rate(FeeRate::from_sat_per_vb(1)),
+    /// ).await?;
+    /// println!("Replacement fee: {}", replacement.fee);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn bump_distribution_fee(
+        &self,
+        wallet_name: &str,
+        asset_id: &str,
+        address_amounts: std::collections::HashMap<String, Amount>,
+        change_address: &str,
+        previous: &DistributionTransaction,
+        fee: FeeStrategy,
+    ) -> Result<DistributionTransaction, AmpError> {
+        Self::distribution_total(&address_amounts)?;
+
+        let fee_rate = self.estimate_fee_rate(fee).await?;
+        if fee_rate <= previous.fee_rate {
+            return Err(AmpError::validation(format!(
+                "Replacement fee rate {fee_rate} must be higher than the original fee rate {}",
+                previous.fee_rate
+            )));
+        }
+
+        let network = self.network().await?;
+        let replacement = self
+            .assemble_distribution_transaction(
+                wallet_name,
+                &network,
+                asset_id,
+                &address_amounts,
+                change_address,
+                previous.asset_inputs.clone(),
+                previous.fee_inputs.clone(),
+                fee_rate,
+                previous.fee,
+            )
+            .await?;
+
+        tracing::info!(
+            "Built replacement distribution transaction: fee {} -> {} ({} -> {})",
+            previous.fee,
+            replacement.fee,
+            previous.fee_rate,
+            replacement.fee_rate
+        );
+
+        Ok(replacement)
+    }
+
+    /// Resolves a fee strategy to a fee rate
+    ///
+    /// [`FeeStrategy::ConfTarget`] asks the node's `estimatesmartfee`. When the node has
+    /// no estimate, which is common on Liquid and regtest where blocks are rarely full,
+    /// the minimum relay fee rate is used. The result is never below
+    /// [`FeeRate::MIN_RELAY`].
+    ///
+    /// # Errors
+    /// Returns an error if the `estimatesmartfee` call fails or returns an invalid rate
+    pub async fn estimate_fee_rate(&self, strategy: FeeStrategy) -> Result<FeeRate, AmpError> {
+        let fee_rate = match strategy {
+            FeeStrategy::Rate(fee_rate) => fee_rate,
+            FeeStrategy::ConfTarget(conf_target) => {
+                let estimate = self.estimatesmartfee(u32::from(conf_target)).await?;
+                match estimate.get("feerate").and_then(serde_json::Value::as_f64) {
+                    Some(btc_per_kvb) => {
+                        let amount = Amount::from_btc(btc_per_kvb).map_err(|e| {
+                            AmpError::rpc(format!("Invalid feerate from estimatesmartfee: {e}"))
+                        })?;
+                        FeeRate::from_btc_per_kvb(amount)
+                    }
+                    None => {
+                        tracing::warn!(
+                            "estimatesmartfee has no estimate for {} blocks, using minimum relay fee rate: {}",
+                            conf_target,
+                            estimate
+                        );
+                        FeeRate::MIN_RELAY
+                    }
+                }
+            }
+        };
+
+        tracing::debug!("Resolved fee strategy {:?} to {}", strategy, fee_rate);
+        Ok(fee_rate.max(FeeRate::MIN_RELAY))
+    }
+
+    /// Returns the total of a distribution, rejecting empty distributions
+    fn distribution_total(
+        address_amounts: &std::collections::HashMap<String, Amount>,
+    ) -> Result<Amount, AmpError> {
+        let total_distribution: Amount = address_amounts.values().sum();
 
-        if total_distribution <= 0.0 {
+        if !total_distribution.is_positive() {
             return Err(AmpError::validation(
                 "Total distribution amount must be greater than zero".to_string(),
             ));
         }
 
-        // Select UTXOs to cover the distribution (custom asset)
-        let (selected_asset_utxos, total_selected) = self
-            .select_utxos_for_amount(wallet_name, asset_id, total_distribution, 0.0)
-            .await?;
+        Ok(total_distribution)
+    }
 
-        // Also select L-BTC UTXOs for transaction fees
-        // Elements requires L-BTC inputs for fees even when distributing custom assets
-        let min_lbtc_fee = 0.00001; // Minimum L-BTC needed for fees
-        let (selected_lbtc_utxos, lbtc_total) = match self
-            .select_utxos_for_amount(wallet_name, LBTC_ASSET_ID, 0.0, min_lbtc_fee)
-            .await
-        {
-            Ok((utxos, total)) => {
-                tracing::info!(
-                    "Selected {} L-BTC UTXOs totaling {} for fees",
-                    utxos.len(),
-                    total
-                );
-                (utxos, total)
-            }
-            Err(e) => {
-                tracing::warn!(
-                    "Could not select L-BTC UTXOs for fees: {}. Transaction may fail.",
-                    e
-                );
-                (Vec::new(), 0.0)
+    /// Selects L-BTC UTXOs paying the fee of a transaction with `other_inputs` inputs
+    /// besides the fee inputs and `confidential_outputs` blinded outputs, including the
+    /// L-BTC change
+    ///
+    /// `fee_inputs` are L-BTC UTXOs that must be spent; more are selected from the wallet
+    /// when they do not cover the fee. A positive `previous_fee` is the fee of a
+    /// transaction being replaced. Change at or below the dust threshold is added to the
+    /// fee instead of creating a change output.
+    #[allow(clippy::too_many_arguments)]
+    async fn fund_fee(
+        &self,
+        wallet_name: &str,
+        policy_asset: &str,
+        other_inputs: usize,
+        confidential_outputs: usize,
+        mut fee_inputs: Vec<Unspent>,
+        fee_rate: FeeRate,
+        previous_fee: Amount,
+    ) -> Result<FeePayment, AmpError> {
+        let required_fee = |input_count: usize| {
+            let vsize = fee::estimate_vsize(input_count, confidential_outputs);
+            let mut fee = fee_rate.fee_for_vsize(vsize);
+            // A replacement must also pay for its own relay on top of the replaced fee
+            if previous_fee.is_positive() {
+                fee = fee.max(previous_fee + FeeRate::MIN_RELAY.fee_for_vsize(vsize));
             }
+            fee
         };
 
-        // Combine custom asset UTXOs and L-BTC UTXOs
-        let mut all_utxos = selected_asset_utxos.clone();
-        all_utxos.extend(selected_lbtc_utxos.clone());
+        // Every L-BTC input added for the fee makes the transaction larger, so select
+        // until the inputs cover the fee of a transaction that includes them
+        let required_total: Amount = fee_inputs.iter().map(|utxo| utxo.amount).sum();
+        let mut candidates: Option<Vec<Unspent>> = None;
+        let mut extra_inputs = Vec::new();
+        loop {
+            let fee_input_count = (fee_inputs.len() + extra_inputs.len()).max(1);
+            let needed = required_fee(other_inputs + fee_input_count);
+            let available = required_total
+                + extra_inputs
+                    .iter()
+                    .map(|utxo: &Unspent| utxo.amount)
+                    .sum::<Amount>();
+            if available >= needed {
+                break;
+            }
 
-        if selected_lbtc_utxos.is_empty() {
-            tracing::warn!(
-                "No L-BTC UTXOs selected for fees. Transaction may fail during broadcast."
-            );
-        } else {
-            tracing::info!(
-                "Transaction includes {} custom asset UTXOs and {} L-BTC UTXOs for fees",
-                selected_asset_utxos.len(),
-                selected_lbtc_utxos.len()
-            );
+            if candidates.is_none() {
+                let mut utxos = self
+                    .list_unspent_for_wallet(wallet_name, Some(policy_asset))
+                    .await?;
+                utxos.retain(|utxo| {
+                    utxo.asset == policy_asset
+                        && !fee_inputs
+                            .iter()
+                            .any(|used| used.txid == utxo.txid && used.vout == utxo.vout)
+                });
+                candidates = Some(utxos);
+            }
+
+            let selection = LargestFirst
+                .select(
+                    candidates.as_deref().unwrap_or_default(),
+                    needed - required_total,
+                )
+                .map_err(|e| {
+                    AmpError::validation(format!(
+                        "Insufficient L-BTC for fees: need {needed} at {fee_rate} ({e})"
+                    ))
+                })?;
+            if selection.selected.len() <= extra_inputs.len() {
+                // The selection cannot grow any further, so the loop would not progress
+                return Err(AmpError::validation(format!(
+                    "Insufficient L-BTC for fees: need {needed}, have {available}"
+                )));
+            }
+            extra_inputs = selection.selected;
+        }
+        fee_inputs.extend(extra_inputs);
+
+        let fee_total: Amount = fee_inputs.iter().map(|utxo| utxo.amount).sum();
+        let input_count = other_inputs + fee_inputs.len();
+        let mut vsize = fee::estimate_vsize(input_count, confidential_outputs);
+        let mut fee = required_fee(input_count);
+        let mut fee_change = fee_total - fee;
+        if fee_change <= DISTRIBUTION_DUST_THRESHOLD {
+            // Not worth a change output; the remainder is added to the fee
+            vsize = fee::estimate_vsize(input_count, confidential_outputs - 1);
+            fee = fee_total;
+            fee_change = Amount::ZERO;
+        }
+
+        tracing::info!(
+            "Fee: {} for an estimated {} vB at {} ({} L-BTC inputs totaling {})",
+            fee,
+            vsize,
+            fee_rate,
+            fee_inputs.len(),
+            fee_total
+        );
+
+        Ok(FeePayment {
+            inputs: fee_inputs,
+            fee,
+            change: fee_change,
+            vsize,
+        })
+    }
+
+    /// Selects L-BTC for the fee, adds change and fee outputs, and builds the blinded
+    /// distribution transaction
+    ///
+    /// `fee_inputs` are L-BTC UTXOs that must be spent (those of a replaced transaction);
+    /// more are selected from the wallet when they do not cover the fee. A positive
+    /// `previous_fee` is the fee of the transaction being replaced.
+    #[allow(clippy::cognitive_complexity)]
+    #[allow(clippy::too_many_arguments)]
+    #[allow(clippy::too_many_lines)]
+    async fn assemble_distribution_transaction(
+        &self,
+        wallet_name: &str,
+        network: &Network,
+        asset_id: &str,
+        address_amounts: &std::collections::HashMap<String, Amount>,
+        change_address: &str,
+        asset_inputs: Vec<Unspent>,
+        fee_inputs: Vec<Unspent>,
+        fee_rate: FeeRate,
+        previous_fee: Amount,
+    ) -> Result<DistributionTransaction, AmpError> {
+        let policy_asset = network.policy_asset();
+        let total_distribution: Amount = address_amounts.values().sum();
+        let total_selected: Amount = asset_inputs.iter().map(|utxo| utxo.amount).sum();
+
+        if total_selected < total_distribution {
+            return Err(AmpError::validation(format!(
+                "Asset inputs total {total_selected} does not cover the distribution total {total_distribution}"
+            )));
         }
 
-        // Create transaction inputs from all selected UTXOs
-        let inputs: Vec<TxInput> = all_utxos
+        // Calculate change amount for custom asset (total selected - distribution)
+        // The asset cannot be given up as fee, so even the smallest change is returned
+        let asset_change = total_selected - total_distribution;
+        let has_asset_change = asset_change.is_positive();
+
+        // Recipients, asset change and L-BTC change are all blinded
+        let confidential_outputs = address_amounts.len() + usize::from(has_asset_change) + 1;
+        let FeePayment {
+            inputs: fee_inputs,
+            fee,
+            change: fee_change,
+            vsize,
+        } = self
+            .fund_fee(
+                wallet_name,
+                policy_asset,
+                asset_inputs.len(),
+                confidential_outputs,
+                fee_inputs,
+                fee_rate,
+                previous_fee,
+            )
+            .await?;
+        let input_count = asset_inputs.len() + fee_inputs.len();
+
+        // Create transaction inputs from all selected UTXOs, signalling replace-by-fee
+        let inputs: Vec<TxInput> = asset_inputs
             .iter()
+            .chain(&fee_inputs)
             .map(|utxo| TxInput {
                 txid: utxo.txid.clone(),
                 vout: utxo.vout,
-                sequence: None, // Use default sequence
+                sequence: Some(fee::SEQUENCE_RBF),
             })
             .collect();
 
-        // Create outputs for distribution (custom asset)
-        // We need to track outputs as a vector since we may have multiple outputs to the same address
+        // Outputs are tracked as a vector since several may go to the same address
         // (e.g., custom asset change + L-BTC change to the same change address)
         let mut output_list = Vec::new();
 
         // Add distribution outputs (custom asset)
-        for (address, amount) in &address_amounts {
+        for (address, amount) in address_amounts {
             output_list.push((address.clone(), *amount, asset_id.to_string()));
         }
 
-        // Calculate change amount for custom asset (total selected - distribution)
-        let asset_change_amount = total_selected - total_distribution;
-
-        // Add asset change output if there's a significant amount left
-        if asset_change_amount > DUST_THRESHOLD {
-            output_list.push((
-                change_address.to_string(),
-                asset_change_amount,
-                asset_id.to_string(),
-            ));
-
+        if has_asset_change {
             tracing::debug!(
                 "Adding asset change output: {} {} to address {}",
-                asset_change_amount,
+                asset_change,
                 asset_id,
                 change_address
             );
-        } else if asset_change_amount > 0.0 {
-            tracing::warn!(
-                "Asset change amount {} is below dust threshold {}, will be lost",
-                asset_change_amount,
-                DUST_THRESHOLD
-            );
+            output_list.push((
+                change_address.to_string(),
+                asset_change,
+                asset_id.to_string(),
+            ));
         }
 
-        // Handle L-BTC change if we selected L-BTC UTXOs for fees
-        // In Elements, the fee is implicit - it's the difference between L-BTC inputs and outputs
-        // We should NOT subtract the fee from outputs; Elements calculates it automatically
-        if !selected_lbtc_utxos.is_empty() {
+        if fee_change.is_positive() {
             tracing::debug!(
-                "L-BTC input total: {}, minimum fee needed: {}",
-                lbtc_total,
-                min_lbtc_fee
-            );
-
-            // Check if we have enough L-BTC for the minimum fee
-            if lbtc_total < min_lbtc_fee {
-                return Err(AmpError::validation(format!(
-                    "Insufficient L-BTC for fees: have {lbtc_total}, need at least {min_lbtc_fee}"
-                )));
-            }
-
-            // For now, let's try NOT adding any L-BTC change output
-            // and let Elements handle the fee automatically from the input/output difference
-            tracing::info!(
-                "Using L-BTC input {} for fees - no explicit L-BTC change output (Elements will handle fee automatically)",
-                lbtc_total
+                "Adding L-BTC change output: {} to address {}",
+                fee_change,
+                change_address
             );
-
-            // Note: If this approach works, the entire L-BTC input will become the fee
-            // If we need change, we'll need to figure out the correct way to handle it
+            output_list.push((
+                change_address.to_string(),
+                fee_change,
+                policy_asset.to_string(),
+            ));
         }
 
+        // Elements requires the fee as an explicit output keyed "fee"
+        output_list.push(("fee".to_string(), fee, policy_asset.to_string()));
+
         // For confidential addresses, we need to import them into the wallet first
         // so Elements knows about the blinding keys
         for address in address_amounts.keys() {
@@ -3781,17 +4509,27 @@ impl ElementsRpc {
             });
 
         tracing::info!(
-            "Built distribution transaction: {} inputs, {} outputs, asset change: {}",
-            all_utxos.len(),
-            address_amounts.len() + usize::from(asset_change_amount > DUST_THRESHOLD),
-            if asset_change_amount > DUST_THRESHOLD {
-                asset_change_amount
-            } else {
-                0.0
-            }
+            "Built distribution transaction: {} inputs, {} outputs, asset change: {}, fee: {}",
+            input_count,
+            address_amounts.len()
+                + usize::from(has_asset_change)
+                + usize::from(fee_change.is_positive())
+                + 1,
+            asset_change,
+            fee
         );
 
-        Ok((blinded_transaction, all_utxos, asset_change_amount))
+        Ok(DistributionTransaction {
+            raw_transaction: blinded_transaction,
+            asset_inputs,
+            fee_inputs,
+            asset_change,
+            fee_change,
+            fee,
+            fee_rate,
+            vsize,
+            pset: None,
+        })
     }
 
     /// Creates a raw transaction with multiple outputs that can handle multiple assets to the same address
@@ -3811,7 +4549,7 @@ impl ElementsRpc {
         &self,
         wallet_name: &str,
         inputs: Vec<TxInput>,
-        outputs: Vec<(String, f64, String)>, // (address, amount, asset_id)
+        outputs: Vec<(String, Amount, String)>, // (address, amount, asset_id)
     ) -> Result<String, AmpError> {
         tracing::debug!(
             "Creating raw transaction with wallet {} - {} inputs and {} outputs",
@@ -3828,11 +4566,8 @@ impl ElementsRpc {
         let mut outputs_array = Vec::new();
 
         for (address, amount, asset_id) in &outputs {
-            // Convert amount to string with proper precision for Elements
-            let amount_str = format!("{amount:.8}");
-
             outputs_array.push(serde_json::json!({
-                address.clone(): amount_str,
+                address.clone(): amount.to_btc_string(),
                 "asset": asset_id
             }));
         }
@@ -4215,7 +4950,6 @@ impl ElementsRpc {
     ///
     /// # Errors
     /// Returns an error if signing or broadcasting fails
-    #[allow(clippy::cognitive_complexity)]
     pub async fn sign_and_broadcast_transaction_with_utxos(
         &self,
         unsigned_tx_hex: &str,
@@ -4227,6 +4961,44 @@ impl ElementsRpc {
             utxos.len()
         );
 
+        let signed_tx_hex = self
+            .sign_transaction_with_utxos(unsigned_tx_hex, utxos, signer)
+            .await?;
+
+        // Broadcast the signed transaction
+        let txid = self
+            .send_raw_transaction(&signed_tx_hex)
+            .await
+            .map_err(|e| e.with_context("Failed during transaction broadcast phase"))?;
+
+        tracing::info!("Successfully signed and broadcast transaction: {}", txid);
+        Ok(txid)
+    }
+
+    /// Signs a transaction with UTXO information for proper PSBT construction
+    ///
+    /// Signers that can use the previous outputs being spent receive `utxos`:
+    /// [`LwkSoftwareSigner`](crate::signer::LwkSoftwareSigner) directly, and signers
+    /// implementing [`Signer::sign_pset`](crate::signer::Signer::sign_pset) as a PSET that
+    /// is finalized with [`finalize_pset`](Self::finalize_pset). Other signers fall back to
+    /// [`sign_transaction`](Self::sign_transaction).
+    ///
+    /// # Arguments
+    /// * `unsigned_tx_hex` - The unsigned transaction in hexadecimal format
+    /// * `utxos` - Vector of UTXOs being spent in the transaction
+    /// * `signer` - Implementation of the Signer trait for transaction signing
+    ///
+    /// # Returns
+    /// Returns the signed transaction as a hex string
+    ///
+    /// # Errors
+    /// Returns an error if signing fails
+    pub async fn sign_transaction_with_utxos(
+        &self,
+        unsigned_tx_hex: &str,
+        utxos: &[Unspent],
+        signer: &dyn crate::signer::Signer,
+    ) -> Result<String, AmpError> {
         // Try to use the enhanced signing method if the signer supports it
         let signed_tx_hex = if let Some(lwk_signer) = signer
             .as_any()
@@ -4241,6 +5013,26 @@ impl ElementsRpc {
                     AmpError::Signer(e)
                         .with_context("Failed during enhanced transaction signing phase")
                 })?
+        } else if let Some(pset) = Self::pset_for_signing(unsigned_tx_hex, utxos) {
+            match signer.sign_pset(pset).await {
+                Ok(signed_pset) => {
+                    tracing::debug!("Signer returned a PSET, finalizing it on the node");
+                    self.finalize_pset(&signed_pset)
+                        .await
+                        .map_err(|e| e.with_context("Failed during PSET finalization phase"))?
+                }
+                Err(crate::signer::SignerError::Unsupported(_)) => {
+                    tracing::debug!("Signer does not sign PSETs, using standard signing method");
+                    self.sign_transaction(unsigned_tx_hex, signer)
+                        .await
+                        .map_err(|e| e.with_context("Failed during transaction signing phase"))?
+                }
+                Err(e) => {
+                    return Err(
+                        AmpError::Signer(e).with_context("Failed during PSET signing phase")
+                    );
+                }
+            }
         } else {
             // Fall back to standard signing method
             tracing::debug!("Using standard signing method (no UTXO information)");
@@ -4249,14 +5041,69 @@ impl ElementsRpc {
                 .map_err(|e| e.with_context("Failed during transaction signing phase"))?
         };
 
-        // Broadcast the signed transaction
-        let txid = self
-            .send_raw_transaction(&signed_tx_hex)
-            .await
-            .map_err(|e| e.with_context("Failed during transaction broadcast phase"))?;
+        Ok(signed_tx_hex)
+    }
 
-        tracing::info!("Successfully signed and broadcast transaction: {}", txid);
-        Ok(txid)
+    /// Builds the PSET handed to [`Signer::sign_pset`](crate::signer::Signer::sign_pset),
+    /// or `None` if the transaction or UTXOs cannot describe one
+    fn pset_for_signing(
+        unsigned_tx_hex: &str,
+        utxos: &[Unspent],
+    ) -> Option<elements::pset::PartiallySignedTransaction> {
+        let tx_bytes = hex::decode(unsigned_tx_hex.trim()).ok()?;
+        let transaction: elements::Transaction = elements::encode::deserialize(&tx_bytes).ok()?;
+        match crate::signer::pset::from_transaction(transaction, utxos) {
+            Ok(pset) => Some(pset),
+            Err(e) => {
+                tracing::debug!("Cannot build PSET for signing: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Finalizes a signed PSET and extracts the network-ready transaction
+    ///
+    /// Uses the node's `finalizepsbt` RPC, which builds the final script witnesses from the
+    /// partial signatures collected in the PSET.
+    ///
+    /// # Arguments
+    /// * `pset` - The signed PSET
+    ///
+    /// # Returns
+    /// Returns the finalized transaction as a hex string
+    ///
+    /// # Errors
+    /// Returns an error if the RPC call fails or the PSET lacks signatures for some inputs
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use amp_rs::ElementsRpc;
+    /// # async fn example(signed_pset: elements::pset::PartiallySignedTransaction) -> Result<(), Box<dyn std::error::Error>> {
+    /// let rpc = ElementsRpc::from_env()?;
+    /// let signed_tx_hex = rpc.finalize_pset(&signed_pset).await?;
+    /// let txid = rpc.send_raw_transaction(&signed_tx_hex).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn finalize_pset(
+        &self,
+        pset: &elements::pset::PartiallySignedTransaction,
+    ) -> Result<String, AmpError> {
+        #[derive(serde::Deserialize)]
+        struct FinalizeResult {
+            hex: Option<String>,
+            complete: bool,
+        }
+
+        let params = serde_json::json!([crate::signer::pset::to_base64(pset), true]);
+        let result: FinalizeResult = self.rpc_call("finalizepsbt", params).await?;
+
+        match result.hex {
+            Some(hex) if result.complete => Ok(hex),
+            _ => Err(AmpError::validation(
+                "PSET is not fully signed and cannot be finalized".to_string(),
+            )),
+        }
     }
 
     /// Collects change data from a confirmed transaction for distribution confirmation
@@ -6263,10 +7110,10 @@ mod elements_rpc_tests {
         let utxos = result.unwrap();
         assert_eq!(utxos.len(), 2);
         assert_eq!(utxos[0].txid, "abc123def456789");
-        assert_eq!(utxos[0].amount, 100.0);
+        assert_eq!(utxos[0].amount, Amount::from_btc(100.0).unwrap());
         assert_eq!(utxos[0].asset, asset_id);
         assert_eq!(utxos[1].txid, "def456abc123789");
-        assert_eq!(utxos[1].amount, 50.0);
+        assert_eq!(utxos[1].amount, Amount::from_btc(50.0).unwrap());
 
         load_wallet_mock.assert();
         mock.assert();
@@ -6336,7 +7183,7 @@ mod elements_rpc_tests {
         let utxos = result.unwrap();
         assert_eq!(utxos.len(), 1);
         assert_eq!(utxos[0].txid, "ghi789jkl012345");
-        assert_eq!(utxos[0].amount, 25.0);
+        assert_eq!(utxos[0].amount, Amount::from_btc(25.0).unwrap());
 
         load_wallet_mock.assert();
         mock.assert();
@@ -6369,7 +7216,7 @@ mod elements_rpc_tests {
                             }
                         ],
                         {
-                            "lq1qq2xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq": 100.0
+                            "lq1qq2xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq": "100.00000000"
                         },
                         0,
                         false,
@@ -6394,7 +7241,7 @@ mod elements_rpc_tests {
         let mut outputs = HashMap::new();
         outputs.insert(
             "lq1qq2xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq".to_string(),
-            100.0,
+            Amount::from_btc(100.0).unwrap(),
         );
 
         let mut assets = HashMap::new();
@@ -6940,7 +7787,7 @@ mod elements_rpc_tests {
                 "asset_id",
                 address_amounts,
                 "change_address",
-                1.0,
+                FeeStrategy::default(),
             )
             .await;
 
@@ -7807,7 +8654,7 @@ mod elements_rpc_tests {
         let server = MockServer::start();
         let wallet_name = "test_wallet";
         let asset_id = "6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d";
-        let amount = 100.0;
+        let amount = Amount::from_btc(100.0).unwrap();
         let expected_txid = "abc123def456789abc123def456789abc123def456789abc123def456789abc123de";
 
         // Mock loadwallet
@@ -7837,7 +8684,7 @@ mod elements_rpc_tests {
                     "jsonrpc": "1.0",
                     "id": "amp-client",
                     "method": "destroyamount",
-                    "params": [asset_id, amount]
+                    "params": [asset_id, amount.to_btc_string()]
                 }));
             then.status(200).json_body(serde_json::json!({
                 "jsonrpc": "1.0",
@@ -7859,7 +8706,7 @@ mod elements_rpc_tests {
         let server = MockServer::start();
         let wallet_name = "test_wallet";
         let asset_id = "6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d";
-        let amount = 100.0;
+        let amount = Amount::from_btc(100.0).unwrap();
 
         // Mock loadwallet
         server.mock(|when, then| {
@@ -7888,7 +8735,7 @@ mod elements_rpc_tests {
                     "jsonrpc": "1.0",
                     "id": "amp-client",
                     "method": "destroyamount",
-                    "params": [asset_id, amount]
+                    "params": [asset_id, amount.to_btc_string()]
                 }));
             then.status(200).json_body(serde_json::json!({
                 "jsonrpc": "1.0",
@@ -7918,7 +8765,7 @@ mod elements_rpc_tests {
         let server = MockServer::start();
         let wallet_name = "test_wallet";
         let asset_id = "invalid_asset_id";
-        let amount = 100.0;
+        let amount = Amount::from_btc(100.0).unwrap();
 
         // Mock loadwallet
         server.mock(|when, then| {
@@ -7947,7 +8794,7 @@ mod elements_rpc_tests {
                     "jsonrpc": "1.0",
                     "id": "amp-client",
                     "method": "destroyamount",
-                    "params": [asset_id, amount]
+                    "params": [asset_id, amount.to_btc_string()]
                 }));
             then.status(200).json_body(serde_json::json!({
                 "jsonrpc": "1.0",
@@ -7977,7 +8824,7 @@ mod elements_rpc_tests {
         let server = MockServer::start();
         let wallet_name = "test_wallet";
         let asset_id = "6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d";
-        let amount = 100.0;
+        let amount = Amount::from_btc(100.0).unwrap();
 
         // Mock loadwallet
         server.mock(|when, then| {
@@ -8006,7 +8853,7 @@ mod elements_rpc_tests {
                     "jsonrpc": "1.0",
                     "id": "amp-client",
                     "method": "destroyamount",
-                    "params": [asset_id, amount]
+                    "params": [asset_id, amount.to_btc_string()]
                 }));
             then.status(500).json_body(serde_json::json!({
                 "error": "Internal server error"
@@ -8027,7 +8874,7 @@ mod elements_rpc_tests {
         let server = MockServer::start();
         let wallet_name = "test_wallet";
         let asset_id = "6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d";
-        let amount = 50.0;
+        let amount = Amount::from_btc(50.0).unwrap();
 
         // Mock loadwallet
         server.mock(|when, then| {
@@ -8056,7 +8903,7 @@ mod elements_rpc_tests {
                     "jsonrpc": "1.0",
                     "id": "amp-client",
                     "method": "reissueasset",
-                    "params": [asset_id, amount]
+                    "params": [asset_id, amount.to_btc_string()]
                 }));
             then.status(200).json_body(serde_json::json!({
                 "jsonrpc": "1.0",
@@ -8088,7 +8935,7 @@ mod elements_rpc_tests {
         let server = MockServer::start();
         let wallet_name = "test_wallet";
         let asset_id = "6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d";
-        let amount = 50.0;
+        let amount = Amount::from_btc(50.0).unwrap();
 
         // Mock loadwallet
         server.mock(|when, then| {
@@ -8117,7 +8964,7 @@ mod elements_rpc_tests {
                     "jsonrpc": "1.0",
                     "id": "amp-client",
                     "method": "reissueasset",
-                    "params": [asset_id, amount]
+                    "params": [asset_id, amount.to_btc_string()]
                 }));
             then.status(200).json_body(serde_json::json!({
                 "jsonrpc": "1.0",
@@ -8147,7 +8994,7 @@ mod elements_rpc_tests {
         let server = MockServer::start();
         let wallet_name = "test_wallet";
         let asset_id = "6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d";
-        let amount = 50.0;
+        let amount = Amount::from_btc(50.0).unwrap();
 
         // Mock loadwallet
         server.mock(|when, then| {
@@ -8176,7 +9023,7 @@ mod elements_rpc_tests {
                     "jsonrpc": "1.0",
                     "id": "amp-client",
                     "method": "reissueasset",
-                    "params": [asset_id, amount]
+                    "params": [asset_id, amount.to_btc_string()]
                 }));
             then.status(200).json_body(serde_json::json!({
                 "jsonrpc": "1.0",
@@ -8206,7 +9053,7 @@ mod elements_rpc_tests {
         let server = MockServer::start();
         let wallet_name = "test_wallet";
         let asset_id = "6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d";
-        let amount = 50.0;
+        let amount = Amount::from_btc(50.0).unwrap();
 
         // Mock loadwallet
         server.mock(|when, then| {
@@ -8235,7 +9082,7 @@ mod elements_rpc_tests {
                     "jsonrpc": "1.0",
                     "id": "amp-client",
                     "method": "reissueasset",
-                    "params": [asset_id, amount]
+                    "params": [asset_id, amount.to_btc_string()]
                 }));
             then.status(500).json_body(serde_json::json!({
                 "error": "Internal server error"
@@ -8946,6 +9793,12 @@ impl RetryClient {
         }
     }
 
+    /// Creates a new `RetryClient` that sends requests through an existing HTTP client
+    #[must_use]
+    pub const fn with_client(client: Client, config: RetryConfig) -> Self {
+        Self { client, config }
+    }
+
     /// Creates a new `RetryClient` with default configuration
     #[must_use]
     pub fn with_default_config() -> Self {
@@ -9137,8 +9990,85 @@ impl RetryClient {
     }
 }
 
-/// Singleton instance of the `TokenManager` for shared token storage across all `ApiClient` instances
-static GLOBAL_TOKEN_MANAGER: OnceCell<Arc<TokenManager>> = OnceCell::const_new();
+/// AMP account credentials used to obtain authentication tokens
+#[derive(Debug, Clone)]
+pub struct Credentials {
+    pub username: String,
+    pub password: Secret<String>,
+}
+
+impl Credentials {
+    /// Creates a new set of credentials
+    #[must_use]
+    pub fn new<S: Into<String>>(username: S, password: Secret<String>) -> Self {
+        Self {
+            username: username.into(),
+            password,
+        }
+    }
+
+    /// Reads credentials from the `AMP_USERNAME` and `AMP_PASSWORD` environment variables
+    ///
+    /// # Errors
+    /// Returns `Error::MissingEnvVar` if either variable is not set
+    pub fn from_env() -> Result<Self, Error> {
+        let username = env::var("AMP_USERNAME")
+            .map_err(|_| Error::MissingEnvVar("AMP_USERNAME".to_string()))?;
+        let password = env::var("AMP_PASSWORD")
+            .map_err(|_| Error::MissingEnvVar("AMP_PASSWORD".to_string()))?;
+
+        Ok(Self::new(username, Secret::new(password)))
+    }
+}
+
+/// Identifies the AMP account a `TokenManager` authenticates as
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TokenAccountKey {
+    /// AMP API base URL, without a trailing slash
+    pub base_url: String,
+    /// AMP username
+    pub username: String,
+}
+
+impl TokenAccountKey {
+    /// Creates a key for the given base URL and username
+    #[must_use]
+    pub fn new(base_url: &Url, username: &str) -> Self {
+        Self {
+            base_url: base_url.as_str().trim_end_matches('/').to_string(),
+            username: username.to_string(),
+        }
+    }
+
+    /// Creates a key for the account configured through environment variables
+    ///
+    /// # Errors
+    /// Returns an error if `AMP_API_BASE_URL` contains an invalid URL
+    pub fn from_env() -> Result<Self, Error> {
+        let base_url = get_amp_api_base_url()?;
+        let username = env::var("AMP_USERNAME").unwrap_or_default();
+        Ok(Self::new(&base_url, &username))
+    }
+
+    /// Returns the token file used for this account when no explicit store is configured
+    ///
+    /// The file name embeds a hash of the base URL and username, so two accounts that
+    /// rely on environment-driven persistence never read or delete each other's token.
+    #[must_use]
+    pub fn token_file(&self) -> PathBuf {
+        use elements::hashes::{sha256, Hash};
+
+        let digest = sha256::Hash::hash(format!("{}\n{}", self.base_url, self.username).as_bytes());
+        PathBuf::from(format!("token-{}.json", &digest.to_string()[..16]))
+    }
+}
+
+/// Registry of shared `TokenManager` instances, one per AMP account
+///
+/// All `ApiClient` instances for the same base URL and username share one token,
+/// while clients for different accounts never see each other's tokens.
+static TOKEN_MANAGERS: Lazy<Mutex<HashMap<TokenAccountKey, Arc<TokenManager>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
 
 /// Core token manager with proactive refresh and secure storage
 #[derive(Debug)]
@@ -9146,30 +10076,67 @@ pub struct TokenManager {
     pub token_data: Arc<Mutex<Option<TokenData>>>,
     pub retry_client: RetryClient,
     base_url: Url,
+    /// Explicit credentials; when `None` they are read from the environment on demand
+    credentials: Option<Credentials>,
+    /// Explicit token store; when `None` persistence follows the environment settings
+    token_store: Option<Arc<dyn TokenStore>>,
     /// Semaphore to ensure only one token operation (obtain/refresh) happens at a time
     /// This prevents race conditions where multiple threads try to refresh/obtain simultaneously
     token_operation_semaphore: Arc<Semaphore>,
 }
 
 impl TokenManager {
-    /// Gets the global singleton instance of `TokenManager`
+    /// Gets the shared `TokenManager` for the account configured in the environment
     ///
-    /// This ensures all `ApiClient` instances share the same token storage,
+    /// The account is identified by `AMP_API_BASE_URL` and `AMP_USERNAME`. All
+    /// `ApiClient` instances for that account share the same token storage,
     /// preventing multiple token acquisition attempts in concurrent tests.
     ///
     /// # Errors
     /// Returns an error if the `TokenManager` cannot be initialized
     pub async fn get_global_instance() -> Result<Arc<Self>, Error> {
-        let manager = GLOBAL_TOKEN_MANAGER
-            .get_or_try_init(|| async {
-                let config = RetryConfig::from_env()?;
-                let base_url = get_amp_api_base_url()?;
-                let manager = Self::with_config_and_base_url(config, base_url).await?;
-                Ok::<Arc<Self>, Error>(Arc::new(manager))
-            })
-            .await?;
+        let key = TokenAccountKey::from_env()?;
+        let mut registry = TOKEN_MANAGERS.lock().await;
+        if let Some(manager) = registry.get(&key) {
+            return Ok(Arc::clone(manager));
+        }
+
+        let config = RetryConfig::from_env()?;
+        let base_url = get_amp_api_base_url()?;
+        let manager = Arc::new(Self::with_config_and_base_url(config, base_url).await?);
+        registry.insert(key, Arc::clone(&manager));
+        drop(registry);
+
+        Ok(manager)
+    }
+
+    /// Gets the shared `TokenManager` for an explicit AMP account, creating it if needed
+    ///
+    /// Managers are keyed by base URL and username, so repeated calls for the same
+    /// account return the same instance while other accounts get their own. The
+    /// retry configuration and token store are only used when the manager is first
+    /// created.
+    ///
+    /// # Errors
+    /// Returns an error if the `TokenManager` cannot be initialized
+    pub async fn for_account(
+        config: RetryConfig,
+        base_url: Url,
+        credentials: Credentials,
+        token_store: Option<Arc<dyn TokenStore>>,
+    ) -> Result<Arc<Self>, Error> {
+        let key = TokenAccountKey::new(&base_url, &credentials.username);
+        let mut registry = TOKEN_MANAGERS.lock().await;
+        if let Some(manager) = registry.get(&key) {
+            return Ok(Arc::clone(manager));
+        }
 
-        Ok(manager.clone())
+        let manager =
+            Arc::new(Self::build(config, base_url, Some(credentials), token_store).await?);
+        registry.insert(key, Arc::clone(&manager));
+        drop(registry);
+
+        Ok(manager)
     }
 
     /// Creates a new `TokenManager` with default configuration
@@ -9197,19 +10164,67 @@ impl TokenManager {
     pub async fn with_config_and_base_url(
         config: RetryConfig,
         base_url: Url,
+    ) -> Result<Self, Error> {
+        Self::build(config, base_url, None, None).await
+    }
+
+    /// Creates a new `TokenManager` that authenticates with explicit credentials
+    ///
+    /// Unlike the other constructors, the returned manager never reads
+    /// `AMP_USERNAME` or `AMP_PASSWORD`, so several managers for different AMP
+    /// accounts can coexist in one process.
+    ///
+    /// # Errors
+    /// This method is infallible but returns Result for API consistency
+    pub async fn with_credentials(
+        config: RetryConfig,
+        base_url: Url,
+        credentials: Credentials,
+    ) -> Result<Self, Error> {
+        Self::build(config, base_url, Some(credentials), None).await
+    }
+
+    /// Creates a new `TokenManager` that persists tokens in the given store
+    ///
+    /// The store is always used, regardless of the `AMP_TOKEN_PERSISTENCE` and
+    /// environment detection settings. Any valid token already in the store is
+    /// loaded during construction.
+    ///
+    /// # Errors
+    /// This method is infallible but returns Result for API consistency
+    pub async fn with_token_store(
+        config: RetryConfig,
+        base_url: Url,
+        token_store: Arc<dyn TokenStore>,
+    ) -> Result<Self, Error> {
+        Self::build(config, base_url, None, Some(token_store)).await
+    }
+
+    /// Shared constructor that optionally restores a persisted token
+    async fn build(
+        config: RetryConfig,
+        base_url: Url,
+        credentials: Option<Credentials>,
+        token_store: Option<Arc<dyn TokenStore>>,
     ) -> Result<Self, Error> {
         let manager = Self {
             token_data: Arc::new(Mutex::new(None)),
             retry_client: RetryClient::new(config),
             base_url,
+            credentials,
+            token_store,
             token_operation_semaphore: Arc::new(Semaphore::new(1)),
         };
 
-        // Load token from disk if persistence is enabled
-        if Self::should_persist_tokens() {
-            if let Ok(Some(token_data)) = manager.load_token_from_disk().await {
-                *manager.token_data.lock().await = Some(token_data);
-                tracing::info!("Token loaded from disk during initialization");
+        // Load token from the store if persistence is enabled
+        if let Some(store) = manager.persistence_store() {
+            match store.load().await {
+                Ok(Some(token_data)) => {
+                    *manager.token_data.lock().await = Some(token_data);
+                    tracing::info!("Token loaded from store during initialization");
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Failed to load token from store: {e}"),
             }
         }
 
@@ -9232,6 +10247,8 @@ impl TokenManager {
             token_data: Arc::new(Mutex::new(Some(token_data))),
             retry_client: RetryClient::new(config),
             base_url,
+            credentials: None,
+            token_store: None,
             token_operation_semaphore: Arc::new(Semaphore::new(1)),
         };
 
@@ -9346,10 +10363,10 @@ impl TokenManager {
         )
     }
 
-    /// Obtains a new authentication token using environment credentials with retry logic
+    /// Obtains a new authentication token with retry logic
     ///
     /// This method:
-    /// 1. Reads credentials from environment variables
+    /// 1. Uses the explicit credentials, falling back to environment variables
     /// 2. Makes a token request with retry logic
     /// 3. Stores the new token with 24-hour expiry
     /// 4. Returns the token string
@@ -9390,7 +10407,7 @@ impl TokenManager {
     async fn obtain_token_internal(&self) -> Result<String, Error> {
         tracing::debug!("Obtaining new authentication token");
 
-        let request_payload = Self::get_credentials_from_env()?;
+        let request_payload = self.token_request()?;
         let url = self.build_obtain_token_url();
         let response = self.execute_token_request(&url, &request_payload).await?;
         let token_response = self.parse_token_response(response).await?;
@@ -9401,14 +10418,17 @@ impl TokenManager {
         Ok(token_response.token)
     }
 
-    /// Gets credentials from environment variables
-    fn get_credentials_from_env() -> Result<TokenRequest, Error> {
-        let username = env::var("AMP_USERNAME")
-            .map_err(|_| Error::MissingEnvVar("AMP_USERNAME".to_string()))?;
-        let password = env::var("AMP_PASSWORD")
-            .map_err(|_| Error::MissingEnvVar("AMP_PASSWORD".to_string()))?;
+    /// Builds the token request payload from explicit or environment credentials
+    fn token_request(&self) -> Result<TokenRequest, Error> {
+        let credentials = match &self.credentials {
+            Some(credentials) => credentials.clone(),
+            None => Credentials::from_env()?,
+        };
 
-        Ok(TokenRequest { username, password })
+        Ok(TokenRequest {
+            username: credentials.username,
+            password: credentials.password.expose_secret().clone(),
+        })
     }
 
     /// Builds the URL for token obtain endpoint
@@ -9461,7 +10481,7 @@ impl TokenManager {
             .map_err(|e| Error::ResponseParsingFailed(e.to_string()))
     }
 
-    /// Stores the token data with 24-hour expiry and optional disk persistence
+    /// Stores the token data with 24-hour expiry and optional persistence
     async fn store_token_data(&self, token: &str) {
         let expires_at = Utc::now() + Duration::days(1);
         let token_data = TokenData::new(token.to_string(), expires_at);
@@ -9470,10 +10490,10 @@ impl TokenManager {
         *self.token_data.lock().await = Some(token_data.clone());
         tracing::debug!("Token data updated atomically in storage");
 
-        // Save to disk if persistence is enabled
-        if Self::should_persist_tokens() {
-            if let Err(e) = self.save_token_to_disk(&token_data).await {
-                tracing::warn!("Failed to save token to disk: {e}");
+        // Save to the token store if persistence is enabled
+        if let Some(store) = self.persistence_store() {
+            if let Err(e) = store.save(&token_data).await {
+                tracing::warn!("Failed to save token to store: {e}");
             }
         }
     }
@@ -9637,7 +10657,7 @@ impl TokenManager {
         tracing::debug!("Clearing stored token from memory and disk");
 
         let had_token = self.clear_token_from_memory().await;
-        self.clear_token_from_disk_if_enabled().await;
+        self.clear_token_from_store_if_enabled().await;
         Self::log_token_clear_result(had_token);
 
         Ok(())
@@ -9652,11 +10672,11 @@ impl TokenManager {
         had_token
     }
 
-    /// Clears the token from disk if persistence is enabled
-    async fn clear_token_from_disk_if_enabled(&self) {
-        if Self::should_persist_tokens() {
-            if let Err(e) = self.remove_token_from_disk().await {
-                tracing::warn!("Failed to remove token from disk: {e}");
+    /// Clears the token from the token store if persistence is enabled
+    async fn clear_token_from_store_if_enabled(&self) {
+        if let Some(store) = self.persistence_store() {
+            if let Err(e) = store.clear().await {
+                tracing::warn!("Failed to remove token from store: {e}");
             }
         }
     }
@@ -9749,145 +10769,40 @@ impl TokenManager {
         should_persist
     }
 
-    /// Loads token data from disk if it exists and is valid
-    async fn load_token_from_disk(&self) -> Result<Option<TokenData>, Error> {
-        let token_file = "token.json";
-
-        if !self.token_file_exists(token_file).await {
-            return Ok(None);
-        }
-
-        let content = self.read_token_file(token_file).await?;
-        self.parse_and_validate_token(token_file, &content).await
-    }
-
-    /// Checks if the token file exists on disk
-    async fn token_file_exists(&self, token_file: &str) -> bool {
-        tokio::fs::try_exists(token_file).await.map_or_else(
-            |_| {
-                tracing::debug!("Error checking token file existence: {}", token_file);
-                false
-            },
-            |exists| {
-                if !exists {
-                    tracing::debug!("Token file does not exist: {}", token_file);
-                }
-                exists
-            },
-        )
-    }
-
-    /// Reads the token file content from disk
-    async fn read_token_file(&self, token_file: &str) -> Result<String, Error> {
-        use tokio::fs;
-
-        match fs::read_to_string(token_file).await {
-            Ok(content) => Ok(content),
-            Err(e) => {
-                tracing::warn!("Failed to read token file: {e}");
-                Err(Error::Token(TokenError::storage(format!(
-                    "Failed to read token file: {e}"
-                ))))
-            }
-        }
-    }
-
-    /// Parses token content and validates expiration
-    async fn parse_and_validate_token(
-        &self,
-        token_file: &str,
-        content: &str,
-    ) -> Result<Option<TokenData>, Error> {
-        match serde_json::from_str::<TokenData>(content) {
-            Ok(token_data) => self.handle_parsed_token(token_file, token_data).await,
-            Err(e) => self.handle_parse_error(token_file, e).await,
+    /// Returns the token store to persist tokens in, if persistence is enabled
+    ///
+    /// An explicitly configured store is always used. Otherwise tokens are written to
+    /// the account's own file (see [`TokenAccountKey::token_file`]) when the environment
+    /// enables persistence.
+    fn persistence_store(&self) -> Option<Arc<dyn TokenStore>> {
+        if let Some(store) = &self.token_store {
+            return Some(Arc::clone(store));
         }
-    }
 
-    /// Handles successfully parsed token data, checking expiration
-    async fn handle_parsed_token(
-        &self,
-        token_file: &str,
-        token_data: TokenData,
-    ) -> Result<Option<TokenData>, Error> {
-        if token_data.is_expired() {
-            tracing::info!("Token loaded from disk is expired, removing file");
-            let _ = tokio::fs::remove_file(token_file).await;
-            Ok(None)
+        if Self::should_persist_tokens() {
+            Some(Arc::new(FileTokenStore::new(
+                self.account_key().token_file(),
+            )))
         } else {
-            tracing::info!("Valid token loaded from disk");
-            Ok(Some(token_data))
-        }
-    }
-
-    /// Handles token parsing errors by cleaning up the invalid file
-    async fn handle_parse_error(
-        &self,
-        token_file: &str,
-        e: serde_json::Error,
-    ) -> Result<Option<TokenData>, Error> {
-        tracing::warn!("Failed to parse token file, removing: {e}");
-        let _ = tokio::fs::remove_file(token_file).await;
-        Err(Error::Token(TokenError::serialization(format!(
-            "Failed to parse token file: {e}"
-        ))))
-    }
-
-    /// Saves token data to disk
-    async fn save_token_to_disk(&self, token_data: &TokenData) -> Result<(), Error> {
-        use tokio::fs;
-
-        let token_file = "token.json";
-
-        match serde_json::to_string_pretty(token_data) {
-            Ok(json) => match fs::write(token_file, json).await {
-                Ok(()) => {
-                    tracing::debug!("Token saved to disk: {}", token_file);
-                    Ok(())
-                }
-                Err(e) => {
-                    tracing::error!("Failed to write token file: {e}");
-                    Err(Error::Token(TokenError::storage(format!(
-                        "Failed to write token file: {e}"
-                    ))))
-                }
-            },
-            Err(e) => {
-                tracing::error!("Failed to serialize token data: {e}");
-                Err(Error::Token(TokenError::serialization(format!(
-                    "Failed to serialize token data: {e}"
-                ))))
-            }
+            None
         }
     }
 
-    /// Removes the token file from disk
-    async fn remove_token_from_disk(&self) -> Result<(), Error> {
-        use tokio::fs;
-
-        let token_file = "token.json";
-
-        match fs::remove_file(token_file).await {
-            Ok(()) => {
-                tracing::debug!("Token file removed from disk: {}", token_file);
-                Ok(())
-            }
-            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-                tracing::debug!("Token file does not exist, nothing to remove");
-                Ok(())
-            }
-            Err(e) => {
-                tracing::warn!("Failed to remove token file: {e}");
-                Err(Error::Token(TokenError::storage(format!(
-                    "Failed to remove token file: {e}"
-                ))))
-            }
-        }
+    /// Returns the key of the account this manager authenticates as
+    fn account_key(&self) -> TokenAccountKey {
+        let username = self.credentials.as_ref().map_or_else(
+            || env::var("AMP_USERNAME").unwrap_or_default(),
+            |credentials| credentials.username.clone(),
+        );
+        TokenAccountKey::new(&self.base_url, &username)
     }
 
     /// Forces cleanup of token persistence files (useful for testing)
     /// This method removes token files regardless of persistence settings
     ///
+    /// Both the file of the account configured in the environment and the legacy
+    /// shared `token.json` file are removed.
+    ///
     /// # Errors
     /// Returns an error if:
     /// - File system permissions prevent deletion of the token file
@@ -9896,103 +10811,494 @@ impl TokenManager {
     pub async fn force_cleanup_token_files() -> Result<(), Error> {
         use tokio::fs;
 
-        let token_file = "token.json";
+        let mut token_files = vec![PathBuf::from(DEFAULT_TOKEN_FILE)];
+        if let Ok(key) = TokenAccountKey::from_env() {
+            token_files.push(key.token_file());
+        }
 
-        match fs::remove_file(token_file).await {
-            Ok(()) => {
-                tracing::debug!("Token file forcefully removed: {}", token_file);
-                Ok(())
-            }
-            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-                tracing::debug!("No token file to clean up");
-                Ok(())
-            }
-            Err(e) => {
-                tracing::warn!("Failed to force cleanup token file: {e}");
-                Err(Error::Token(TokenError::storage(format!(
-                    "Failed to force cleanup token file: {e}"
-                ))))
+        for token_file in token_files {
+            match fs::remove_file(&token_file).await {
+                Ok(()) => {
+                    tracing::debug!("Token file forcefully removed: {}", token_file.display());
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    tracing::debug!("No token file to clean up: {}", token_file.display());
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to force cleanup token file: {e}");
+                    return Err(Error::Token(TokenError::storage(format!(
+                        "Failed to force cleanup token file: {e}"
+                    ))));
+                }
             }
         }
+
+        Ok(())
     }
 
-    /// Resets the global `TokenManager` singleton (useful for testing)
+    /// Resets the shared `TokenManager` for the account configured in the environment
+    /// (useful for testing)
     ///
-    /// This method clears the global singleton instance, forcing the next
-    /// call to `get_global_instance()` to create a fresh `TokenManager`.
-    /// Primarily intended for test scenarios where a clean state is needed.
+    /// Only the account identified by `AMP_API_BASE_URL` and `AMP_USERNAME` is
+    /// affected; managers for other accounts keep their tokens.
     ///
     /// # Errors
-    /// Returns an error if:
-    /// - Token clearing operations fail during the reset process
-    /// - File system errors occur when clearing persistent token data
-    /// - The global instance is in an invalid state that prevents cleanup
+    /// Returns an error if `AMP_API_BASE_URL` contains an invalid URL
     pub async fn reset_global_instance() -> Result<(), Error> {
-        // Clear any existing token from the current global instance
-        if let Some(manager) = GLOBAL_TOKEN_MANAGER.get() {
-            let _ = manager.clear_token().await;
+        let key = TokenAccountKey::from_env()?;
+        Self::reset_account(&key).await;
+        Ok(())
+    }
+
+    /// Resets the shared `TokenManager` for one AMP account
+    ///
+    /// The account's token is cleared and the manager is removed from the registry,
+    /// so the next lookup creates a fresh instance. Returns `true` if a manager was
+    /// registered for the account.
+    pub async fn reset_account(key: &TokenAccountKey) -> bool {
+        let removed = TOKEN_MANAGERS.lock().await.remove(key);
+
+        match removed {
+            Some(manager) => {
+                let _ = manager.clear_token().await;
+                tracing::debug!("TokenManager for {}@{} reset", key.username, key.base_url);
+                true
+            }
+            None => false,
         }
+    }
+}
 
-        // Reset the OnceCell to allow a new instance to be created
-        // Note: OnceCell doesn't have a reset method, so we can't actually reset it
-        // The best we can do is clear the token from the existing instance
-        tracing::debug!("Global TokenManager instance token cleared for testing");
-        Ok(())
+/// Default per-request timeout for AMP API calls
+const DEFAULT_REQUEST_TIMEOUT: StdDuration = StdDuration::from_secs(60);
+
+/// Number of progress steps reported by the distribution workflow
+const DISTRIBUTION_TOTAL_STEPS: u32 = 13;
+
+/// Comment the node wallet attaches to distribution transactions sent with `sendmany`
+const DISTRIBUTION_TX_COMMENT: &str = "AMP asset distribution";
+
+/// Number of recent wallet transactions searched for a distribution sent before a crash
+const SENT_DISTRIBUTION_LOOKBACK: u32 = 1_000;
+
+/// Allowed difference between the local clock and the node's transaction times
+const SENT_DISTRIBUTION_CLOCK_SKEW: Duration = Duration::minutes(2);
+
+/// Sends a step update of the distribution workflow, if anyone is listening
+fn send_distribution_progress(
+    progress_tx: Option<&tokio::sync::mpsc::UnboundedSender<crate::model::ProgressUpdate>>,
+    step: u32,
+    total: u32,
+    message: &str,
+) {
+    if let Some(tx) = progress_tx {
+        let _ = tx.send(crate::model::ProgressUpdate::Step {
+            current: step,
+            total,
+            message: message.to_string(),
+        });
     }
 }
 
+/// Returns the unconfidential form of `address`, or `address` itself if it does not parse
+fn unconfidential_address(address: &str) -> String {
+    elements::Address::from_str(address).map_or_else(
+        |_| address.to_string(),
+        |address| address.to_unconfidential().to_string(),
+    )
+}
+
+/// Computes the transaction ID of a signed transaction
+fn transaction_id(signed_tx_hex: &str) -> Result<String, AmpError> {
+    let tx_bytes = hex::decode(signed_tx_hex)
+        .map_err(|e| AmpError::validation(format!("Invalid signed transaction hex: {e}")))?;
+    let transaction: elements::Transaction = elements::encode::deserialize(&tx_bytes)
+        .map_err(|e| AmpError::validation(format!("Failed to decode signed transaction: {e}")))?;
+    Ok(transaction.txid().to_string())
+}
+
+/// Number of items requested per page by the pagination streams when the filter sets no `count`
+pub const DEFAULT_PAGE_SIZE: i64 = 100;
+
+/// Turns a page fetcher into a lazy stream of items
+///
+/// `fetch_page` is called with `(start, count)` only when the consumer has drained the
+/// previous page, so a slow consumer never has more than one page buffered. The stream
+/// ends after the first page that returns fewer than `page_size` items.
+fn paginate<'a, T, F, Fut>(
+    start: i64,
+    page_size: i64,
+    fetch_page: F,
+) -> impl Stream<Item = Result<T, Error>> + 'a
+where
+    T: 'a,
+    F: Fn(i64, i64) -> Fut + 'a,
+    Fut: Future<Output = Result<Vec<T>, Error>> + 'a,
+{
+    let page_size = page_size.max(1);
+
+    stream::try_unfold(Some(start), move |offset| {
+        let request = offset.map(|offset| (offset, fetch_page(offset, page_size)));
+        async move {
+            let Some((offset, request)) = request else {
+                return Ok(None);
+            };
+
+            let page = request.await?;
+            let fetched = i64::try_from(page.len()).unwrap_or(i64::MAX);
+            let next_offset = (fetched >= page_size).then(|| offset.saturating_add(fetched));
+
+            Ok::<_, Error>(Some((stream::iter(page.into_iter().map(Ok)), next_offset)))
+        }
+    })
+    .try_flatten()
+}
+
 #[derive(Debug, Clone)]
 pub struct ApiClient {
     client: Client,
     base_url: Url,
     token_strategy: Arc<Box<dyn TokenStrategy>>,
+    request_timeout: StdDuration,
+    retry_client: RetryClient,
+    checkpoint_store: Arc<dyn CheckpointStore>,
+    distribution_funding: DistributionFunding,
+    coin_selector: Arc<dyn CoinSelector>,
+    chain_backend: Option<Arc<dyn ChainBackend>>,
+    funding_addresses: Option<FundingAddresses>,
+    reorg_monitor: Option<ReorgMonitor>,
 }
 
-#[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
-impl ApiClient {
-    /// Creates a new API client with the base URL from environment variables.
-    ///
-    /// Automatically selects the appropriate token strategy based on environment detection:
-    /// - Mock strategy for mock environments (no persistence, isolated tokens)
-    /// - Live strategy for live environments (full token management with persistence)
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if:
-    /// - The `AMP_API_BASE_URL` environment variable contains an invalid URL
-    /// - Token strategy initialization fails
-    ///
-    /// # Examples
-    /// ```no_run
-    /// # use amp_rs::ApiClient;
-    /// # #[tokio::main]
-    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    /// // Create a new client - automatically detects environment
-    /// let client = ApiClient::new().await?;
-    ///
-    /// // Client is ready to use
-    /// let assets = client.get_assets().await?;
-    /// println!("Found {} assets", assets.len());
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub async fn new() -> Result<Self, Error> {
-        let base_url = get_amp_api_base_url()?;
-        let client = Client::new();
+/// How long asset workflows wait for their transactions to confirm
+const WORKFLOW_CONFIRMATION_TIMEOUT: StdDuration = StdDuration::from_secs(10 * 60);
 
-        // Automatic strategy selection based on environment
-        let token_strategy = TokenEnvironment::create_auto_strategy(None).await?;
+/// Builder for [`ApiClient`] with explicit configuration
+///
+/// Every setting is optional. Anything left unset falls back to the same
+/// environment variables and defaults used by [`ApiClient::new`]:
+/// - base URL: `AMP_API_BASE_URL`, then the AMP test environment
+/// - credentials: `AMP_USERNAME` / `AMP_PASSWORD` via environment detection
+/// - retry configuration: [`RetryConfig::from_env`]
+///
+/// Supplying credentials creates a dedicated [`TokenManager`] for the client
+/// instead of the process-wide one, which allows several AMP accounts to be
+/// used from the same process.
+///
+/// # Examples
+/// ```no_run
+/// # use amp_rs::ApiClient;
+/// # use reqwest::Url;
+/// # use secrecy::Secret;
+/// # use std::time::Duration;
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = ApiClient::builder()
+///     .base_url(Url::parse("https://amp-test.blockstream.com/api")?)
+///     .credentials("issuer_a", Secret::new("password".to_string()))
+///     .timeout(Duration::from_secs(30))
+///     .user_agent("my-service/1.0")
+///     .build()
+///     .await?;
+///
+/// let assets = client.get_assets().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct ApiClientBuilder {
+    base_url: Option<Url>,
+    credentials: Option<Credentials>,
+    retry_config: Option<RetryConfig>,
+    timeout: Option<StdDuration>,
+    user_agent: Option<String>,
+    token_store: Option<Arc<dyn TokenStore>>,
+    token_strategy: Option<Box<dyn TokenStrategy>>,
+    checkpoint_store: Option<Arc<dyn CheckpointStore>>,
+    distribution_funding: DistributionFunding,
+    coin_selector: Option<Arc<dyn CoinSelector>>,
+    chain_backend: Option<Arc<dyn ChainBackend>>,
+    funding_addresses: Option<FundingAddresses>,
+    reorg_monitor: Option<ReorgMonitor>,
+}
 
-        tracing::info!(
-            "Created ApiClient with {} strategy for base URL: {}",
-            token_strategy.strategy_type(),
-            base_url
+impl ApiClientBuilder {
+    /// Creates a builder with nothing configured
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the AMP API base URL
+    #[must_use]
+    pub fn base_url(mut self, base_url: Url) -> Self {
+        self.base_url = Some(base_url);
+        self
+    }
+
+    /// Sets the AMP account credentials used to obtain tokens
+    #[must_use]
+    pub fn credentials<S: Into<String>>(mut self, username: S, password: Secret<String>) -> Self {
+        self.credentials = Some(Credentials::new(username, password));
+        self
+    }
+
+    /// Sets the retry configuration used for token requests
+    #[must_use]
+    pub fn retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = Some(retry_config);
+        self
+    }
+
+    /// Sets the per-request timeout for API calls (default: 60 seconds)
+    #[must_use]
+    pub const fn timeout(mut self, timeout: StdDuration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with API requests
+    #[must_use]
+    pub fn user_agent<S: Into<String>>(mut self, user_agent: S) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Persists tokens in the given store instead of the default per-account token file
+    #[must_use]
+    pub fn token_store(mut self, token_store: Arc<dyn TokenStore>) -> Self {
+        self.token_store = Some(token_store);
+        self
+    }
+
+    /// Uses an explicit token strategy, bypassing credential handling entirely
+    #[must_use]
+    pub fn token_strategy(mut self, token_strategy: Box<dyn TokenStrategy>) -> Self {
+        self.token_strategy = Some(token_strategy);
+        self
+    }
+
+    /// Records distribution checkpoints in the given store instead of process memory
+    ///
+    /// Without this setting checkpoints are kept in an
+    /// [`InMemoryCheckpointStore`], so nothing survives a restart. Use a persistent store
+    /// such as [`FileCheckpointStore`](crate::distribution::FileCheckpointStore) to resume
+    /// distributions after the process dies.
+    #[must_use]
+    pub fn checkpoint_store(mut self, checkpoint_store: Arc<dyn CheckpointStore>) -> Self {
+        self.checkpoint_store = Some(checkpoint_store);
+        self
+    }
+
+    /// Sets how distribution transactions are funded and signed
+    ///
+    /// Defaults to [`DistributionFunding::NodeWallet`], which pays distributions from the
+    /// node wallet with `sendmany`. [`DistributionFunding::Signer`] builds the transaction
+    /// from the wallet's UTXOs and signs it with the `Signer` passed to the workflow.
+    #[must_use]
+    pub const fn distribution_funding(mut self, funding: DistributionFunding) -> Self {
+        self.distribution_funding = funding;
+        self
+    }
+
+    /// Sets the strategy that selects the asset UTXOs of distribution transactions
+    ///
+    /// Defaults to [`LargestFirst`]. The selector is used when a distribution is funded
+    /// with [`DistributionFunding::Signer`] and by the dry runs; with
+    /// [`DistributionFunding::NodeWallet`], `sendmany` selects the UTXOs itself.
+    #[must_use]
+    pub fn coin_selector(mut self, coin_selector: Arc<dyn CoinSelector>) -> Self {
+        self.coin_selector = Some(coin_selector);
+        self
+    }
+
+    /// Broadcasts and tracks workflow transactions through the given chain backend
+    ///
+    /// Without a backend, `distribute_asset`, `reissue_asset` and `burn_asset` use the
+    /// `ElementsRpc` they are given. With one, for example an
+    /// [`EsploraBackend`](crate::chain::EsploraBackend), transactions are broadcast and
+    /// tracked through the backend. Unless [`funding_addresses`](Self::funding_addresses)
+    /// are set too, the node wallet still selects the UTXOs and builds the transactions.
+    #[must_use]
+    pub fn chain_backend(mut self, chain_backend: Arc<dyn ChainBackend>) -> Self {
+        self.chain_backend = Some(chain_backend);
+        self
+    }
+
+    /// Funds workflow transactions from the UTXOs of `funding` instead of the node wallet
+    ///
+    /// Only takes effect together with a [`chain_backend`](Self::chain_backend).
+    /// `distribute_asset`, `reissue_asset` and `burn_asset` then look up the UTXOs of
+    /// the funding addresses through the backend, build their transactions with a
+    /// [`PsetBuilder`], sign them with the workflow's [`Signer`] and broadcast them
+    /// through the backend. Distributions are built this way whatever their
+    /// [`DistributionFunding`]. The node wallet only reports transaction details to the
+    /// AMP API when a workflow is confirmed.
+    #[must_use]
+    pub fn funding_addresses(mut self, funding: FundingAddresses) -> Self {
+        self.funding_addresses = Some(funding);
+        self
+    }
+
+    /// Monitors workflow transactions for reorgs after they are confirmed with the AMP API
+    ///
+    /// After `confirm_distribution`, `reissue_confirm` or `burn_confirm` succeeds inside
+    /// a workflow, a background task follows the transaction with `monitor` until it is
+    /// final and reports reorgs as [`ReorgEvent`](crate::confirmation::ReorgEvent)s.
+    #[must_use]
+    pub fn reorg_monitor(mut self, monitor: ReorgMonitor) -> Self {
+        self.reorg_monitor = Some(monitor);
+        self
+    }
+
+    /// Uses an existing token manager
+    #[must_use]
+    pub fn token_manager(self, token_manager: Arc<TokenManager>) -> Self {
+        self.token_strategy(Box::new(LiveTokenStrategy::with_token_manager(
+            token_manager,
+        )))
+    }
+
+    /// Builds the `ApiClient`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The base URL falls back to an invalid `AMP_API_BASE_URL`
+    /// - The retry configuration falls back to invalid environment values
+    /// - The HTTP client cannot be constructed
+    /// - Token strategy initialization fails
+    pub async fn build(self) -> Result<ApiClient, Error> {
+        let base_url = match self.base_url {
+            Some(base_url) => base_url,
+            None => get_amp_api_base_url()?,
+        };
+
+        let mut http_builder = Client::builder();
+        if let Some(user_agent) = self.user_agent {
+            http_builder = http_builder.user_agent(user_agent);
+        }
+        let client = http_builder.build()?;
+
+        let explicit_retry_config = self.retry_config.is_some();
+        let retry_config = match self.retry_config {
+            Some(retry_config) => retry_config,
+            None => RetryConfig::from_env()?,
+        };
+
+        let token_strategy: Box<dyn TokenStrategy> = if let Some(strategy) = self.token_strategy {
+            strategy
+        } else if self.credentials.is_some() || self.token_store.is_some() || explicit_retry_config
+        {
+            let token_manager = match self.credentials {
+                // Clients for the same account share one token manager
+                Some(credentials) => {
+                    TokenManager::for_account(
+                        retry_config.clone(),
+                        base_url.clone(),
+                        credentials,
+                        self.token_store,
+                    )
+                    .await?
+                }
+                // Without explicit credentials the client gets a dedicated manager
+                None => Arc::new(
+                    TokenManager::build(
+                        retry_config.clone(),
+                        base_url.clone(),
+                        None,
+                        self.token_store,
+                    )
+                    .await?,
+                ),
+            };
+            Box::new(LiveTokenStrategy::with_token_manager(token_manager))
+        } else {
+            TokenEnvironment::create_auto_strategy(None).await?
+        };
+
+        tracing::info!(
+            "Built ApiClient with {} strategy for base URL: {}",
+            token_strategy.strategy_type(),
+            base_url
+        );
+
+        Ok(ApiClient {
+            retry_client: RetryClient::with_client(client.clone(), retry_config),
+            client,
+            base_url,
+            token_strategy: Arc::new(token_strategy),
+            request_timeout: self.timeout.unwrap_or(DEFAULT_REQUEST_TIMEOUT),
+            checkpoint_store: self
+                .checkpoint_store
+                .unwrap_or_else(|| Arc::new(InMemoryCheckpointStore::new())),
+            distribution_funding: self.distribution_funding,
+            coin_selector: self.coin_selector.unwrap_or_else(|| Arc::new(LargestFirst)),
+            chain_backend: self.chain_backend,
+            funding_addresses: self.funding_addresses,
+            reorg_monitor: self.reorg_monitor,
+        })
+    }
+}
+
+#[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+impl ApiClient {
+    /// Returns a builder for configuring an `ApiClient` explicitly
+    #[must_use]
+    pub fn builder() -> ApiClientBuilder {
+        ApiClientBuilder::new()
+    }
+
+    /// Creates a new API client with the base URL from environment variables.
+    ///
+    /// Automatically selects the appropriate token strategy based on environment detection:
+    /// - Mock strategy for mock environments (no persistence, isolated tokens)
+    /// - Live strategy for live environments (full token management with persistence)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The `AMP_API_BASE_URL` environment variable contains an invalid URL
+    /// - Token strategy initialization fails
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use amp_rs::ApiClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// // Create a new client - automatically detects environment
+    /// let client = ApiClient::new().await?;
+    ///
+    /// // Client is ready to use
+    /// let assets = client.get_assets().await?;
+    /// println!("Found {} assets", assets.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn new() -> Result<Self, Error> {
+        let base_url = get_amp_api_base_url()?;
+        let client = Client::new();
+
+        // Automatic strategy selection based on environment
+        let token_strategy = TokenEnvironment::create_auto_strategy(None).await?;
+
+        tracing::info!(
+            "Created ApiClient with {} strategy for base URL: {}",
+            token_strategy.strategy_type(),
+            base_url
         );
 
         Ok(Self {
+            retry_client: RetryClient::with_client(client.clone(), RetryConfig::from_env()?),
             client,
             base_url,
             token_strategy: Arc::new(token_strategy),
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            checkpoint_store: Arc::new(InMemoryCheckpointStore::new()),
+            distribution_funding: DistributionFunding::default(),
+            coin_selector: Arc::new(LargestFirst),
+            chain_backend: None,
+            funding_addresses: None,
+            reorg_monitor: None,
         })
     }
 
@@ -10031,9 +11337,17 @@ impl ApiClient {
         );
 
         Ok(Self {
+            retry_client: RetryClient::with_client(client.clone(), RetryConfig::from_env()?),
             client,
             base_url,
             token_strategy: Arc::new(token_strategy),
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            checkpoint_store: Arc::new(InMemoryCheckpointStore::new()),
+            distribution_funding: DistributionFunding::default(),
+            coin_selector: Arc::new(LargestFirst),
+            chain_backend: None,
+            funding_addresses: None,
+            reorg_monitor: None,
         })
     }
 
@@ -10051,10 +11365,19 @@ impl ApiClient {
             base_url
         );
 
+        let client = Client::new();
         Ok(Self {
-            client: Client::new(),
+            retry_client: RetryClient::with_client(client.clone(), RetryConfig::from_env()?),
+            client,
             base_url,
             token_strategy: Arc::new(token_strategy),
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            checkpoint_store: Arc::new(InMemoryCheckpointStore::new()),
+            distribution_funding: DistributionFunding::default(),
+            coin_selector: Arc::new(LargestFirst),
+            chain_backend: None,
+            funding_addresses: None,
+            reorg_monitor: None,
         })
     }
 
@@ -10073,10 +11396,19 @@ impl ApiClient {
             base_url
         );
 
+        let client = Client::new();
         Ok(Self {
-            client: Client::new(),
+            retry_client: RetryClient::with_client(client.clone(), RetryConfig::from_env()?),
+            client,
             base_url,
             token_strategy: Arc::new(token_strategy),
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            checkpoint_store: Arc::new(InMemoryCheckpointStore::new()),
+            distribution_funding: DistributionFunding::default(),
+            coin_selector: Arc::new(LargestFirst),
+            chain_backend: None,
+            funding_addresses: None,
+            reorg_monitor: None,
         })
     }
 
@@ -10112,9 +11444,17 @@ impl ApiClient {
         );
 
         Ok(Self {
+            retry_client: RetryClient::with_client(client.clone(), RetryConfig::for_tests()),
             client,
             base_url,
             token_strategy: Arc::new(token_strategy),
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            checkpoint_store: Arc::new(InMemoryCheckpointStore::new()),
+            distribution_funding: DistributionFunding::default(),
+            coin_selector: Arc::new(LargestFirst),
+            chain_backend: None,
+            funding_addresses: None,
+            reorg_monitor: None,
         })
     }
 
@@ -10313,6 +11653,30 @@ impl ApiClient {
         method: Method,
         path: &[&str],
         body: Option<impl serde::Serialize>,
+    ) -> Result<reqwest::Response, Error> {
+        self.send_request(method, path, body)
+            .await
+            .map_err(|e| match e {
+                Error::RequestFailedDetailed {
+                    status,
+                    error_message,
+                    ..
+                } => Error::RequestFailed(format!(
+                    "Request to {path:?} failed with status {status}: {error_message}"
+                )),
+                other => other,
+            })
+    }
+
+    /// Sends a request, retrying network errors, and returns the successful response
+    ///
+    /// Error statuses are returned as `Error::RequestFailedDetailed` so that callers can
+    /// match on the status code.
+    async fn send_request(
+        &self,
+        method: Method,
+        path: &[&str],
+        body: Option<impl serde::Serialize>,
     ) -> Result<reqwest::Response, Error> {
         let debug_logging = std::env::var("AMP_DEBUG").is_ok();
 
@@ -10341,7 +11705,7 @@ impl ApiClient {
                 .client
                 .request(method.clone(), url.clone())
                 .header(AUTHORIZATION, format!("token {token}"))
-                .timeout(std::time::Duration::from_secs(60)); // Increase timeout to 60 seconds
+                .timeout(self.request_timeout);
 
             if let Some(ref body) = body {
                 if debug_logging && attempt == 1 {
@@ -10382,9 +11746,12 @@ impl ApiClient {
                             eprintln!("❌ Error response body: {error_text}");
                         }
 
-                        return Err(Error::RequestFailed(format!(
-                            "Request to {path:?} failed with status {status}: {error_text}"
-                        )));
+                        return Err(Error::RequestFailedDetailed {
+                            method: method.to_string(),
+                            endpoint: url.to_string(),
+                            status,
+                            error_message: error_text,
+                        });
                     }
 
                     if debug_logging {
@@ -10435,11 +11802,18 @@ impl ApiClient {
         let mut url = self.base_url.clone();
         url.path_segments_mut().unwrap().extend(path);
         let endpoint = url.to_string();
-        let expected_type = std::any::type_name::<T>().to_string();
 
         let response = self.request_raw(method, path, body).await?;
+        Self::read_json(response, method_str, endpoint).await
+    }
 
-        // Try to deserialize, capturing raw response on failure
+    /// Deserializes a response body, capturing the raw response on failure
+    async fn read_json<T: DeserializeOwned>(
+        response: reqwest::Response,
+        method_str: String,
+        endpoint: String,
+    ) -> Result<T, Error> {
+        let expected_type = std::any::type_name::<T>().to_string();
         match response.text().await {
             Ok(raw_response) => serde_json::from_str(&raw_response).map_err(|e| {
                 Error::ResponseDeserializationFailed {
@@ -10456,6 +11830,50 @@ impl ApiClient {
         }
     }
 
+    /// Fetches one page of a list endpoint through the client's `RetryClient`
+    ///
+    /// Retries, `Retry-After` handling and backoff all follow the client's `RetryConfig`.
+    async fn request_page<T: DeserializeOwned>(
+        &self,
+        path: &[&str],
+        params: &(impl serde::Serialize + Sync),
+    ) -> Result<Vec<T>, Error> {
+        let mut url = self.base_url.clone();
+        url.path_segments_mut().unwrap().extend(path);
+
+        let token = self.get_token().await?;
+        let response = self
+            .retry_client
+            .execute_with_retry(|| {
+                self.retry_client
+                    .client()
+                    .get(url.clone())
+                    .header(AUTHORIZATION, format!("token {token}"))
+                    .json(params)
+            })
+            .await
+            .map_err(|e| match e {
+                TokenError::ObtainFailed {
+                    attempts,
+                    last_error,
+                } => Error::RequestFailed(format!(
+                    "Request to {path:?} failed after {attempts} attempts: {last_error}"
+                )),
+                other => Error::Token(other),
+            })?;
+
+        let raw_response = response.text().await.map_err(|e| {
+            Error::ResponseParsingFailed(format!("Failed to read response body: {e}"))
+        })?;
+        serde_json::from_str(&raw_response).map_err(|e| Error::ResponseDeserializationFailed {
+            method: Method::GET.to_string(),
+            endpoint: url.to_string(),
+            expected_type: std::any::type_name::<Vec<T>>().to_string(),
+            serde_error: e.to_string(),
+            raw_response,
+        })
+    }
+
     async fn request_empty(
         &self,
         method: Method,
@@ -10918,6 +12336,86 @@ impl ApiClient {
             .await
     }
 
+    /// Streams all activities for a specific asset, fetching pages lazily.
+    ///
+    /// Pages are requested one at a time as the stream is consumed, starting at
+    /// `filter.start` (or 0) with `filter.count` items per page (or [`DEFAULT_PAGE_SIZE`]).
+    /// The remaining filter fields are sent with every page. Each page request is retried
+    /// according to the client's `RetryConfig`. The stream ends after the first short page.
+    ///
+    /// # Errors
+    /// The stream yields an error, and should not be polled further, if a page cannot be
+    /// fetched after all retry attempts or its response cannot be parsed.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use amp_rs::ApiClient;
+    /// # use amp_rs::model::AssetActivityParams;
+    /// # use futures::TryStreamExt;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ApiClient::new().await?;
+    /// let mut activities =
+    ///     std::pin::pin!(client.stream_asset_activities("asset-uuid", AssetActivityParams::default()));
+    ///
+    /// while let Some(activity) = activities.try_next().await? {
+    ///     println!("{} {}", activity.txid, activity.amount);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream_asset_activities<'a>(
+        &'a self,
+        asset_uuid: &'a str,
+        filter: AssetActivityParams,
+    ) -> impl Stream<Item = Result<Activity, Error>> + 'a {
+        let start = filter.start.unwrap_or(0);
+        let page_size = filter.count.unwrap_or(DEFAULT_PAGE_SIZE);
+
+        paginate(start, page_size, move |start, count| {
+            let params = AssetActivityParams {
+                start: Some(start),
+                count: Some(count),
+                ..filter.clone()
+            };
+            async move {
+                self.request_page(&["assets", asset_uuid, "activities"], &params)
+                    .await
+            }
+        })
+    }
+
+    /// Streams all transactions for a specific asset, fetching pages lazily.
+    ///
+    /// Pages are requested one at a time as the stream is consumed, starting at
+    /// `filter.start` (or 0) with `filter.count` items per page (or [`DEFAULT_PAGE_SIZE`]).
+    /// The remaining filter fields are sent with every page. Each page request is retried
+    /// according to the client's `RetryConfig`. The stream ends after the first short page.
+    ///
+    /// # Errors
+    /// The stream yields an error, and should not be polled further, if a page cannot be
+    /// fetched after all retry attempts or its response cannot be parsed.
+    pub fn stream_asset_transactions<'a>(
+        &'a self,
+        asset_uuid: &'a str,
+        filter: AssetTransactionParams,
+    ) -> impl Stream<Item = Result<AssetTransaction, Error>> + 'a {
+        let start = filter.start.unwrap_or(0);
+        let page_size = filter.count.unwrap_or(DEFAULT_PAGE_SIZE);
+
+        paginate(start, page_size, move |start, count| {
+            let params = AssetTransactionParams {
+                start: Some(start),
+                count: Some(count),
+                ..filter.clone()
+            };
+            async move {
+                self.request_page(&["assets", asset_uuid, "txs"], &params)
+                    .await
+            }
+        })
+    }
+
     /// Gets a specific transaction for an asset by transaction ID.
     ///
     /// This method retrieves detailed information about a specific transaction
@@ -11115,6 +12613,49 @@ impl ApiClient {
         self.request_json(Method::GET, &path, None::<&()>).await
     }
 
+    /// Returns the cap table of an asset at a block height
+    ///
+    /// See [`CapTable::from_ownerships`](crate::cap_table::CapTable::from_ownerships).
+    ///
+    /// # Errors
+    /// Returns an error if the ownerships at `height` cannot be retrieved
+    pub async fn cap_table_at_height(
+        &self,
+        asset_uuid: &str,
+        height: i64,
+    ) -> Result<crate::cap_table::CapTable, Error> {
+        let ownerships = self.get_asset_ownerships(asset_uuid, Some(height)).await?;
+        Ok(crate::cap_table::CapTable::from_ownerships(
+            asset_uuid, height, ownerships,
+        ))
+    }
+
+    /// Returns the cap table of an asset as of `at`
+    ///
+    /// The snapshot is taken at the last block at or before `at`, found with
+    /// [`ElementsRpc::height_at`].
+    ///
+    /// # Errors
+    /// Returns an error if the height cannot be determined or the ownerships cannot be
+    /// retrieved
+    pub async fn cap_table_at(
+        &self,
+        asset_uuid: &str,
+        at: chrono::DateTime<Utc>,
+        node_rpc: &ElementsRpc,
+    ) -> Result<crate::cap_table::CapTable, AmpError> {
+        let height = node_rpc.height_at(at).await?;
+        tracing::debug!(
+            "Cap table of {} as of {} is at height {}",
+            asset_uuid,
+            at,
+            height
+        );
+        let mut table = self.cap_table_at_height(asset_uuid, height).await?;
+        table.record_time = Some(at);
+        Ok(table)
+    }
+
     /// # Errors
     /// Returns an error if:
     /// - The asset UUID is invalid or not found
@@ -11151,6 +12692,42 @@ impl ApiClient {
             .await
     }
 
+    /// Reconciles the holders of an asset across the AMP API and the treasury wallet
+    ///
+    /// Fetches the asset's summary, ownerships, UTXOs, lost outputs and assignments from
+    /// AMP and the asset's UTXOs in `wallet_name`, and joins them with
+    /// [`reconcile`](crate::reconciliation::reconcile). Nothing is changed.
+    ///
+    /// # Errors
+    /// Returns an error if any AMP request or the wallet's `listunspent` fails
+    pub async fn reconcile_asset(
+        &self,
+        asset_uuid: &str,
+        node_rpc: &ElementsRpc,
+        wallet_name: &str,
+    ) -> Result<crate::reconciliation::ReconciliationReport, AmpError> {
+        let asset = self.get_asset(asset_uuid).await?;
+        let asset_id = asset.asset_id;
+        let input = crate::reconciliation::ReconciliationInput {
+            asset_uuid: asset_uuid.to_string(),
+            summary: self.get_asset_summary(asset_uuid).await?,
+            ownerships: self.get_asset_ownerships(asset_uuid, None).await?,
+            utxos: self.get_asset_utxos(asset_uuid).await?,
+            lost_outputs: self.get_asset_lost_outputs(asset_uuid).await?,
+            assignments: self.get_asset_assignments(asset_uuid).await?,
+            wallet_utxos: node_rpc.list_unspent(wallet_name, Some(&asset_id)).await?,
+            asset_id,
+        };
+        let report = crate::reconciliation::reconcile(input);
+        tracing::info!(
+            "Reconciled asset {}: {} holders, {} mismatches",
+            asset_uuid,
+            report.holders.len(),
+            report.mismatches.len()
+        );
+        Ok(report)
+    }
+
     /// Gets the reissuances for a specific asset.
     ///
     /// # Arguments
@@ -11928,21 +13505,190 @@ impl ApiClient {
     /// - The GAID has no associated user
     /// - The GAID is invalid
     /// - Network or authentication errors occur
+    ///
+    /// Error statuses are returned as `Error::RequestFailedDetailed`; a GAID without a
+    /// registered user has status `404 Not Found`.
     pub async fn get_gaid_registered_user(
         &self,
         gaid: &str,
     ) -> Result<crate::model::RegisteredUserResponse, Error> {
-        self.request_json(
-            Method::GET,
-            &["gaids", gaid, "registered_user"],
-            None::<&()>,
-        )
-        .await
+        let path = ["gaids", gaid, "registered_user"];
+        let mut url = self.base_url.clone();
+        url.path_segments_mut().unwrap().extend(path);
+
+        let response = self.send_request(Method::GET, &path, None::<&()>).await?;
+        Self::read_json(response, Method::GET.to_string(), url.to_string()).await
     }
 
-    /// Gets the balance information for a specific GAID.
-    ///
-    /// This method retrieves all asset balances associated with the given GAID,
+    /// Looks up the registered user of a GAID, returning `Ok(None)` if it has none
+    async fn find_gaid_registered_user(
+        &self,
+        gaid: &str,
+    ) -> Result<Option<crate::model::RegisteredUserResponse>, Error> {
+        match self.get_gaid_registered_user(gaid).await {
+            Ok(user) => Ok(Some(user)),
+            Err(Error::RequestFailedDetailed { status, .. })
+                if status == reqwest::StatusCode::NOT_FOUND =>
+            {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Onboards investors, applying only the changes each one needs
+    ///
+    /// Investors are matched to registered users by GAID. See the
+    /// [`onboarding`](crate::onboarding) module for what is created and updated.
+    ///
+    /// # Errors
+    /// Returns an error if the categories cannot be retrieved. Failures of single rows
+    /// are reported in the returned [`OnboardingReport`](crate::onboarding::OnboardingReport).
+    pub async fn import_investors(
+        &self,
+        investors: &[crate::onboarding::InvestorRecord],
+    ) -> Result<crate::onboarding::OnboardingReport, Error> {
+        use crate::onboarding::{OnboardingReport, RowOutcome, RowResult};
+
+        let categories = self.get_categories().await?;
+        let mut report = OnboardingReport::default();
+        for (index, investor) in investors.iter().enumerate() {
+            let mut result = RowResult {
+                row: index + 1,
+                gaid: investor.gaid.clone(),
+                registered_user: None,
+                outcome: RowOutcome::Unchanged,
+                warnings: Vec::new(),
+            };
+            if let Err(error) = self
+                .import_investor(investor, &categories, &mut result)
+                .await
+            {
+                tracing::warn!(
+                    "Onboarding row {} ({}) failed: {}",
+                    result.row,
+                    result.gaid,
+                    error
+                );
+                result.outcome = RowOutcome::Failed { error };
+            }
+            report.results.push(result);
+        }
+        tracing::info!(
+            "Onboarded {} investors: {} created, {} updated, {} unchanged, {} failed",
+            investors.len(),
+            report.created().len(),
+            report.updated().len(),
+            report.unchanged().len(),
+            report.failed().len()
+        );
+        Ok(report)
+    }
+
+    async fn import_investor(
+        &self,
+        investor: &crate::onboarding::InvestorRecord,
+        categories: &[CategoryResponse],
+        result: &mut crate::onboarding::RowResult,
+    ) -> Result<(), String> {
+        use crate::onboarding::{CategoryRef, RowOutcome, UserChange};
+
+        let validation = self
+            .validate_gaid(&investor.gaid)
+            .await
+            .map_err(|e| e.to_string())?;
+        if !validation.is_valid {
+            return Err(format!(
+                "Invalid GAID: {}",
+                validation.error.unwrap_or_default()
+            ));
+        }
+        let mut category_ids = investor
+            .categories
+            .iter()
+            .map(|category| {
+                categories
+                    .iter()
+                    .find(|c| match category {
+                        CategoryRef::Id(id) => c.id == *id,
+                        CategoryRef::Name(name) => c.name == *name,
+                    })
+                    .map(|c| c.id)
+                    .ok_or_else(|| format!("Unknown category {category:?}"))
+            })
+            .collect::<Result<Vec<i64>, String>>()?;
+        category_ids.sort_unstable();
+        category_ids.dedup();
+
+        let existing = self
+            .find_gaid_registered_user(&investor.gaid)
+            .await
+            .map_err(|e| e.to_string())?;
+        let created = existing.is_none();
+        let user = match existing {
+            Some(user) => user,
+            None => self
+                .add_registered_user(&crate::model::RegisteredUserAdd {
+                    name: investor.name.clone(),
+                    gaid: Some(investor.gaid.clone()),
+                    is_company: investor.is_company,
+                })
+                .await
+                .map_err(|e| e.to_string())?,
+        };
+        result.registered_user = Some(user.id);
+
+        let mut changes = Vec::new();
+        if !created {
+            if user.name != investor.name {
+                self.edit_registered_user(
+                    user.id,
+                    &crate::model::RegisteredUserEdit {
+                        name: Some(investor.name.clone()),
+                    },
+                )
+                .await
+                .map_err(|e| e.to_string())?;
+                changes.push(UserChange::Renamed {
+                    from: user.name.clone(),
+                    to: investor.name.clone(),
+                });
+            }
+            if let Some(default_gaid) = user.gaid.as_deref().filter(|gaid| *gaid != investor.gaid) {
+                result.warnings.push(format!(
+                    "{} is not the default GAID of the registered user, which is {}",
+                    investor.gaid, default_gaid
+                ));
+            }
+            if user.is_company != investor.is_company {
+                result.warnings.push(format!(
+                    "is_company is {} in AMP but {} in the record and cannot be changed",
+                    user.is_company, investor.is_company
+                ));
+            }
+        }
+        for category_id in category_ids {
+            if !user.categories.contains(&category_id) {
+                self.add_registered_user_to_category(category_id, user.id)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                changes.push(UserChange::AddedToCategory { category_id });
+            }
+        }
+
+        result.outcome = if created {
+            RowOutcome::Created { changes }
+        } else if changes.is_empty() {
+            RowOutcome::Unchanged
+        } else {
+            RowOutcome::Updated { changes }
+        };
+        Ok(())
+    }
+
+    /// Gets the balance information for a specific GAID.
+    ///
+    /// This method retrieves all asset balances associated with the given GAID,
     /// including confirmed balances and any lost outputs.
     ///
     /// # Arguments
@@ -12667,6 +14413,217 @@ impl ApiClient {
             .await
     }
 
+    /// Reads the parts of the account an environment spec covers
+    ///
+    /// Registered users and managers are only read when the spec lists any, and treasury
+    /// addresses only for the assets the spec lists.
+    ///
+    /// # Errors
+    /// Returns an error if any of the requests fails
+    pub async fn live_environment(
+        &self,
+        spec: &crate::environment::EnvironmentSpec,
+    ) -> Result<crate::environment::LiveEnvironment, Error> {
+        let mut live = crate::environment::LiveEnvironment {
+            categories: self.get_categories().await?,
+            ..Default::default()
+        };
+        if !spec.registered_users.is_empty() {
+            live.registered_users = self.get_registered_users().await?;
+        }
+        if !spec.managers.is_empty() {
+            live.managers = self.get_managers().await?;
+        }
+        for asset_uuid in spec.treasury_addresses.keys() {
+            live.treasury_addresses.insert(
+                asset_uuid.clone(),
+                self.get_asset_treasury_addresses(asset_uuid).await?,
+            );
+        }
+        Ok(live)
+    }
+
+    /// Computes the changes that bring the account to `spec`, without applying them
+    ///
+    /// See the [`environment`](crate::environment) module.
+    ///
+    /// # Errors
+    /// Returns an error if the account cannot be read or the spec is invalid
+    pub async fn plan_environment(
+        &self,
+        spec: &crate::environment::EnvironmentSpec,
+    ) -> Result<crate::environment::EnvironmentPlan, AmpError> {
+        let live = self.live_environment(spec).await?;
+        let plan = spec
+            .plan(&live)
+            .map_err(|e| AmpError::validation(e.to_string()))?;
+        tracing::info!(
+            "Environment plan has {} changes and {} warnings",
+            plan.changes.len(),
+            plan.warnings.len()
+        );
+        Ok(plan)
+    }
+
+    /// Applies the changes of an environment plan in order
+    ///
+    /// Stops at the first change that fails; the returned report lists the changes
+    /// applied and the failure. Planning again picks up where the plan stopped.
+    pub async fn apply_environment(
+        &self,
+        plan: &crate::environment::EnvironmentPlan,
+    ) -> crate::environment::ApplyReport {
+        use crate::environment::{ApplyFailure, ApplyReport};
+
+        let mut ids = crate::environment::ResolvedIds::from_plan(plan);
+        let mut report = ApplyReport::default();
+        for change in &plan.changes {
+            tracing::info!("Applying {}", change);
+            if let Err(error) = self.apply_environment_change(change, plan, &mut ids).await {
+                tracing::error!("Failed to apply {}: {}", change, error);
+                report.failure = Some(ApplyFailure {
+                    change: change.clone(),
+                    error,
+                });
+                break;
+            }
+            report.applied.push(change.clone());
+        }
+        report
+    }
+
+    async fn apply_environment_change(
+        &self,
+        change: &crate::environment::Change,
+        plan: &crate::environment::EnvironmentPlan,
+        ids: &mut crate::environment::ResolvedIds,
+    ) -> Result<(), String> {
+        use crate::environment::Change;
+        use crate::model::{CategoryAdd, CategoryEdit, RegisteredUserAdd, RegisteredUserEdit};
+
+        let result = match change {
+            Change::CreateCategory { name, description } => {
+                let category = self
+                    .add_category(&CategoryAdd {
+                        name: name.clone(),
+                        description: description.clone(),
+                    })
+                    .await
+                    .map_err(|e| e.to_string())?;
+                ids.categories.insert(name.clone(), category.id);
+                Ok(())
+            }
+            Change::UpdateCategoryDescription {
+                category,
+                description,
+            } => self
+                .edit_category(
+                    ids.category(category)?,
+                    &CategoryEdit {
+                        name: None,
+                        description: description.clone(),
+                    },
+                )
+                .await
+                .map(drop),
+            Change::LinkAssetToCategory {
+                category,
+                asset_uuid,
+            } => self
+                .add_asset_to_category(ids.category(category)?, asset_uuid)
+                .await
+                .map(drop),
+            Change::UnlinkAssetFromCategory {
+                category,
+                asset_uuid,
+            } => self
+                .remove_asset_from_category(ids.category(category)?, asset_uuid)
+                .await
+                .map(drop),
+            Change::CreateRegisteredUser {
+                user,
+                name,
+                gaid,
+                is_company,
+            } => {
+                let created = self
+                    .add_registered_user(&RegisteredUserAdd {
+                        name: name.clone(),
+                        gaid: gaid.clone(),
+                        is_company: *is_company,
+                    })
+                    .await
+                    .map_err(|e| e.to_string())?;
+                ids.users.insert(user.clone(), created.id);
+                Ok(())
+            }
+            Change::RenameRegisteredUser { user, name } => self
+                .edit_registered_user(
+                    ids.user(user)?,
+                    &RegisteredUserEdit {
+                        name: Some(name.clone()),
+                    },
+                )
+                .await
+                .map(drop),
+            Change::AddUserToCategory { user, category } => self
+                .add_registered_user_to_category(ids.category(category)?, ids.user(user)?)
+                .await
+                .map(drop),
+            Change::RemoveUserFromCategory { user, category } => self
+                .remove_registered_user_from_category(ids.category(category)?, ids.user(user)?)
+                .await
+                .map(drop),
+            Change::CreateManager { username } => {
+                let password = plan.passwords.get(username).ok_or_else(|| {
+                    format!(
+                        "No password for manager '{username}'; plan the spec again to create it"
+                    )
+                })?;
+                let manager = self
+                    .create_manager(&crate::model::ManagerCreate {
+                        username: username.clone(),
+                        password: password.expose_secret().clone(),
+                    })
+                    .await
+                    .map_err(|e| e.to_string())?;
+                ids.managers.insert(username.clone(), manager.id);
+                Ok(())
+            }
+            Change::AddAssetToManager {
+                username,
+                asset_uuid,
+            } => {
+                self.add_asset_to_manager(ids.manager(username)?, asset_uuid)
+                    .await
+            }
+            Change::RemoveAssetFromManager {
+                username,
+                asset_uuid,
+            } => {
+                self.manager_remove_asset(ids.manager(username)?, asset_uuid)
+                    .await
+            }
+            Change::LockManager { username } => self.lock_manager(ids.manager(username)?).await,
+            Change::UnlockManager { username } => self.unlock_manager(ids.manager(username)?).await,
+            Change::AddTreasuryAddresses {
+                asset_uuid,
+                addresses,
+            } => {
+                self.add_asset_treasury_addresses(asset_uuid, addresses)
+                    .await
+            }
+            Change::RemoveTreasuryAddresses {
+                asset_uuid,
+                addresses,
+            } => {
+                self.delete_asset_treasury_addresses(asset_uuid, addresses)
+                    .await
+            }
+        };
+        result.map_err(|e| e.to_string())
+    }
+
     /// Gets all assignments for a specific asset.
     ///
     /// # Arguments
@@ -12801,6 +14758,106 @@ impl ApiClient {
         Ok(all_assignments)
     }
 
+    /// Creates one assignment per tranche of a vesting schedule
+    ///
+    /// See [`VestingSchedule::assignment_requests`](crate::vesting::VestingSchedule::assignment_requests).
+    ///
+    /// # Errors
+    /// Returns an error if the schedule is invalid or an assignment cannot be created
+    pub async fn create_vesting_assignments(
+        &self,
+        asset_uuid: &str,
+        schedule: &crate::vesting::VestingSchedule,
+    ) -> Result<Vec<Assignment>, AmpError> {
+        let requests = schedule
+            .assignment_requests()
+            .map_err(|e| AmpError::validation(e.to_string()))?;
+        tracing::info!(
+            "Creating {} vesting assignments for user {} on asset {}",
+            requests.len(),
+            schedule.registered_user,
+            asset_uuid
+        );
+        Ok(self.create_asset_assignments(asset_uuid, &requests).await?)
+    }
+
+    /// Returns a [`VestingTracker`](crate::vesting::VestingTracker) over the current
+    /// assignments of an asset
+    ///
+    /// # Errors
+    /// Returns an error if the assignments cannot be retrieved
+    pub async fn vesting_tracker(
+        &self,
+        asset_uuid: &str,
+    ) -> Result<crate::vesting::VestingTracker, Error> {
+        Ok(crate::vesting::VestingTracker::new(
+            self.get_asset_assignments(asset_uuid).await?,
+        ))
+    }
+
+    /// Calculates a pro-rata dividend over the holders of `source_asset_uuid` at
+    /// `record_height`
+    ///
+    /// Each holder's GAID is resolved to its registered user with
+    /// [`get_gaid_registered_user`](Self::get_gaid_registered_user), so holdings on any of
+    /// a user's GAIDs count, not just their default GAID. See
+    /// [`DividendCalculator`](crate::dividend::DividendCalculator).
+    ///
+    /// # Errors
+    /// Returns an error if the ownerships or registered users cannot be retrieved, or the
+    /// dividend cannot be calculated
+    pub async fn plan_dividend(
+        &self,
+        source_asset_uuid: &str,
+        record_height: i64,
+        calculator: &crate::dividend::DividendCalculator,
+    ) -> Result<crate::dividend::DividendPlan, AmpError> {
+        let snapshot = self
+            .cap_table_at_height(source_asset_uuid, record_height)
+            .await?;
+        // A user's default GAID is only one of their GAIDs, so resolve each holder's GAID
+        let mut registered_users: HashMap<String, i64> = HashMap::new();
+        for gaid in snapshot
+            .entries
+            .iter()
+            .filter_map(|entry| entry.gaid.as_deref())
+        {
+            if registered_users.contains_key(gaid) {
+                continue;
+            }
+            if let Some(user) = self.find_gaid_registered_user(gaid).await? {
+                registered_users.insert(gaid.to_string(), user.id);
+            }
+        }
+        calculator
+            .calculate(&snapshot, &registered_users)
+            .map_err(|e| AmpError::validation(e.to_string()))
+    }
+
+    /// Creates the assignments of a dividend plan on the target asset
+    ///
+    /// # Errors
+    /// Returns an error if the plan allocates nothing or an assignment cannot be created
+    pub async fn create_dividend_assignments(
+        &self,
+        target_asset_uuid: &str,
+        plan: &crate::dividend::DividendPlan,
+    ) -> Result<Vec<Assignment>, AmpError> {
+        let requests = plan.assignment_requests();
+        if requests.is_empty() {
+            return Err(AmpError::validation("Dividend plan has no allocations"));
+        }
+        tracing::info!(
+            "Creating {} dividend assignments totalling {} on asset {}",
+            requests.len(),
+            plan.allocated(),
+            target_asset_uuid
+        );
+        Ok(self
+            .create_asset_assignments(target_asset_uuid, &requests)
+            .await?)
+    }
+
     /// Gets a specific asset assignment by asset UUID and assignment ID.
     ///
     /// This method sends a GET request to retrieve detailed information about a specific asset
@@ -12889,7 +14946,7 @@ impl ApiClient {
     ///
     /// # Examples
     /// ```no_run
-    /// # use amp_rs::{ApiClient, model::AssetDistributionAssignment, AmpError};
+    /// # use amp_rs::{ApiClient, model::AssetDistributionAssignment, AmpError, Amount};
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), AmpError> {
     /// let client = ApiClient::new().await.map_err(AmpError::from)?;
@@ -12899,12 +14956,12 @@ impl ApiClient {
     ///     AssetDistributionAssignment {
     ///         user_id: "user123".to_string(),
     ///         address: "lq1qq2xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq".to_string(),
-    ///         amount: 100.0,
+    ///         amount: Amount::from_sat(100),
     ///     },
     ///     AssetDistributionAssignment {
     ///         user_id: "user456".to_string(),
     ///         address: "lq1qq3xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq".to_string(),
-    ///         amount: 50.0,
+    ///         amount: Amount::from_sat(50),
     ///     },
     /// ];
     ///
@@ -12953,7 +15010,7 @@ impl ApiClient {
         // Convert AssetDistributionAssignment to DistributionAssignmentRequest
         // The API expects user_uuid field, but our input uses user_id
         tracing::trace!("Converting {} assignments to API format", assignments.len());
-        let mut total_amount = 0.0;
+        let mut total_amount = Amount::ZERO;
         let api_assignments: Vec<DistributionAssignmentRequest> = assignments
             .into_iter()
             .enumerate()
@@ -12981,7 +15038,7 @@ impl ApiClient {
                             "Assignment {index} has empty address"
                         )));
                     }
-                    if assignment.amount <= 0.0 {
+                    if !assignment.amount.is_positive() {
                         tracing::error!(
                             "Assignment {} has non-positive amount: {}",
                             index,
@@ -13114,7 +15171,7 @@ impl ApiClient {
     ///
     /// # Examples
     /// ```no_run
-    /// # use amp_rs::{ApiClient, model::{AmpTxData, Unspent}, AmpError};
+    /// # use amp_rs::{ApiClient, model::{AmpTxData, Unspent}, AmpError, Amount};
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), AmpError> {
     /// # let client = ApiClient::new().await?;
@@ -13139,7 +15196,7 @@ impl ApiClient {
     ///     Unspent {
     ///         txid: "abc123def456...".to_string(),
     ///         vout: 1,
-    ///         amount: 25.0,
+    ///         amount: Amount::from_sat(25),
     ///         asset: "asset_id_hex".to_string(),
     ///         address: "change_address".to_string(),
     ///         spendable: true,
@@ -13211,7 +15268,7 @@ impl ApiClient {
         if change_data.is_empty() {
             tracing::debug!("No change UTXOs to include in confirmation");
         } else {
-            let total_change: f64 = change_data.iter().map(|utxo| utxo.amount).sum();
+            let total_change: Amount = change_data.iter().map(|utxo| utxo.amount).sum();
             tracing::debug!(
                 "Change data - {} UTXOs, total amount: {}",
                 change_data.len(),
@@ -14520,6 +16577,10 @@ impl ApiClient {
     /// 7. Waits for blockchain confirmations (2 confirmations minimum)
     /// 8. Confirms the distribution with the AMP API
     ///
+    /// Once the distribution is created, every completed step is recorded in the client's
+    /// [`CheckpointStore`]. If the workflow is interrupted, `resume_distribution` continues
+    /// from the last recorded step instead of leaving the distribution unconfirmed.
+    ///
     /// # Arguments
     /// * `asset_uuid` - The UUID of the asset to distribute (must be valid UUID format)
     /// * `assignments` - Vector of assignments specifying `user_id`, address, and amount
@@ -14540,7 +16601,7 @@ impl ApiClient {
     ///
     /// # Examples
     /// ```no_run
-    /// # use amp_rs::{ApiClient, ElementsRpc, AmpError};
+    /// # use amp_rs::{ApiClient, ElementsRpc, AmpError, Amount};
     /// # use amp_rs::model::AssetDistributionAssignment;
     /// # use amp_rs::signer::{Signer, LwkSoftwareSigner};
     /// # #[tokio::main]
@@ -14553,7 +16614,7 @@ impl ApiClient {
     ///     AssetDistributionAssignment {
     ///         user_id: "user123".to_string(),
     ///         address: "lq1qq2xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq".to_string(),
-    ///         amount: 100.0,
+    ///         amount: Amount::from_sat(100),
     ///     },
     /// ];
     ///
@@ -14574,7 +16635,7 @@ impl ApiClient {
     /// - 2.2: Assignment details validation
     /// - 2.4: Input validation for all parameters
     /// - 5.1: Comprehensive error handling with context
-    #[allow(clippy::cognitive_complexity, clippy::too_many_lines)]
+    ///
     /// Distributes an asset with progress callbacks
     ///
     /// This method is similar to `distribute_asset` but provides real-time progress updates
@@ -14590,7 +16651,6 @@ impl ApiClient {
     ///
     /// # Returns
     /// Returns the transaction ID on success, or an error if any step fails
-    #[allow(clippy::too_many_arguments)]
     pub async fn distribute_asset_with_progress(
         &self,
         asset_uuid: &str,
@@ -14600,6 +16660,32 @@ impl ApiClient {
         signer: &dyn Signer,
         progress_tx: Option<tokio::sync::mpsc::UnboundedSender<crate::model::ProgressUpdate>>,
     ) -> Result<String, AmpError> {
+        let checkpoint = self
+            .register_distribution(
+                asset_uuid,
+                assignments,
+                node_rpc,
+                wallet_name,
+                signer,
+                progress_tx.as_ref(),
+            )
+            .await?;
+        self.send_distribution(checkpoint, node_rpc, signer, progress_tx.as_ref())
+            .await
+    }
+
+    /// Validates a distribution, creates it with the AMP API and records its first
+    /// checkpoint (steps 1 to 6)
+    #[allow(clippy::cognitive_complexity, clippy::too_many_lines)]
+    async fn register_distribution(
+        &self,
+        asset_uuid: &str,
+        assignments: Vec<AssetDistributionAssignment>,
+        node_rpc: &ElementsRpc,
+        wallet_name: &str,
+        signer: &dyn Signer,
+        progress_tx: Option<&tokio::sync::mpsc::UnboundedSender<crate::model::ProgressUpdate>>,
+    ) -> Result<DistributionCheckpoint, AmpError> {
         let distribution_span = tracing::info_span!(
             "distribute_asset_with_progress",
             asset_uuid = %asset_uuid,
@@ -14613,18 +16699,10 @@ impl ApiClient {
             assignments.len()
         );
 
-        // Helper to send progress updates
         let send_progress = |step: u32, total: u32, message: &str| {
-            if let Some(ref tx) = progress_tx {
-                let _ = tx.send(crate::model::ProgressUpdate::Step {
-                    current: step,
-                    total,
-                    message: message.to_string(),
-                });
-            }
+            send_distribution_progress(progress_tx, step, total, message);
         };
-
-        let total_steps = 11u32;
+        let total_steps = DISTRIBUTION_TOTAL_STEPS;
 
         // Step 1: Input validation - asset_uuid format
         send_progress(1, total_steps, "Validating asset UUID format");
@@ -14658,6 +16736,25 @@ impl ApiClient {
             })?;
         tracing::debug!("Elements RPC connection validation passed");
 
+        // Reject addresses of another network before the distribution is registered, so a
+        // bad address never leaves an open distribution behind
+        let network = node_rpc
+            .network()
+            .await
+            .map_err(|e| e.with_context("Step 3: Elements network detection"))?;
+        for assignment in &assignments {
+            network.validate_address(&assignment.address).map_err(|e| {
+                tracing::error!("Assignment address rejected: {}", e);
+                AmpError::validation(e.to_string())
+                    .with_context("Step 3: Assignment address validation")
+            })?;
+        }
+        tracing::debug!(
+            "All {} assignment addresses are valid {} addresses",
+            assignments.len(),
+            network
+        );
+
         // Step 4: Check signer interface availability
         send_progress(4, total_steps, "Validating signer interface");
         tracing::debug!("Step 4: Validating signer interface");
@@ -14710,8 +16807,33 @@ impl ApiClient {
             distribution_response.asset_id
         );
 
+        // Record the registered distribution so that it can be resumed if the process dies
+        let checkpoint = DistributionCheckpoint::new(
+            distribution_response.distribution_uuid.clone(),
+            asset_uuid.to_string(),
+            distribution_response.asset_id.clone(),
+            wallet_name.to_string(),
+            distribution_response.map_address_amount.clone(),
+        );
+        self.checkpoint_store.save(&checkpoint).await?;
+        Ok(checkpoint)
+    }
+
+    /// Verifies the node and runs a registered distribution (steps 7 to 13)
+    async fn send_distribution(
+        &self,
+        checkpoint: DistributionCheckpoint,
+        node_rpc: &ElementsRpc,
+        signer: &dyn Signer,
+        progress_tx: Option<&tokio::sync::mpsc::UnboundedSender<crate::model::ProgressUpdate>>,
+    ) -> Result<String, AmpError> {
         // Step 7: Verify Elements node status and execute transaction workflow
-        send_progress(7, total_steps, "Verifying Elements node status");
+        send_distribution_progress(
+            progress_tx,
+            7,
+            DISTRIBUTION_TOTAL_STEPS,
+            "Verifying Elements node status",
+        );
         tracing::debug!("Step 7: Verifying Elements node status");
         let (network_info, blockchain_info) = node_rpc.get_node_status().await.map_err(|e| {
             tracing::error!("Elements node status verification failed: {}", e);
@@ -14730,65 +16852,761 @@ impl ApiClient {
             network_info.connections
         );
 
-        // Step 8: Send distribution transaction using Elements' sendmany
-        send_progress(8, total_steps, "Sending distribution transaction");
-        tracing::debug!("Step 8: Sending distribution transaction using Elements sendmany");
+        self.run_distribution(checkpoint, node_rpc, signer, progress_tx)
+            .await
+    }
 
-        // Create asset amounts map for sendmany (all outputs use the same asset)
-        let mut asset_amounts = std::collections::HashMap::new();
-        for address in distribution_response.map_address_amount.keys() {
-            asset_amounts.insert(address.clone(), distribution_response.asset_id.clone());
-        }
+    /// Resumes an interrupted distribution from its last checkpoint
+    ///
+    /// This method calls `resume_distribution_with_progress` with no progress callback.
+    ///
+    /// # Errors
+    /// Returns an error if no checkpoint exists for the distribution or a remaining step fails
+    pub async fn resume_distribution(
+        &self,
+        distribution_uuid: &str,
+        node_rpc: &ElementsRpc,
+        signer: &dyn Signer,
+    ) -> Result<String, AmpError> {
+        self.resume_distribution_with_progress(distribution_uuid, node_rpc, signer, None)
+            .await
+    }
+
+    /// Resumes an interrupted distribution from its last checkpoint with progress callbacks
+    ///
+    /// Loads the checkpoint recorded by `distribute_asset_with_progress` from the client's
+    /// checkpoint store and runs the remaining steps:
+    /// - `created`: send the transaction (through `sendmany`, or build, sign and broadcast
+    ///   it with [`DistributionFunding::Signer`]), then confirm
+    /// - `sending`: look for the transaction in the node wallet's recent `sendmany`
+    ///   transactions and only send it if none matches the checkpoint's outputs, then
+    ///   confirm
+    /// - `tx_built`: sign and broadcast the stored transaction, then confirm
+    /// - `signed`: broadcast the stored signed transaction (a transaction the node already
+    ///   knows is not sent again), then confirm
+    /// - `broadcast`: wait for confirmations and confirm with the AMP API
+    /// - `confirmed`: nothing left to do, the transaction ID is returned
+    ///
+    /// Checkpoints only survive a restart if the client was built with a persistent
+    /// [`ApiClientBuilder::checkpoint_store`]. The default in-memory store loses them
+    /// when the process exits.
+    ///
+    /// # Arguments
+    /// * `distribution_uuid` - The UUID of the distribution to resume
+    /// * `node_rpc` - ElementsRpc client for blockchain operations
+    /// * `signer` - Signer implementation for transaction signing
+    /// * `progress_tx` - Optional channel for sending progress updates
+    ///
+    /// # Returns
+    /// Returns the transaction ID of the distribution
+    ///
+    /// # Errors
+    /// Returns an error if no checkpoint exists for the distribution or a remaining step fails
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use amp_rs::{ApiClient, ElementsRpc, signer::LwkSoftwareSigner};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ApiClient::new().await?;
+    /// let node_rpc = ElementsRpc::from_env()?;
+    /// let (_, signer) = LwkSoftwareSigner::generate_new()?;
+    ///
+    /// let txid = client
+    ///     .resume_distribution_with_progress("distribution-uuid", &node_rpc, &signer, None)
+    ///     .await?;
+    /// println!("Distribution confirmed with transaction {}", txid);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn resume_distribution_with_progress(
+        &self,
+        distribution_uuid: &str,
+        node_rpc: &ElementsRpc,
+        signer: &dyn Signer,
+        progress_tx: Option<tokio::sync::mpsc::UnboundedSender<crate::model::ProgressUpdate>>,
+    ) -> Result<String, AmpError> {
+        let checkpoint = self
+            .checkpoint_store
+            .load(distribution_uuid)
+            .await?
+            .ok_or_else(|| {
+                AmpError::validation(format!(
+                    "No checkpoint found for distribution {distribution_uuid}. \
+                    Checkpoints only survive a restart when the client is built with a \
+                    persistent checkpoint store such as FileCheckpointStore; the default \
+                    in-memory store keeps them for the lifetime of the process only"
+                ))
+            })?;
 
         tracing::info!(
-            "Using sendmany for {} outputs with asset {}",
-            distribution_response.map_address_amount.len(),
-            distribution_response.asset_id
+            "Resuming distribution {} for asset {} from state {}",
+            checkpoint.distribution_uuid,
+            checkpoint.asset_uuid,
+            checkpoint.state
         );
 
-        // Use Elements' sendmany which properly handles confidential transactions
-        let txid = node_rpc
-            .sendmany(
-                wallet_name,
-                distribution_response.map_address_amount.clone(),
-                asset_amounts,
-                Some(0), // min_conf: 0 to include unconfirmed UTXOs (matches Python implementation)
-                Some("AMP asset distribution"), // comment
-                None,    // subtract_fee_from: let Elements handle fees automatically
-                Some(false), // replaceable: false for final transactions
-                Some(1), // conf_target: 1 block for faster confirmation
-                Some("UNSET"), // estimate_mode: let Elements choose
-            )
+        self.run_distribution(checkpoint, node_rpc, signer, progress_tx.as_ref())
             .await
-            .map_err(|e| {
-                tracing::error!("Sendmany transaction failed: {}", e);
-                if e.is_retryable() {
-                    if let Some(instructions) = e.retry_instructions() {
-                        tracing::warn!("Retry instructions: {}", instructions);
+    }
+
+    /// Returns the store distribution checkpoints are recorded in
+    #[must_use]
+    pub fn checkpoint_store(&self) -> &Arc<dyn CheckpointStore> {
+        &self.checkpoint_store
+    }
+
+    /// Returns the chain backend configured with
+    /// [`ApiClientBuilder::chain_backend`], if any
+    #[must_use]
+    pub fn chain_backend(&self) -> Option<&Arc<dyn ChainBackend>> {
+        self.chain_backend.as_ref()
+    }
+
+    /// Returns the chain backend and funding addresses of workflows that build their
+    /// transactions without the node wallet, if both are configured
+    fn walletless_funding(&self) -> Option<(&dyn ChainBackend, &FundingAddresses)> {
+        self.chain_backend
+            .as_deref()
+            .zip(self.funding_addresses.as_ref())
+    }
+
+    /// Builds `builder`'s PSET, adding L-BTC UTXOs from `fee_candidates`, largest first,
+    /// until they cover the fee
+    ///
+    /// Returns the PSET and the L-BTC UTXOs it spends.
+    fn build_funded_pset(
+        builder: &PsetBuilder,
+        policy_asset: &str,
+        mut fee_candidates: Vec<Unspent>,
+    ) -> Result<(elements::pset::PartiallySignedTransaction, Vec<Unspent>), AmpError> {
+        fee_candidates.retain(|utxo| utxo.asset == policy_asset);
+        fee_candidates.sort_by_key(|utxo| std::cmp::Reverse(utxo.amount));
+        let mut fee_candidates = fee_candidates.into_iter();
+        let mut fee_inputs = Vec::new();
+        loop {
+            match builder.clone().add_inputs(fee_inputs.clone()).build() {
+                Ok(pset) => return Ok((pset, fee_inputs)),
+                Err(PsetBuilderError::InsufficientFunds { ref asset, .. })
+                    if asset == policy_asset =>
+                {
+                    if let Some(utxo) = fee_candidates.next() {
+                        fee_inputs.push(utxo);
+                        continue;
                     }
+                    return Err(AmpError::validation(format!(
+                        "Insufficient L-BTC for fees in the funding addresses: {}",
+                        builder
+                            .clone()
+                            .add_inputs(fee_inputs)
+                            .build()
+                            .err()
+                            .map_or_else(String::new, |e| e.to_string())
+                    )));
+                }
+                Err(e) => {
+                    return Err(AmpError::validation(format!(
+                        "Failed to build transaction: {e}"
+                    )))
                 }
-                e.with_context("Step 8: Sendmany transaction")
+            }
+        }
+    }
+
+    /// Signs a PSET built without the node wallet and returns the finalized transaction hex
+    async fn sign_built_pset(pset_base64: &str, signer: &dyn Signer) -> Result<String, AmpError> {
+        let pset = crate::signer::pset::from_base64(pset_base64).map_err(AmpError::Signer)?;
+        let signed = signer
+            .sign_pset(pset.clone())
+            .await
+            .map_err(|e| AmpError::Signer(e).with_context("Failed during PSET signing phase"))?;
+        crate::signer::pset::ensure_same_transaction(&pset, &signed).map_err(AmpError::Signer)?;
+        let transaction = crate::signer::pset::finalize(signed).map_err(|e| {
+            AmpError::Signer(e).with_context("Failed during PSET finalization phase")
+        })?;
+        Ok(elements::encode::serialize_hex(&transaction))
+    }
+
+    /// Lists the unspent outputs of the funding addresses, or of the node wallet with
+    /// their blinding data when the node wallet funds the workflows
+    async fn list_workflow_unspent(
+        &self,
+        node_rpc: &ElementsRpc,
+        wallet_name: &str,
+    ) -> Result<Vec<Unspent>, AmpError> {
+        match self.walletless_funding() {
+            Some((backend, funding)) => backend.list_unspent(&funding.addresses).await,
+            None => node_rpc.list_unspent_with_blinding_data(wallet_name).await,
+        }
+    }
+
+    /// Reissues an asset from the reissuance token UTXOs AMP selected among `utxos`, the
+    /// UTXOs of the funding addresses, and broadcasts the transaction through the chain
+    /// backend
+    ///
+    /// Returns the transaction id and the token input in the shape of `reissueasset`'s
+    /// result, which is what the AMP API expects.
+    async fn send_walletless_reissuance(
+        &self,
+        node_rpc: &ElementsRpc,
+        funding: &FundingAddresses,
+        utxos: Vec<Unspent>,
+        reissue_response: &ReissueRequestResponse,
+        signer: &dyn Signer,
+    ) -> Result<serde_json::Value, AmpError> {
+        let network = node_rpc.network().await?;
+        let fee_rate = node_rpc.estimate_fee_rate(FeeStrategy::default()).await?;
+        let entropy = node_rpc
+            .list_issuances(Some(&reissue_response.asset_id))
+            .await?
+            .iter()
+            .find_map(|issuance| issuance.get("entropy")?.as_str().map(str::to_string))
+            .ok_or_else(|| {
+                AmpError::rpc(format!(
+                    "No issuance entropy found for asset {}",
+                    reissue_response.asset_id
+                ))
             })?;
 
-        tracing::info!("✓ Transaction sent successfully with ID: {}", txid);
+        let (token_inputs, fee_utxos): (Vec<Unspent>, Vec<Unspent>) =
+            utxos.into_iter().partition(|utxo| {
+                reissue_response.reissuance_utxos.iter().any(|outpoint| {
+                    outpoint.txid == utxo.txid && outpoint.vout == i64::from(utxo.vout)
+                })
+            });
+        let builder = PsetBuilder::new(network.clone())
+            .add_inputs(token_inputs)
+            .add_reissuance(entropy, reissue_response.amount, &funding.change_address)
+            .change_address(&funding.change_address)
+            .fee_rate(fee_rate);
+        let (pset, _) = Self::build_funded_pset(&builder, network.policy_asset(), fee_utxos)?;
+        let vin = pset
+            .inputs()
+            .iter()
+            .position(elements::pset::Input::has_issuance)
+            .unwrap_or_default();
 
-        // Notify of transaction sent
-        if let Some(ref tx) = progress_tx {
-            let _ = tx.send(crate::model::ProgressUpdate::TxSent { txid: txid.clone() });
+        let signed_tx =
+            Self::sign_built_pset(&crate::signer::pset::to_base64(&pset), signer).await?;
+        let txid = self
+            .broadcast_signed_transaction(node_rpc, &signed_tx)
+            .await?;
+        tracing::info!(
+            "Broadcast reissuance transaction {} from the funding addresses",
+            txid
+        );
+        Ok(serde_json::json!({ "txid": txid, "vin": vin }))
+    }
+
+    /// Burns `amount` of `asset_id` from `utxos`, the UTXOs of the funding addresses, and
+    /// broadcasts the transaction through the chain backend
+    async fn send_walletless_burn(
+        &self,
+        node_rpc: &ElementsRpc,
+        funding: &FundingAddresses,
+        utxos: Vec<Unspent>,
+        asset_id: &str,
+        amount: Amount,
+        signer: &dyn Signer,
+    ) -> Result<String, AmpError> {
+        let network = node_rpc.network().await?;
+        let fee_rate = node_rpc.estimate_fee_rate(FeeStrategy::default()).await?;
+        let (asset_utxos, fee_utxos): (Vec<Unspent>, Vec<Unspent>) =
+            utxos.into_iter().partition(|utxo| utxo.asset == asset_id);
+        let asset_inputs = self
+            .coin_selector
+            .select(&asset_utxos, amount)
+            .map_err(|e| {
+                AmpError::validation(format!(
+                    "Insufficient asset UTXOs in the funding addresses: {e}"
+                ))
+            })?
+            .selected;
+
+        let builder = PsetBuilder::new(network.clone())
+            .add_inputs(asset_inputs)
+            .add_burn(asset_id, amount)
+            .change_address(&funding.change_address)
+            .fee_rate(fee_rate);
+        let (pset, _) = Self::build_funded_pset(&builder, network.policy_asset(), fee_utxos)?;
+
+        let signed_tx =
+            Self::sign_built_pset(&crate::signer::pset::to_base64(&pset), signer).await?;
+        let txid = self
+            .broadcast_signed_transaction(node_rpc, &signed_tx)
+            .await?;
+        tracing::info!(
+            "Broadcast burn transaction {} from the funding addresses",
+            txid
+        );
+        Ok(txid)
+    }
+
+    /// Builds a distribution transaction from the UTXOs of the funding addresses
+    async fn build_walletless_distribution(
+        &self,
+        node_rpc: &ElementsRpc,
+        backend: &dyn ChainBackend,
+        funding: &FundingAddresses,
+        checkpoint: &DistributionCheckpoint,
+    ) -> Result<DistributionTransaction, AmpError> {
+        let total_distribution = ElementsRpc::distribution_total(&checkpoint.map_address_amount)?;
+        let network = node_rpc.network().await?;
+        let fee_rate = node_rpc.estimate_fee_rate(FeeStrategy::default()).await?;
+        let (asset_utxos, fee_utxos): (Vec<Unspent>, Vec<Unspent>) = backend
+            .list_unspent(&funding.addresses)
+            .await?
+            .into_iter()
+            .partition(|utxo| utxo.asset == checkpoint.asset_id);
+
+        let asset_inputs = self
+            .coin_selector
+            .select(&asset_utxos, total_distribution)
+            .map_err(|e| {
+                AmpError::validation(format!(
+                    "Insufficient asset UTXOs in the funding addresses: {e}"
+                ))
+            })?
+            .selected;
+        let mut builder = PsetBuilder::new(network.clone())
+            .add_inputs(asset_inputs.clone())
+            .change_address(&funding.change_address)
+            .fee_rate(fee_rate);
+        for (address, amount) in &checkpoint.map_address_amount {
+            builder = builder.add_recipient(address, &checkpoint.asset_id, *amount);
+        }
+        let (pset, fee_inputs) =
+            Self::build_funded_pset(&builder, network.policy_asset(), fee_utxos)?;
+
+        let asset_change =
+            asset_inputs.iter().map(|utxo| utxo.amount).sum::<Amount>() - total_distribution;
+        let fee = pset
+            .outputs()
+            .iter()
+            .filter(|output| output.script_pubkey.is_empty())
+            .filter_map(|output| output.amount)
+            .map(|sats| Amount::from_sat(i64::try_from(sats).unwrap_or(i64::MAX)))
+            .sum::<Amount>();
+        let fee_change = fee_inputs.iter().map(|utxo| utxo.amount).sum::<Amount>() - fee;
+        let transaction = pset
+            .extract_tx()
+            .map_err(|e| AmpError::validation(format!("Failed to extract transaction: {e}")))?;
+        tracing::info!(
+            "Built distribution transaction from the funding addresses: {} inputs, fee {}",
+            transaction.input.len(),
+            fee
+        );
+
+        Ok(DistributionTransaction {
+            raw_transaction: elements::encode::serialize_hex(&transaction),
+            vsize: fee::estimate_vsize(transaction.input.len(), transaction.output.len() - 1),
+            asset_inputs,
+            fee_inputs,
+            asset_change,
+            fee_change,
+            fee,
+            fee_rate,
+            pset: Some(crate::signer::pset::to_base64(&pset)),
+        })
+    }
+
+    /// Starts following a confirmed workflow transaction if a reorg monitor is configured
+    fn spawn_reorg_monitor(
+        &self,
+        node_rpc: &ElementsRpc,
+        wallet_name: &str,
+        txid: &str,
+        confirmed_blockhash: Option<String>,
+        operation: ConfirmedOperation,
+    ) {
+        let Some(monitor) = self.reorg_monitor.clone() else {
+            return;
+        };
+        let node_rpc = node_rpc.clone();
+        let broadcaster = self
+            .chain_backend
+            .clone()
+            .unwrap_or_else(|| Arc::new(node_rpc.clone()));
+        let wallet_name = wallet_name.to_string();
+        let txid = txid.to_string();
+        tokio::spawn(async move {
+            if let Err(e) = monitor
+                .monitor(
+                    &node_rpc,
+                    broadcaster.as_ref(),
+                    &wallet_name,
+                    &txid,
+                    confirmed_blockhash,
+                    operation,
+                )
+                .await
+            {
+                tracing::warn!("Reorg monitoring of transaction {} stopped: {}", txid, e);
+            }
+        });
+    }
+
+    /// Broadcasts a signed transaction through the chain backend, or the node without one
+    async fn broadcast_signed_transaction(
+        &self,
+        node_rpc: &ElementsRpc,
+        signed_tx: &str,
+    ) -> Result<String, AmpError> {
+        match &self.chain_backend {
+            Some(backend) => backend.broadcast(signed_tx).await,
+            None => node_rpc.send_raw_transaction(signed_tx).await,
+        }
+    }
+
+    /// Returns `true` if the chain backend, or the node wallet without one, knows `txid`
+    async fn is_transaction_known(
+        &self,
+        node_rpc: &ElementsRpc,
+        wallet_name: &str,
+        txid: &str,
+    ) -> bool {
+        match &self.chain_backend {
+            Some(backend) => backend.get_transaction(txid).await.is_ok(),
+            None => node_rpc
+                .get_transaction_from_wallet(wallet_name, txid)
+                .await
+                .is_ok(),
+        }
+    }
+
+    /// Waits up to ten minutes for `txid` to reach `min_confirmations`
+    ///
+    /// Confirmations are tracked by the chain backend when one is configured. The
+    /// returned details always come from the node wallet, since the AMP API expects the
+    /// wallet's view of the transaction.
+    async fn wait_for_workflow_confirmations(
+        &self,
+        node_rpc: &ElementsRpc,
+        wallet_name: &str,
+        txid: &str,
+        min_confirmations: u32,
+        progress_tx: Option<&tokio::sync::mpsc::UnboundedSender<crate::model::ProgressUpdate>>,
+    ) -> Result<TransactionDetail, AmpError> {
+        match &self.chain_backend {
+            Some(backend) => {
+                backend
+                    .wait_for_confirmations(
+                        txid,
+                        min_confirmations,
+                        WORKFLOW_CONFIRMATION_TIMEOUT,
+                        chain::DEFAULT_POLL_INTERVAL,
+                        progress_tx,
+                    )
+                    .await?;
+                node_rpc
+                    .get_transaction_from_wallet(wallet_name, txid)
+                    .await
+            }
+            None => {
+                node_rpc
+                    .wait_for_confirmations_with_progress(
+                        wallet_name,
+                        txid,
+                        Some(min_confirmations),
+                        Some(10),
+                        None,
+                        progress_tx,
+                    )
+                    .await
+            }
+        }
+    }
+
+    /// Finds the transaction the node wallet sent for a distribution in `Sending`
+    ///
+    /// Matches the wallet's recent `sendmany` transactions carrying the distribution
+    /// comment against the checkpoint's address/amount outputs. Returns `Ok(None)` if
+    /// the wallet never sent the distribution.
+    async fn find_sent_distribution(
+        &self,
+        node_rpc: &ElementsRpc,
+        checkpoint: &DistributionCheckpoint,
+        started_at: chrono::DateTime<Utc>,
+    ) -> Result<Option<String>, AmpError> {
+        let earliest = (started_at - SENT_DISTRIBUTION_CLOCK_SKEW).timestamp();
+        let expected: HashMap<String, Amount> = checkpoint
+            .map_address_amount
+            .iter()
+            .map(|(address, amount)| (unconfidential_address(address), *amount))
+            .collect();
+
+        let mut sent: HashMap<String, HashMap<String, Amount>> = HashMap::new();
+        for entry in node_rpc
+            .list_transactions(&checkpoint.wallet_name, SENT_DISTRIBUTION_LOOKBACK)
+            .await?
+        {
+            if entry.category != "send"
+                || entry.comment.as_deref() != Some(DISTRIBUTION_TX_COMMENT)
+                || entry.time < earliest
+                || entry
+                    .asset
+                    .as_deref()
+                    .is_some_and(|asset| asset != checkpoint.asset_id)
+            {
+                continue;
+            }
+            let Some(address) = entry.address else {
+                continue;
+            };
+            let amount = Amount::from_btc(entry.amount.abs())
+                .map_err(|e| AmpError::rpc(format!("Invalid amount in listtransactions: {e}")))?;
+            *sent
+                .entry(entry.txid)
+                .or_default()
+                .entry(unconfidential_address(&address))
+                .or_insert(Amount::ZERO) += amount;
+        }
+
+        Ok(sent
+            .into_iter()
+            .find(|(_, outputs)| *outputs == expected)
+            .map(|(txid, _)| txid))
+    }
+
+    /// Drives a distribution from its checkpoint to confirmation
+    ///
+    /// Every completed step advances the checkpoint and saves it before the next step
+    /// starts, so an interruption at any point can be resumed.
+    #[allow(clippy::cognitive_complexity, clippy::too_many_lines)]
+    async fn run_distribution(
+        &self,
+        mut checkpoint: DistributionCheckpoint,
+        node_rpc: &ElementsRpc,
+        signer: &dyn Signer,
+        progress_tx: Option<&tokio::sync::mpsc::UnboundedSender<crate::model::ProgressUpdate>>,
+    ) -> Result<String, AmpError> {
+        let send_progress = |step: u32, message: &str| {
+            if let Some(tx) = progress_tx {
+                let _ = tx.send(crate::model::ProgressUpdate::Step {
+                    current: step,
+                    total: DISTRIBUTION_TOTAL_STEPS,
+                    message: message.to_string(),
+                });
+            }
+        };
+        let distribution_uuid = checkpoint.distribution_uuid.clone();
+        let wallet_name = checkpoint.wallet_name.clone();
+        // A run that starts in `Sending` may have been interrupted after `sendmany`
+        let resumed_while_sending = matches!(checkpoint.state, DistributionState::Sending { .. });
+
+        loop {
+            let next = match &checkpoint.state {
+                DistributionState::Created
+                    if self.distribution_funding == DistributionFunding::NodeWallet
+                        && self.walletless_funding().is_none() =>
+                {
+                    // Record the send before asking the node for it, so that a resume
+                    // looks for the transaction instead of sending it blindly
+                    DistributionState::Sending {
+                        started_at: Utc::now(),
+                    }
+                }
+                DistributionState::Sending { started_at } => {
+                    // Step 8: Send the distribution transaction using Elements' sendmany
+                    send_progress(8, "Sending distribution transaction");
+                    let sent_txid = if resumed_while_sending {
+                        tracing::debug!(
+                            "Step 8: Looking for a distribution transaction sent since {}",
+                            started_at
+                        );
+                        self.find_sent_distribution(node_rpc, &checkpoint, *started_at)
+                            .await
+                            .map_err(|e| e.with_context("Step 8: Sent transaction lookup"))?
+                    } else {
+                        None
+                    };
+                    let txid = if let Some(txid) = sent_txid {
+                        tracing::info!("Transaction {} was already sent, continuing", txid);
+                        txid
+                    } else {
+                        tracing::debug!(
+                            "Step 8: Sending distribution transaction using Elements sendmany"
+                        );
+                        let asset_amounts = checkpoint
+                            .map_address_amount
+                            .keys()
+                            .map(|address| (address.clone(), checkpoint.asset_id.clone()))
+                            .collect();
+                        node_rpc
+                            .sendmany(
+                                &wallet_name,
+                                checkpoint.map_address_amount.clone(),
+                                asset_amounts,
+                                Some(0), // min_conf: 0 to include unconfirmed UTXOs
+                                Some(DISTRIBUTION_TX_COMMENT),
+                                None, // subtract_fee_from: let Elements handle fees automatically
+                                Some(false),
+                                Some(1),
+                                Some("UNSET"),
+                            )
+                            .await
+                            .map_err(|e| {
+                                tracing::error!("Sendmany transaction failed: {}", e);
+                                e.with_context("Step 8: Sendmany transaction")
+                            })?
+                    };
+
+                    tracing::info!("✓ Transaction sent successfully with ID: {}", txid);
+                    if let Some(tx) = progress_tx {
+                        let _ =
+                            tx.send(crate::model::ProgressUpdate::TxSent { txid: txid.clone() });
+                    }
+
+                    // The node already broadcast the transaction; record it so that a resume
+                    // never sends the distribution a second time
+                    match node_rpc
+                        .get_transaction_from_wallet(&wallet_name, &txid)
+                        .await
+                    {
+                        Ok(detail) => {
+                            checkpoint.advance(DistributionState::Signed {
+                                signed_tx: detail.hex,
+                                txid: txid.clone(),
+                            })?;
+                            self.checkpoint_store.save(&checkpoint).await?;
+                        }
+                        Err(e) => tracing::warn!(
+                            "Could not fetch sent transaction {} for the checkpoint: {}",
+                            txid,
+                            e
+                        ),
+                    }
+                    DistributionState::Broadcast { txid }
+                }
+                DistributionState::Created => {
+                    // Step 8: Build the distribution transaction
+                    send_progress(8, "Building distribution transaction");
+                    tracing::debug!("Step 8: Building distribution transaction");
+                    let transaction = if let Some((backend, funding)) = self.walletless_funding() {
+                        self.build_walletless_distribution(node_rpc, backend, funding, &checkpoint)
+                            .await
+                    } else {
+                        let change_address = node_rpc
+                            .get_new_address(&wallet_name, None)
+                            .await
+                            .map_err(|e| e.with_context("Step 8: Change address generation"))?;
+                        node_rpc
+                            .build_distribution_transaction_with_selector(
+                                &wallet_name,
+                                &checkpoint.asset_id,
+                                checkpoint.map_address_amount.clone(),
+                                &change_address,
+                                FeeStrategy::default(),
+                                self.coin_selector.as_ref(),
+                            )
+                            .await
+                    }
+                    .map_err(|e| e.with_context("Step 8: Transaction construction"))?;
+
+                    tracing::info!(
+                        "✓ Built distribution transaction spending {} inputs with fee {}",
+                        transaction.inputs().len(),
+                        transaction.fee
+                    );
+                    DistributionState::TxBuilt { transaction }
+                }
+                DistributionState::TxBuilt { transaction } => {
+                    // Step 9: Sign the distribution transaction
+                    send_progress(9, "Signing distribution transaction");
+                    tracing::debug!("Step 9: Signing distribution transaction");
+                    let signed_tx = match &transaction.pset {
+                        Some(pset) => Self::sign_built_pset(pset, signer).await,
+                        None => {
+                            node_rpc
+                                .sign_transaction_with_utxos(
+                                    &transaction.raw_transaction,
+                                    &transaction.inputs(),
+                                    signer,
+                                )
+                                .await
+                        }
+                    }
+                    .map_err(|e| e.with_context("Step 9: Transaction signing"))?;
+                    let txid = transaction_id(&signed_tx)
+                        .map_err(|e| e.with_context("Step 9: Transaction signing"))?;
+
+                    tracing::info!("✓ Signed distribution transaction {}", txid);
+                    DistributionState::Signed { signed_tx, txid }
+                }
+                DistributionState::Signed { signed_tx, txid } => {
+                    // Step 10: Broadcast the distribution transaction
+                    send_progress(10, "Broadcasting distribution transaction");
+                    tracing::debug!("Step 10: Broadcasting distribution transaction {}", txid);
+                    if let Err(e) = self.broadcast_signed_transaction(node_rpc, signed_tx).await {
+                        // A previous run may have broadcast the transaction before recording it
+                        if !self
+                            .is_transaction_known(node_rpc, &wallet_name, txid)
+                            .await
+                        {
+                            tracing::error!("Transaction broadcast failed: {}", e);
+                            return Err(e.with_context("Step 10: Transaction broadcast"));
+                        }
+                        tracing::info!(
+                            "Transaction {} was already broadcast, continuing: {}",
+                            txid,
+                            e
+                        );
+                    }
+
+                    tracing::info!("✓ Transaction sent successfully with ID: {}", txid);
+                    if let Some(tx) = progress_tx {
+                        let _ =
+                            tx.send(crate::model::ProgressUpdate::TxSent { txid: txid.clone() });
+                    }
+                    DistributionState::Broadcast { txid: txid.clone() }
+                }
+                DistributionState::Broadcast { txid } => {
+                    self.confirm_broadcast_distribution(
+                        &checkpoint,
+                        txid,
+                        node_rpc,
+                        &send_progress,
+                        progress_tx,
+                    )
+                    .await?;
+                    DistributionState::Confirmed { txid: txid.clone() }
+                }
+                DistributionState::Confirmed { txid } => {
+                    tracing::info!(
+                        "🎉 Asset distribution {} completed successfully for asset: {} with transaction: {}",
+                        distribution_uuid,
+                        checkpoint.asset_uuid,
+                        txid
+                    );
+                    return Ok(txid.clone());
+                }
+            };
+
+            checkpoint.advance(next)?;
+            self.checkpoint_store.save(&checkpoint).await?;
         }
+    }
+
+    /// Waits for a broadcast distribution transaction and confirms it with the AMP API
+    #[allow(clippy::cognitive_complexity, clippy::too_many_lines)]
+    async fn confirm_broadcast_distribution(
+        &self,
+        checkpoint: &DistributionCheckpoint,
+        txid: &str,
+        node_rpc: &ElementsRpc,
+        send_progress: &(dyn Fn(u32, &str) + Sync),
+        progress_tx: Option<&tokio::sync::mpsc::UnboundedSender<crate::model::ProgressUpdate>>,
+    ) -> Result<(), AmpError> {
+        let wallet_name = checkpoint.wallet_name.as_str();
+        let distribution_uuid = checkpoint.distribution_uuid.as_str();
 
-        // Step 9: Wait for confirmations (with progress updates)
-        send_progress(9, total_steps, "Waiting for blockchain confirmations");
-        tracing::debug!("Step 9: Waiting for blockchain confirmations (minimum 2 confirmations, 10-minute timeout)");
+        // Step 11: Wait for confirmations (with progress updates)
+        send_progress(11, "Waiting for blockchain confirmations");
+        tracing::debug!("Step 11: Waiting for blockchain confirmations (minimum 2 confirmations, 10-minute timeout)");
         let confirmation_start = std::time::Instant::now();
-        let tx_detail = node_rpc.wait_for_confirmations_with_progress(
-                wallet_name,
-                &txid,
-                Some(2),
-                Some(10),
-                None,
-                progress_tx.as_ref()
-            ).await
+        let tx_detail = self
+            .wait_for_workflow_confirmations(node_rpc, wallet_name, txid, 2, progress_tx)
+            .await
             .map_err(|e| {
                 let elapsed = confirmation_start.elapsed();
                 tracing::error!(
@@ -14800,20 +17618,21 @@ impl ApiClient {
                 if let AmpError::Timeout(_) = &e {
                     tracing::warn!(
                         "Confirmation timeout - transaction {} may still be pending. \
-                        Use this txid to manually confirm the distribution if it gets confirmed later.",
-                        txid
+                        Resume distribution {} once it is confirmed.",
+                        txid,
+                        distribution_uuid
                     );
                     let timeout_error = AmpError::timeout(format!(
-                        "Confirmation timeout for txid: {txid}. Use this txid to manually confirm the distribution."
+                        "Confirmation timeout for txid: {txid}. Use resume_distribution for distribution {distribution_uuid} once it is confirmed."
                     ));
-                    timeout_error.with_context("Step 9: Confirmation waiting")
+                    timeout_error.with_context("Step 11: Confirmation waiting")
                 } else {
                     if e.is_retryable() {
                         if let Some(instructions) = e.retry_instructions() {
                             tracing::warn!("Retry instructions: {}", instructions);
                         }
                     }
-                    e.with_context(format!("Step 9: Confirmation waiting for txid: {txid}"))
+                    e.with_context(format!("Step 11: Confirmation waiting for txid: {txid}"))
                 }
             })?;
 
@@ -14825,123 +17644,700 @@ impl ApiClient {
             confirmation_duration
         );
 
-        // Step 10: Collect change data for confirmation
-        send_progress(10, total_steps, "Collecting change data");
-        tracing::debug!("Step 10: Collecting change data for distribution confirmation");
-        let change_data = node_rpc
-            .collect_change_data(
-                &distribution_response.asset_id,
-                &txid,
-                node_rpc,
-                wallet_name,
-            )
-            .await
-            .map_err(|e| {
-                tracing::error!("Change data collection failed: {}", e);
-                if e.is_retryable() {
-                    if let Some(instructions) = e.retry_instructions() {
-                        tracing::warn!("Retry instructions: {}", instructions);
-                    }
+        // Step 12: Collect change data for confirmation
+        send_progress(12, "Collecting change data");
+        tracing::debug!("Step 12: Collecting change data for distribution confirmation");
+        let change_data = match self.walletless_funding() {
+            Some(_) => self
+                .list_workflow_unspent(node_rpc, wallet_name)
+                .await
+                .map(|utxos| {
+                    utxos
+                        .into_iter()
+                        .filter(|utxo| utxo.txid == txid && utxo.asset == checkpoint.asset_id)
+                        .collect()
+                }),
+            None => {
+                node_rpc
+                    .collect_change_data(&checkpoint.asset_id, txid, node_rpc, wallet_name)
+                    .await
+            }
+        }
+        .map_err(|e| {
+            tracing::error!("Change data collection failed: {}", e);
+            if e.is_retryable() {
+                if let Some(instructions) = e.retry_instructions() {
+                    tracing::warn!("Retry instructions: {}", instructions);
                 }
-                e.with_context("Step 10: Change data collection")
-            })?;
+            }
+            e.with_context("Step 12: Change data collection")
+        })?;
 
         tracing::info!("✓ Collected {} change UTXOs", change_data.len());
         if !change_data.is_empty() {
             tracing::debug!("Change UTXOs: {:?}", change_data);
         }
 
-        // Step 11: Submit final confirmation to AMP API
-        send_progress(11, total_steps, "Confirming distribution with AMP API");
-        tracing::debug!("Step 11: Submitting final confirmation to AMP API");
+        // Step 13: Submit final confirmation to AMP API
+        send_progress(13, "Confirming distribution with AMP API");
+        tracing::debug!("Step 13: Submitting final confirmation to AMP API");
+
+        let confirmed_blockhash = tx_detail.blockhash.clone();
+
+        // Extract the details field from the transaction (matching Python implementation)
+        // Python: details = rpc.call('gettransaction', txid).get('details')
+        let transaction_details = tx_detail.details.unwrap_or_else(Vec::new);
+        tracing::debug!(
+            "Transaction details for confirmation: {:?}",
+            transaction_details
+        );
+
+        let amp_tx_data = crate::model::AmpTxData {
+            details: serde_json::Value::Array(transaction_details),
+            txid: txid.to_string(),
+        };
+
+        // Log the exact payload being sent to AMP for debugging
+        tracing::info!("Sending confirmation payload to AMP:");
+        tracing::info!("  tx_data.txid: {}", amp_tx_data.txid);
+        tracing::info!("  tx_data.details: {:?}", amp_tx_data.details);
+        tracing::info!("  change_data: {} UTXOs", change_data.len());
+
+        let confirmation_request = crate::model::ConfirmDistributionRequest {
+            tx_data: amp_tx_data.clone(),
+            change_data: change_data.clone(),
+        };
+
+        if let Ok(payload_json) = serde_json::to_string_pretty(&confirmation_request) {
+            tracing::debug!("Full confirmation payload: {}", payload_json);
+        }
+
+        self.confirm_distribution(
+            &checkpoint.asset_uuid,
+            distribution_uuid,
+            amp_tx_data,
+            change_data,
+        )
+        .await
+        .map_err(|e| {
+            tracing::error!("Distribution confirmation failed: {}", e);
+
+            // For confirmation failures, always provide retry instructions with txid
+            let confirmation_error = AmpError::api(format!(
+                "Failed to confirm distribution {distribution_uuid}: {e}. \
+                IMPORTANT: Transaction {txid} was successful on blockchain. \
+                Use resume_distribution to retry confirmation."
+            ));
+
+            if e.is_retryable() {
+                if let Some(instructions) = e.retry_instructions() {
+                    tracing::warn!("Retry instructions: {}", instructions);
+                }
+            }
+
+            confirmation_error.with_context("Step 13: Distribution confirmation")
+        })?;
+
+        self.spawn_reorg_monitor(
+            node_rpc,
+            wallet_name,
+            txid,
+            confirmed_blockhash,
+            ConfirmedOperation::Distribution {
+                asset_uuid: checkpoint.asset_uuid.clone(),
+                distribution_uuid: distribution_uuid.to_string(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Distributes an asset through a comprehensive workflow
+    ///
+    /// This method calls `distribute_asset_with_progress` with no progress callback.
+    /// For progress updates during distribution, use `distribute_asset_with_progress` directly.
+    pub async fn distribute_asset(
+        &self,
+        asset_uuid: &str,
+        assignments: Vec<AssetDistributionAssignment>,
+        node_rpc: &ElementsRpc,
+        wallet_name: &str,
+        signer: &dyn Signer,
+    ) -> Result<(), AmpError> {
+        // Delegate to the progress variant with no progress callback
+        self.distribute_asset_with_progress(
+            asset_uuid,
+            assignments,
+            node_rpc,
+            wallet_name,
+            signer,
+            None,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Distributes an asset in batches planned by a [`BatchPlanner`](crate::batch::BatchPlanner)
+    ///
+    /// Every batch is distributed as by `distribute_asset_with_progress`, as its own AMP
+    /// distribution and transaction, one after the other. Before the first batch, all
+    /// assignments are validated and the wallet's L-BTC is checked against the estimated
+    /// fees of the plan, so that a distribution that cannot be paid is not started.
+    ///
+    /// When a batch fails, the remaining batches are not attempted: the failed batch may
+    /// still hold an open AMP distribution or an unconfirmed transaction that has to be
+    /// resumed with `resume_distribution` first. The returned report records the outcome
+    /// of every batch, including the distribution UUID of a failed batch, and
+    /// [`BatchDistributionReport::skipped_assignments`] lists the assignments that were
+    /// not attempted.
+    ///
+    /// Progress updates of each batch are forwarded to `progress_tx`, preceded by a
+    /// [`ProgressUpdate::Batch`](crate::model::ProgressUpdate::Batch) update.
+    ///
+    /// # Errors
+    /// Returns an error if the asset UUID or an assignment is invalid, or the wallet lacks
+    /// the L-BTC for the fees of the plan. Failures of individual batches are reported in
+    /// the returned report instead.
+    pub async fn distribute_asset_in_batches(
+        &self,
+        asset_uuid: &str,
+        plan: BatchPlan,
+        node_rpc: &ElementsRpc,
+        wallet_name: &str,
+        signer: &dyn Signer,
+        progress_tx: Option<tokio::sync::mpsc::UnboundedSender<crate::model::ProgressUpdate>>,
+    ) -> Result<BatchDistributionReport, AmpError> {
+        Self::validate_asset_uuid(asset_uuid)
+            .map_err(|e| AmpError::validation(format!("Invalid asset UUID: {e}")))?;
+        let assignments: Vec<AssetDistributionAssignment> = plan
+            .batches()
+            .iter()
+            .flat_map(|batch| batch.assignments.iter().cloned())
+            .collect();
+        Self::validate_assignments(&assignments)
+            .map_err(|e| AmpError::validation(format!("Invalid assignments: {e}")))?;
+
+        let network = node_rpc.network().await?;
+        let utxos = node_rpc
+            .list_unspent_for_wallet(wallet_name, Some(network.policy_asset()))
+            .await?;
+        let funding = plan.check_fee_funding(&utxos, network.policy_asset());
+        if !funding.is_sufficient() {
+            return Err(AmpError::validation(format!(
+                "Insufficient L-BTC for the fees of {} batches: need {}, have {}",
+                plan.batches().len(),
+                funding.needed,
+                funding.available
+            )));
+        }
+        if !funding.is_fully_covered() {
+            tracing::warn!(
+                "Wallet has {} L-BTC UTXOs for {} batches; batches {:?} will wait for the change of earlier batches",
+                funding.available_utxos,
+                funding.needed_utxos,
+                funding.uncovered_batches
+            );
+        }
+
+        let total = u32::try_from(plan.batches().len()).unwrap_or(u32::MAX);
+        let mut results = Vec::with_capacity(plan.batches().len());
+        let mut failed = false;
+        for batch in plan.batches() {
+            if failed {
+                results.push(BatchResult {
+                    batch: batch.clone(),
+                    status: BatchStatus::Skipped,
+                });
+                continue;
+            }
+
+            tracing::info!(
+                "Distributing batch {}/{} with {} assignments totaling {}",
+                batch.index + 1,
+                total,
+                batch.assignments.len(),
+                batch.total
+            );
+            if let Some(tx) = &progress_tx {
+                let _ = tx.send(crate::model::ProgressUpdate::Batch {
+                    current: u32::try_from(batch.index + 1).unwrap_or(u32::MAX),
+                    total,
+                    assignments: batch.assignments.len(),
+                });
+            }
+
+            let mut distribution_uuid = None;
+            let result = match self
+                .register_distribution(
+                    asset_uuid,
+                    batch.assignments.clone(),
+                    node_rpc,
+                    wallet_name,
+                    signer,
+                    progress_tx.as_ref(),
+                )
+                .await
+            {
+                Ok(checkpoint) => {
+                    distribution_uuid = Some(checkpoint.distribution_uuid.clone());
+                    self.send_distribution(checkpoint, node_rpc, signer, progress_tx.as_ref())
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+            let status = match result {
+                Ok(txid) => BatchStatus::Completed { txid },
+                Err(e) => {
+                    tracing::error!("Batch {}/{} failed: {}", batch.index + 1, total, e);
+                    failed = true;
+                    BatchStatus::Failed {
+                        error: e.to_string(),
+                        distribution_uuid,
+                    }
+                }
+            };
+            results.push(BatchResult {
+                batch: batch.clone(),
+                status,
+            });
+        }
+
+        Ok(BatchDistributionReport {
+            asset_uuid: asset_uuid.to_string(),
+            results,
+        })
+    }
+
+    /// Previews `distribute_asset` without registering a distribution or spending funds
+    ///
+    /// Validates the asset UUID, the assignments and the node connection, fetches the
+    /// asset and its AMP assignments, checks the recipient addresses against the node's
+    /// network, selects asset UTXOs with the client's coin selector and estimates the fee
+    /// with the default [`FeeStrategy`]. Nothing is created on the AMP API and no
+    /// transaction is built, signed or broadcast.
+    ///
+    /// With [`DistributionFunding::Signer`] the inputs are selected as `distribute_asset`
+    /// would. With the default [`DistributionFunding::NodeWallet`], `sendmany` selects the
+    /// inputs and sets the fee itself, so the planned inputs, change and fee are estimates
+    /// and [`DryRunPlan::estimated`] is set.
+    ///
+    /// Assignments of the asset that already belong to an unconfirmed distribution are
+    /// reported in [`DryRunPlan::warnings`].
+    ///
+    /// # Errors
+    /// Returns an error if validation fails, the asset cannot be fetched, or the wallet
+    /// lacks the asset or L-BTC for the distribution
+    pub async fn distribute_asset_dry_run(
+        &self,
+        asset_uuid: &str,
+        assignments: &[AssetDistributionAssignment],
+        node_rpc: &ElementsRpc,
+        wallet_name: &str,
+    ) -> Result<DryRunPlan, AmpError> {
+        Self::validate_asset_uuid(asset_uuid)
+            .map_err(|e| AmpError::validation(format!("Invalid asset UUID: {e}")))?;
+        Self::validate_assignments(assignments)
+            .map_err(|e| AmpError::validation(format!("Invalid assignments: {e}")))?;
+        self.validate_elements_rpc_connection(node_rpc)
+            .await
+            .map_err(|e| AmpError::rpc(format!("ElementsRpc connection validation failed: {e}")))?;
 
-        // Extract the details field from the transaction (matching Python implementation)
-        // Python: details = rpc.call('gettransaction', txid).get('details')
-        let transaction_details = tx_detail.details.unwrap_or_else(Vec::new);
-        tracing::debug!(
-            "Transaction details for confirmation: {:?}",
-            transaction_details
-        );
+        let asset = self.get_asset(asset_uuid).await?;
+        let mut warnings = Vec::new();
+        for assignment in self.get_asset_assignments(asset_uuid).await? {
+            if let (false, Some(distribution_uuid)) =
+                (assignment.is_distributed, &assignment.distribution_uuid)
+            {
+                warnings.push(format!(
+                    "Assignment {} is part of distribution {distribution_uuid}, which is not confirmed",
+                    assignment.id
+                ));
+            }
+        }
 
-        let amp_tx_data = crate::model::AmpTxData {
-            details: serde_json::Value::Array(transaction_details),
-            txid: txid.clone(),
-        };
+        // Assignments to the same address are paid by one output
+        let network = node_rpc.network().await?;
+        let mut recipients: Vec<(String, Amount)> = Vec::new();
+        for assignment in assignments {
+            network
+                .validate_address(&assignment.address)
+                .map_err(|e| AmpError::validation(e.to_string()))?;
+            match recipients
+                .iter_mut()
+                .find(|(address, _)| *address == assignment.address)
+            {
+                Some((_, amount)) => *amount += assignment.amount,
+                None => recipients.push((assignment.address.clone(), assignment.amount)),
+            }
+        }
+        let total: Amount = recipients.iter().map(|(_, amount)| *amount).sum();
 
-        // Log the exact payload being sent to AMP for debugging
-        tracing::info!("Sending confirmation payload to AMP:");
-        tracing::info!("  tx_data.txid: {}", amp_tx_data.txid);
-        tracing::info!("  tx_data.details: {:?}", amp_tx_data.details);
-        tracing::info!("  change_data: {} UTXOs", change_data.len());
+        let (asset_inputs, total_selected) = node_rpc
+            .select_utxos_with_selector(
+                wallet_name,
+                &asset.asset_id,
+                total,
+                Amount::ZERO,
+                self.coin_selector.as_ref(),
+            )
+            .await?;
+        // The issued asset cannot go to the fee, so any excess is returned as change
+        let asset_change = total_selected - total;
+        let has_asset_change = asset_change.is_positive();
 
-        let confirmation_request = crate::model::ConfirmDistributionRequest {
-            tx_data: amp_tx_data.clone(),
-            change_data: change_data.clone(),
-        };
+        let fee_rate = node_rpc.estimate_fee_rate(FeeStrategy::default()).await?;
+        let payment = node_rpc
+            .fund_fee(
+                wallet_name,
+                network.policy_asset(),
+                asset_inputs.len(),
+                recipients.len() + usize::from(has_asset_change) + 1,
+                Vec::new(),
+                fee_rate,
+                Amount::ZERO,
+            )
+            .await?;
 
-        if let Ok(payload_json) = serde_json::to_string_pretty(&confirmation_request) {
-            tracing::debug!("Full confirmation payload: {}", payload_json);
+        let mut outputs: Vec<PlannedOutput> = recipients
+            .into_iter()
+            .map(|(address, amount)| PlannedOutput {
+                kind: OutputKind::Recipient,
+                address: Some(address),
+                asset: asset.asset_id.clone(),
+                amount,
+            })
+            .collect();
+        if has_asset_change {
+            outputs.push(PlannedOutput {
+                kind: OutputKind::Change,
+                address: None,
+                asset: asset.asset_id.clone(),
+                amount: asset_change,
+            });
         }
 
-        self.confirm_distribution(
+        Ok(Self::dry_run_plan(
+            DryRunOperation::Distribution,
             asset_uuid,
-            &distribution_response.distribution_uuid,
-            amp_tx_data,
-            change_data,
-        )
-        .await
-        .map_err(|e| {
-            tracing::error!("Distribution confirmation failed: {}", e);
+            &asset.asset_id,
+            total,
+            asset_inputs,
+            outputs,
+            network.policy_asset(),
+            payment,
+            fee_rate,
+            vec![
+                PlannedAmpCall::new(
+                    "GET",
+                    format!("/assets/{asset_uuid}/distributions/create"),
+                    "Create the distribution for the assignments",
+                ),
+                PlannedAmpCall::new(
+                    "POST",
+                    format!("/assets/{asset_uuid}/distributions/{{distribution_uuid}}/confirm"),
+                    "Confirm the distribution with the transaction details",
+                ),
+            ],
+            match self.distribution_funding {
+                DistributionFunding::NodeWallet => vec![PlannedRpcCall::new(
+                    "sendmany",
+                    "Fund, sign and broadcast the distribution transaction from the wallet",
+                )],
+                DistributionFunding::Signer => vec![
+                    PlannedRpcCall::new("getnewaddress", "Generate the change address"),
+                    PlannedRpcCall::new(
+                        "createrawtransaction",
+                        "Build the distribution transaction",
+                    ),
+                    PlannedRpcCall::new("blindrawtransaction", "Blind the transaction outputs"),
+                    PlannedRpcCall::new("sendrawtransaction", "Broadcast the signed transaction"),
+                ],
+            },
+            self.distribution_funding == DistributionFunding::NodeWallet,
+            warnings,
+        ))
+    }
 
-            // For confirmation failures, always provide retry instructions with txid
-            let confirmation_error = AmpError::api(format!(
-                "Failed to confirm distribution {}: {}. \
-                IMPORTANT: Transaction {} was successful on blockchain. \
-                Use this txid to manually retry confirmation.",
-                distribution_response.distribution_uuid, e, txid
+    /// Previews `reissue_asset` without creating a reissuance request or spending funds
+    ///
+    /// Validates the asset UUID, the amount and the node connection, fetches the asset and
+    /// finds the reissuance token UTXO in the wallet that `reissueasset` would spend. The
+    /// fee is estimated for a transaction paying the reissued asset, the token change and
+    /// L-BTC change; the node chooses the actual fee inputs.
+    ///
+    /// # Errors
+    /// Returns an error if validation fails, the asset cannot be fetched, has no
+    /// reissuance token, or the wallet lacks the token or L-BTC for the fee
+    pub async fn reissue_asset_dry_run(
+        &self,
+        asset_uuid: &str,
+        amount_to_reissue: i64,
+        node_rpc: &ElementsRpc,
+        wallet_name: &str,
+    ) -> Result<DryRunPlan, AmpError> {
+        Self::validate_asset_uuid(asset_uuid)
+            .map_err(|e| AmpError::validation(format!("Invalid asset UUID: {e}")))?;
+        if amount_to_reissue <= 0 {
+            return Err(AmpError::validation(
+                "Amount to reissue must be positive".to_string(),
             ));
+        }
+        self.validate_elements_rpc_connection(node_rpc)
+            .await
+            .map_err(|e| AmpError::rpc(format!("ElementsRpc connection validation failed: {e}")))?;
 
-            if e.is_retryable() {
-                if let Some(instructions) = e.retry_instructions() {
-                    tracing::warn!("Retry instructions: {}", instructions);
-                }
-            }
-
-            confirmation_error.with_context("Step 11: Distribution confirmation")
+        let asset = self.get_asset(asset_uuid).await?;
+        let token_id = asset.reissuance_token_id.clone().ok_or_else(|| {
+            AmpError::validation(format!("Asset {asset_uuid} has no reissuance token"))
         })?;
+        let token_input = node_rpc
+            .list_unspent_for_wallet(wallet_name, Some(&token_id))
+            .await?
+            .into_iter()
+            .filter(|utxo| utxo.spendable && utxo.asset == token_id)
+            .max_by_key(|utxo| utxo.amount)
+            .ok_or_else(|| {
+                AmpError::validation(format!(
+                    "No reissuance token UTXOs for {token_id} in wallet {wallet_name}"
+                ))
+            })?;
 
-        tracing::info!(
-            "🎉 Asset distribution completed successfully for asset: {} with transaction: {}",
-            asset_uuid,
-            txid
-        );
+        let network = node_rpc.network().await?;
+        let fee_rate = node_rpc.estimate_fee_rate(FeeStrategy::default()).await?;
+        // Reissued asset, token change and L-BTC change
+        let payment = node_rpc
+            .fund_fee(
+                wallet_name,
+                network.policy_asset(),
+                1,
+                3,
+                Vec::new(),
+                fee_rate,
+                Amount::ZERO,
+            )
+            .await?;
 
-        Ok(txid)
+        let amount = Amount::from_sat(amount_to_reissue);
+        let outputs = vec![
+            PlannedOutput {
+                kind: OutputKind::Reissuance,
+                address: None,
+                asset: asset.asset_id.clone(),
+                amount,
+            },
+            PlannedOutput {
+                kind: OutputKind::Change,
+                address: None,
+                asset: token_id,
+                amount: token_input.amount,
+            },
+        ];
+
+        Ok(Self::dry_run_plan(
+            DryRunOperation::Reissuance,
+            asset_uuid,
+            &asset.asset_id,
+            amount,
+            vec![token_input],
+            outputs,
+            network.policy_asset(),
+            payment,
+            fee_rate,
+            vec![
+                PlannedAmpCall::new(
+                    "POST",
+                    format!("/assets/{asset_uuid}/reissue-request"),
+                    "Create the reissuance request",
+                ),
+                PlannedAmpCall::new(
+                    "GET",
+                    format!("/assets/{asset_uuid}/balance"),
+                    "Check for lost outputs",
+                ),
+                PlannedAmpCall::new(
+                    "POST",
+                    format!("/assets/{asset_uuid}/reissue-confirm"),
+                    "Confirm the reissuance with the transaction details",
+                ),
+                PlannedAmpCall::new(
+                    "POST",
+                    format!("/assets/{asset_uuid}/treasury-addresses/add"),
+                    "Register the reissuance token change address as a treasury address",
+                ),
+            ],
+            vec![PlannedRpcCall::new(
+                "reissueasset",
+                "Build, sign and broadcast the reissuance transaction",
+            )],
+            true,
+            Vec::new(),
+        ))
     }
 
-    /// Distributes an asset through a comprehensive workflow
+    /// Previews `burn_asset` without creating a burn request or spending funds
     ///
-    /// This method calls `distribute_asset_with_progress` with no progress callback.
-    /// For progress updates during distribution, use `distribute_asset_with_progress` directly.
-    pub async fn distribute_asset(
+    /// Validates the asset UUID, the amount and the node connection, fetches the asset,
+    /// selects the asset UTXOs with the client's coin selector and estimates the fee.
+    /// `destroyamount` chooses its own inputs, so the plan shows an estimate of the inputs
+    /// the node will spend.
+    ///
+    /// # Errors
+    /// Returns an error if validation fails, the asset cannot be fetched, or the wallet
+    /// lacks the asset or L-BTC for the fee
+    pub async fn burn_asset_dry_run(
         &self,
         asset_uuid: &str,
-        assignments: Vec<AssetDistributionAssignment>,
+        amount_to_burn: i64,
         node_rpc: &ElementsRpc,
         wallet_name: &str,
-        signer: &dyn Signer,
-    ) -> Result<(), AmpError> {
-        // Delegate to the progress variant with no progress callback
-        self.distribute_asset_with_progress(
+    ) -> Result<DryRunPlan, AmpError> {
+        Self::validate_asset_uuid(asset_uuid)
+            .map_err(|e| AmpError::validation(format!("Invalid asset UUID: {e}")))?;
+        if amount_to_burn <= 0 {
+            return Err(AmpError::validation(
+                "Amount to burn must be positive".to_string(),
+            ));
+        }
+        self.validate_elements_rpc_connection(node_rpc)
+            .await
+            .map_err(|e| AmpError::rpc(format!("ElementsRpc connection validation failed: {e}")))?;
+
+        let asset = self.get_asset(asset_uuid).await?;
+        let amount = Amount::from_sat(amount_to_burn);
+        let (asset_inputs, total_selected) = node_rpc
+            .select_utxos_with_selector(
+                wallet_name,
+                &asset.asset_id,
+                amount,
+                Amount::ZERO,
+                self.coin_selector.as_ref(),
+            )
+            .await?;
+        let asset_change = total_selected - amount;
+
+        let network = node_rpc.network().await?;
+        let fee_rate = node_rpc.estimate_fee_rate(FeeStrategy::default()).await?;
+        // Burn output, asset change and L-BTC change
+        let payment = node_rpc
+            .fund_fee(
+                wallet_name,
+                network.policy_asset(),
+                asset_inputs.len(),
+                2 + usize::from(asset_change.is_positive()),
+                Vec::new(),
+                fee_rate,
+                Amount::ZERO,
+            )
+            .await?;
+
+        let mut outputs = vec![PlannedOutput {
+            kind: OutputKind::Burn,
+            address: None,
+            asset: asset.asset_id.clone(),
+            amount,
+        }];
+        if asset_change.is_positive() {
+            outputs.push(PlannedOutput {
+                kind: OutputKind::Change,
+                address: None,
+                asset: asset.asset_id.clone(),
+                amount: asset_change,
+            });
+        }
+
+        Ok(Self::dry_run_plan(
+            DryRunOperation::Burn,
             asset_uuid,
-            assignments,
-            node_rpc,
-            wallet_name,
-            signer,
-            None,
-        )
-        .await?;
-        Ok(())
+            &asset.asset_id,
+            amount,
+            asset_inputs,
+            outputs,
+            network.policy_asset(),
+            payment,
+            fee_rate,
+            vec![
+                PlannedAmpCall::new(
+                    "POST",
+                    format!("/assets/{asset_uuid}/burn-request"),
+                    "Create the burn request",
+                ),
+                PlannedAmpCall::new(
+                    "GET",
+                    format!("/assets/{asset_uuid}/balance"),
+                    "Check for lost outputs",
+                ),
+                PlannedAmpCall::new(
+                    "POST",
+                    format!("/assets/{asset_uuid}/burn-confirm"),
+                    "Confirm the burn with the transaction details",
+                ),
+            ],
+            vec![PlannedRpcCall::new(
+                "destroyamount",
+                "Build, sign and broadcast the burn transaction",
+            )],
+            true,
+            Vec::new(),
+        ))
+    }
+
+    /// Completes a dry-run plan with the L-BTC fee inputs, change and fee output
+    #[allow(clippy::too_many_arguments)]
+    fn dry_run_plan(
+        operation: DryRunOperation,
+        asset_uuid: &str,
+        asset_id: &str,
+        amount: Amount,
+        mut inputs: Vec<Unspent>,
+        mut outputs: Vec<PlannedOutput>,
+        policy_asset: &str,
+        payment: FeePayment,
+        fee_rate: FeeRate,
+        amp_calls: Vec<PlannedAmpCall>,
+        node_calls: Vec<PlannedRpcCall>,
+        estimated: bool,
+        warnings: Vec<String>,
+    ) -> DryRunPlan {
+        inputs.extend(payment.inputs);
+        if payment.change.is_positive() {
+            outputs.push(PlannedOutput {
+                kind: OutputKind::Change,
+                address: None,
+                asset: policy_asset.to_string(),
+                amount: payment.change,
+            });
+        }
+        outputs.push(PlannedOutput {
+            kind: OutputKind::Fee,
+            address: None,
+            asset: policy_asset.to_string(),
+            amount: payment.fee,
+        });
+
+        tracing::info!(
+            "Dry run of {:?} for asset {}: {} inputs, {} outputs, fee {}",
+            operation,
+            asset_uuid,
+            inputs.len(),
+            outputs.len(),
+            payment.fee
+        );
+
+        DryRunPlan {
+            operation,
+            asset_uuid: asset_uuid.to_string(),
+            asset_id: asset_id.to_string(),
+            amount,
+            inputs,
+            outputs,
+            fee: payment.fee,
+            fee_rate,
+            vsize: payment.vsize,
+            amp_calls,
+            node_calls,
+            estimated,
+            warnings,
+        }
     }
 
     /// Reissues an asset with progress callbacks
@@ -15094,14 +18490,15 @@ impl ApiClient {
         }
 
         send_progress(10, total_steps, "Verifying reissuance token UTXOs");
-        let available_utxos = node_rpc
-            .list_unspent(wallet_name, None)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to list UTXOs: {}", e);
-                AmpError::rpc(format!("Failed to list UTXOs: {e}"))
-                    .with_context("Step 10: UTXO verification")
-            })?;
+        let available_utxos = match self.walletless_funding() {
+            Some((backend, funding)) => backend.list_unspent(&funding.addresses).await,
+            None => node_rpc.list_unspent(wallet_name, None).await,
+        }
+        .map_err(|e| {
+            tracing::error!("Failed to list UTXOs: {}", e);
+            AmpError::rpc(format!("Failed to list UTXOs: {e}"))
+                .with_context("Step 10: UTXO verification")
+        })?;
 
         let local_utxos: std::collections::HashSet<(String, i64)> = available_utxos
             .iter()
@@ -15125,22 +18522,36 @@ impl ApiClient {
         }
 
         send_progress(11, total_steps, "Executing reissuance transaction");
-        let reissuance_output = node_rpc
-            .reissueasset(
-                wallet_name,
-                &reissue_response.asset_id,
-                reissue_response.amount,
-            )
-            .await
-            .map_err(|e| {
-                tracing::error!("Reissuance transaction creation failed: {}", e);
-                if e.is_retryable() {
-                    if let Some(instructions) = e.retry_instructions() {
-                        tracing::warn!("Retry instructions: {}", instructions);
-                    }
+        let reissuance_output = match self.walletless_funding() {
+            Some((_, funding)) => {
+                self.send_walletless_reissuance(
+                    node_rpc,
+                    funding,
+                    available_utxos,
+                    &reissue_response,
+                    signer,
+                )
+                .await
+            }
+            None => {
+                node_rpc
+                    .reissueasset(
+                        wallet_name,
+                        &reissue_response.asset_id,
+                        reissue_response.amount,
+                    )
+                    .await
+            }
+        }
+        .map_err(|e| {
+            tracing::error!("Reissuance transaction creation failed: {}", e);
+            if e.is_retryable() {
+                if let Some(instructions) = e.retry_instructions() {
+                    tracing::warn!("Retry instructions: {}", instructions);
                 }
-                e.with_context("Step 11: Reissuance transaction creation")
-            })?;
+            }
+            e.with_context("Step 11: Reissuance transaction creation")
+        })?;
 
         let txid = reissuance_output
             .get("txid")
@@ -15157,15 +18568,7 @@ impl ApiClient {
 
         // Wait for 1 confirmation before spawning treasury address task
         send_progress(12, total_steps, "Waiting for confirmations");
-        node_rpc
-            .wait_for_confirmations_with_progress(
-                wallet_name,
-                &txid,
-                Some(1),
-                Some(10),
-                None,
-                progress_tx.as_ref(),
-            )
+        self.wait_for_workflow_confirmations(node_rpc, wallet_name, &txid, 1, progress_tx.as_ref())
             .await
             .map_err(|e| {
                 tracing::error!("Confirmation waiting (1 conf) failed: {}", e);
@@ -15200,8 +18603,8 @@ impl ApiClient {
         });
 
         // Continue waiting for the full 2 confirmations
-        let _tx_detail = node_rpc
-            .wait_for_confirmations_with_progress(wallet_name, &txid, Some(2), Some(10), None, progress_tx.as_ref())
+        let _tx_detail = self
+            .wait_for_workflow_confirmations(node_rpc, wallet_name, &txid, 2, progress_tx.as_ref())
             .await
             .map_err(|e| {
                 tracing::error!("Confirmation waiting failed: {}", e);
@@ -15235,6 +18638,7 @@ impl ApiClient {
                     .with_context("Step 13: Transaction details retrieval")
             })?;
 
+        let confirmed_blockhash = tx_detail.blockhash.clone();
         let details = serde_json::to_value(tx_detail.details).map_err(|e| {
             tracing::error!("Failed to serialize transaction details: {}", e);
             AmpError::api(format!("Failed to serialize transaction details: {e}"))
@@ -15272,6 +18676,16 @@ impl ApiClient {
                 confirmation_error.with_context("Step 13: Reissuance confirmation")
             })?;
 
+        self.spawn_reorg_monitor(
+            node_rpc,
+            wallet_name,
+            &txid,
+            confirmed_blockhash,
+            ConfirmedOperation::Reissuance {
+                asset_uuid: asset_uuid.to_string(),
+            },
+        );
+
         tracing::info!(
             "🎉 Asset reissuance completed successfully for asset: {} with transaction: {}",
             asset_uuid,
@@ -15529,8 +18943,7 @@ impl ApiClient {
         let confirmation_start = std::time::Instant::now();
 
         // First, wait for 1 confirmation before spawning treasury address task
-        node_rpc
-            .wait_for_confirmations(wallet_name, txid, Some(1), Some(10))
+        self.wait_for_workflow_confirmations(node_rpc, wallet_name, txid, 1, None)
             .await
             .map_err(|e| {
                 let elapsed = confirmation_start.elapsed();
@@ -15575,8 +18988,8 @@ impl ApiClient {
         });
 
         // Continue waiting for the full 2 confirmations
-        let _tx_detail = node_rpc
-            .wait_for_confirmations(wallet_name, txid, Some(2), Some(10))
+        let _tx_detail = self
+            .wait_for_workflow_confirmations(node_rpc, wallet_name, txid, 2, None)
             .await
             .map_err(|e| {
                 let elapsed = confirmation_start.elapsed();
@@ -15622,6 +19035,7 @@ impl ApiClient {
             })?;
 
         // Convert details to JSON Value
+        let confirmed_blockhash = tx_detail.blockhash.clone();
         let details = serde_json::to_value(tx_detail.details).map_err(|e| {
             tracing::error!("Failed to serialize transaction details: {}", e);
             AmpError::api(format!("Failed to serialize transaction details: {e}"))
@@ -15680,6 +19094,16 @@ impl ApiClient {
                 confirmation_error.with_context("Step 14: Reissuance confirmation")
             })?;
 
+        self.spawn_reorg_monitor(
+            node_rpc,
+            wallet_name,
+            txid,
+            confirmed_blockhash,
+            ConfirmedOperation::Reissuance {
+                asset_uuid: asset_uuid.to_string(),
+            },
+        );
+
         tracing::info!(
             "🎉 Asset reissuance completed successfully for asset: {} with transaction: {}",
             asset_uuid,
@@ -16099,14 +19523,15 @@ impl ApiClient {
             burn_response.utxos.len()
         );
 
-        let available_utxos = node_rpc
-            .list_unspent(wallet_name, None)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to list UTXOs: {}", e);
-                AmpError::rpc(format!("Failed to list UTXOs: {e}"))
-                    .with_context("Step 10: UTXO verification")
-            })?;
+        let available_utxos = match self.walletless_funding() {
+            Some((backend, funding)) => backend.list_unspent(&funding.addresses).await,
+            None => node_rpc.list_unspent(wallet_name, None).await,
+        }
+        .map_err(|e| {
+            tracing::error!("Failed to list UTXOs: {}", e);
+            AmpError::rpc(format!("Failed to list UTXOs: {e}"))
+                .with_context("Step 10: UTXO verification")
+        })?;
 
         // Check that all required UTXOs are available
         let local_utxos: std::collections::HashSet<(String, i64)> = available_utxos
@@ -16137,17 +19562,32 @@ impl ApiClient {
 
         // Step 11: Check local balance >= requested amount
         tracing::debug!("Step 11: Verifying sufficient balance");
-        let balances = node_rpc.get_balance(wallet_name, None).await.map_err(|e| {
-            tracing::error!("Failed to get balance: {}", e);
-            AmpError::rpc(format!("Failed to get balance: {e}"))
-                .with_context("Step 11: Balance verification")
-        })?;
+        let local_amount = if self.walletless_funding().is_some() {
+            available_utxos
+                .iter()
+                .filter(|utxo| utxo.asset == burn_response.asset_id)
+                .map(|utxo| utxo.amount)
+                .sum()
+        } else {
+            let balances = node_rpc.get_balance(wallet_name, None).await.map_err(|e| {
+                tracing::error!("Failed to get balance: {}", e);
+                AmpError::rpc(format!("Failed to get balance: {e}"))
+                    .with_context("Step 11: Balance verification")
+            })?;
 
-        // Extract balance for the specific asset_id (getbalance returns a map)
-        let local_amount = balances
-            .get(&burn_response.asset_id)
-            .and_then(serde_json::Value::as_f64)
-            .unwrap_or(0.0);
+            // Extract balance for the specific asset_id (getbalance returns a map)
+            balances
+                .get(&burn_response.asset_id)
+                .and_then(serde_json::Value::as_f64)
+                .map_or(Ok(Amount::ZERO), Amount::from_btc)
+                .map_err(|e| {
+                    AmpError::rpc(format!(
+                        "Invalid balance for asset {}: {e}",
+                        burn_response.asset_id
+                    ))
+                    .with_context("Step 11: Balance verification")
+                })?
+        };
         let requested_amount = burn_response.amount;
 
         if local_amount < requested_amount {
@@ -16165,27 +19605,42 @@ impl ApiClient {
         );
 
         // Step 12: Call Elements node's destroyamount RPC method
-        tracing::debug!("Step 12: Calling Elements destroyamount RPC method");
-        let txid = node_rpc
-            .destroyamount(wallet_name, &burn_response.asset_id, requested_amount)
-            .await
-            .map_err(|e| {
-                tracing::error!("Burn transaction creation failed: {}", e);
-                if e.is_retryable() {
-                    if let Some(instructions) = e.retry_instructions() {
-                        tracing::warn!("Retry instructions: {}", instructions);
-                    }
+        tracing::debug!("Step 12: Creating the burn transaction");
+        let txid = match self.walletless_funding() {
+            Some((_, funding)) => {
+                self.send_walletless_burn(
+                    node_rpc,
+                    funding,
+                    available_utxos,
+                    &burn_response.asset_id,
+                    requested_amount,
+                    signer,
+                )
+                .await
+            }
+            None => {
+                node_rpc
+                    .destroyamount(wallet_name, &burn_response.asset_id, requested_amount)
+                    .await
+            }
+        }
+        .map_err(|e| {
+            tracing::error!("Burn transaction creation failed: {}", e);
+            if e.is_retryable() {
+                if let Some(instructions) = e.retry_instructions() {
+                    tracing::warn!("Retry instructions: {}", instructions);
                 }
-                e.with_context("Step 12: Burn transaction creation")
-            })?;
+            }
+            e.with_context("Step 12: Burn transaction creation")
+        })?;
 
         tracing::info!("✓ Burn transaction created: txid={}", txid);
 
         // Step 13: Wait for confirmations
         tracing::debug!("Step 13: Waiting for blockchain confirmations (minimum 2 confirmations, 10-minute timeout)");
         let confirmation_start = std::time::Instant::now();
-        let _tx_detail = node_rpc
-            .wait_for_confirmations(wallet_name, &txid, Some(2), Some(10))
+        let tx_detail = self
+            .wait_for_workflow_confirmations(node_rpc, wallet_name, &txid, 2, None)
             .await
             .map_err(|e| {
                 let elapsed = confirmation_start.elapsed();
@@ -16228,8 +19683,8 @@ impl ApiClient {
         // Get change_data from listunspent with blinding data filtered by asset_id and txid
         // We need to use list_unspent_with_blinding_data to get amountblinder and assetblinder fields
         // required by the AMP API
-        let all_unspent = node_rpc
-            .list_unspent_with_blinding_data(wallet_name)
+        let all_unspent = self
+            .list_workflow_unspent(node_rpc, wallet_name)
             .await
             .map_err(|e| {
                 tracing::error!("Failed to list unspent outputs with blinding data: {}", e);
@@ -16293,6 +19748,16 @@ impl ApiClient {
                 confirmation_error.with_context("Step 15: Burn confirmation")
             })?;
 
+        self.spawn_reorg_monitor(
+            node_rpc,
+            wallet_name,
+            &txid,
+            tx_detail.blockhash,
+            ConfirmedOperation::Burn {
+                asset_uuid: asset_uuid.to_string(),
+            },
+        );
+
         tracing::info!(
             "🎉 Asset burn completed successfully for asset: {} with transaction: {}",
             asset_uuid,
@@ -16381,7 +19846,7 @@ impl ApiClient {
             return Err("Assignments vector cannot be empty".to_string());
         }
 
-        let mut total_amount = 0.0;
+        let mut total_amount = Amount::ZERO;
         let mut unique_addresses = std::collections::HashSet::new();
         let mut unique_users = std::collections::HashSet::new();
 
@@ -16423,7 +19888,7 @@ impl ApiClient {
             }
 
             // Validate amount
-            if assignment.amount <= 0.0 {
+            if !assignment.amount.is_positive() {
                 tracing::error!(
                     "Assignment {} validation failed: non-positive amount {}",
                     index,
@@ -16436,7 +19901,7 @@ impl ApiClient {
             }
 
             // Check for reasonable amount limits (prevent overflow issues)
-            if assignment.amount > 21_000_000.0 {
+            if assignment.amount > Amount::MAX_MONEY {
                 tracing::error!(
                     "Assignment {} validation failed: unreasonably large amount {} (max: 21,000,000)",
                     index, assignment.amount
@@ -16447,17 +19912,6 @@ impl ApiClient {
                 ));
             }
 
-            // Check for precision issues (more than 8 decimal places)
-            let amount_str = format!("{:.8}", assignment.amount);
-            if amount_str.len() > 20 {
-                // Reasonable length check
-                tracing::warn!(
-                    "Assignment {} has high precision amount: {} - may cause precision issues",
-                    index,
-                    assignment.amount
-                );
-            }
-
             // Track duplicates for warnings
             if !unique_addresses.insert(&assignment.address) {
                 tracing::warn!(
@@ -16486,7 +19940,7 @@ impl ApiClient {
             unique_users.len()
         );
 
-        if total_amount > 100_000_000.0 {
+        if total_amount > Amount::MAX_MONEY {
             tracing::warn!(
                 "Total distribution amount is very large: {} - ensure this is intentional",
                 total_amount
@@ -17274,7 +20728,7 @@ mod tests {
         let assignments = vec![AssetDistributionAssignment {
             user_id: "user123".to_string(),
             address: "lq1qq2xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq".to_string(),
-            amount: 100.0,
+            amount: Amount::from_btc(100.0).unwrap(),
         }];
 
         // Create a mock ElementsRpc (this will fail connection validation, but that's expected)
@@ -17359,7 +20813,7 @@ mod tests {
         let valid_assignments = vec![AssetDistributionAssignment {
             user_id: "user123".to_string(),
             address: "lq1qq2xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq".to_string(),
-            amount: 100.0,
+            amount: Amount::from_btc(100.0).unwrap(),
         }];
         assert!(ApiClient::validate_assignments(&valid_assignments).is_ok());
 
@@ -17370,7 +20824,7 @@ mod tests {
         let invalid_assignments = vec![AssetDistributionAssignment {
             user_id: "".to_string(),
             address: "lq1qq2xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq".to_string(),
-            amount: 100.0,
+            amount: Amount::from_btc(100.0).unwrap(),
         }];
         assert!(ApiClient::validate_assignments(&invalid_assignments).is_err());
 
@@ -17378,7 +20832,7 @@ mod tests {
         let invalid_assignments = vec![AssetDistributionAssignment {
             user_id: "user123".to_string(),
             address: "".to_string(),
-            amount: 100.0,
+            amount: Amount::from_btc(100.0).unwrap(),
         }];
         assert!(ApiClient::validate_assignments(&invalid_assignments).is_err());
 
@@ -17386,7 +20840,7 @@ mod tests {
         let invalid_assignments = vec![AssetDistributionAssignment {
             user_id: "user123".to_string(),
             address: "invalid_address".to_string(),
-            amount: 100.0,
+            amount: Amount::from_btc(100.0).unwrap(),
         }];
         assert!(ApiClient::validate_assignments(&invalid_assignments).is_err());
 
@@ -17394,7 +20848,7 @@ mod tests {
         let invalid_assignments = vec![AssetDistributionAssignment {
             user_id: "user123".to_string(),
             address: "lq1qq2xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq".to_string(),
-            amount: 0.0,
+            amount: Amount::from_btc(0.0).unwrap(),
         }];
         assert!(ApiClient::validate_assignments(&invalid_assignments).is_err());
 
@@ -17402,7 +20856,7 @@ mod tests {
         let invalid_assignments = vec![AssetDistributionAssignment {
             user_id: "user123".to_string(),
             address: "lq1qq2xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq".to_string(),
-            amount: 25_000_000.0,
+            amount: Amount::from_btc(25_000_000.0).unwrap(),
         }];
         assert!(ApiClient::validate_assignments(&invalid_assignments).is_err());
     }
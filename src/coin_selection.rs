@@ -0,0 +1,507 @@
+//! # Coin Selection
+//!
+//! Strategies for choosing which UTXOs fund a transaction. Selection runs over an
+//! in-memory slice of [`Unspent`] outputs, so strategies can be tested and compared
+//! without an Elements node. [`ElementsRpc`](crate::ElementsRpc) fetches the wallet's
+//! UTXOs and hands them to the selector chosen by the caller.
+//!
+//! Provided strategies:
+//! - [`LargestFirst`] - spends the largest UTXOs first, minimising the number of inputs
+//! - [`BranchAndBound`] - searches for a combination that needs no change output
+//! - [`OldestFirst`] - spends the UTXOs with the most confirmations first
+//! - [`ConsolidateSmall`] - spends the smallest UTXOs first and sweeps in extra dust
+//!
+//! ## Example
+//!
+//! ```
+//! use amp_rs::coin_selection::{BranchAndBound, CoinSelector};
+//! use amp_rs::{Amount, Unspent};
+//!
+//! # fn utxo(vout: u32, sats: i64) -> Unspent {
+//! #     Unspent {
+//! #         txid: "00".repeat(32),
+//! #         vout,
+//! #         amount: Amount::from_sat(sats),
+//! #         asset: "asset_id_hex".to_string(),
+//! #         address: "address".to_string(),
+//! #         spendable: true,
+//! #         confirmations: Some(1),
+//! #         scriptpubkey: None,
+//! #         redeemscript: None,
+//! #         witnessscript: None,
+//! #         amountblinder: None,
+//! #         assetblinder: None,
+//! #     }
+//! # }
+//! let utxos = vec![utxo(0, 5_000), utxo(1, 3_000), utxo(2, 2_000)];
+//!
+//! let selection = BranchAndBound::default()
+//!     .select(&utxos, Amount::from_sat(7_000))
+//!     .unwrap();
+//! assert_eq!(selection.total, Amount::from_sat(7_000));
+//! assert_eq!(selection.change(), Amount::ZERO);
+//! ```
+
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::amount::Amount;
+use crate::model::Unspent;
+
+/// Errors produced by coin selection strategies
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum CoinSelectionError {
+    #[error("No spendable UTXOs available")]
+    NoUtxos,
+    #[error("Insufficient UTXOs: need {needed}, have {available}")]
+    InsufficientFunds { needed: Amount, available: Amount },
+    #[error("No changeless selection found for {target} within tolerance {tolerance}")]
+    NoChangelessSolution { target: Amount, tolerance: Amount },
+}
+
+/// Result of a coin selection
+#[derive(Debug, Clone)]
+pub struct CoinSelection {
+    /// The UTXOs chosen to fund the transaction, in the order the strategy picked them
+    pub selected: Vec<Unspent>,
+    /// Sum of the selected UTXO amounts
+    pub total: Amount,
+    /// The amount the selection had to cover
+    pub target: Amount,
+}
+
+impl CoinSelection {
+    /// Returns the amount left over after covering the target
+    #[must_use]
+    pub fn change(&self) -> Amount {
+        self.total - self.target
+    }
+}
+
+/// A strategy for choosing UTXOs that cover a target amount
+///
+/// Implementations only consider spendable UTXOs and must return a selection whose
+/// total is at least `target`. The caller is responsible for passing UTXOs of a single
+/// asset.
+pub trait CoinSelector: Send + Sync + fmt::Debug {
+    /// Selects UTXOs from `utxos` covering at least `target`
+    ///
+    /// # Errors
+    /// Returns an error if no spendable UTXOs are available or they cannot cover the target
+    fn select(
+        &self,
+        utxos: &[Unspent],
+        target: Amount,
+    ) -> Result<CoinSelection, CoinSelectionError>;
+}
+
+/// Spends the largest UTXOs first
+///
+/// Produces the fewest inputs and therefore the smallest transaction. This is the
+/// strategy used by [`ElementsRpc::select_utxos_for_amount`](crate::ElementsRpc::select_utxos_for_amount).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LargestFirst;
+
+impl CoinSelector for LargestFirst {
+    fn select(
+        &self,
+        utxos: &[Unspent],
+        target: Amount,
+    ) -> Result<CoinSelection, CoinSelectionError> {
+        let mut candidates = spendable(utxos)?;
+        candidates.sort_by_key(|utxo| std::cmp::Reverse(utxo.amount));
+        accumulate(candidates, target)
+    }
+}
+
+/// Spends the UTXOs with the most confirmations first
+///
+/// UTXOs without a confirmation count are treated as unconfirmed and spent last. Ties
+/// are broken by spending the larger UTXO first.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OldestFirst;
+
+impl CoinSelector for OldestFirst {
+    fn select(
+        &self,
+        utxos: &[Unspent],
+        target: Amount,
+    ) -> Result<CoinSelection, CoinSelectionError> {
+        let mut candidates = spendable(utxos)?;
+        candidates.sort_by_key(|utxo| {
+            (
+                std::cmp::Reverse(utxo.confirmations.unwrap_or(0)),
+                std::cmp::Reverse(utxo.amount),
+            )
+        });
+        accumulate(candidates, target)
+    }
+}
+
+/// Searches for a combination of UTXOs that needs no change output
+///
+/// A selection is accepted when its total lies between `target` and
+/// `target + cost_of_change`; anything left over is considered cheaper to give up than
+/// to spend a change output on. Among the candidates found within `max_tries` search
+/// steps the one with the least excess wins. When no such combination exists the
+/// selector returns [`CoinSelectionError::NoChangelessSolution`] rather than falling
+/// back to a selection with change.
+///
+/// Only the policy asset can be given up as fee. Distribution transactions return any
+/// excess of an issued asset as change, so use `BranchAndBound::new(Amount::ZERO)` to
+/// select issued asset UTXOs without change.
+#[derive(Debug, Clone, Copy)]
+pub struct BranchAndBound {
+    cost_of_change: Amount,
+    max_tries: usize,
+}
+
+impl Default for BranchAndBound {
+    fn default() -> Self {
+        Self {
+            cost_of_change: Self::DEFAULT_COST_OF_CHANGE,
+            max_tries: Self::DEFAULT_MAX_TRIES,
+        }
+    }
+}
+
+impl BranchAndBound {
+    /// Default excess tolerated instead of creating a change output (the dust threshold)
+    pub const DEFAULT_COST_OF_CHANGE: Amount = Amount::from_sat(1000);
+
+    /// Default number of search steps before giving up
+    pub const DEFAULT_MAX_TRIES: usize = 100_000;
+
+    /// Creates a selector that tolerates up to `cost_of_change` of excess
+    #[must_use]
+    pub const fn new(cost_of_change: Amount) -> Self {
+        Self {
+            cost_of_change,
+            max_tries: Self::DEFAULT_MAX_TRIES,
+        }
+    }
+
+    /// Sets the maximum number of search steps
+    #[must_use]
+    pub const fn with_max_tries(mut self, max_tries: usize) -> Self {
+        self.max_tries = max_tries;
+        self
+    }
+}
+
+impl CoinSelector for BranchAndBound {
+    fn select(
+        &self,
+        utxos: &[Unspent],
+        target: Amount,
+    ) -> Result<CoinSelection, CoinSelectionError> {
+        let mut candidates = spendable(utxos)?;
+        candidates.sort_by_key(|utxo| std::cmp::Reverse(utxo.amount));
+
+        let available: Amount = candidates.iter().map(|utxo| utxo.amount).sum();
+        if available < target {
+            return Err(CoinSelectionError::InsufficientFunds {
+                needed: target,
+                available,
+            });
+        }
+
+        let amounts: Vec<Amount> = candidates.iter().map(|utxo| utxo.amount).collect();
+        let mut search = BnbSearch {
+            amounts: &amounts,
+            target,
+            upper_bound: target + self.cost_of_change,
+            tries_left: self.max_tries,
+            current: Vec::new(),
+            best: None,
+        };
+        search.explore(0, Amount::ZERO, available);
+
+        let Some((indices, total)) = search.best else {
+            return Err(CoinSelectionError::NoChangelessSolution {
+                target,
+                tolerance: self.cost_of_change,
+            });
+        };
+
+        Ok(CoinSelection {
+            selected: indices.into_iter().map(|i| candidates[i].clone()).collect(),
+            total,
+            target,
+        })
+    }
+}
+
+/// Depth-first include/exclude search over UTXOs sorted largest first
+struct BnbSearch<'a> {
+    amounts: &'a [Amount],
+    target: Amount,
+    upper_bound: Amount,
+    tries_left: usize,
+    current: Vec<usize>,
+    best: Option<(Vec<usize>, Amount)>,
+}
+
+impl BnbSearch<'_> {
+    fn explore(&mut self, index: usize, total: Amount, remaining: Amount) {
+        if self.tries_left == 0 || self.best.as_ref().is_some_and(|(_, t)| *t == self.target) {
+            return;
+        }
+        self.tries_left -= 1;
+
+        // Overshot the window, or cannot reach the target with what is left
+        if total > self.upper_bound || total + remaining < self.target {
+            return;
+        }
+
+        if total >= self.target {
+            if self.best.as_ref().is_none_or(|(_, best)| total < *best) {
+                self.best = Some((self.current.clone(), total));
+            }
+            return;
+        }
+
+        let Some(&amount) = self.amounts.get(index) else {
+            return;
+        };
+        let remaining = remaining - amount;
+
+        self.current.push(index);
+        self.explore(index + 1, total + amount, remaining);
+        self.current.pop();
+
+        self.explore(index + 1, total, remaining);
+    }
+}
+
+/// Spends the smallest UTXOs first and sweeps in additional small UTXOs
+///
+/// After the target is covered, remaining UTXOs at or below `small_threshold` are added
+/// (smallest first) until the selection holds `max_inputs` inputs. This reduces the
+/// number of dust outputs held by the wallet at the cost of a larger transaction.
+#[derive(Debug, Clone, Copy)]
+pub struct ConsolidateSmall {
+    small_threshold: Amount,
+    max_inputs: usize,
+}
+
+impl ConsolidateSmall {
+    /// Default maximum number of inputs in a consolidating selection
+    pub const DEFAULT_MAX_INPUTS: usize = 50;
+
+    /// Creates a selector that sweeps UTXOs at or below `small_threshold`
+    #[must_use]
+    pub const fn new(small_threshold: Amount) -> Self {
+        Self {
+            small_threshold,
+            max_inputs: Self::DEFAULT_MAX_INPUTS,
+        }
+    }
+
+    /// Sets the maximum number of inputs, including those needed to cover the target
+    #[must_use]
+    pub const fn with_max_inputs(mut self, max_inputs: usize) -> Self {
+        self.max_inputs = max_inputs;
+        self
+    }
+}
+
+impl CoinSelector for ConsolidateSmall {
+    fn select(
+        &self,
+        utxos: &[Unspent],
+        target: Amount,
+    ) -> Result<CoinSelection, CoinSelectionError> {
+        let mut candidates = spendable(utxos)?;
+        candidates.sort_by_key(|utxo| utxo.amount);
+
+        let mut selection = accumulate(candidates.iter().copied(), target)?;
+        let extra = candidates
+            .into_iter()
+            .skip(selection.selected.len())
+            .take_while(|utxo| utxo.amount <= self.small_threshold)
+            .take(self.max_inputs.saturating_sub(selection.selected.len()));
+        for utxo in extra {
+            selection.total += utxo.amount;
+            selection.selected.push(utxo.clone());
+        }
+
+        Ok(selection)
+    }
+}
+
+/// Returns the spendable UTXOs, or an error if there are none
+fn spendable(utxos: &[Unspent]) -> Result<Vec<&Unspent>, CoinSelectionError> {
+    let candidates: Vec<&Unspent> = utxos.iter().filter(|utxo| utxo.spendable).collect();
+    if candidates.is_empty() {
+        return Err(CoinSelectionError::NoUtxos);
+    }
+    Ok(candidates)
+}
+
+/// Takes UTXOs in order until their total covers `target`
+fn accumulate<'a>(
+    candidates: impl IntoIterator<Item = &'a Unspent>,
+    target: Amount,
+) -> Result<CoinSelection, CoinSelectionError> {
+    let mut selected = Vec::new();
+    let mut total = Amount::ZERO;
+
+    for utxo in candidates {
+        if total >= target {
+            break;
+        }
+        selected.push(utxo.clone());
+        total += utxo.amount;
+    }
+
+    if total < target {
+        return Err(CoinSelectionError::InsufficientFunds {
+            needed: target,
+            available: total,
+        });
+    }
+
+    Ok(CoinSelection {
+        selected,
+        total,
+        target,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utxo(vout: u32, sats: i64, confirmations: Option<u32>) -> Unspent {
+        Unspent {
+            txid: format!("{vout:064x}"),
+            vout,
+            amount: Amount::from_sat(sats),
+            asset: "asset".to_string(),
+            address: format!("address_{vout}"),
+            spendable: true,
+            confirmations,
+            scriptpubkey: None,
+            redeemscript: None,
+            witnessscript: None,
+            amountblinder: None,
+            assetblinder: None,
+        }
+    }
+
+    fn vouts(selection: &CoinSelection) -> Vec<u32> {
+        selection.selected.iter().map(|utxo| utxo.vout).collect()
+    }
+
+    #[test]
+    fn test_largest_first_selects_fewest_inputs() {
+        let utxos = vec![
+            utxo(0, 2_500, Some(1)),
+            utxo(1, 10_000, Some(1)),
+            utxo(2, 7_500, Some(1)),
+        ];
+
+        let selection = LargestFirst
+            .select(&utxos, Amount::from_sat(12_000))
+            .unwrap();
+        assert_eq!(vouts(&selection), vec![1, 2]);
+        assert_eq!(selection.total, Amount::from_sat(17_500));
+        assert_eq!(selection.change(), Amount::from_sat(5_500));
+    }
+
+    #[test]
+    fn test_selection_skips_unspendable_and_reports_shortfall() {
+        let mut locked = utxo(0, 50_000, Some(1));
+        locked.spendable = false;
+        let utxos = vec![locked, utxo(1, 1_000, Some(1))];
+
+        assert_eq!(
+            LargestFirst
+                .select(&utxos, Amount::from_sat(2_000))
+                .unwrap_err(),
+            CoinSelectionError::InsufficientFunds {
+                needed: Amount::from_sat(2_000),
+                available: Amount::from_sat(1_000),
+            }
+        );
+        assert_eq!(
+            LargestFirst
+                .select(&utxos[..1], Amount::from_sat(1))
+                .unwrap_err(),
+            CoinSelectionError::NoUtxos
+        );
+    }
+
+    #[test]
+    fn test_oldest_first_orders_by_confirmations() {
+        let utxos = vec![
+            utxo(0, 5_000, Some(2)),
+            utxo(1, 5_000, None),
+            utxo(2, 5_000, Some(100)),
+            utxo(3, 5_000, Some(10)),
+        ];
+
+        let selection = OldestFirst
+            .select(&utxos, Amount::from_sat(12_000))
+            .unwrap();
+        assert_eq!(vouts(&selection), vec![2, 3, 0]);
+    }
+
+    #[test]
+    fn test_branch_and_bound_finds_changeless_combination() {
+        let utxos = vec![
+            utxo(0, 10_000, Some(1)),
+            utxo(1, 6_000, Some(1)),
+            utxo(2, 4_000, Some(1)),
+            utxo(3, 3_000, Some(1)),
+        ];
+
+        // Largest first would pick 10_000 + 6_000 and need 3_000 change
+        let selection = BranchAndBound::default()
+            .select(&utxos, Amount::from_sat(13_000))
+            .unwrap();
+        assert_eq!(selection.total, Amount::from_sat(13_000));
+        assert_eq!(vouts(&selection), vec![0, 3]);
+    }
+
+    #[test]
+    fn test_branch_and_bound_accepts_excess_within_cost_of_change() {
+        let utxos = vec![utxo(0, 8_000, Some(1)), utxo(1, 5_400, Some(1))];
+
+        let selection = BranchAndBound::new(Amount::from_sat(500))
+            .select(&utxos, Amount::from_sat(13_000))
+            .unwrap();
+        assert_eq!(selection.change(), Amount::from_sat(400));
+
+        assert_eq!(
+            BranchAndBound::new(Amount::from_sat(100))
+                .select(&utxos, Amount::from_sat(13_000))
+                .unwrap_err(),
+            CoinSelectionError::NoChangelessSolution {
+                target: Amount::from_sat(13_000),
+                tolerance: Amount::from_sat(100),
+            }
+        );
+    }
+
+    #[test]
+    fn test_consolidate_small_sweeps_dust_up_to_max_inputs() {
+        let utxos = vec![
+            utxo(0, 100_000, Some(1)),
+            utxo(1, 300, Some(1)),
+            utxo(2, 200, Some(1)),
+            utxo(3, 700, Some(1)),
+            utxo(4, 5_000, Some(1)),
+        ];
+
+        let selection = ConsolidateSmall::new(Amount::from_sat(1_000))
+            .with_max_inputs(3)
+            .select(&utxos, Amount::from_sat(400))
+            .unwrap();
+        // 200 + 300 covers the target, 700 is swept in, 5_000 is above the threshold
+        assert_eq!(vouts(&selection), vec![2, 1, 3]);
+        assert_eq!(selection.total, Amount::from_sat(1_200));
+    }
+}
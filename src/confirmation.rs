@@ -0,0 +1,1080 @@
+//! # Confirmation Watcher
+//!
+//! [`ElementsRpc::wait_for_confirmations`](crate::ElementsRpc::wait_for_confirmations)
+//! polls one transaction in its own loop. When many distributions run at once, that
+//! becomes many independent polling loops. A [`ConfirmationWatcher`] follows the chain
+//! tip once for all of them and only looks up the watched transactions when a new block
+//! arrives.
+//!
+//! New blocks are detected by polling [`ChainBackend::tip_height`]. With
+//! [`with_zmq_hashblock`](ConfirmationWatcher::with_zmq_hashblock) the watcher also
+//! subscribes to the node's ZMQ `hashblock` notifications (`-zmqpubhashblock`) and
+//! reacts to blocks as soon as they are announced; polling then only acts as a fallback.
+//!
+//! Each subscriber receives [`ProgressUpdate::Confirmation`] whenever the confirmation
+//! count of its transaction changes, and [`ProgressUpdate::Reorg`] if a transaction that
+//! was already confirmed loses confirmations or disappears from the chain.
+//!
+//! ## Reorg monitoring
+//!
+//! Once a workflow has confirmed a transaction with the AMP API, the AMP state assumes
+//! the transaction is final. A [`ReorgMonitor`] keeps checking the `blockhash` of the
+//! transaction until it is buried `depth` blocks deep. If the block changes or the
+//! transaction leaves the chain, it emits a [`ReorgEvent`] so that integrators can
+//! reconcile, and optionally re-broadcasts the transaction. Configure it with
+//! [`ApiClientBuilder::reorg_monitor`](crate::client::ApiClientBuilder::reorg_monitor)
+//! to monitor every distribution, reissuance and burn.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//! use amp_rs::confirmation::ConfirmationWatcher;
+//! use amp_rs::ElementsRpc;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let rpc = Arc::new(ElementsRpc::from_env()?);
+//! let watcher = ConfirmationWatcher::new(rpc)
+//!     .with_zmq_hashblock("tcp://127.0.0.1:28332")
+//!     .start();
+//!
+//! let first = watcher.watch("txid1...", 2, None);
+//! let second = watcher.watch("txid2...", 2, None);
+//! let timeout = Duration::from_secs(600);
+//! let (first, second) = tokio::join!(first.wait(timeout), second.wait(timeout));
+//! println!("Confirmed at heights {:?} and {:?}", first?.block_height, second?.block_height);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, oneshot, Notify};
+use tokio::task::JoinHandle;
+
+use crate::chain::{ChainBackend, ChainTransaction, DEFAULT_POLL_INTERVAL};
+use crate::client::{AmpError, ElementsRpc};
+use crate::model::ProgressUpdate;
+
+/// Delay before reconnecting to a ZMQ publisher after the connection fails
+const ZMQ_RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// ZMQ topic of new block hash notifications
+const HASHBLOCK_TOPIC: &[u8] = b"hashblock";
+
+/// Largest ZMQ frame accepted from the publisher
+///
+/// `hashblock` messages (topic, 32-byte hash, sequence number) and the handshake
+/// commands all fit in short frames; anything larger is rejected instead of allocated.
+const ZMQ_MAX_FRAME_SIZE: u64 = 255;
+
+/// A subscriber waiting for a transaction to reach its confirmation target
+#[derive(Debug)]
+struct Subscriber {
+    min_confirmations: u32,
+    progress_tx: Option<mpsc::UnboundedSender<ProgressUpdate>>,
+    result_tx: oneshot::Sender<ChainTransaction>,
+}
+
+/// Watch state of a single transaction
+#[derive(Debug, Default)]
+struct WatchedTransaction {
+    /// Confirmations seen at the last lookup, `None` before the first successful lookup
+    confirmations: Option<u32>,
+    block_height: Option<u64>,
+    subscribers: Vec<Subscriber>,
+}
+
+impl WatchedTransaction {
+    /// Sends `update` to every subscriber that asked for progress updates
+    fn broadcast(&self, update: &ProgressUpdate) {
+        for subscriber in &self.subscribers {
+            if let Some(progress_tx) = &subscriber.progress_tx {
+                let _ = progress_tx.send(update.clone());
+            }
+        }
+    }
+
+    /// Applies the result of a lookup, notifying and resolving subscribers
+    fn apply(&mut self, txid: &str, lookup: Option<ChainTransaction>) {
+        let confirmations = lookup.as_ref().map_or(0, |tx| tx.confirmations);
+        let block_height = lookup.as_ref().and_then(|tx| tx.block_height);
+
+        if let Some(previous) = self.confirmations {
+            let reorged_out = previous > 0 && confirmations < previous;
+            let moved = previous > 0
+                && confirmations > 0
+                && self.block_height.is_some()
+                && block_height != self.block_height;
+            if reorged_out || moved {
+                tracing::warn!(
+                    "Transaction {} lost confirmations in a reorg ({} -> {})",
+                    txid,
+                    previous,
+                    confirmations
+                );
+                self.broadcast(&ProgressUpdate::Reorg {
+                    txid: txid.to_string(),
+                    previous_confirmations: previous,
+                    current: confirmations,
+                });
+            }
+        }
+
+        // A transaction that is not visible yet is only reported once it appears
+        let Some(transaction) = lookup else {
+            if self.confirmations.is_some() {
+                self.confirmations = Some(0);
+                self.block_height = None;
+            }
+            return;
+        };
+
+        if self.confirmations != Some(confirmations) {
+            for subscriber in &self.subscribers {
+                if let Some(progress_tx) = &subscriber.progress_tx {
+                    let _ = progress_tx.send(ProgressUpdate::Confirmation {
+                        current: confirmations,
+                        required: subscriber.min_confirmations,
+                        txid: txid.to_string(),
+                    });
+                }
+            }
+        }
+        self.confirmations = Some(confirmations);
+        self.block_height = block_height;
+
+        let (resolved, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.subscribers)
+            .into_iter()
+            .partition(|subscriber| confirmations >= subscriber.min_confirmations);
+        self.subscribers = pending;
+        for subscriber in resolved {
+            let _ = subscriber.result_tx.send(transaction.clone());
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Shared {
+    backend: Arc<dyn ChainBackend>,
+    watched: Mutex<HashMap<String, WatchedTransaction>>,
+    /// Wakes the watcher for a new block or a new subscription
+    wake: Notify,
+}
+
+impl Shared {
+    fn watched(&self) -> std::sync::MutexGuard<'_, HashMap<String, WatchedTransaction>> {
+        self.watched
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Looks up every watched transaction once and updates its subscribers
+    async fn check_all(&self) {
+        let txids: Vec<String> = {
+            let mut watched = self.watched();
+            // Forget transactions whose subscribers have all gone away
+            watched.retain(|_, entry| {
+                entry
+                    .subscribers
+                    .retain(|subscriber| !subscriber.result_tx.is_closed());
+                !entry.subscribers.is_empty()
+            });
+            watched.keys().cloned().collect()
+        };
+
+        for txid in txids {
+            let lookup = match self.backend.get_transaction(&txid).await {
+                Ok(transaction) => Some(transaction),
+                Err(e) if e.is_not_found() => {
+                    tracing::debug!("Transaction {} is not visible: {}", txid, e);
+                    None
+                }
+                Err(e) => {
+                    // A failed lookup says nothing about the transaction; keep its state
+                    tracing::warn!("Failed to look up transaction {}: {}", txid, e);
+                    continue;
+                }
+            };
+            let mut watched = self.watched();
+            if let Some(entry) = watched.get_mut(&txid) {
+                entry.apply(&txid, lookup);
+                if entry.subscribers.is_empty() {
+                    watched.remove(&txid);
+                }
+            }
+        }
+    }
+}
+
+/// Aborts the watcher tasks when the last handle is dropped
+#[derive(Debug)]
+struct Tasks(Vec<JoinHandle<()>>);
+
+impl Drop for Tasks {
+    fn drop(&mut self) {
+        for task in &self.0 {
+            task.abort();
+        }
+    }
+}
+
+/// Follows the chain tip once and resolves many pending transactions
+///
+/// Create the watcher with [`new`](Self::new), configure it, then call
+/// [`start`](Self::start) from within a Tokio runtime. Clones share the same background
+/// task, which stops when the last clone is dropped.
+#[derive(Debug, Clone)]
+pub struct ConfirmationWatcher {
+    shared: Arc<Shared>,
+    poll_interval: Duration,
+    zmq_endpoint: Option<String>,
+    tasks: Option<Arc<Tasks>>,
+}
+
+impl ConfirmationWatcher {
+    /// Creates a watcher that polls `backend` for new blocks
+    #[must_use]
+    pub fn new(backend: Arc<dyn ChainBackend>) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                backend,
+                watched: Mutex::new(HashMap::new()),
+                wake: Notify::new(),
+            }),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            zmq_endpoint: None,
+            tasks: None,
+        }
+    }
+
+    /// Sets how often the chain tip is polled
+    #[must_use]
+    pub const fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Subscribes to the node's ZMQ `hashblock` publisher at `endpoint`
+    ///
+    /// `endpoint` is the value given to `-zmqpubhashblock`, such as
+    /// `tcp://127.0.0.1:28332`. Only `tcp://` endpoints are supported. The connection is
+    /// re-established if it drops.
+    #[must_use]
+    pub fn with_zmq_hashblock(mut self, endpoint: impl Into<String>) -> Self {
+        self.zmq_endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// Starts following the chain tip in the background
+    ///
+    /// # Panics
+    /// Panics if called outside of a Tokio runtime
+    #[must_use]
+    pub fn start(mut self) -> Self {
+        if self.tasks.is_some() {
+            return self;
+        }
+
+        let mut tasks = Vec::new();
+        let shared = Arc::clone(&self.shared);
+        let poll_interval = self.poll_interval;
+        tasks.push(tokio::spawn(async move {
+            follow_tip(shared, poll_interval).await;
+        }));
+
+        if let Some(endpoint) = self.zmq_endpoint.clone() {
+            let shared = Arc::clone(&self.shared);
+            tasks.push(tokio::spawn(async move {
+                loop {
+                    if let Err(e) = subscribe_hashblock(&endpoint, &shared.wake).await {
+                        tracing::warn!(
+                            "ZMQ hashblock subscription to {} failed: {}. Reconnecting in {}s",
+                            endpoint,
+                            e,
+                            ZMQ_RECONNECT_DELAY.as_secs()
+                        );
+                    }
+                    tokio::time::sleep(ZMQ_RECONNECT_DELAY).await;
+                }
+            }));
+        }
+
+        self.tasks = Some(Arc::new(Tasks(tasks)));
+        self
+    }
+
+    /// Watches `txid` until it has `min_confirmations` confirmations
+    ///
+    /// Progress updates are sent to `progress_tx` when given. Several subscriptions may
+    /// watch the same transaction with different targets.
+    #[must_use]
+    pub fn watch(
+        &self,
+        txid: &str,
+        min_confirmations: u32,
+        progress_tx: Option<mpsc::UnboundedSender<ProgressUpdate>>,
+    ) -> ConfirmationSubscription {
+        let (result_tx, result_rx) = oneshot::channel();
+        self.shared
+            .watched()
+            .entry(txid.to_string())
+            .or_default()
+            .subscribers
+            .push(Subscriber {
+                min_confirmations,
+                progress_tx,
+                result_tx,
+            });
+        self.shared.wake.notify_one();
+
+        ConfirmationSubscription {
+            txid: txid.to_string(),
+            min_confirmations,
+            result_rx,
+        }
+    }
+
+    /// Returns the number of transactions currently being watched
+    #[must_use]
+    pub fn pending(&self) -> usize {
+        self.shared.watched().len()
+    }
+}
+
+/// A pending confirmation created by [`ConfirmationWatcher::watch`]
+#[derive(Debug)]
+pub struct ConfirmationSubscription {
+    txid: String,
+    min_confirmations: u32,
+    result_rx: oneshot::Receiver<ChainTransaction>,
+}
+
+impl ConfirmationSubscription {
+    /// Returns the watched transaction ID
+    #[must_use]
+    pub fn txid(&self) -> &str {
+        &self.txid
+    }
+
+    /// Waits until the transaction reaches its confirmation target
+    ///
+    /// Dropping the subscription, or a timed out wait, stops watching the transaction
+    /// for this subscriber.
+    ///
+    /// # Errors
+    /// Returns `AmpError::Timeout` if the target is not reached within `timeout`, or an
+    /// RPC error if the watcher stopped
+    pub async fn wait(self, timeout: Duration) -> Result<ChainTransaction, AmpError> {
+        match tokio::time::timeout(timeout, self.result_rx).await {
+            Ok(Ok(transaction)) => Ok(transaction),
+            Ok(Err(_)) => Err(AmpError::rpc(format!(
+                "Confirmation watcher stopped before transaction {} was confirmed",
+                self.txid
+            ))),
+            Err(_) => Err(AmpError::timeout(format!(
+                "Transaction {} did not reach {} confirmations within {}s",
+                self.txid,
+                self.min_confirmations,
+                timeout.as_secs()
+            ))),
+        }
+    }
+}
+
+/// Checks the watched transactions whenever the tip moves or a subscription is added
+async fn follow_tip(shared: Arc<Shared>, poll_interval: Duration) {
+    let mut last_tip = None;
+    loop {
+        let woken = tokio::select! {
+            () = shared.wake.notified() => true,
+            () = tokio::time::sleep(poll_interval) => false,
+        };
+
+        match shared.backend.tip_height().await {
+            Ok(tip) => {
+                if woken || last_tip != Some(tip) {
+                    if last_tip != Some(tip) {
+                        tracing::debug!("Chain tip moved to height {}", tip);
+                    }
+                    last_tip = Some(tip);
+                    shared.check_all().await;
+                }
+            }
+            Err(e) => tracing::warn!("Failed to fetch chain tip: {}", e),
+        }
+    }
+}
+
+/// Connects to a ZMQ publisher and wakes `wake` for every `hashblock` message
+///
+/// Implements the subset of ZMTP 3.0 needed by a SUB socket with the NULL security
+/// mechanism, which is what Elements and Bitcoin Core publish with.
+async fn subscribe_hashblock(endpoint: &str, wake: &Notify) -> std::io::Result<()> {
+    let address = endpoint.strip_prefix("tcp://").ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("unsupported ZMQ endpoint '{endpoint}', expected tcp://host:port"),
+        )
+    })?;
+    let mut stream = TcpStream::connect(address).await?;
+
+    // Greeting: signature, version 3.0, NULL mechanism, as-server = 0
+    let mut greeting = [0u8; 64];
+    greeting[0] = 0xff;
+    greeting[9] = 0x7f;
+    greeting[10] = 3;
+    greeting[12..16].copy_from_slice(b"NULL");
+    stream.write_all(&greeting).await?;
+    let mut peer_greeting = [0u8; 64];
+    stream.read_exact(&mut peer_greeting).await?;
+    if peer_greeting[0] != 0xff || peer_greeting[9] != 0x7f || peer_greeting[10] < 3 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "peer is not a ZMTP 3 endpoint",
+        ));
+    }
+
+    // READY command advertising a SUB socket
+    let mut ready = Vec::new();
+    ready.push(5);
+    ready.extend_from_slice(b"READY");
+    ready.push(11);
+    ready.extend_from_slice(b"Socket-Type");
+    ready.extend_from_slice(&3u32.to_be_bytes());
+    ready.extend_from_slice(b"SUB");
+    write_frame(&mut stream, 0x04, &ready).await?;
+
+    // Subscription message: 0x01 followed by the topic
+    let mut subscribe = vec![1];
+    subscribe.extend_from_slice(HASHBLOCK_TOPIC);
+    write_frame(&mut stream, 0x00, &subscribe).await?;
+    tracing::info!("Subscribed to ZMQ hashblock notifications at {}", endpoint);
+
+    let mut message: Vec<Vec<u8>> = Vec::new();
+    loop {
+        let (flags, body) = read_frame(&mut stream).await?;
+        if flags & 0x04 != 0 {
+            // Commands (READY, PING) carry no notifications
+            continue;
+        }
+        message.push(body);
+        if flags & 0x01 != 0 {
+            continue;
+        }
+        if message.first().map(Vec::as_slice) == Some(HASHBLOCK_TOPIC) {
+            if let Some(hash) = message.get(1) {
+                let mut hash = hash.clone();
+                hash.reverse();
+                tracing::debug!("ZMQ hashblock {}", hex::encode(hash));
+            }
+            wake.notify_one();
+        }
+        message.clear();
+    }
+}
+
+async fn write_frame(stream: &mut TcpStream, flags: u8, body: &[u8]) -> std::io::Result<()> {
+    match u8::try_from(body.len()) {
+        Ok(size) => stream.write_all(&[flags, size]).await?,
+        Err(_) => {
+            stream.write_all(&[flags | 0x02]).await?;
+            stream.write_all(&(body.len() as u64).to_be_bytes()).await?;
+        }
+    }
+    stream.write_all(body).await
+}
+
+async fn read_frame(stream: &mut TcpStream) -> std::io::Result<(u8, Vec<u8>)> {
+    let flags = stream.read_u8().await?;
+    let size = if flags & 0x02 == 0 {
+        u64::from(stream.read_u8().await?)
+    } else {
+        stream.read_u64().await?
+    };
+    if size > ZMQ_MAX_FRAME_SIZE {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("ZMQ frame of {size} bytes exceeds {ZMQ_MAX_FRAME_SIZE} bytes"),
+        ));
+    }
+    let mut body = vec![0; usize::try_from(size).unwrap_or_default()];
+    stream.read_exact(&mut body).await?;
+    Ok((flags, body))
+}
+
+/// Default depth after which a confirmed transaction is considered final
+pub const DEFAULT_FINALITY_DEPTH: u32 = 6;
+
+/// Default time a [`ReorgMonitor`] follows a transaction before giving up
+pub const DEFAULT_REORG_MONITOR_TIMEOUT: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// The AMP operation a monitored transaction was confirmed for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ConfirmedOperation {
+    Distribution {
+        asset_uuid: String,
+        distribution_uuid: String,
+    },
+    Reissuance {
+        asset_uuid: String,
+    },
+    Burn {
+        asset_uuid: String,
+    },
+}
+
+/// A transaction that was confirmed with the AMP API was affected by a reorg
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReorgEvent {
+    pub operation: ConfirmedOperation,
+    pub txid: String,
+    /// Block the transaction was in when it was last seen confirmed
+    pub previous_blockhash: String,
+    /// Block the transaction is in now, `None` if it is no longer in the chain
+    pub current_blockhash: Option<String>,
+    /// Confirmations after the reorg
+    pub confirmations: u32,
+    /// Whether the transaction was re-broadcast successfully
+    pub rebroadcast: bool,
+}
+
+/// Rechecks confirmed transactions until they are buried deep enough to be final
+#[derive(Debug, Clone)]
+pub struct ReorgMonitor {
+    depth: u32,
+    poll_interval: Duration,
+    timeout: Duration,
+    rebroadcast: bool,
+    events_tx: Option<mpsc::UnboundedSender<ReorgEvent>>,
+}
+
+impl Default for ReorgMonitor {
+    fn default() -> Self {
+        Self::new(DEFAULT_FINALITY_DEPTH)
+    }
+}
+
+impl ReorgMonitor {
+    /// Creates a monitor that follows transactions until they have `depth` confirmations
+    #[must_use]
+    pub const fn new(depth: u32) -> Self {
+        Self {
+            depth,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            timeout: DEFAULT_REORG_MONITOR_TIMEOUT,
+            rebroadcast: false,
+            events_tx: None,
+        }
+    }
+
+    /// Sets how often the transaction is rechecked
+    #[must_use]
+    pub const fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Sets how long a transaction is followed before monitoring gives up
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Re-broadcasts transactions that a reorg removed from the chain
+    #[must_use]
+    pub const fn with_rebroadcast(mut self, rebroadcast: bool) -> Self {
+        self.rebroadcast = rebroadcast;
+        self
+    }
+
+    /// Sends every [`ReorgEvent`] to `events_tx`
+    #[must_use]
+    pub fn with_events(mut self, events_tx: mpsc::UnboundedSender<ReorgEvent>) -> Self {
+        self.events_tx = Some(events_tx);
+        self
+    }
+
+    /// Returns the depth at which a transaction is considered final
+    #[must_use]
+    pub const fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// Follows `txid` through the node wallet until it is `depth` blocks deep
+    ///
+    /// `confirmed_blockhash` is the block the transaction was in when it was confirmed
+    /// with the AMP API. Each change of block is reported as a [`ReorgEvent`]; when the
+    /// transaction has left the chain and re-broadcasting is enabled, it is sent through
+    /// `broadcaster`. Returns the events observed before the transaction became final.
+    ///
+    /// # Errors
+    /// Returns `AmpError::Timeout` if the transaction does not reach the depth within
+    /// the monitor's timeout
+    pub async fn monitor(
+        &self,
+        node_rpc: &ElementsRpc,
+        broadcaster: &dyn ChainBackend,
+        wallet_name: &str,
+        txid: &str,
+        confirmed_blockhash: Option<String>,
+        operation: ConfirmedOperation,
+    ) -> Result<Vec<ReorgEvent>, AmpError> {
+        let start_time = std::time::Instant::now();
+        let mut last_blockhash = confirmed_blockhash;
+        let mut events = Vec::new();
+
+        loop {
+            match node_rpc
+                .get_transaction_from_wallet(wallet_name, txid)
+                .await
+            {
+                Ok(detail) => {
+                    if let Some(previous) = &last_blockhash {
+                        if detail.blockhash.as_ref() != Some(previous) {
+                            let rebroadcast = if self.rebroadcast && detail.blockhash.is_none() {
+                                match broadcaster.broadcast(&detail.hex).await {
+                                    Ok(_) => true,
+                                    Err(e) => {
+                                        tracing::warn!(
+                                            "Re-broadcast of reorged transaction {} failed: {}",
+                                            txid,
+                                            e
+                                        );
+                                        false
+                                    }
+                                }
+                            } else {
+                                false
+                            };
+                            let event = ReorgEvent {
+                                operation: operation.clone(),
+                                txid: txid.to_string(),
+                                previous_blockhash: previous.clone(),
+                                current_blockhash: detail.blockhash.clone(),
+                                confirmations: detail.confirmations,
+                                rebroadcast,
+                            };
+                            tracing::warn!(
+                                "Reorg affected confirmed transaction {}: block {} -> {:?}",
+                                txid,
+                                event.previous_blockhash,
+                                event.current_blockhash
+                            );
+                            if let Some(events_tx) = &self.events_tx {
+                                let _ = events_tx.send(event.clone());
+                            }
+                            events.push(event);
+                        }
+                    }
+                    last_blockhash.clone_from(&detail.blockhash);
+
+                    if detail.blockhash.is_some() && detail.confirmations >= self.depth {
+                        tracing::info!(
+                            "Transaction {} is final with {} confirmations",
+                            txid,
+                            detail.confirmations
+                        );
+                        return Ok(events);
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to recheck transaction {}: {}", txid, e);
+                }
+            }
+
+            if start_time.elapsed() + self.poll_interval > self.timeout {
+                return Err(AmpError::timeout(format!(
+                    "Transaction {txid} did not reach finality depth {} within {}s",
+                    self.depth,
+                    self.timeout.as_secs()
+                )));
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Unspent;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+    /// Chain with a settable tip where each transaction confirms at a fixed height
+    #[derive(Debug, Default)]
+    struct TestChain {
+        tip: AtomicU64,
+        heights: Mutex<HashMap<String, u64>>,
+        /// Fails every lookup as if the backend were unreachable
+        unavailable: AtomicBool,
+    }
+
+    impl TestChain {
+        fn confirm(&self, txid: &str, height: u64) {
+            self.heights
+                .lock()
+                .unwrap()
+                .insert(txid.to_string(), height);
+        }
+
+        fn remove(&self, txid: &str) {
+            self.heights.lock().unwrap().remove(txid);
+        }
+    }
+
+    #[async_trait]
+    impl ChainBackend for TestChain {
+        async fn tip_height(&self) -> Result<u64, AmpError> {
+            Ok(self.tip.load(Ordering::SeqCst))
+        }
+
+        async fn list_unspent(&self, _addresses: &[String]) -> Result<Vec<Unspent>, AmpError> {
+            Ok(Vec::new())
+        }
+
+        async fn get_transaction(&self, txid: &str) -> Result<ChainTransaction, AmpError> {
+            if self.unavailable.load(Ordering::SeqCst) {
+                return Err(AmpError::rpc("connection refused"));
+            }
+            let tip = self.tip.load(Ordering::SeqCst);
+            let height = self
+                .heights
+                .lock()
+                .unwrap()
+                .get(txid)
+                .copied()
+                .ok_or_else(|| AmpError::NotFound(format!("transaction {txid}")))?;
+            Ok(ChainTransaction {
+                txid: txid.to_string(),
+                hex: String::new(),
+                confirmations: u32::try_from(tip.saturating_sub(height) + 1).unwrap(),
+                block_height: Some(height),
+            })
+        }
+
+        async fn broadcast(&self, _tx_hex: &str) -> Result<String, AmpError> {
+            Err(AmpError::rpc("TestChain does not broadcast"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_watcher_resolves_many_transactions_and_reports_reorgs() {
+        let chain = Arc::new(TestChain::default());
+        chain.tip.store(100, Ordering::SeqCst);
+        chain.confirm("a", 100);
+        chain.confirm("b", 100);
+
+        let watcher = ConfirmationWatcher::new(chain.clone())
+            .with_poll_interval(Duration::from_millis(10))
+            .start();
+        let (progress_tx, mut progress_rx) = mpsc::unbounded_channel();
+        let first = watcher.watch("a", 2, Some(progress_tx));
+        let second = watcher.watch("b", 1, None);
+        assert_eq!(watcher.pending(), 2);
+
+        let second = second.wait(Duration::from_secs(5)).await.unwrap();
+        assert_eq!(second.confirmations, 1);
+
+        // "a" is reorged out before reaching its target, then confirms again
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        chain.remove("a");
+        chain.tip.store(101, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        chain.confirm("a", 101);
+        chain.tip.store(102, Ordering::SeqCst);
+
+        let first = first.wait(Duration::from_secs(5)).await.unwrap();
+        assert_eq!(first.block_height, Some(101));
+        assert_eq!(watcher.pending(), 0);
+
+        let mut updates = Vec::new();
+        while let Ok(update) = progress_rx.try_recv() {
+            updates.push(update);
+        }
+        assert!(matches!(
+            updates.first(),
+            Some(ProgressUpdate::Confirmation {
+                current: 1,
+                required: 2,
+                ..
+            })
+        ));
+        assert!(updates.iter().any(|update| matches!(
+            update,
+            ProgressUpdate::Reorg {
+                previous_confirmations: 1,
+                current: 0,
+                ..
+            }
+        )));
+        assert!(matches!(
+            updates.last(),
+            Some(ProgressUpdate::Confirmation { current: 2, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_watcher_ignores_failed_lookups() {
+        let chain = Arc::new(TestChain::default());
+        chain.tip.store(100, Ordering::SeqCst);
+        chain.confirm("d", 100);
+
+        let watcher = ConfirmationWatcher::new(chain.clone())
+            .with_poll_interval(Duration::from_millis(10))
+            .start();
+        let (progress_tx, mut progress_rx) = mpsc::unbounded_channel();
+        let subscription = watcher.watch("d", 3, Some(progress_tx));
+        assert!(matches!(
+            progress_rx.recv().await,
+            Some(ProgressUpdate::Confirmation { current: 1, .. })
+        ));
+
+        // The backend is unreachable while a block arrives
+        chain.unavailable.store(true, Ordering::SeqCst);
+        chain.tip.store(101, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        chain.unavailable.store(false, Ordering::SeqCst);
+        chain.tip.store(102, Ordering::SeqCst);
+
+        let transaction = subscription.wait(Duration::from_secs(5)).await.unwrap();
+        assert_eq!(transaction.confirmations, 3);
+        while let Ok(update) = progress_rx.try_recv() {
+            assert!(!matches!(update, ProgressUpdate::Reorg { .. }));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_frame_rejects_oversized_frames() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            // Long frame announcing an exabyte body
+            stream.write_all(&[0x02]).await.unwrap();
+            stream.write_all(&u64::MAX.to_be_bytes()).await.unwrap();
+        });
+
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        let err = read_frame(&mut stream).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    /// Mocks the wallet's view of `txid` until the returned mock is deleted
+    fn mock_wallet_transaction<'a>(
+        server: &'a httpmock::MockServer,
+        blockhash: Option<&str>,
+        confirmations: i64,
+    ) -> httpmock::Mock<'a> {
+        use httpmock::prelude::*;
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/wallet/wallet")
+                .json_body_partial(r#"{"method": "gettransaction"}"#);
+            then.status(200).json_body(serde_json::json!({
+                "result": {
+                    "txid": "tx",
+                    "confirmations": confirmations,
+                    "blockhash": blockhash,
+                    "hex": "0200",
+                    "details": []
+                },
+                "error": null,
+                "id": "amp-client"
+            }));
+        })
+    }
+
+    #[tokio::test]
+    async fn test_reorg_monitor_reports_block_changes_until_final() {
+        use httpmock::prelude::*;
+
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .json_body_partial(r#"{"method": "loadwallet"}"#);
+            then.status(200).json_body(serde_json::json!({
+                "result": {"name": "wallet", "warning": ""},
+                "error": null,
+                "id": "amp-client"
+            }));
+        });
+        let rebroadcast = server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .json_body_partial(r#"{"method": "sendrawtransaction"}"#);
+            then.status(200).json_body(serde_json::json!({
+                "result": "tx",
+                "error": null,
+                "id": "amp-client"
+            }));
+        });
+        let node_rpc = ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string());
+
+        let (events_tx, mut events_rx) = mpsc::unbounded_channel();
+        let monitor = ReorgMonitor::new(3)
+            .with_poll_interval(Duration::from_millis(10))
+            .with_rebroadcast(true)
+            .with_events(events_tx);
+        let operation = ConfirmedOperation::Burn {
+            asset_uuid: "asset".to_string(),
+        };
+
+        // The transaction is evicted from its block, then mined again in another one
+        let mut evicted = mock_wallet_transaction(&server, None, 0);
+        let task = {
+            let node_rpc = node_rpc.clone();
+            let monitor = monitor.clone();
+            let operation = operation.clone();
+            tokio::spawn(async move {
+                monitor
+                    .monitor(
+                        &node_rpc,
+                        &node_rpc,
+                        "wallet",
+                        "tx",
+                        Some("aa".to_string()),
+                        operation,
+                    )
+                    .await
+            })
+        };
+        let first = events_rx.recv().await.unwrap();
+        evicted.delete();
+        mock_wallet_transaction(&server, Some("bb"), 3);
+
+        let events = task.await.unwrap().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0], first);
+        assert_eq!(first.operation, operation);
+        assert_eq!(first.previous_blockhash, "aa");
+        assert_eq!(first.current_blockhash, None);
+        assert!(first.rebroadcast);
+        rebroadcast.assert_hits(1);
+
+        // A transaction still in its block is final without events
+        let events = monitor
+            .monitor(
+                &node_rpc,
+                &node_rpc,
+                "wallet",
+                "tx",
+                Some("bb".to_string()),
+                operation,
+            )
+            .await
+            .unwrap();
+        assert!(events.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reorg_monitor_reports_conflicted_transactions() {
+        use httpmock::prelude::*;
+
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .json_body_partial(r#"{"method": "loadwallet"}"#);
+            then.status(200).json_body(serde_json::json!({
+                "result": {"name": "wallet", "warning": ""},
+                "error": null,
+                "id": "amp-client"
+            }));
+        });
+        let node_rpc = ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string());
+        let operation = ConfirmedOperation::Reissuance {
+            asset_uuid: "asset".to_string(),
+        };
+
+        // After the reorg a conflicting transaction was mined instead
+        mock_wallet_transaction(&server, None, -1);
+        let (events_tx, mut events_rx) = mpsc::unbounded_channel();
+        let monitor = ReorgMonitor::new(3)
+            .with_poll_interval(Duration::from_millis(10))
+            .with_timeout(Duration::from_millis(100))
+            .with_events(events_tx);
+        let result = monitor
+            .monitor(
+                &node_rpc,
+                &node_rpc,
+                "wallet",
+                "tx",
+                Some("aa".to_string()),
+                operation.clone(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(AmpError::Timeout(_))));
+        let event = events_rx.try_recv().unwrap();
+        assert_eq!(event.operation, operation);
+        assert_eq!(event.previous_blockhash, "aa");
+        assert_eq!(event.current_blockhash, None);
+        assert_eq!(event.confirmations, 0);
+        assert!(!event.rebroadcast);
+        assert!(events_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_watcher_wakes_on_zmq_hashblock() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("tcp://{}", listener.local_addr().unwrap());
+        let (block_tx, mut block_rx) = mpsc::unbounded_channel::<()>();
+
+        // Minimal ZMTP publisher that sends a hashblock message per request
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut greeting = [0u8; 64];
+            stream.read_exact(&mut greeting).await.unwrap();
+            assert_eq!(&greeting[12..16], b"NULL");
+            let mut reply = [0u8; 64];
+            reply[0] = 0xff;
+            reply[9] = 0x7f;
+            reply[10] = 3;
+            reply[12..16].copy_from_slice(b"NULL");
+            stream.write_all(&reply).await.unwrap();
+            let (flags, ready) = read_frame(&mut stream).await.unwrap();
+            assert_eq!(flags, 0x04);
+            assert!(ready.ends_with(b"SUB"));
+            write_frame(&mut stream, 0x04, b"\x05READY").await.unwrap();
+            let (_, subscription) = read_frame(&mut stream).await.unwrap();
+            assert_eq!(subscription, b"\x01hashblock");
+
+            while block_rx.recv().await.is_some() {
+                write_frame(&mut stream, 0x01, HASHBLOCK_TOPIC)
+                    .await
+                    .unwrap();
+                write_frame(&mut stream, 0x01, &[0xab; 32]).await.unwrap();
+                write_frame(&mut stream, 0x00, &[0; 4]).await.unwrap();
+            }
+        });
+
+        let chain = Arc::new(TestChain::default());
+        chain.tip.store(10, Ordering::SeqCst);
+        // Polling alone would not notice the new block within the test
+        let watcher = ConfirmationWatcher::new(chain.clone())
+            .with_poll_interval(Duration::from_secs(3600))
+            .with_zmq_hashblock(endpoint)
+            .start();
+        let subscription = watcher.watch("c", 1, None);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        chain.confirm("c", 11);
+        chain.tip.store(11, Ordering::SeqCst);
+        block_tx.send(()).unwrap();
+
+        let transaction = subscription.wait(Duration::from_secs(5)).await.unwrap();
+        assert_eq!(transaction.txid, "c");
+    }
+}
@@ -0,0 +1,574 @@
+//! # Resumable Distributions
+//!
+//! An AMP distribution spans two systems: the distribution is registered with the AMP
+//! API, paid on chain, and finally confirmed with the AMP API. A process that dies in
+//! between leaves the distribution unconfirmed. To make the workflow restartable,
+//! [`ApiClient::distribute_asset`](crate::ApiClient::distribute_asset) records a
+//! [`DistributionCheckpoint`] after every completed step and
+//! [`ApiClient::resume_distribution`](crate::ApiClient::resume_distribution) continues
+//! from the last one.
+//!
+//! The workflow moves through these states, in order:
+//!
+//! ```text
+//! Created -> TxBuilt -> Signed -> Broadcast -> Confirmed
+//! ```
+//!
+//! With [`DistributionFunding::NodeWallet`], the default, the node wallet builds, signs
+//! and sends the transaction in a single `sendmany` call. The checkpoint moves from
+//! `Created` to `Sending` before that call, and then to `Signed`, recording the
+//! transaction the node sent, and to `Broadcast`. A distribution resumed in `Sending`
+//! looks for its transaction in the node wallet and only sends again if none is found.
+//!
+//! Checkpoints are persisted through a [`CheckpointStore`]:
+//! - [`InMemoryCheckpointStore`] - keeps checkpoints in process memory only (the default)
+//! - [`FileCheckpointStore`] - one JSON file per distribution in a directory
+//!
+//! Only a persistent store lets a distribution be resumed after the process restarts.
+//! With the default in-memory store, checkpoints are lost together with the process.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use amp_rs::{ApiClient, ElementsRpc, LwkSoftwareSigner};
+//! # use amp_rs::distribution::FileCheckpointStore;
+//! # use std::sync::Arc;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = ApiClient::builder()
+//!     .checkpoint_store(Arc::new(FileCheckpointStore::new("/var/lib/my-service/distributions")))
+//!     .build()
+//!     .await?;
+//!
+//! // After a crash, pick up an unfinished distribution where it stopped
+//! let node_rpc = ElementsRpc::from_env()?;
+//! let (_, signer) = LwkSoftwareSigner::generate_new()?;
+//! let txid = client
+//!     .resume_distribution("distribution-uuid", &node_rpc, &signer)
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use crate::amount::Amount;
+use crate::model::DistributionTransaction;
+
+/// Errors produced by checkpoint stores and state transitions
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum CheckpointError {
+    #[error("Checkpoint storage failed: {0}")]
+    Storage(String),
+    #[error("Checkpoint serialization failed: {0}")]
+    Serialization(String),
+    #[error("Invalid distribution uuid for checkpoint: '{0}'")]
+    InvalidDistributionUuid(String),
+    #[error("Invalid distribution state transition from {from} to {to}")]
+    InvalidTransition {
+        from: &'static str,
+        to: &'static str,
+    },
+}
+
+/// How a distribution transaction is funded and signed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DistributionFunding {
+    /// The node wallet selects the inputs, pays the fee, signs and sends the transaction
+    /// with `sendmany`
+    #[default]
+    NodeWallet,
+    /// The transaction is built from the wallet's UTXOs, signed by the workflow's
+    /// [`Signer`](crate::signer::Signer) and broadcast separately
+    Signer,
+}
+
+/// The last completed step of a distribution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum DistributionState {
+    /// The distribution is registered with the AMP API
+    Created,
+    /// The node wallet was asked to send the transaction with `sendmany`
+    Sending { started_at: DateTime<Utc> },
+    /// The unsigned distribution transaction is built
+    TxBuilt {
+        transaction: DistributionTransaction,
+    },
+    /// The transaction is signed but may not have reached the network
+    Signed { signed_tx: String, txid: String },
+    /// The transaction was accepted by the node
+    Broadcast { txid: String },
+    /// The distribution is confirmed with the AMP API
+    Confirmed { txid: String },
+}
+
+impl DistributionState {
+    /// Returns the name of the state
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Sending { .. } => "sending",
+            Self::TxBuilt { .. } => "tx_built",
+            Self::Signed { .. } => "signed",
+            Self::Broadcast { .. } => "broadcast",
+            Self::Confirmed { .. } => "confirmed",
+        }
+    }
+
+    /// Returns the distribution transaction id once it is known
+    #[must_use]
+    pub fn txid(&self) -> Option<&str> {
+        match self {
+            Self::Created | Self::Sending { .. } | Self::TxBuilt { .. } => None,
+            Self::Signed { txid, .. } | Self::Broadcast { txid } | Self::Confirmed { txid } => {
+                Some(txid)
+            }
+        }
+    }
+
+    /// Returns `true` once the distribution is confirmed with the AMP API
+    #[must_use]
+    pub const fn is_complete(&self) -> bool {
+        matches!(self, Self::Confirmed { .. })
+    }
+
+    const fn position(&self) -> u8 {
+        match self {
+            Self::Created => 0,
+            Self::Sending { .. } | Self::TxBuilt { .. } => 1,
+            Self::Signed { .. } => 2,
+            Self::Broadcast { .. } => 3,
+            Self::Confirmed { .. } => 4,
+        }
+    }
+}
+
+impl fmt::Display for DistributionState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Persisted progress of a single distribution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DistributionCheckpoint {
+    pub distribution_uuid: String,
+    pub asset_uuid: String,
+    pub asset_id: String,
+    pub wallet_name: String,
+    pub map_address_amount: HashMap<String, Amount>,
+    pub state: DistributionState,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl DistributionCheckpoint {
+    /// Creates a checkpoint for a distribution that was just registered with the AMP API
+    #[must_use]
+    pub fn new(
+        distribution_uuid: String,
+        asset_uuid: String,
+        asset_id: String,
+        wallet_name: String,
+        map_address_amount: HashMap<String, Amount>,
+    ) -> Self {
+        Self {
+            distribution_uuid,
+            asset_uuid,
+            asset_id,
+            wallet_name,
+            map_address_amount,
+            state: DistributionState::Created,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Moves the checkpoint to the next state
+    ///
+    /// A `Sending` checkpoint may also skip ahead to `Broadcast`, for a transaction the
+    /// node wallet sent but whose details could not be read back.
+    ///
+    /// # Errors
+    /// Returns an error unless `next` directly follows the current state
+    pub fn advance(&mut self, next: DistributionState) -> Result<(), CheckpointError> {
+        let follows = match (&self.state, &next) {
+            (
+                DistributionState::Sending { .. },
+                DistributionState::Signed { .. } | DistributionState::Broadcast { .. },
+            )
+            | (DistributionState::Created, DistributionState::Sending { .. }) => true,
+            (DistributionState::Sending { .. }, _) | (_, DistributionState::Sending { .. }) => {
+                false
+            }
+            _ => next.position() == self.state.position() + 1,
+        };
+        if !follows {
+            return Err(CheckpointError::InvalidTransition {
+                from: self.state.name(),
+                to: next.name(),
+            });
+        }
+
+        self.state = next;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+}
+
+/// Storage backend for distribution checkpoints
+///
+/// Implementations must be thread-safe; a single store may be shared by several clients.
+#[async_trait]
+pub trait CheckpointStore: Send + Sync + fmt::Debug {
+    /// Loads the checkpoint of a distribution, returning `Ok(None)` if none is stored
+    async fn load(
+        &self,
+        distribution_uuid: &str,
+    ) -> Result<Option<DistributionCheckpoint>, CheckpointError>;
+
+    /// Persists a checkpoint, replacing any previous checkpoint of the same distribution
+    async fn save(&self, checkpoint: &DistributionCheckpoint) -> Result<(), CheckpointError>;
+
+    /// Removes the checkpoint of a distribution
+    async fn remove(&self, distribution_uuid: &str) -> Result<(), CheckpointError>;
+
+    /// Returns all stored checkpoints
+    async fn list(&self) -> Result<Vec<DistributionCheckpoint>, CheckpointError>;
+}
+
+/// Checkpoint store that keeps checkpoints in process memory only
+#[derive(Debug, Default)]
+pub struct InMemoryCheckpointStore {
+    checkpoints: Mutex<HashMap<String, DistributionCheckpoint>>,
+}
+
+impl InMemoryCheckpointStore {
+    /// Creates an empty in-memory store
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for InMemoryCheckpointStore {
+    async fn load(
+        &self,
+        distribution_uuid: &str,
+    ) -> Result<Option<DistributionCheckpoint>, CheckpointError> {
+        Ok(self
+            .checkpoints
+            .lock()
+            .await
+            .get(distribution_uuid)
+            .cloned())
+    }
+
+    async fn save(&self, checkpoint: &DistributionCheckpoint) -> Result<(), CheckpointError> {
+        self.checkpoints
+            .lock()
+            .await
+            .insert(checkpoint.distribution_uuid.clone(), checkpoint.clone());
+        Ok(())
+    }
+
+    async fn remove(&self, distribution_uuid: &str) -> Result<(), CheckpointError> {
+        self.checkpoints.lock().await.remove(distribution_uuid);
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<DistributionCheckpoint>, CheckpointError> {
+        Ok(self.checkpoints.lock().await.values().cloned().collect())
+    }
+}
+
+/// Checkpoint store that writes each checkpoint as `<distribution_uuid>.json` into a
+/// directory
+///
+/// Files are replaced atomically, so a crash while saving leaves the previous checkpoint
+/// intact.
+#[derive(Debug, Clone)]
+pub struct FileCheckpointStore {
+    dir: PathBuf,
+}
+
+impl FileCheckpointStore {
+    /// Creates a file store in the given directory, which is created on first save
+    #[must_use]
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Returns the checkpoint directory
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn checkpoint_path(&self, distribution_uuid: &str) -> Result<PathBuf, CheckpointError> {
+        // The uuid becomes a file name, so reject anything that could escape the directory
+        if distribution_uuid.is_empty()
+            || !distribution_uuid
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(CheckpointError::InvalidDistributionUuid(
+                distribution_uuid.to_string(),
+            ));
+        }
+
+        Ok(self.dir.join(format!("{distribution_uuid}.json")))
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for FileCheckpointStore {
+    async fn load(
+        &self,
+        distribution_uuid: &str,
+    ) -> Result<Option<DistributionCheckpoint>, CheckpointError> {
+        let path = self.checkpoint_path(distribution_uuid)?;
+        match tokio::fs::read_to_string(&path).await {
+            Ok(content) => serde_json::from_str(&content).map(Some).map_err(|e| {
+                CheckpointError::Serialization(format!(
+                    "Failed to parse checkpoint {}: {e}",
+                    path.display()
+                ))
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(CheckpointError::Storage(format!(
+                "Failed to read checkpoint {}: {e}",
+                path.display()
+            ))),
+        }
+    }
+
+    async fn save(&self, checkpoint: &DistributionCheckpoint) -> Result<(), CheckpointError> {
+        let path = self.checkpoint_path(&checkpoint.distribution_uuid)?;
+        let json = serde_json::to_string_pretty(checkpoint).map_err(|e| {
+            CheckpointError::Serialization(format!("Failed to serialize checkpoint: {e}"))
+        })?;
+
+        tokio::fs::create_dir_all(&self.dir).await.map_err(|e| {
+            CheckpointError::Storage(format!(
+                "Failed to create checkpoint directory {}: {e}",
+                self.dir.display()
+            ))
+        })?;
+
+        let tmp_path = path.with_extension("json.tmp");
+        tokio::fs::write(&tmp_path, json).await.map_err(|e| {
+            CheckpointError::Storage(format!(
+                "Failed to write checkpoint {}: {e}",
+                tmp_path.display()
+            ))
+        })?;
+        tokio::fs::rename(&tmp_path, &path).await.map_err(|e| {
+            CheckpointError::Storage(format!(
+                "Failed to replace checkpoint {}: {e}",
+                path.display()
+            ))
+        })?;
+
+        tracing::debug!(
+            "Saved distribution checkpoint {} ({})",
+            checkpoint.distribution_uuid,
+            checkpoint.state
+        );
+        Ok(())
+    }
+
+    async fn remove(&self, distribution_uuid: &str) -> Result<(), CheckpointError> {
+        let path = self.checkpoint_path(distribution_uuid)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(CheckpointError::Storage(format!(
+                "Failed to remove checkpoint {}: {e}",
+                path.display()
+            ))),
+        }
+    }
+
+    async fn list(&self) -> Result<Vec<DistributionCheckpoint>, CheckpointError> {
+        let mut entries = match tokio::fs::read_dir(&self.dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(CheckpointError::Storage(format!(
+                    "Failed to read checkpoint directory {}: {e}",
+                    self.dir.display()
+                )))
+            }
+        };
+
+        let mut checkpoints = Vec::new();
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| CheckpointError::Storage(format!("Failed to list checkpoints: {e}")))?
+        {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                if let Some(uuid) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    if let Some(checkpoint) = self.load(uuid).await? {
+                        checkpoints.push(checkpoint);
+                    }
+                }
+            }
+        }
+
+        Ok(checkpoints)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkpoint(distribution_uuid: &str) -> DistributionCheckpoint {
+        let mut map_address_amount = HashMap::new();
+        map_address_amount.insert("address1".to_string(), Amount::from_sat(150_000));
+        DistributionCheckpoint::new(
+            distribution_uuid.to_string(),
+            "asset-uuid".to_string(),
+            "asset-id".to_string(),
+            "wallet".to_string(),
+            map_address_amount,
+        )
+    }
+
+    fn tx_built() -> DistributionState {
+        DistributionState::TxBuilt {
+            transaction: DistributionTransaction {
+                raw_transaction: "00".to_string(),
+                asset_inputs: Vec::new(),
+                fee_inputs: Vec::new(),
+                asset_change: Amount::ZERO,
+                fee_change: Amount::ZERO,
+                fee: Amount::from_sat(300),
+                fee_rate: crate::fee::FeeRate::MIN_RELAY,
+                vsize: 2_900,
+                pset: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_advances_in_order() {
+        let mut checkpoint = checkpoint("dist-1");
+        assert_eq!(
+            checkpoint
+                .advance(DistributionState::Confirmed {
+                    txid: "txid".to_string()
+                })
+                .unwrap_err(),
+            CheckpointError::InvalidTransition {
+                from: "created",
+                to: "confirmed"
+            }
+        );
+
+        checkpoint.advance(tx_built()).unwrap();
+        checkpoint
+            .advance(DistributionState::Signed {
+                signed_tx: "00".to_string(),
+                txid: "txid".to_string(),
+            })
+            .unwrap();
+        assert_eq!(checkpoint.state.txid(), Some("txid"));
+        checkpoint
+            .advance(DistributionState::Broadcast {
+                txid: "txid".to_string(),
+            })
+            .unwrap();
+        checkpoint
+            .advance(DistributionState::Confirmed {
+                txid: "txid".to_string(),
+            })
+            .unwrap();
+        assert!(checkpoint.state.is_complete());
+    }
+
+    #[test]
+    fn test_node_wallet_checkpoint_goes_through_sending() {
+        let sending = || DistributionState::Sending {
+            started_at: Utc::now(),
+        };
+
+        let mut recorded = checkpoint("dist-1");
+        assert!(recorded
+            .advance(DistributionState::Signed {
+                signed_tx: "00".to_string(),
+                txid: "txid".to_string(),
+            })
+            .is_err());
+        recorded.advance(sending()).unwrap();
+        assert_eq!(recorded.state.name(), "sending");
+        assert_eq!(recorded.state.txid(), None);
+        assert!(recorded.advance(tx_built()).is_err());
+        recorded
+            .advance(DistributionState::Signed {
+                signed_tx: "00".to_string(),
+                txid: "txid".to_string(),
+            })
+            .unwrap();
+        assert_eq!(recorded.state.name(), "signed");
+
+        let mut unrecorded = checkpoint("dist-2");
+        unrecorded.advance(sending()).unwrap();
+        unrecorded
+            .advance(DistributionState::Broadcast {
+                txid: "txid".to_string(),
+            })
+            .unwrap();
+        assert_eq!(unrecorded.state.txid(), Some("txid"));
+
+        let mut built = checkpoint("dist-3");
+        built.advance(tx_built()).unwrap();
+        assert!(built.advance(sending()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_file_checkpoint_store_round_trip() {
+        let dir = std::env::temp_dir().join(format!("amp_rs_checkpoints_{}", std::process::id()));
+        let store = FileCheckpointStore::new(&dir);
+
+        assert!(store.load("dist-1").await.unwrap().is_none());
+        assert!(store.list().await.unwrap().is_empty());
+
+        let mut saved = checkpoint("dist-1");
+        store.save(&saved).await.unwrap();
+        saved.advance(tx_built()).unwrap();
+        store.save(&saved).await.unwrap();
+
+        let loaded = store.load("dist-1").await.unwrap().unwrap();
+        assert_eq!(loaded.state.name(), "tx_built");
+        assert_eq!(
+            loaded.map_address_amount["address1"],
+            Amount::from_sat(150_000)
+        );
+        assert_eq!(store.list().await.unwrap().len(), 1);
+
+        store.remove("dist-1").await.unwrap();
+        assert!(store.load("dist-1").await.unwrap().is_none());
+        assert!(matches!(
+            store.load("../escape").await,
+            Err(CheckpointError::InvalidDistributionUuid(_))
+        ));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,522 @@
+//! # Pro-Rata Dividends and Airdrops
+//!
+//! A [`DividendCalculator`] splits a total amount of a target asset among the holders of
+//! a source asset in proportion to their holdings in a [`CapTable`] snapshot, and
+//! produces one [`CreateAssetAssignmentRequest`] per registered user.
+//!
+//! Allocation is exact: holders receive whole multiples of the calculator's unit (one
+//! base unit by default), computed with integer arithmetic by the largest-remainder
+//! method. Every holder first receives the rounded-down quota of their share, and the
+//! units left over go one each to the holders with the largest remainders. With
+//! [`RemainderPolicy::RoundDown`] the units left over are not allocated instead. Any
+//! part of the total smaller than one unit is never allocated. Ties are broken by the
+//! larger holding, then the lower registered user id, so the result is deterministic.
+//!
+//! Holders are matched to registered users by GAID. Holdings of several GAIDs of the
+//! same registered user are added up. Holders that are not registered users, such as
+//! the issuer's treasury, are excluded, as are empty holdings and holdings below
+//! [`DividendCalculator::with_min_holding`]. A holder whose allocation would fall below
+//! [`DividendCalculator::with_min_allocation`] is excluded and the total is
+//! reallocated among the remaining holders. Holders whose share rounds down to nothing
+//! are reported as excluded too.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use amp_rs::{Amount, ApiClient};
+//! # use amp_rs::dividend::DividendCalculator;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = ApiClient::new().await?;
+//!
+//! // Distribute 1,000 units of asset B to holders of asset A at height 3,000,000,
+//! // ignoring holders of less than 100 base units of A
+//! let calculator = DividendCalculator::new(Amount::from_sat(1_000))
+//!     .with_min_holding(100)
+//!     .with_ready_for_distribution(true);
+//! let plan = client
+//!     .plan_dividend("source-asset-uuid", 3_000_000, &calculator)
+//!     .await?;
+//! for excluded in &plan.excluded {
+//!     println!("{} excluded: {:?}", excluded.holder, excluded.reason);
+//! }
+//! client.create_dividend_assignments("target-asset-uuid", &plan).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::amount::Amount;
+use crate::cap_table::CapTable;
+use crate::model::CreateAssetAssignmentRequest;
+
+/// Errors produced when calculating a dividend
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum DividendError {
+    #[error("Dividend total must be positive, got {0}")]
+    InvalidTotal(Amount),
+    #[error("Allocation unit must be positive, got {0}")]
+    InvalidUnit(i64),
+    #[error("Dividend total {total} is smaller than the allocation unit {unit}")]
+    TotalBelowUnit { total: Amount, unit: i64 },
+    #[error("No holder is eligible for the dividend")]
+    NoEligibleHolders,
+}
+
+/// What happens to the units left over after every holder received their rounded-down
+/// quota
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemainderPolicy {
+    /// Allocate them one each to the holders with the largest remainders, so the whole
+    /// total is allocated
+    #[default]
+    LargestRemainder,
+    /// Leave them unallocated
+    RoundDown,
+}
+
+/// Why a holder of the source asset receives nothing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExclusionReason {
+    /// The holder has no GAID of a registered user
+    NotRegistered,
+    /// The holding is empty or below the minimum holding
+    BelowMinimumHolding,
+    /// The allocation would be below the minimum allocation
+    BelowMinimumAllocation,
+    /// The holding is too small for a share of at least one allocation unit
+    RoundedToZero,
+}
+
+/// A holder of the source asset that receives nothing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExcludedHolder {
+    /// GAID or owner name, see [`CapTableEntry::holder`](crate::cap_table::CapTableEntry::holder)
+    pub holder: String,
+    pub registered_user: Option<i64>,
+    pub holding: i64,
+    pub reason: ExclusionReason,
+}
+
+/// The part of the dividend allocated to one registered user
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DividendAllocation {
+    pub registered_user: i64,
+    /// Holding of the source asset, across all of the user's GAIDs
+    pub holding: i64,
+    pub amount: Amount,
+}
+
+/// Result of a [`DividendCalculator`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DividendPlan {
+    /// Height of the source snapshot
+    pub record_height: i64,
+    /// Total to distribute, as configured
+    pub total: Amount,
+    /// Allocations ordered by registered user
+    pub allocations: Vec<DividendAllocation>,
+    /// Part of the total that is not allocated because of rounding
+    pub unallocated: Amount,
+    pub excluded: Vec<ExcludedHolder>,
+    /// Whether the assignment requests are marked ready for distribution
+    pub ready_for_distribution: bool,
+}
+
+impl DividendPlan {
+    /// Returns the total allocated
+    #[must_use]
+    pub fn allocated(&self) -> Amount {
+        self.allocations.iter().map(|a| a.amount).sum()
+    }
+
+    /// Converts the allocations into one assignment request per registered user
+    #[must_use]
+    pub fn assignment_requests(&self) -> Vec<CreateAssetAssignmentRequest> {
+        self.allocations
+            .iter()
+            .map(|allocation| CreateAssetAssignmentRequest {
+                registered_user: allocation.registered_user,
+                amount: allocation.amount.to_sat(),
+                vesting_timestamp: None,
+                ready_for_distribution: self.ready_for_distribution,
+            })
+            .collect()
+    }
+}
+
+/// Computes pro-rata allocations of a target asset from a source asset snapshot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DividendCalculator {
+    total: Amount,
+    unit: i64,
+    remainder: RemainderPolicy,
+    min_holding: i64,
+    min_allocation: Amount,
+    ready_for_distribution: bool,
+}
+
+impl DividendCalculator {
+    /// Creates a calculator distributing `total` base units of the target asset
+    #[must_use]
+    pub const fn new(total: Amount) -> Self {
+        Self {
+            total,
+            unit: 1,
+            remainder: RemainderPolicy::LargestRemainder,
+            min_holding: 0,
+            min_allocation: Amount::ZERO,
+            ready_for_distribution: false,
+        }
+    }
+
+    /// Allocates in whole multiples of `unit` base units, e.g. `100_000_000` to hand out
+    /// whole tokens of an asset with precision 8
+    #[must_use]
+    pub const fn with_unit(mut self, unit: i64) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Sets what happens to the units left over after rounding down
+    #[must_use]
+    pub const fn with_remainder(mut self, remainder: RemainderPolicy) -> Self {
+        self.remainder = remainder;
+        self
+    }
+
+    /// Excludes holders holding less than `min_holding` of the source asset
+    ///
+    /// Holders with an empty holding are always excluded.
+    #[must_use]
+    pub const fn with_min_holding(mut self, min_holding: i64) -> Self {
+        self.min_holding = min_holding;
+        self
+    }
+
+    /// Excludes holders whose allocation would be below `min_allocation`
+    #[must_use]
+    pub const fn with_min_allocation(mut self, min_allocation: Amount) -> Self {
+        self.min_allocation = min_allocation;
+        self
+    }
+
+    /// Marks the assignment requests ready for distribution
+    #[must_use]
+    pub const fn with_ready_for_distribution(mut self, ready: bool) -> Self {
+        self.ready_for_distribution = ready;
+        self
+    }
+
+    /// Allocates the total among the holders in `snapshot`
+    ///
+    /// `registered_users` maps GAIDs to registered user ids.
+    ///
+    /// # Errors
+    /// Returns an error if the total or unit is invalid, or no holder is eligible
+    pub fn calculate(
+        &self,
+        snapshot: &CapTable,
+        registered_users: &HashMap<String, i64>,
+    ) -> Result<DividendPlan, DividendError> {
+        if !self.total.is_positive() {
+            return Err(DividendError::InvalidTotal(self.total));
+        }
+        if self.unit <= 0 {
+            return Err(DividendError::InvalidUnit(self.unit));
+        }
+        let units = self.total.to_sat() / self.unit;
+        if units == 0 {
+            return Err(DividendError::TotalBelowUnit {
+                total: self.total,
+                unit: self.unit,
+            });
+        }
+
+        let mut excluded = Vec::new();
+        let mut holdings: BTreeMap<i64, i64> = BTreeMap::new();
+        let mut holders: BTreeMap<i64, Vec<&str>> = BTreeMap::new();
+        for entry in &snapshot.entries {
+            let user = entry
+                .gaid
+                .as_ref()
+                .and_then(|gaid| registered_users.get(gaid).copied());
+            match user {
+                Some(user) => {
+                    *holdings.entry(user).or_default() += entry.amount;
+                    holders.entry(user).or_default().push(entry.holder());
+                }
+                None => excluded.push(ExcludedHolder {
+                    holder: entry.holder().to_string(),
+                    registered_user: None,
+                    holding: entry.amount,
+                    reason: ExclusionReason::NotRegistered,
+                }),
+            }
+        }
+        let mut exclude = |user: i64, holding: i64, reason: ExclusionReason| {
+            excluded.push(ExcludedHolder {
+                holder: holders[&user].join(", "),
+                registered_user: Some(user),
+                holding,
+                reason,
+            });
+        };
+        holdings.retain(|&user, &mut holding| {
+            // Empty holdings are excluded even without a minimum, so the total is positive
+            let eligible = holding > 0 && holding >= self.min_holding;
+            if !eligible {
+                exclude(user, holding, ExclusionReason::BelowMinimumHolding);
+            }
+            eligible
+        });
+
+        // Excluding holders below the minimum allocation raises everyone else's share,
+        // so repeat until every remaining allocation meets it
+        let allocations = loop {
+            if holdings.is_empty() {
+                return Err(DividendError::NoEligibleHolders);
+            }
+            let allocations = self.allocate(units, &holdings);
+            let below: Vec<i64> = allocations
+                .iter()
+                .filter(|(_, amount)| *amount < self.min_allocation)
+                .map(|(user, _)| *user)
+                .collect();
+            if below.is_empty() {
+                break allocations;
+            }
+            for user in below {
+                let holding = holdings.remove(&user).unwrap_or_default();
+                exclude(user, holding, ExclusionReason::BelowMinimumAllocation);
+            }
+        };
+
+        let (allocations, rounded_to_zero): (Vec<_>, Vec<_>) = allocations
+            .into_iter()
+            .partition(|(_, amount)| amount.is_positive());
+        for (user, _) in rounded_to_zero {
+            exclude(user, holdings[&user], ExclusionReason::RoundedToZero);
+        }
+
+        let allocations: Vec<DividendAllocation> = allocations
+            .into_iter()
+            .map(|(registered_user, amount)| DividendAllocation {
+                registered_user,
+                holding: holdings[&registered_user],
+                amount,
+            })
+            .collect();
+        let allocated: Amount = allocations.iter().map(|a| a.amount).sum();
+        Ok(DividendPlan {
+            record_height: snapshot.height,
+            total: self.total,
+            allocations,
+            unallocated: self.total - allocated,
+            excluded,
+            ready_for_distribution: self.ready_for_distribution,
+        })
+    }
+
+    /// Splits `units` among `holdings` by the largest-remainder method
+    fn allocate(&self, units: i64, holdings: &BTreeMap<i64, i64>) -> Vec<(i64, Amount)> {
+        let total_holding: i128 = holdings.values().map(|&h| i128::from(h)).sum();
+        let mut shares: Vec<(i64, i64, i128, i128)> = holdings
+            .iter()
+            .map(|(&user, &holding)| {
+                let exact = i128::from(units) * i128::from(holding);
+                (user, holding, exact / total_holding, exact % total_holding)
+            })
+            .collect();
+
+        if self.remainder == RemainderPolicy::LargestRemainder {
+            let allocated: i128 = shares.iter().map(|share| share.2).sum();
+            let left_over = usize::try_from(i128::from(units) - allocated).unwrap_or_default();
+            shares.sort_by(|a, b| b.3.cmp(&a.3).then(b.1.cmp(&a.1)).then(a.0.cmp(&b.0)));
+            for share in shares.iter_mut().take(left_over) {
+                share.2 += 1;
+            }
+            shares.sort_by_key(|share| share.0);
+        }
+
+        shares
+            .into_iter()
+            .map(|(user, _, quota, _)| {
+                // A quota is at most `units`, which fits in an i64
+                let quota = i64::try_from(quota).unwrap_or(i64::MAX);
+                (user, Amount::from_sat(quota * self.unit))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cap_table::CapTableEntry;
+
+    fn snapshot(holdings: &[(&str, i64)]) -> CapTable {
+        CapTable {
+            asset_uuid: "source".to_string(),
+            height: 500,
+            record_time: None,
+            entries: holdings
+                .iter()
+                .map(|&(gaid, amount)| CapTableEntry {
+                    gaid: Some(gaid.to_string()),
+                    owner: None,
+                    amount,
+                })
+                .collect(),
+        }
+    }
+
+    fn users() -> HashMap<String, i64> {
+        [("GA1", 1), ("GA2", 2), ("GA3", 3), ("GA1b", 1)]
+            .into_iter()
+            .map(|(gaid, user)| (gaid.to_string(), user))
+            .collect()
+    }
+
+    fn amounts(plan: &DividendPlan) -> Vec<(i64, i64)> {
+        plan.allocations
+            .iter()
+            .map(|a| (a.registered_user, a.amount.to_sat()))
+            .collect()
+    }
+
+    #[test]
+    fn test_largest_remainder_allocates_exact_total() {
+        let snapshot = snapshot(&[("GA1", 1), ("GA2", 1), ("GA3", 1)]);
+        let plan = DividendCalculator::new(Amount::from_sat(100))
+            .calculate(&snapshot, &users())
+            .unwrap();
+        // 33.33 each; the tie on remainders goes to the lowest user id
+        assert_eq!(amounts(&plan), vec![(1, 34), (2, 33), (3, 33)]);
+        assert_eq!(plan.allocated(), Amount::from_sat(100));
+        assert_eq!(plan.unallocated, Amount::ZERO);
+
+        let plan = DividendCalculator::new(Amount::from_sat(100))
+            .with_remainder(RemainderPolicy::RoundDown)
+            .calculate(&snapshot, &users())
+            .unwrap();
+        assert_eq!(amounts(&plan), vec![(1, 33), (2, 33), (3, 33)]);
+        assert_eq!(plan.unallocated, Amount::from_sat(1));
+    }
+
+    #[test]
+    fn test_units_merged_users_and_unregistered_holders() {
+        let mut snapshot = snapshot(&[("GA1", 600), ("GA2", 250), ("GA1b", 100), ("GA9", 50)]);
+        snapshot.entries.push(CapTableEntry {
+            gaid: None,
+            owner: Some("Treasury".to_string()),
+            amount: 5_000,
+        });
+        let plan = DividendCalculator::new(Amount::from_sat(1_050))
+            .with_unit(100)
+            .with_ready_for_distribution(true)
+            .calculate(&snapshot, &users())
+            .unwrap();
+
+        // User 1 holds 700 of 950 eligible: 7.37 units, user 2: 2.63 units
+        assert_eq!(amounts(&plan), vec![(1, 700), (2, 300)]);
+        assert_eq!(plan.allocations[0].holding, 700);
+        assert_eq!(plan.unallocated, Amount::from_sat(50));
+        let not_registered: Vec<&str> = plan
+            .excluded
+            .iter()
+            .filter(|e| e.reason == ExclusionReason::NotRegistered)
+            .map(|e| e.holder.as_str())
+            .collect();
+        assert_eq!(not_registered, vec!["GA9", "Treasury"]);
+
+        let requests = plan.assignment_requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].ready_for_distribution);
+        assert_eq!(requests[1].amount, 300);
+    }
+
+    #[test]
+    fn test_minimum_thresholds_exclude_and_reallocate() {
+        let snapshot = snapshot(&[("GA1", 900), ("GA2", 95), ("GA3", 5)]);
+        let plan = DividendCalculator::new(Amount::from_sat(1_000))
+            .with_min_holding(10)
+            .with_min_allocation(Amount::from_sat(100))
+            .calculate(&snapshot, &users())
+            .unwrap();
+
+        assert_eq!(amounts(&plan), vec![(1, 1_000)]);
+        let reasons: Vec<(Option<i64>, ExclusionReason)> = plan
+            .excluded
+            .iter()
+            .map(|e| (e.registered_user, e.reason))
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                (Some(3), ExclusionReason::BelowMinimumHolding),
+                (Some(2), ExclusionReason::BelowMinimumAllocation),
+            ]
+        );
+
+        assert_eq!(
+            DividendCalculator::new(Amount::from_sat(1_000))
+                .with_min_holding(1_000)
+                .calculate(&snapshot, &users()),
+            Err(DividendError::NoEligibleHolders)
+        );
+        assert_eq!(
+            DividendCalculator::new(Amount::from_sat(10))
+                .with_unit(100)
+                .calculate(&snapshot, &users()),
+            Err(DividendError::TotalBelowUnit {
+                total: Amount::from_sat(10),
+                unit: 100
+            })
+        );
+    }
+
+    #[test]
+    fn test_holders_rounded_to_zero_are_excluded() {
+        let snapshot = snapshot(&[("GA1", 1_000), ("GA2", 1_000), ("GA3", 1)]);
+        let plan = DividendCalculator::new(Amount::from_sat(3))
+            .with_remainder(RemainderPolicy::RoundDown)
+            .calculate(&snapshot, &users())
+            .unwrap();
+
+        assert_eq!(amounts(&plan), vec![(1, 1), (2, 1)]);
+        assert_eq!(plan.unallocated, Amount::from_sat(1));
+        assert_eq!(
+            plan.excluded,
+            vec![ExcludedHolder {
+                holder: "GA3".to_string(),
+                registered_user: Some(3),
+                holding: 1,
+                reason: ExclusionReason::RoundedToZero,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_empty_holdings_are_excluded() {
+        let calculator = DividendCalculator::new(Amount::from_sat(100));
+        let result = calculator.calculate(&snapshot(&[("GA1", 0), ("GA2", 0)]), &users());
+        assert_eq!(result, Err(DividendError::NoEligibleHolders));
+
+        let plan = calculator
+            .calculate(&snapshot(&[("GA1", 0), ("GA2", 4)]), &users())
+            .unwrap();
+        assert_eq!(amounts(&plan), vec![(2, 100)]);
+        assert_eq!(plan.excluded.len(), 1);
+        assert_eq!(
+            plan.excluded[0].reason,
+            ExclusionReason::BelowMinimumHolding
+        );
+    }
+}
@@ -0,0 +1,178 @@
+//! # Dry Runs
+//!
+//! The asset workflows of [`ApiClient`](crate::ApiClient) register operations with the
+//! AMP API and spend wallet funds. Their dry-run counterparts preview an operation
+//! without doing either:
+//!
+//! - [`ApiClient::distribute_asset_dry_run`](crate::ApiClient::distribute_asset_dry_run)
+//! - [`ApiClient::reissue_asset_dry_run`](crate::ApiClient::reissue_asset_dry_run)
+//! - [`ApiClient::burn_asset_dry_run`](crate::ApiClient::burn_asset_dry_run)
+//!
+//! A dry run validates the inputs, fetches the asset from the AMP API, selects UTXOs
+//! from the wallet and estimates the fee. It only reads: it never calls
+//! `create_distribution`, `reissue_request`, `burn_request` or `sendrawtransaction`, and
+//! it does not ask the node for new addresses. The resulting [`DryRunPlan`] lists the
+//! inputs and outputs of the transaction, the fee, and the AMP and node calls the real
+//! workflow would make.
+//!
+//! Reissuance and burn transactions are built by the node (`reissueasset` and
+//! `destroyamount`), which picks its own inputs, and so are distributions funded by the
+//! node wallet with `sendmany`, the default. For these, the plan shows the inputs the
+//! node is expected to spend and an estimate of the fee, and [`DryRunPlan::estimated`]
+//! is set. Only distributions funded with
+//! [`DistributionFunding::Signer`](crate::distribution::DistributionFunding::Signer) spend
+//! exactly the planned inputs.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use amp_rs::{Amount, ApiClient, AssetDistributionAssignment, ElementsRpc};
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = ApiClient::new().await?;
+//! let node_rpc = ElementsRpc::from_env()?;
+//! let assignments = vec![AssetDistributionAssignment {
+//!     user_id: "user123".to_string(),
+//!     address: "vjTvpDMQx7EQUyGKJ2a4LdUfyGpvUq5yqM8N4v6sHaP5f8x9Z3kL".to_string(),
+//!     amount: Amount::from_sat(100_000),
+//! }];
+//!
+//! let plan = client
+//!     .distribute_asset_dry_run("asset-uuid", &assignments, &node_rpc, "wallet_name")
+//!     .await?;
+//! println!("{} inputs, fee {} at {}", plan.inputs.len(), plan.fee, plan.fee_rate);
+//! for call in &plan.amp_calls {
+//!     println!("would call {} {}", call.method, call.path);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::amount::Amount;
+use crate::fee::FeeRate;
+use crate::model::Unspent;
+
+/// The workflow a [`DryRunPlan`] previews
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DryRunOperation {
+    Distribution,
+    Reissuance,
+    Burn,
+}
+
+/// What a planned output pays for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputKind {
+    /// Asset paid to a distribution recipient
+    Recipient,
+    /// Newly reissued asset
+    Reissuance,
+    /// Asset destroyed by a burn
+    Burn,
+    /// Asset, reissuance token or L-BTC returned to the wallet
+    Change,
+    /// The explicit L-BTC fee output
+    Fee,
+}
+
+/// An output of the planned transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedOutput {
+    pub kind: OutputKind,
+    /// Recipient address, `None` when the node wallet picks the address
+    pub address: Option<String>,
+    /// Asset id of the output
+    pub asset: String,
+    pub amount: Amount,
+}
+
+/// An AMP API request the workflow would make
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedAmpCall {
+    /// HTTP method
+    pub method: String,
+    /// Request path; values only known once the workflow runs are shown in braces
+    pub path: String,
+    pub description: String,
+}
+
+/// An Elements RPC call that changes wallet or network state
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedRpcCall {
+    /// RPC method name
+    pub method: String,
+    pub description: String,
+}
+
+/// Preview of an asset workflow, produced without changing any state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DryRunPlan {
+    pub operation: DryRunOperation,
+    pub asset_uuid: String,
+    pub asset_id: String,
+    /// Amount distributed, reissued or burned
+    pub amount: Amount,
+    /// UTXOs the transaction spends, asset inputs first
+    pub inputs: Vec<Unspent>,
+    /// Outputs of the transaction, including change and the fee
+    pub outputs: Vec<PlannedOutput>,
+    /// Estimated L-BTC fee
+    pub fee: Amount,
+    /// Fee rate the fee was estimated at
+    pub fee_rate: FeeRate,
+    /// Estimated virtual size of the signed transaction
+    pub vsize: usize,
+    /// AMP API requests the workflow would make, in order
+    pub amp_calls: Vec<PlannedAmpCall>,
+    /// Elements RPC calls the workflow would make that change state, in order
+    pub node_calls: Vec<PlannedRpcCall>,
+    /// `true` when the node wallet selects the inputs and sets the fee, so `inputs`, the
+    /// change and `fee` are estimates of what the workflow will spend
+    pub estimated: bool,
+    /// Conditions that do not prevent the workflow but deserve attention
+    pub warnings: Vec<String>,
+}
+
+impl DryRunPlan {
+    /// Returns the change outputs of the transaction
+    #[must_use]
+    pub fn change(&self) -> Vec<&PlannedOutput> {
+        self.outputs
+            .iter()
+            .filter(|output| output.kind == OutputKind::Change)
+            .collect()
+    }
+
+    /// Returns the total change returned to the wallet in `asset`
+    #[must_use]
+    pub fn change_in(&self, asset: &str) -> Amount {
+        self.change()
+            .into_iter()
+            .filter(|output| output.asset == asset)
+            .map(|output| output.amount)
+            .sum()
+    }
+}
+
+impl PlannedAmpCall {
+    pub(crate) fn new(method: &str, path: String, description: &str) -> Self {
+        Self {
+            method: method.to_string(),
+            path,
+            description: description.to_string(),
+        }
+    }
+}
+
+impl PlannedRpcCall {
+    pub(crate) fn new(method: &str, description: &str) -> Self {
+        Self {
+            method: method.to_string(),
+            description: description.to_string(),
+        }
+    }
+}
@@ -0,0 +1,169 @@
+//! Passphrase-based authenticated encryption shared by the encrypted storage backends.
+//!
+//! Data is sealed with XChaCha20-Poly1305 using a key derived from the passphrase with
+//! Argon2id. Each sealed value carries its own random salt and nonce together with an
+//! explicit format version, so the format can evolve without breaking existing files.
+
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+/// Current version of the encrypted envelope format
+pub(crate) const ENVELOPE_VERSION: u32 = 1;
+
+/// Key derivation function identifier stored in the envelope
+const KDF_ARGON2ID: &str = "argon2id";
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const KEY_LEN: usize = 32;
+
+/// Errors produced while sealing or opening encrypted data
+#[derive(Error, Debug)]
+pub(crate) enum EncryptionError {
+    #[error("Unsupported encryption format version {0}")]
+    UnsupportedVersion(u32),
+    #[error("Unsupported key derivation function '{0}'")]
+    UnsupportedKdf(String),
+    #[error("Key derivation failed: {0}")]
+    KeyDerivation(String),
+    #[error("Encryption failed")]
+    Encryption,
+    #[error("Decryption failed: wrong passphrase or corrupted data")]
+    Decryption,
+    #[error("Invalid encrypted data: {0}")]
+    InvalidData(String),
+}
+
+/// Serialized form of an encrypted value
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct EncryptedEnvelope {
+    pub version: u32,
+    pub kdf: String,
+    pub salt: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+/// Encrypts `plaintext` with a key derived from `passphrase`
+pub(crate) fn seal(
+    passphrase: &[u8],
+    plaintext: &[u8],
+) -> Result<EncryptedEnvelope, EncryptionError> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    let cipher = cipher_for(passphrase, &salt)?;
+    let ciphertext = cipher
+        .encrypt(&XNonce::from(nonce), plaintext)
+        .map_err(|_| EncryptionError::Encryption)?;
+
+    Ok(EncryptedEnvelope {
+        version: ENVELOPE_VERSION,
+        kdf: KDF_ARGON2ID.to_string(),
+        salt: hex::encode(salt),
+        nonce: hex::encode(nonce),
+        ciphertext: hex::encode(ciphertext),
+    })
+}
+
+/// Decrypts an envelope produced by [`seal`]
+///
+/// The returned plaintext is zeroized when dropped.
+pub(crate) fn open(
+    passphrase: &[u8],
+    envelope: &EncryptedEnvelope,
+) -> Result<Zeroizing<Vec<u8>>, EncryptionError> {
+    if envelope.version != ENVELOPE_VERSION {
+        return Err(EncryptionError::UnsupportedVersion(envelope.version));
+    }
+    if envelope.kdf != KDF_ARGON2ID {
+        return Err(EncryptionError::UnsupportedKdf(envelope.kdf.clone()));
+    }
+
+    let salt = decode_field("salt", &envelope.salt, Some(SALT_LEN))?;
+    let nonce = decode_field("nonce", &envelope.nonce, Some(NONCE_LEN))?;
+    let ciphertext = decode_field("ciphertext", &envelope.ciphertext, None)?;
+
+    let nonce: [u8; NONCE_LEN] = nonce
+        .try_into()
+        .map_err(|_| EncryptionError::InvalidData("nonce has wrong length".to_string()))?;
+
+    let cipher = cipher_for(passphrase, &salt)?;
+    cipher
+        .decrypt(&XNonce::from(nonce), ciphertext.as_ref())
+        .map(Zeroizing::new)
+        .map_err(|_| EncryptionError::Decryption)
+}
+
+fn cipher_for(passphrase: &[u8], salt: &[u8]) -> Result<XChaCha20Poly1305, EncryptionError> {
+    let key = derive_key(passphrase, salt)?;
+    XChaCha20Poly1305::new_from_slice(key.as_ref())
+        .map_err(|e| EncryptionError::KeyDerivation(e.to_string()))
+}
+
+fn derive_key(passphrase: &[u8], salt: &[u8]) -> Result<Zeroizing<[u8; KEY_LEN]>, EncryptionError> {
+    let mut key = Zeroizing::new([0u8; KEY_LEN]);
+    Argon2::default()
+        .hash_password_into(passphrase, salt, key.as_mut())
+        .map_err(|e| EncryptionError::KeyDerivation(e.to_string()))?;
+    Ok(key)
+}
+
+fn decode_field(
+    name: &str,
+    value: &str,
+    expected_len: Option<usize>,
+) -> Result<Vec<u8>, EncryptionError> {
+    let bytes = hex::decode(value)
+        .map_err(|e| EncryptionError::InvalidData(format!("{name} is not valid hex: {e}")))?;
+    if let Some(expected) = expected_len {
+        if bytes.len() != expected {
+            return Err(EncryptionError::InvalidData(format!(
+                "{name} must be {expected} bytes, got {}",
+                bytes.len()
+            )));
+        }
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open_round_trip() {
+        let envelope = seal(b"passphrase", b"secret data").unwrap();
+        assert_eq!(envelope.version, ENVELOPE_VERSION);
+        assert!(!envelope.ciphertext.contains(&hex::encode(b"secret data")));
+
+        let plaintext = open(b"passphrase", &envelope).unwrap();
+        assert_eq!(plaintext.as_slice(), b"secret data");
+    }
+
+    #[test]
+    fn test_open_with_wrong_passphrase_fails() {
+        let envelope = seal(b"passphrase", b"secret data").unwrap();
+        assert!(matches!(
+            open(b"wrong", &envelope),
+            Err(EncryptionError::Decryption)
+        ));
+    }
+
+    #[test]
+    fn test_open_rejects_unknown_version() {
+        let mut envelope = seal(b"passphrase", b"secret data").unwrap();
+        envelope.version = ENVELOPE_VERSION + 1;
+        assert!(matches!(
+            open(b"passphrase", &envelope),
+            Err(EncryptionError::UnsupportedVersion(_))
+        ));
+    }
+}
@@ -0,0 +1,775 @@
+//! # Declarative Environments
+//!
+//! An [`EnvironmentSpec`] describes the state an AMP account should be in: categories
+//! and the assets linked to them, registered users and their categories, managers and
+//! the assets they manage, and the treasury addresses of assets. Instead of scripting
+//! the calls, the spec is compared with the live account:
+//!
+//! - [`ApiClient::plan_environment`](crate::ApiClient::plan_environment) reads the
+//!   account and returns an [`EnvironmentPlan`] listing the [`Change`]s needed, without
+//!   changing anything
+//! - [`ApiClient::apply_environment`](crate::ApiClient::apply_environment) applies a
+//!   plan in order and stops at the first failure
+//!
+//! Applying the same spec twice makes no changes the second time.
+//!
+//! Objects are matched by name: categories by name, registered users by GAID (their
+//! default GAID in AMP) or, for users without a GAID in the spec, by name, and managers
+//! by username. Objects that are not in the spec are left untouched. For objects that
+//! are in the spec, the listed links are exact: links that are not listed are removed.
+//! This covers a category's assets, a user's categories, a manager's assets and the
+//! treasury addresses of the listed assets. Omitting a field leaves it alone: a category
+//! without `description` or `assets` keeps its current ones, a user without
+//! `categories` keeps their categories, and a manager without `assets` or `locked`
+//! keeps its assets and lock state. An empty list removes every link.
+//!
+//! The spec is plain serde data. [`EnvironmentSpec::from_json`] reads JSON; TOML or
+//! YAML can be read with the respective serde crate.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use amp_rs::ApiClient;
+//! # use amp_rs::environment::EnvironmentSpec;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let spec = EnvironmentSpec::from_json(
+//!     r#"{
+//!         "categories": [
+//!             {"name": "Accredited", "description": "Accredited investors",
+//!              "assets": ["asset-uuid"]}
+//!         ],
+//!         "registered_users": [
+//!             {"name": "Alice", "GAID": "GAbYScu6jkWUND2jo3L4KJxyvo55d",
+//!              "categories": ["Accredited"]}
+//!         ],
+//!         "managers": [
+//!             {"username": "ops", "password": "change-me", "assets": ["asset-uuid"]}
+//!         ],
+//!         "treasury_addresses": {"asset-uuid": ["vjTwqhz69nh7xHhtsHnx7mezsJV95EYHPqxs"]}
+//!     }"#,
+//! )?;
+//!
+//! let client = ApiClient::new().await?;
+//! let plan = client.plan_environment(&spec).await?;
+//! for change in &plan.changes {
+//!     println!("{change}");
+//! }
+//! let report = client.apply_environment(&plan).await;
+//! println!("applied {} changes", report.applied.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+
+use secrecy::Secret;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::model::{CategoryResponse, Manager, RegisteredUserResponse};
+
+/// Errors produced when reading a spec or planning changes
+#[derive(Error, Debug)]
+pub enum EnvironmentError {
+    #[error("Invalid environment spec: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Category '{0}' is listed more than once")]
+    DuplicateCategory(String),
+    #[error("Registered user '{0}' is listed more than once")]
+    DuplicateUser(String),
+    #[error("Manager '{0}' is listed more than once")]
+    DuplicateManager(String),
+    #[error("Registered user '{user}' refers to unknown category '{category}'")]
+    UnknownCategory { user: String, category: String },
+    #[error("More than one registered user is named '{0}'; add its GAID to the spec")]
+    AmbiguousUser(String),
+    #[error("Manager '{0}' does not exist and has no password in the spec")]
+    MissingManagerPassword(String),
+}
+
+/// The desired state of an AMP account
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EnvironmentSpec {
+    #[serde(default)]
+    pub categories: Vec<CategorySpec>,
+    #[serde(default)]
+    pub registered_users: Vec<RegisteredUserSpec>,
+    #[serde(default)]
+    pub managers: Vec<ManagerSpec>,
+    /// Treasury addresses per asset UUID
+    #[serde(default)]
+    pub treasury_addresses: BTreeMap<String, Vec<String>>,
+}
+
+/// A category and the assets linked to it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CategorySpec {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// UUIDs of the assets in the category; `None` keeps the current assets
+    #[serde(default)]
+    pub assets: Option<Vec<String>>,
+}
+
+/// A registered user and the categories they belong to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegisteredUserSpec {
+    pub name: String,
+    #[serde(rename = "GAID", default)]
+    pub gaid: Option<String>,
+    #[serde(default)]
+    pub is_company: bool,
+    /// Names of the user's categories; `None` keeps the current categories
+    #[serde(default)]
+    pub categories: Option<Vec<String>>,
+}
+
+impl RegisteredUserSpec {
+    /// Returns the key the user is matched and referred to by: the GAID, or the name
+    #[must_use]
+    pub fn key(&self) -> &str {
+        self.gaid.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// A manager and the assets they manage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManagerSpec {
+    pub username: String,
+    /// Only used to create the manager; existing passwords are not changed
+    #[serde(default, skip_serializing)]
+    pub password: Option<Secret<String>>,
+    /// UUIDs of the assets the manager manages; `None` keeps the current assets
+    #[serde(default)]
+    pub assets: Option<Vec<String>>,
+    #[serde(default)]
+    pub locked: Option<bool>,
+}
+
+impl EnvironmentSpec {
+    /// Reads a spec from JSON
+    ///
+    /// # Errors
+    /// Returns an error if the JSON does not describe a spec
+    pub fn from_json(input: &str) -> Result<Self, EnvironmentError> {
+        Ok(serde_json::from_str(input)?)
+    }
+
+    /// Computes the changes that bring `live` to this spec
+    ///
+    /// # Errors
+    /// Returns an error if the spec lists an object twice, refers to an unknown
+    /// category, matches several users by name, or creates a manager without a password
+    pub fn plan(&self, live: &LiveEnvironment) -> Result<EnvironmentPlan, EnvironmentError> {
+        let mut plan = EnvironmentPlan::default();
+        self.plan_categories(live, &mut plan)?;
+        self.plan_users(live, &mut plan)?;
+        self.plan_managers(live, &mut plan)?;
+        self.plan_treasury(live, &mut plan);
+        Ok(plan)
+    }
+
+    fn plan_categories(
+        &self,
+        live: &LiveEnvironment,
+        plan: &mut EnvironmentPlan,
+    ) -> Result<(), EnvironmentError> {
+        let mut seen = BTreeSet::new();
+        for category in &self.categories {
+            if !seen.insert(category.name.as_str()) {
+                return Err(EnvironmentError::DuplicateCategory(category.name.clone()));
+            }
+            let existing = live.categories.iter().find(|c| c.name == category.name);
+            let current: BTreeSet<&str> = existing
+                .map(|c| c.assets.iter().map(String::as_str).collect())
+                .unwrap_or_default();
+            match existing {
+                None => plan.changes.push(Change::CreateCategory {
+                    name: category.name.clone(),
+                    description: category.description.clone(),
+                }),
+                Some(existing) => {
+                    plan.category_ids.insert(existing.name.clone(), existing.id);
+                    if category.description.is_some()
+                        && category.description != existing.description
+                    {
+                        plan.changes.push(Change::UpdateCategoryDescription {
+                            category: category.name.clone(),
+                            description: category.description.clone(),
+                        });
+                    }
+                }
+            }
+            let Some(assets) = &category.assets else {
+                continue;
+            };
+            let desired: BTreeSet<&str> = assets.iter().map(String::as_str).collect();
+            for asset_uuid in desired.difference(&current) {
+                plan.changes.push(Change::LinkAssetToCategory {
+                    category: category.name.clone(),
+                    asset_uuid: (*asset_uuid).to_string(),
+                });
+            }
+            for asset_uuid in current.difference(&desired) {
+                plan.changes.push(Change::UnlinkAssetFromCategory {
+                    category: category.name.clone(),
+                    asset_uuid: (*asset_uuid).to_string(),
+                });
+            }
+        }
+        // Users may refer to categories that exist but are not managed by the spec
+        for category in &live.categories {
+            plan.category_ids
+                .entry(category.name.clone())
+                .or_insert(category.id);
+        }
+        Ok(())
+    }
+
+    fn plan_users(
+        &self,
+        live: &LiveEnvironment,
+        plan: &mut EnvironmentPlan,
+    ) -> Result<(), EnvironmentError> {
+        let category_names: HashMap<i64, &str> = live
+            .categories
+            .iter()
+            .map(|c| (c.id, c.name.as_str()))
+            .collect();
+        let mut seen = BTreeSet::new();
+        for user in &self.registered_users {
+            let key = user.key().to_string();
+            if !seen.insert(key.clone()) {
+                return Err(EnvironmentError::DuplicateUser(key));
+            }
+            for category in user.categories.iter().flatten() {
+                if !plan.category_ids.contains_key(category)
+                    && !self.categories.iter().any(|c| c.name == *category)
+                {
+                    return Err(EnvironmentError::UnknownCategory {
+                        user: key,
+                        category: category.clone(),
+                    });
+                }
+            }
+
+            let existing = match &user.gaid {
+                Some(gaid) => live
+                    .registered_users
+                    .iter()
+                    .find(|u| u.gaid.as_ref() == Some(gaid)),
+                None => {
+                    let mut named = live.registered_users.iter().filter(|u| u.name == user.name);
+                    let first = named.next();
+                    if named.next().is_some() {
+                        return Err(EnvironmentError::AmbiguousUser(user.name.clone()));
+                    }
+                    first
+                }
+            };
+            let current: BTreeSet<&str> = match existing {
+                None => {
+                    plan.changes.push(Change::CreateRegisteredUser {
+                        user: key.clone(),
+                        name: user.name.clone(),
+                        gaid: user.gaid.clone(),
+                        is_company: user.is_company,
+                    });
+                    BTreeSet::new()
+                }
+                Some(existing) => {
+                    plan.user_ids.insert(key.clone(), existing.id);
+                    if existing.name != user.name {
+                        plan.changes.push(Change::RenameRegisteredUser {
+                            user: key.clone(),
+                            name: user.name.clone(),
+                        });
+                    }
+                    if existing.is_company != user.is_company {
+                        plan.warnings.push(format!(
+                            "Registered user '{key}' has is_company = {}, which cannot be changed",
+                            existing.is_company
+                        ));
+                    }
+                    existing
+                        .categories
+                        .iter()
+                        .filter_map(|id| category_names.get(id).copied())
+                        .collect()
+                }
+            };
+            let Some(categories) = &user.categories else {
+                continue;
+            };
+            let desired: BTreeSet<&str> = categories.iter().map(String::as_str).collect();
+            for category in desired.difference(&current) {
+                plan.changes.push(Change::AddUserToCategory {
+                    user: key.clone(),
+                    category: (*category).to_string(),
+                });
+            }
+            for category in current.difference(&desired) {
+                plan.changes.push(Change::RemoveUserFromCategory {
+                    user: key.clone(),
+                    category: (*category).to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn plan_managers(
+        &self,
+        live: &LiveEnvironment,
+        plan: &mut EnvironmentPlan,
+    ) -> Result<(), EnvironmentError> {
+        let mut seen = BTreeSet::new();
+        for manager in &self.managers {
+            let username = manager.username.clone();
+            if !seen.insert(username.clone()) {
+                return Err(EnvironmentError::DuplicateManager(username));
+            }
+            let existing = live.managers.iter().find(|m| m.username == username);
+            let (current, is_locked): (BTreeSet<&str>, bool) = match existing {
+                None => {
+                    let password = manager.password.clone().ok_or_else(|| {
+                        EnvironmentError::MissingManagerPassword(username.clone())
+                    })?;
+                    plan.passwords.insert(username.clone(), password);
+                    plan.changes.push(Change::CreateManager {
+                        username: username.clone(),
+                    });
+                    (BTreeSet::new(), false)
+                }
+                Some(existing) => {
+                    plan.manager_ids.insert(username.clone(), existing.id);
+                    (
+                        existing.assets.iter().map(String::as_str).collect(),
+                        existing.is_locked,
+                    )
+                }
+            };
+            if let Some(assets) = &manager.assets {
+                let desired: BTreeSet<&str> = assets.iter().map(String::as_str).collect();
+                for asset_uuid in desired.difference(&current) {
+                    plan.changes.push(Change::AddAssetToManager {
+                        username: username.clone(),
+                        asset_uuid: (*asset_uuid).to_string(),
+                    });
+                }
+                for asset_uuid in current.difference(&desired) {
+                    plan.changes.push(Change::RemoveAssetFromManager {
+                        username: username.clone(),
+                        asset_uuid: (*asset_uuid).to_string(),
+                    });
+                }
+            }
+            match manager.locked {
+                Some(true) if !is_locked => plan.changes.push(Change::LockManager { username }),
+                Some(false) if is_locked => plan.changes.push(Change::UnlockManager { username }),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn plan_treasury(&self, live: &LiveEnvironment, plan: &mut EnvironmentPlan) {
+        for (asset_uuid, addresses) in &self.treasury_addresses {
+            let current: BTreeSet<&String> = live
+                .treasury_addresses
+                .get(asset_uuid)
+                .map(|addresses| addresses.iter().collect())
+                .unwrap_or_default();
+            let desired: BTreeSet<&String> = addresses.iter().collect();
+            let missing: Vec<String> = desired.difference(&current).map(|a| (*a).clone()).collect();
+            let extra: Vec<String> = current.difference(&desired).map(|a| (*a).clone()).collect();
+            if !missing.is_empty() {
+                plan.changes.push(Change::AddTreasuryAddresses {
+                    asset_uuid: asset_uuid.clone(),
+                    addresses: missing,
+                });
+            }
+            if !extra.is_empty() {
+                plan.changes.push(Change::RemoveTreasuryAddresses {
+                    asset_uuid: asset_uuid.clone(),
+                    addresses: extra,
+                });
+            }
+        }
+    }
+}
+
+/// The current state of an AMP account, as far as an [`EnvironmentSpec`] covers it
+#[derive(Debug, Default)]
+pub struct LiveEnvironment {
+    pub categories: Vec<CategoryResponse>,
+    pub registered_users: Vec<RegisteredUserResponse>,
+    pub managers: Vec<Manager>,
+    /// Treasury addresses of the assets listed in the spec
+    pub treasury_addresses: HashMap<String, Vec<String>>,
+}
+
+/// One change to the AMP account
+///
+/// Categories are referred to by name, registered users by their key (see
+/// [`RegisteredUserSpec::key`]) and managers by username. Objects created earlier in the
+/// same plan are resolved when the plan is applied.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum Change {
+    CreateCategory {
+        name: String,
+        description: Option<String>,
+    },
+    UpdateCategoryDescription {
+        category: String,
+        description: Option<String>,
+    },
+    LinkAssetToCategory {
+        category: String,
+        asset_uuid: String,
+    },
+    UnlinkAssetFromCategory {
+        category: String,
+        asset_uuid: String,
+    },
+    CreateRegisteredUser {
+        user: String,
+        name: String,
+        #[serde(rename = "GAID")]
+        gaid: Option<String>,
+        is_company: bool,
+    },
+    RenameRegisteredUser {
+        user: String,
+        name: String,
+    },
+    AddUserToCategory {
+        user: String,
+        category: String,
+    },
+    RemoveUserFromCategory {
+        user: String,
+        category: String,
+    },
+    CreateManager {
+        username: String,
+    },
+    AddAssetToManager {
+        username: String,
+        asset_uuid: String,
+    },
+    RemoveAssetFromManager {
+        username: String,
+        asset_uuid: String,
+    },
+    LockManager {
+        username: String,
+    },
+    UnlockManager {
+        username: String,
+    },
+    AddTreasuryAddresses {
+        asset_uuid: String,
+        addresses: Vec<String>,
+    },
+    RemoveTreasuryAddresses {
+        asset_uuid: String,
+        addresses: Vec<String>,
+    },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CreateCategory { name, .. } => write!(f, "+ category '{name}'"),
+            Self::UpdateCategoryDescription { category, .. } => {
+                write!(f, "~ category '{category}' description")
+            }
+            Self::LinkAssetToCategory {
+                category,
+                asset_uuid,
+            } => write!(f, "+ asset {asset_uuid} in category '{category}'"),
+            Self::UnlinkAssetFromCategory {
+                category,
+                asset_uuid,
+            } => write!(f, "- asset {asset_uuid} from category '{category}'"),
+            Self::CreateRegisteredUser { user, .. } => write!(f, "+ registered user '{user}'"),
+            Self::RenameRegisteredUser { user, name } => {
+                write!(f, "~ registered user '{user}' renamed to '{name}'")
+            }
+            Self::AddUserToCategory { user, category } => {
+                write!(f, "+ registered user '{user}' in category '{category}'")
+            }
+            Self::RemoveUserFromCategory { user, category } => {
+                write!(f, "- registered user '{user}' from category '{category}'")
+            }
+            Self::CreateManager { username } => write!(f, "+ manager '{username}'"),
+            Self::AddAssetToManager {
+                username,
+                asset_uuid,
+            } => write!(f, "+ asset {asset_uuid} for manager '{username}'"),
+            Self::RemoveAssetFromManager {
+                username,
+                asset_uuid,
+            } => write!(f, "- asset {asset_uuid} from manager '{username}'"),
+            Self::LockManager { username } => write!(f, "~ lock manager '{username}'"),
+            Self::UnlockManager { username } => write!(f, "~ unlock manager '{username}'"),
+            Self::AddTreasuryAddresses {
+                asset_uuid,
+                addresses,
+            } => write!(
+                f,
+                "+ {} treasury addresses for asset {asset_uuid}",
+                addresses.len()
+            ),
+            Self::RemoveTreasuryAddresses {
+                asset_uuid,
+                addresses,
+            } => write!(
+                f,
+                "- {} treasury addresses from asset {asset_uuid}",
+                addresses.len()
+            ),
+        }
+    }
+}
+
+/// Changes that bring an AMP account to an [`EnvironmentSpec`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EnvironmentPlan {
+    /// Changes in the order they are applied
+    pub changes: Vec<Change>,
+    /// Differences that cannot be applied through the AMP API
+    pub warnings: Vec<String>,
+    /// Ids of existing categories by name
+    pub category_ids: BTreeMap<String, i64>,
+    /// Ids of existing registered users by key
+    pub user_ids: BTreeMap<String, i64>,
+    /// Ids of existing managers by username
+    pub manager_ids: BTreeMap<String, i64>,
+    /// Passwords of the managers to create; never serialised
+    #[serde(skip)]
+    pub(crate) passwords: HashMap<String, Secret<String>>,
+}
+
+impl EnvironmentPlan {
+    /// Returns `true` if the account already matches the spec
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Ids of the objects a plan refers to, including those created while applying it
+#[derive(Debug, Default)]
+pub(crate) struct ResolvedIds {
+    pub(crate) categories: BTreeMap<String, i64>,
+    pub(crate) users: BTreeMap<String, i64>,
+    pub(crate) managers: BTreeMap<String, i64>,
+}
+
+impl ResolvedIds {
+    pub(crate) fn from_plan(plan: &EnvironmentPlan) -> Self {
+        Self {
+            categories: plan.category_ids.clone(),
+            users: plan.user_ids.clone(),
+            managers: plan.manager_ids.clone(),
+        }
+    }
+
+    pub(crate) fn category(&self, name: &str) -> Result<i64, String> {
+        Self::resolve(&self.categories, "category", name)
+    }
+
+    pub(crate) fn user(&self, key: &str) -> Result<i64, String> {
+        Self::resolve(&self.users, "registered user", key)
+    }
+
+    pub(crate) fn manager(&self, username: &str) -> Result<i64, String> {
+        Self::resolve(&self.managers, "manager", username)
+    }
+
+    fn resolve(ids: &BTreeMap<String, i64>, kind: &str, key: &str) -> Result<i64, String> {
+        ids.get(key)
+            .copied()
+            .ok_or_else(|| format!("Unknown {kind} '{key}'"))
+    }
+}
+
+/// A change that could not be applied
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApplyFailure {
+    pub change: Change,
+    pub error: String,
+}
+
+/// Result of [`ApiClient::apply_environment`](crate::ApiClient::apply_environment)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApplyReport {
+    /// Changes applied, in order
+    pub applied: Vec<Change>,
+    /// The change that failed; the changes after it were not attempted
+    pub failure: Option<ApplyFailure>,
+}
+
+impl ApplyReport {
+    /// Returns `true` if every change was applied
+    #[must_use]
+    pub const fn is_complete(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn live() -> LiveEnvironment {
+        serde_json::from_value::<Vec<CategoryResponse>>(serde_json::json!([
+            {"id": 1, "name": "Accredited", "description": "Old", "registered_users": [10],
+             "assets": ["asset-a", "asset-old"]},
+            {"id": 2, "name": "Legacy", "description": null, "registered_users": [10],
+             "assets": []}
+        ]))
+        .map(|categories| LiveEnvironment {
+            categories,
+            registered_users: serde_json::from_value(serde_json::json!([
+                {"id": 10, "GAID": "GA1", "is_company": false, "name": "Alice",
+                 "categories": [1, 2], "creator": 1}
+            ]))
+            .unwrap(),
+            managers: serde_json::from_value(serde_json::json!([
+                {"username": "ops", "id": 5, "is_locked": true, "assets": ["asset-b"]}
+            ]))
+            .unwrap(),
+            treasury_addresses: HashMap::from([(
+                "asset-a".to_string(),
+                vec!["vjkeep".to_string(), "vjdrop".to_string()],
+            )]),
+        })
+        .unwrap()
+    }
+
+    fn spec() -> EnvironmentSpec {
+        EnvironmentSpec::from_json(
+            r#"{
+                "categories": [
+                    {"name": "Accredited", "description": "Accredited investors",
+                     "assets": ["asset-a", "asset-b"]},
+                    {"name": "EU"}
+                ],
+                "registered_users": [
+                    {"name": "Alice Smith", "GAID": "GA1", "is_company": true,
+                     "categories": ["Accredited", "EU"]},
+                    {"name": "Bob", "categories": ["Legacy"]}
+                ],
+                "managers": [
+                    {"username": "ops", "assets": ["asset-a"], "locked": false},
+                    {"username": "auditor", "password": "secret", "assets": []}
+                ],
+                "treasury_addresses": {"asset-a": ["vjkeep", "vjnew"]}
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_plan_lists_changes_in_dependency_order() {
+        let plan = spec().plan(&live()).unwrap();
+        let changes: Vec<String> = plan.changes.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            changes,
+            vec![
+                "~ category 'Accredited' description",
+                "+ asset asset-b in category 'Accredited'",
+                "- asset asset-old from category 'Accredited'",
+                "+ category 'EU'",
+                "~ registered user 'GA1' renamed to 'Alice Smith'",
+                "+ registered user 'GA1' in category 'EU'",
+                "- registered user 'GA1' from category 'Legacy'",
+                "+ registered user 'Bob'",
+                "+ registered user 'Bob' in category 'Legacy'",
+                "+ asset asset-a for manager 'ops'",
+                "- asset asset-b from manager 'ops'",
+                "~ unlock manager 'ops'",
+                "+ manager 'auditor'",
+                "+ 1 treasury addresses for asset asset-a",
+                "- 1 treasury addresses from asset asset-a",
+            ]
+        );
+        assert_eq!(plan.warnings.len(), 1);
+        assert_eq!(plan.category_ids["Legacy"], 2);
+        assert_eq!(plan.user_ids["GA1"], 10);
+        assert!(plan.passwords.contains_key("auditor"));
+
+        let json = serde_json::to_string(&plan).unwrap();
+        assert!(!json.contains("secret"));
+    }
+
+    #[test]
+    fn test_plan_is_empty_when_live_matches() {
+        let spec = EnvironmentSpec::from_json(
+            r#"{
+                "categories": [{"name": "Legacy", "assets": []}],
+                "registered_users": [
+                    {"name": "Alice", "GAID": "GA1", "categories": ["Accredited", "Legacy"]}
+                ],
+                "managers": [{"username": "ops", "assets": ["asset-b"]}],
+                "treasury_addresses": {"asset-a": ["vjdrop", "vjkeep"]}
+            }"#,
+        )
+        .unwrap();
+        let plan = spec.plan(&live()).unwrap();
+        assert!(plan.is_empty(), "{:?}", plan.changes);
+    }
+
+    #[test]
+    fn test_plan_rejects_invalid_specs() {
+        let mut spec = spec();
+        spec.registered_users[1].categories = Some(vec!["Unknown".to_string()]);
+        assert!(matches!(
+            spec.plan(&live()),
+            Err(EnvironmentError::UnknownCategory { .. })
+        ));
+
+        let mut spec = self::spec();
+        spec.managers[1].password = None;
+        assert!(matches!(
+            spec.plan(&live()),
+            Err(EnvironmentError::MissingManagerPassword(name)) if name == "auditor"
+        ));
+
+        let mut spec = self::spec();
+        spec.categories.push(spec.categories[0].clone());
+        assert!(matches!(
+            spec.plan(&live()),
+            Err(EnvironmentError::DuplicateCategory(_))
+        ));
+    }
+
+    #[test]
+    fn test_omitted_links_are_left_alone() {
+        let spec = EnvironmentSpec::from_json(
+            r#"{
+                "categories": [{"name": "Accredited", "description": "Old"}],
+                "registered_users": [{"name": "Alice", "GAID": "GA1"}],
+                "managers": [{"username": "ops"}]
+            }"#,
+        )
+        .unwrap();
+        let plan = spec.plan(&live()).unwrap();
+        assert!(plan.is_empty(), "{:?}", plan.changes);
+
+        // An empty list is not the same as an omitted one
+        let spec = EnvironmentSpec::from_json(
+            r#"{"registered_users": [{"name": "Alice", "GAID": "GA1", "categories": []}]}"#,
+        )
+        .unwrap();
+        let plan = spec.plan(&live()).unwrap();
+        assert_eq!(plan.changes.len(), 2);
+    }
+}
@@ -0,0 +1,184 @@
+//! # Transaction Fees
+//!
+//! Fee rates, fee strategies and size estimation for Liquid transactions.
+//!
+//! Liquid fees are paid in L-BTC through an explicit fee output and are proportional to
+//! the virtual size of the final transaction. Confidential outputs dominate that size:
+//! each carries a range proof and an asset surjection proof in its witness. Distribution
+//! transactions are built before they are signed, so [`estimate_vsize`] predicts the
+//! size of the blinded, signed transaction from its input and output counts.
+//!
+//! ## Example
+//!
+//! ```
+//! use amp_rs::fee::{estimate_vsize, FeeRate};
+//!
+//! // Two inputs, two confidential recipients plus confidential change
+//! let vsize = estimate_vsize(2, 3);
+//! let fee = FeeRate::from_sat_per_vb(1).fee_for_vsize(vsize);
+//! assert_eq!(fee.to_sat(), i64::try_from(vsize).unwrap());
+//! ```
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::amount::Amount;
+
+/// Input sequence number that signals opt-in replace-by-fee (BIP 125)
+pub const SEQUENCE_RBF: u32 = 0xffff_fffd;
+
+/// Confirmation target used when none is specified
+pub const DEFAULT_CONF_TARGET: u16 = 6;
+
+/// Version, flag, input/output counts and locktime
+const TX_OVERHEAD_BYTES: usize = 4 + 1 + 1 + 1 + 4;
+/// Outpoint, P2SH-wrapped segwit script sig and sequence
+const INPUT_BYTES: usize = 36 + 1 + 23 + 4;
+/// Empty issuance proofs, a signature and a compressed public key, empty peg-in witness
+const INPUT_WITNESS_BYTES: usize = 2 + 1 + 73 + 34 + 1;
+/// Asset, value and nonce commitments plus a script of up to 34 bytes
+const CONFIDENTIAL_OUTPUT_BYTES: usize = 33 + 33 + 33 + 1 + 34;
+/// Length prefixes of the range proof and surjection proof
+const CONFIDENTIAL_OUTPUT_WITNESS_BYTES: usize = 3 + 1;
+/// Range proof for a 52-bit blinded value (the Elements default)
+const RANGE_PROOF_BYTES: usize = 4174;
+/// Maximum number of inputs Elements uses in an asset surjection proof
+const SURJECTION_PROOF_MAX_USED_INPUTS: usize = 3;
+/// Explicit asset and value, empty nonce and empty script
+const FEE_OUTPUT_BYTES: usize = 33 + 9 + 1 + 1;
+/// Empty range proof and surjection proof
+const FEE_OUTPUT_WITNESS_BYTES: usize = 2;
+
+/// A fee rate, stored in satoshis per 1000 virtual bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FeeRate(u64);
+
+impl FeeRate {
+    /// Default minimum relay fee rate of Liquid nodes (0.1 sat/vB)
+    pub const MIN_RELAY: Self = Self(100);
+
+    /// Creates a fee rate from satoshis per 1000 virtual bytes
+    #[must_use]
+    pub const fn from_sat_per_kvb(sat_per_kvb: u64) -> Self {
+        Self(sat_per_kvb)
+    }
+
+    /// Creates a fee rate from whole satoshis per virtual byte
+    #[must_use]
+    pub const fn from_sat_per_vb(sat_per_vb: u64) -> Self {
+        Self(sat_per_vb.saturating_mul(1000))
+    }
+
+    /// Creates a fee rate from an amount per 1000 virtual bytes, the unit used by
+    /// `estimatesmartfee`
+    ///
+    /// Negative amounts are treated as zero.
+    #[must_use]
+    pub fn from_btc_per_kvb(amount: Amount) -> Self {
+        Self(u64::try_from(amount.to_sat()).unwrap_or(0))
+    }
+
+    /// Returns the fee rate in satoshis per 1000 virtual bytes
+    #[must_use]
+    pub const fn to_sat_per_kvb(self) -> u64 {
+        self.0
+    }
+
+    /// Returns the fee for a transaction of `vsize` virtual bytes, rounded up
+    #[must_use]
+    pub fn fee_for_vsize(self, vsize: usize) -> Amount {
+        let vsize = u64::try_from(vsize).unwrap_or(u64::MAX);
+        let sats = vsize.saturating_mul(self.0).div_ceil(1000);
+        Amount::from_sat(i64::try_from(sats).unwrap_or(i64::MAX))
+    }
+}
+
+impl fmt::Display for FeeRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{:03} sat/vB", self.0 / 1000, self.0 % 1000)
+    }
+}
+
+/// How the fee rate of a transaction is chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeeStrategy {
+    /// Use the given fee rate
+    Rate(FeeRate),
+    /// Ask the node's `estimatesmartfee` for a rate that confirms within this many blocks
+    ConfTarget(u16),
+}
+
+impl Default for FeeStrategy {
+    fn default() -> Self {
+        Self::ConfTarget(DEFAULT_CONF_TARGET)
+    }
+}
+
+/// Estimates the virtual size of a blinded, signed Liquid transaction
+///
+/// The estimate assumes P2SH-wrapped segwit inputs signed with a single key, that every
+/// non-fee output is confidential, and that the transaction has one explicit fee output.
+/// These assumptions over-estimate native segwit inputs and explicit outputs slightly,
+/// which keeps fees computed from the estimate above the relay minimum.
+#[must_use]
+pub fn estimate_vsize(inputs: usize, confidential_outputs: usize) -> usize {
+    let used_inputs = inputs.clamp(1, SURJECTION_PROOF_MAX_USED_INPUTS);
+    let surjection_proof_bytes = 2 + inputs.div_ceil(8) + 32 * (1 + used_inputs);
+
+    let base = TX_OVERHEAD_BYTES
+        + inputs * INPUT_BYTES
+        + confidential_outputs * CONFIDENTIAL_OUTPUT_BYTES
+        + FEE_OUTPUT_BYTES;
+    let witness = inputs * INPUT_WITNESS_BYTES
+        + confidential_outputs
+            * (CONFIDENTIAL_OUTPUT_WITNESS_BYTES + RANGE_PROOF_BYTES + surjection_proof_bytes)
+        + FEE_OUTPUT_WITNESS_BYTES;
+
+    (base * 4 + witness).div_ceil(4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_rate_conversions() {
+        assert_eq!(FeeRate::from_sat_per_vb(2).to_sat_per_kvb(), 2000);
+        assert_eq!(
+            FeeRate::from_btc_per_kvb(Amount::from_btc_str("0.00000100").unwrap()),
+            FeeRate::MIN_RELAY
+        );
+        assert_eq!(FeeRate::MIN_RELAY.to_string(), "0.100 sat/vB");
+        assert_eq!(
+            FeeRate::from_btc_per_kvb(Amount::from_sat(-5)).to_sat_per_kvb(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_fee_for_vsize_rounds_up() {
+        assert_eq!(
+            FeeRate::MIN_RELAY.fee_for_vsize(2505),
+            Amount::from_sat(251)
+        );
+        assert_eq!(
+            FeeRate::from_sat_per_vb(1).fee_for_vsize(2505),
+            Amount::from_sat(2505)
+        );
+        assert_eq!(FeeRate::MIN_RELAY.fee_for_vsize(0), Amount::ZERO);
+    }
+
+    #[test]
+    fn test_estimate_vsize_is_dominated_by_confidential_outputs() {
+        let one_output = estimate_vsize(1, 1);
+        let two_outputs = estimate_vsize(1, 2);
+        let per_output = two_outputs - one_output;
+
+        // Range proof and surjection proof are witness data, discounted by four
+        assert!((1_100..1_200).contains(&per_output), "{per_output}");
+        // Each additional input adds its outpoint and signature
+        assert!(estimate_vsize(2, 2) - two_outputs < 120);
+    }
+}
@@ -5,11 +5,28 @@
 //!
 //! ## Modules
 //!
+//! - [`amount`] - Exact satoshi amounts with BTC-style and asset precision conversions
+//! - [`batch`] - Splits large distributions into size-bounded transactions
+//! - [`cap_table`] - Point-in-time cap tables from AMP ownerships, and their differences
+//! - [`chain`] - Chain backends (Elements RPC, Esplora) for UTXO lookup and broadcast
 //! - [`client`] - HTTP API client for AMP operations
+//! - [`coin_selection`] - UTXO selection strategies for transaction building
+//! - [`confirmation`] - Shared confirmation tracking for many pending transactions
+//! - [`distribution`] - Checkpoints for resumable asset distributions
+//! - [`dividend`] - Pro-rata dividends and airdrops allocated by the largest-remainder method
+//! - [`dry_run`] - Previews of distributions, reissuances and burns
+//! - [`environment`] - Declarative AMP account specs with plan and apply
+//! - [`fee`] - Fee rates and transaction size estimation
 //! - [`model`] - Data structures for API requests and responses  
 //! - [`mocks`] - Mock implementations for testing
 //! - [`mock_client`] - Mock API client for integration testing
+//! - [`network`] - Liquid networks, policy assets and address parameters
+//! - [`onboarding`] - Idempotent bulk onboarding of investors from CSV or JSON
+//! - [`pset_builder`] - Builds blinded PSETs locally, without a node wallet
+//! - [`reconciliation`] - Reconciles asset holders across AMP and the chain
 //! - [`signer`] - Transaction signing implementations ⚠️ **TESTNET ONLY**
+//! - [`token_store`] - Pluggable persistence backends for authentication tokens
+//! - [`vesting`] - Vesting schedules expanded into assignments, and vesting tracking
 //!
 //! ## Signer Security Warning
 //!
@@ -27,21 +44,45 @@
 //! - Remote signing services with proper security
 //! - Hardware Security Modules (HSMs)
 
+pub mod amount;
+pub mod batch;
+pub mod cap_table;
+pub mod chain;
 pub mod client;
 pub mod client_trait;
+pub mod coin_selection;
+pub mod confirmation;
+pub mod distribution;
+pub mod dividend;
+pub mod dry_run;
+mod encryption;
+pub mod environment;
+pub mod fee;
 pub mod mock_client;
 pub mod mocks;
 pub mod model;
+pub mod network;
+pub mod onboarding;
+pub mod pset_builder;
+pub mod reconciliation;
 pub mod signer;
+pub mod token_store;
+pub mod vesting;
 
-pub use client::{AmpError, ApiClient, ElementsRpc, Error};
+pub use amount::Amount;
+pub use client::{
+    AmpError, ApiClient, ApiClientBuilder, Credentials, ElementsRpc, Error, TokenAccountKey,
+};
 pub use client_trait::AmpClient;
 pub use mock_client::MockApiClient;
 pub use model::{
     AssetDistributionAssignment, AssetTransaction, AssetTransactionInput, AssetTransactionOutput,
     AssetTransactionParams, BurnConfirmRequest, BurnCreate, BurnRequest, BurnResponse,
-    ConfirmDistributionRequest, DistributionResponse, DistributionTxData, ProgressUpdate,
-    ReceivedByAddress, Reissuance, ReissueConfirmRequest, ReissueRequest, ReissueRequestResponse,
-    ReissueResponse, TransactionDetail, TxInput, Unspent, UpdateBlindersRequest,
+    ConfirmDistributionRequest, DistributionResponse, DistributionTransaction, DistributionTxData,
+    ProgressUpdate, ReceivedByAddress, Reissuance, ReissueConfirmRequest, ReissueRequest,
+    ReissueRequestResponse, ReissueResponse, TransactionDetail, TxInput, Unspent,
+    UpdateBlindersRequest,
 };
+pub use network::Network;
 pub use signer::{LwkSoftwareSigner, Signer, SignerError};
+pub use token_store::{EncryptedFileTokenStore, FileTokenStore, InMemoryTokenStore, TokenStore};
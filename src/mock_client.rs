@@ -84,6 +84,7 @@ use std::sync::Mutex;
 
 use secrecy::ExposeSecret;
 
+use crate::amount::Amount;
 use crate::client::{AmpError, Error};
 use crate::model::{
     Activity, AddressGaidResponse, Asset, AssetActivityParams, AssetSummary, Assignment, Balance,
@@ -1526,7 +1527,7 @@ impl MockApiClient {
             base_url: "https://amp-test.blockstream.com/api".to_string(),
             asset_uuid: asset_uuid.to_string(),
             asset_id: asset.asset_id.clone(),
-            amount: request.amount_to_reissue as f64,
+            amount: Amount::from_sat(request.amount_to_reissue),
             reissuance_utxos: vec![Outpoint {
                 txid: format!("{:064x}", 12345),
                 vout: 0,
@@ -2000,7 +2001,7 @@ impl MockApiClient {
             base_url: "https://amp-test.blockstream.com/api".to_string(),
             asset_uuid: asset_uuid.to_string(),
             asset_id: asset.asset_id.clone(),
-            amount: amount as f64,
+            amount: Amount::from_sat(amount),
             utxos: vec![Outpoint {
                 txid: format!("{:064x}", 67890),
                 vout: 0,
@@ -7,6 +7,9 @@ use std::collections::HashMap;
 
 use zeroize::Zeroize;
 
+use crate::amount::{Amount, AmountError};
+use crate::fee::FeeRate;
+
 /// Request payload for AMP token acquisition
 #[derive(Debug, Serialize)]
 pub struct TokenRequest {
@@ -79,6 +82,25 @@ pub struct Asset {
     pub transfer_restricted: bool,
 }
 
+impl Asset {
+    /// Parses an amount expressed in this asset's display units (see [`Asset::precision`])
+    ///
+    /// # Errors
+    /// Returns an error if the amount has more decimal places than the asset's precision,
+    /// or if the asset's precision is invalid
+    pub fn parse_amount(&self, amount: &str) -> Result<Amount, AmountError> {
+        Amount::from_asset_units(amount, self.precision)
+    }
+
+    /// Formats an amount in this asset's display units
+    ///
+    /// # Errors
+    /// Returns an error if the asset's precision is invalid
+    pub fn format_amount(&self, amount: Amount) -> Result<String, AmountError> {
+        amount.to_asset_units_string(self.precision)
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct IssuanceRequest {
     pub name: String,
@@ -321,7 +343,7 @@ pub struct Activity {
     pub amount: i64,
 }
 
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Default, Clone)]
 pub struct AssetActivityParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub start: Option<i64>,
@@ -574,7 +596,7 @@ pub struct ReissueRequestResponse {
     pub base_url: String,
     pub asset_uuid: String,
     pub asset_id: String,
-    pub amount: f64,
+    pub amount: Amount,
     pub reissuance_utxos: Vec<Outpoint>,
 }
 
@@ -606,7 +628,7 @@ pub struct BurnCreate {
     pub base_url: String,
     pub asset_uuid: String,
     pub asset_id: String,
-    pub amount: f64,
+    pub amount: Amount,
     pub utxos: Vec<Outpoint>,
 }
 
@@ -772,14 +794,14 @@ impl From<&TokenData> for TokenInfo {
 pub struct AssetDistributionAssignment {
     pub user_id: String,
     pub address: String,
-    pub amount: f64,
+    pub amount: Amount,
 }
 
 /// Assignment for distribution creation API request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DistributionAssignmentRequest {
     pub user_uuid: String,
-    pub amount: f64,
+    pub amount: Amount,
     pub address: String,
 }
 
@@ -794,7 +816,7 @@ pub struct CreateDistributionRequest {
 pub struct Unspent {
     pub txid: String,
     pub vout: u32,
-    pub amount: f64,
+    pub amount: Amount,
     pub asset: String,
     pub address: String,
     pub spendable: bool,
@@ -818,6 +840,12 @@ pub struct Unspent {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionDetail {
     pub txid: String,
+    /// Confirmations of the transaction
+    ///
+    /// The node reports negative confirmations for a wallet transaction that conflicts
+    /// with a transaction in the chain, such as one double-spent after a reorg; these are
+    /// read as 0.
+    #[serde(deserialize_with = "deserialize_confirmations")]
     pub confirmations: u32,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub blockheight: Option<u64>,
@@ -835,6 +863,30 @@ pub struct TransactionDetail {
     pub details: Option<Vec<serde_json::Value>>,
 }
 
+/// Reads a possibly negative `gettransaction` confirmation count, clamping it to 0
+fn deserialize_confirmations<'de, D>(deserializer: D) -> Result<u32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let confirmations = i64::deserialize(deserializer)?;
+    Ok(u32::try_from(confirmations.max(0)).unwrap_or(u32::MAX))
+}
+
+/// Wallet transaction entry from Elements listtransactions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletTransaction {
+    pub txid: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    pub category: String,
+    pub amount: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    pub time: i64,
+}
+
 /// Transaction output detail from Elements gettransaction details array
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionOutputDetail {
@@ -876,11 +928,53 @@ pub struct TxInput {
     pub sequence: Option<u32>,
 }
 
+/// Unsigned distribution transaction built by
+/// [`ElementsRpc::build_distribution_transaction_with_selector`](crate::ElementsRpc::build_distribution_transaction_with_selector)
+///
+/// Keep this value until the transaction confirms; it is needed to replace the
+/// transaction with a higher fee through
+/// [`ElementsRpc::bump_distribution_fee`](crate::ElementsRpc::bump_distribution_fee).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DistributionTransaction {
+    /// Blinded raw transaction hex, ready for signing
+    pub raw_transaction: String,
+    /// Asset UTXOs spent by the transaction
+    pub asset_inputs: Vec<Unspent>,
+    /// L-BTC UTXOs spent to pay the fee
+    pub fee_inputs: Vec<Unspent>,
+    /// Asset change sent back to the change address
+    pub asset_change: Amount,
+    /// L-BTC change sent back to the change address
+    pub fee_change: Amount,
+    /// Fee paid by the transaction in L-BTC
+    pub fee: Amount,
+    /// Fee rate the fee was computed from
+    pub fee_rate: FeeRate,
+    /// Estimated virtual size of the signed transaction
+    pub vsize: usize,
+    /// Base64 PSET of a transaction built without the node wallet, signed in place of
+    /// `raw_transaction`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pset: Option<String>,
+}
+
+impl DistributionTransaction {
+    /// Returns all UTXOs spent by the transaction, asset inputs first
+    #[must_use]
+    pub fn inputs(&self) -> Vec<Unspent> {
+        self.asset_inputs
+            .iter()
+            .chain(&self.fee_inputs)
+            .cloned()
+            .collect()
+    }
+}
+
 /// Response from distribution creation API
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DistributionResponse {
     pub distribution_uuid: String,
-    pub map_address_amount: std::collections::HashMap<String, f64>,
+    pub map_address_amount: std::collections::HashMap<String, Amount>,
     pub map_address_asset: std::collections::HashMap<String, String>,
     pub asset_id: String,
 }
@@ -931,7 +1025,7 @@ mod tests {
         let assignment = AssetDistributionAssignment {
             user_id: "user123".to_string(),
             address: "lq1qq2xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq".to_string(),
-            amount: 100.5,
+            amount: Amount::from_btc(100.5).unwrap(),
         };
 
         assert_eq!(assignment.user_id, "user123");
@@ -939,7 +1033,7 @@ mod tests {
             assignment.address,
             "lq1qq2xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq"
         );
-        assert_eq!(assignment.amount, 100.5);
+        assert_eq!(assignment.amount, Amount::from_btc(100.5).unwrap());
     }
 
     #[test]
@@ -947,7 +1041,7 @@ mod tests {
         let assignment = AssetDistributionAssignment {
             user_id: "user123".to_string(),
             address: "lq1qq2xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq".to_string(),
-            amount: 100.5,
+            amount: Amount::from_btc(100.5).unwrap(),
         };
 
         // Test serialization
@@ -968,7 +1062,7 @@ mod tests {
         let assignment = AssetDistributionAssignment {
             user_id: "user123".to_string(),
             address: "lq1qq2xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq".to_string(),
-            amount: 100.5,
+            amount: Amount::from_btc(100.5).unwrap(),
         };
 
         let cloned = assignment.clone();
@@ -982,7 +1076,7 @@ mod tests {
         let unspent = Unspent {
             txid: "abc123def456".to_string(),
             vout: 1,
-            amount: 50.0,
+            amount: Amount::from_btc(50.0).unwrap(),
             asset: "asset_id_hex".to_string(),
             address: "lq1qq2xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq".to_string(),
             spendable: true,
@@ -1013,6 +1107,19 @@ mod tests {
         assert_eq!(deserialized.confirmations, unspent.confirmations);
     }
 
+    #[test]
+    fn test_transaction_detail_negative_confirmations() {
+        let tx_detail: TransactionDetail = serde_json::from_value(serde_json::json!({
+            "txid": "def456abc123",
+            "confirmations": -1,
+            "hex": "0200",
+            "walletconflicts": ["abc123def456"]
+        }))
+        .unwrap();
+        assert_eq!(tx_detail.confirmations, 0);
+        assert_eq!(tx_detail.blockhash, None);
+    }
+
     #[test]
     fn test_transaction_detail_creation() {
         let tx_detail = TransactionDetail {
@@ -1059,8 +1166,8 @@ mod tests {
     #[test]
     fn test_distribution_response_creation() {
         let mut map_address_amount = HashMap::new();
-        map_address_amount.insert("address1".to_string(), 100.0);
-        map_address_amount.insert("address2".to_string(), 50.0);
+        map_address_amount.insert("address1".to_string(), Amount::from_btc(100.0).unwrap());
+        map_address_amount.insert("address2".to_string(), Amount::from_btc(50.0).unwrap());
 
         let mut map_address_asset = HashMap::new();
         map_address_asset.insert("address1".to_string(), "asset_id_1".to_string());
@@ -1090,12 +1197,12 @@ mod tests {
     fn test_distribution_assignment_request_creation() {
         let assignment_request = DistributionAssignmentRequest {
             user_uuid: "user_uuid_123".to_string(),
-            amount: 150.0,
+            amount: Amount::from_btc(150.0).unwrap(),
             address: "lq1qq2xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq".to_string(),
         };
 
         assert_eq!(assignment_request.user_uuid, "user_uuid_123");
-        assert_eq!(assignment_request.amount, 150.0);
+        assert_eq!(assignment_request.amount, Amount::from_btc(150.0).unwrap());
         assert_eq!(
             assignment_request.address,
             "lq1qq2xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq"
@@ -1119,12 +1226,12 @@ mod tests {
         let assignments = vec![
             DistributionAssignmentRequest {
                 user_uuid: "user1".to_string(),
-                amount: 100.0,
+                amount: Amount::from_btc(100.0).unwrap(),
                 address: "address1".to_string(),
             },
             DistributionAssignmentRequest {
                 user_uuid: "user2".to_string(),
-                amount: 50.0,
+                amount: Amount::from_btc(50.0).unwrap(),
                 address: "address2".to_string(),
             },
         ];
@@ -1135,7 +1242,10 @@ mod tests {
 
         assert_eq!(create_request.assignments.len(), 2);
         assert_eq!(create_request.assignments[0].user_uuid, "user1");
-        assert_eq!(create_request.assignments[1].amount, 50.0);
+        assert_eq!(
+            create_request.assignments[1].amount,
+            Amount::from_btc(50.0).unwrap()
+        );
 
         // Test serialization
         let json = serde_json::to_string(&create_request).unwrap();
@@ -1156,7 +1266,7 @@ mod tests {
     fn test_distribution_request_serialization_format() {
         let assignment = DistributionAssignmentRequest {
             user_uuid: "test_user".to_string(),
-            amount: 123.45,
+            amount: Amount::from_btc(123.45).unwrap(),
             address: "test_address".to_string(),
         };
 
@@ -1238,7 +1348,7 @@ mod tests {
         let change_utxo = Unspent {
             txid: "change_txid_123".to_string(),
             vout: 1,
-            amount: 25.0,
+            amount: Amount::from_btc(25.0).unwrap(),
             asset: "change_asset_id".to_string(),
             address: "change_address".to_string(),
             spendable: true,
@@ -1394,6 +1504,18 @@ pub enum ProgressUpdate {
     },
     /// Transaction sent (provides txid for tracking)
     TxSent { txid: String },
+    /// A confirmed transaction lost confirmations because of a chain reorganization
+    Reorg {
+        txid: String,
+        previous_confirmations: u32,
+        current: u32,
+    },
+    /// A batch of a batch distribution is starting
+    Batch {
+        current: u32,
+        total: u32,
+        assignments: usize,
+    },
 }
 
 /// Custom serialization module for Secret<String>
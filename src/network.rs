@@ -0,0 +1,241 @@
+//! # Liquid Networks
+//!
+//! The network an Elements node runs on determines its policy asset (the asset fees are
+//! paid in, L-BTC on Liquid) and the address encoding. [`Network`] captures both so that
+//! transaction building, address validation and signer descriptors agree with the node.
+//!
+//! [`ElementsRpc`](crate::ElementsRpc) can be configured with a network explicitly, or
+//! detects it from the `chain` reported by `getblockchaininfo`.
+//!
+//! ## Example
+//!
+//! ```
+//! use amp_rs::network::Network;
+//!
+//! let network: Network = "liquidtestnet".parse().unwrap();
+//! assert_eq!(network, Network::LiquidTestnet);
+//! assert_eq!(
+//!     network.policy_asset(),
+//!     "144c654344aa716d6f3abcc1ca90e5641e4e2a7f633bc09fe3baf64585819a49"
+//! );
+//!
+//! let regtest = Network::elements_regtest(
+//!     "5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225",
+//! )
+//! .unwrap();
+//! assert!(!regtest.is_mainnet());
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use elements::{Address, AddressParams};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Policy asset (L-BTC) of Liquid mainnet
+pub const LIQUID_POLICY_ASSET: &str =
+    "6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d";
+
+/// Policy asset (tL-BTC) of Liquid testnet
+pub const LIQUID_TESTNET_POLICY_ASSET: &str =
+    "144c654344aa716d6f3abcc1ca90e5641e4e2a7f633bc09fe3baf64585819a49";
+
+/// Policy asset of an `elementsregtest` chain started with the default genesis parameters
+pub const ELEMENTS_REGTEST_POLICY_ASSET: &str =
+    "5ac9f65c0efcc4775e0baec4ec03abdde22473cd3cf33c0419ca290e0751b225";
+
+/// Errors produced when resolving networks or validating addresses against them
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum NetworkError {
+    #[error("Unknown Elements chain '{0}'")]
+    UnknownChain(String),
+    #[error("Invalid policy asset id '{0}' (must be 64 hex characters)")]
+    InvalidPolicyAsset(String),
+    #[error("Invalid {network} address '{address}': {reason}")]
+    InvalidAddress {
+        network: String,
+        address: String,
+        reason: String,
+    },
+}
+
+/// An Elements network
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Network {
+    /// Liquid mainnet (`liquidv1`)
+    Liquid,
+    /// Liquid testnet (`liquidtestnet`)
+    LiquidTestnet,
+    /// A local `elementsregtest` chain and its policy asset
+    ElementsRegtest { policy_asset: String },
+}
+
+impl Network {
+    /// Creates a regtest network with the given policy asset
+    ///
+    /// # Errors
+    /// Returns an error if `policy_asset` is not a 32-byte hex asset id
+    pub fn elements_regtest(policy_asset: &str) -> Result<Self, NetworkError> {
+        if policy_asset.len() != 64 || !policy_asset.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(NetworkError::InvalidPolicyAsset(policy_asset.to_string()));
+        }
+
+        Ok(Self::ElementsRegtest {
+            policy_asset: policy_asset.to_ascii_lowercase(),
+        })
+    }
+
+    /// Resolves the network from the `chain` field of `getblockchaininfo`
+    ///
+    /// `policy_asset` is only used for regtest chains; when `None` the default regtest
+    /// policy asset is assumed.
+    ///
+    /// # Errors
+    /// Returns an error for unknown chains or an invalid policy asset
+    pub fn from_chain(chain: &str, policy_asset: Option<&str>) -> Result<Self, NetworkError> {
+        match chain {
+            "liquidv1" => Ok(Self::Liquid),
+            "liquidtestnet" => Ok(Self::LiquidTestnet),
+            "elementsregtest" | "liquidregtest" => {
+                Self::elements_regtest(policy_asset.unwrap_or(ELEMENTS_REGTEST_POLICY_ASSET))
+            }
+            other => Err(NetworkError::UnknownChain(other.to_string())),
+        }
+    }
+
+    /// Returns the chain name used by Elements for this network
+    #[must_use]
+    pub const fn chain_name(&self) -> &'static str {
+        match self {
+            Self::Liquid => "liquidv1",
+            Self::LiquidTestnet => "liquidtestnet",
+            Self::ElementsRegtest { .. } => "elementsregtest",
+        }
+    }
+
+    /// Returns the id of the asset transaction fees are paid in
+    #[must_use]
+    pub fn policy_asset(&self) -> &str {
+        match self {
+            Self::Liquid => LIQUID_POLICY_ASSET,
+            Self::LiquidTestnet => LIQUID_TESTNET_POLICY_ASSET,
+            Self::ElementsRegtest { policy_asset } => policy_asset,
+        }
+    }
+
+    /// Returns the address encoding parameters of this network
+    #[must_use]
+    pub const fn address_params(&self) -> &'static AddressParams {
+        match self {
+            Self::Liquid => &AddressParams::LIQUID,
+            Self::LiquidTestnet => &AddressParams::LIQUID_TESTNET,
+            Self::ElementsRegtest { .. } => &AddressParams::ELEMENTS,
+        }
+    }
+
+    /// Returns `true` for Liquid mainnet
+    #[must_use]
+    pub const fn is_mainnet(&self) -> bool {
+        matches!(self, Self::Liquid)
+    }
+
+    /// Returns the BIP44 coin type used in derivation paths (1776 on mainnet, 1 otherwise)
+    #[must_use]
+    pub const fn coin_type(&self) -> u32 {
+        if self.is_mainnet() {
+            1776
+        } else {
+            1
+        }
+    }
+
+    /// Parses `address` and checks that it belongs to this network
+    ///
+    /// # Errors
+    /// Returns an error if the address is malformed or encoded for another network
+    pub fn validate_address(&self, address: &str) -> Result<Address, NetworkError> {
+        Address::parse_with_params(address, self.address_params()).map_err(|e| {
+            NetworkError::InvalidAddress {
+                network: self.to_string(),
+                address: address.to_string(),
+                reason: e.to_string(),
+            }
+        })
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.chain_name())
+    }
+}
+
+impl FromStr for Network {
+    type Err = NetworkError;
+
+    /// Parses a chain name; regtest chains use the default policy asset
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_chain(s, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_chain() {
+        assert_eq!(
+            Network::from_chain("liquidv1", None).unwrap(),
+            Network::Liquid
+        );
+        assert_eq!(
+            Network::from_chain("elementsregtest", None)
+                .unwrap()
+                .policy_asset(),
+            ELEMENTS_REGTEST_POLICY_ASSET
+        );
+
+        let custom = "AB".repeat(32);
+        let regtest = Network::from_chain("elementsregtest", Some(&custom)).unwrap();
+        assert_eq!(regtest.policy_asset(), "ab".repeat(32));
+
+        assert_eq!(
+            Network::from_chain("main", None).unwrap_err(),
+            NetworkError::UnknownChain("main".to_string())
+        );
+        assert!(Network::elements_regtest("not-an-asset").is_err());
+    }
+
+    fn address_for(params: &'static AddressParams) -> String {
+        let secp = elements::secp256k1_zkp::Secp256k1::new();
+        let secret_key = elements::secp256k1_zkp::SecretKey::from_slice(&[1u8; 32]).unwrap();
+        let public_key = secret_key.public_key(&secp);
+        Address::p2wpkh(
+            &elements::bitcoin::PublicKey::new(public_key),
+            Some(public_key),
+            params,
+        )
+        .to_string()
+    }
+
+    #[test]
+    fn test_validate_address_checks_network() {
+        let testnet_address = address_for(&AddressParams::LIQUID_TESTNET);
+        let regtest_address = address_for(&AddressParams::ELEMENTS);
+
+        assert!(Network::LiquidTestnet
+            .validate_address(&testnet_address)
+            .is_ok());
+        assert!(Network::LiquidTestnet
+            .validate_address(&regtest_address)
+            .is_err());
+        assert!(Network::elements_regtest(ELEMENTS_REGTEST_POLICY_ASSET)
+            .unwrap()
+            .validate_address(&regtest_address)
+            .is_ok());
+        assert!(Network::Liquid.validate_address("not an address").is_err());
+    }
+}
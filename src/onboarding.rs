@@ -0,0 +1,358 @@
+//! # Bulk Investor Onboarding
+//!
+//! Onboarding an investor takes several AMP calls: creating the registered user,
+//! attaching a GAID, making it the default and adding the user to categories.
+//! [`ApiClient::import_investors`](crate::ApiClient::import_investors) runs this for a
+//! list of [`InvestorRecord`]s, read from CSV ([`InvestorRecord::from_csv`]) or JSON
+//! ([`InvestorRecord::from_json`]).
+//!
+//! The import is idempotent. Each investor is looked up by GAID and only the missing
+//! changes are applied, so a file can be imported again after a partial failure:
+//!
+//! - a GAID that fails `validate_gaid` is rejected
+//! - an unknown GAID creates a registered user with that GAID
+//! - for an existing user, the name is updated if it differs and the user is added to
+//!   the categories they are not in yet
+//!
+//! Categories are only ever added, never removed. `is_company` cannot be changed
+//! through the AMP API, so a difference is reported as a warning. The default GAID of
+//! an existing user is left alone: a record naming another GAID of the user is reported
+//! as a warning, as changing the default would redirect the user's distributions.
+//!
+//! Every row is processed, even after another row failed, and the outcome of each is
+//! returned in an [`OnboardingReport`].
+//!
+//! ## CSV format
+//!
+//! The first line is a header naming the columns `name` and `GAID`, and optionally
+//! `categories` and `is_company`, in any order. Categories are separated by `;` and are
+//! category ids or names. `is_company` accepts `true`/`false`, `yes`/`no` or `1`/`0`
+//! and defaults to `false`. Fields containing commas are quoted:
+//!
+//! ```text
+//! name,GAID,categories,is_company
+//! Alice,GAbYScu6jkWUND2jo3L4KJxyvo55d,Accredited;EU,false
+//! "Acme, Inc",GA2Pvbe8B6oTqv1kjrHgcnM2ttcAw,42,true
+//! ```
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use amp_rs::ApiClient;
+//! # use amp_rs::onboarding::InvestorRecord;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = ApiClient::new().await?;
+//! let investors = InvestorRecord::from_csv(&std::fs::read_to_string("investors.csv")?)?;
+//!
+//! let report = client.import_investors(&investors).await?;
+//! for result in report.failed() {
+//!     println!("row {} ({}): {:?}", result.row, result.gaid, result.outcome);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Errors produced when reading investor records
+#[derive(Error, Debug)]
+pub enum OnboardingError {
+    #[error("Investor CSV is missing the {0} column")]
+    MissingColumn(&'static str),
+    #[error("Invalid investor CSV at line {line}: {message}")]
+    Csv { line: usize, message: String },
+    #[error("Invalid investor JSON: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// A category given by id or by name
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CategoryRef {
+    Id(i64),
+    Name(String),
+}
+
+/// One investor to onboard
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InvestorRecord {
+    pub name: String,
+    #[serde(rename = "GAID")]
+    pub gaid: String,
+    #[serde(default)]
+    pub categories: Vec<CategoryRef>,
+    #[serde(default)]
+    pub is_company: bool,
+}
+
+impl InvestorRecord {
+    /// Reads investor records from CSV, see the [module documentation](self)
+    ///
+    /// # Errors
+    /// Returns an error if a required column is missing or a line is malformed
+    pub fn from_csv(input: &str) -> Result<Vec<Self>, OnboardingError> {
+        let mut lines = parse_csv(input)?.into_iter();
+        let Some((_, header)) = lines.next() else {
+            return Err(OnboardingError::MissingColumn("name"));
+        };
+        let column = |name: &str| {
+            header
+                .iter()
+                .position(|h| h.trim().eq_ignore_ascii_case(name))
+        };
+        let name_column = column("name").ok_or(OnboardingError::MissingColumn("name"))?;
+        let gaid_column = column("gaid").ok_or(OnboardingError::MissingColumn("GAID"))?;
+        let categories_column = column("categories");
+        let company_column = column("is_company");
+
+        lines
+            .map(|(line, fields)| {
+                let field = |index: Option<usize>| {
+                    index
+                        .and_then(|i| fields.get(i))
+                        .map_or("", |value| value.trim())
+                };
+                let error = |message: String| OnboardingError::Csv { line, message };
+
+                let name = field(Some(name_column));
+                let gaid = field(Some(gaid_column));
+                if name.is_empty() || gaid.is_empty() {
+                    return Err(error("name and GAID are required".to_string()));
+                }
+                let categories = field(categories_column)
+                    .split(';')
+                    .map(str::trim)
+                    .filter(|category| !category.is_empty())
+                    .map(|category| {
+                        category.parse().map_or_else(
+                            |_| CategoryRef::Name(category.to_string()),
+                            CategoryRef::Id,
+                        )
+                    })
+                    .collect();
+                let is_company = match field(company_column).to_ascii_lowercase().as_str() {
+                    "" | "false" | "no" | "0" => false,
+                    "true" | "yes" | "1" => true,
+                    other => return Err(error(format!("invalid is_company value '{other}'"))),
+                };
+                Ok(Self {
+                    name: name.to_string(),
+                    gaid: gaid.to_string(),
+                    categories,
+                    is_company,
+                })
+            })
+            .collect()
+    }
+
+    /// Reads investor records from a JSON array of objects with the fields `name`,
+    /// `GAID`, `categories` (ids or names) and `is_company`
+    ///
+    /// # Errors
+    /// Returns an error if the JSON does not describe a list of investors
+    pub fn from_json(input: &str) -> Result<Vec<Self>, OnboardingError> {
+        Ok(serde_json::from_str(input)?)
+    }
+}
+
+/// Splits CSV input into records, each with the line it starts on
+///
+/// Follows RFC 4180: fields may be quoted, quotes inside quoted fields are doubled and
+/// quoted fields may span lines. Blank lines are skipped.
+fn parse_csv(input: &str) -> Result<Vec<(usize, Vec<String>)>, OnboardingError> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', true) => in_quotes = false,
+            ('"', false) if field.is_empty() => in_quotes = true,
+            (',', false) => fields.push(std::mem::take(&mut field)),
+            ('\r', false) => {}
+            ('\n', false) => {
+                fields.push(std::mem::take(&mut field));
+                if fields.iter().any(|f| !f.is_empty()) {
+                    records.push((record_line, std::mem::take(&mut fields)));
+                }
+                fields.clear();
+                line += 1;
+                record_line = line;
+            }
+            ('\n', true) => {
+                field.push(c);
+                line += 1;
+            }
+            _ => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(OnboardingError::Csv {
+            line: record_line,
+            message: "unterminated quoted field".to_string(),
+        });
+    }
+    fields.push(field);
+    if fields.iter().any(|f| !f.is_empty()) {
+        records.push((record_line, fields));
+    }
+    Ok(records)
+}
+
+/// A change applied to an existing registered user
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum UserChange {
+    Renamed { from: String, to: String },
+    AddedToCategory { category_id: i64 },
+}
+
+/// What happened to one investor record
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum RowOutcome {
+    /// A new registered user was created
+    Created { changes: Vec<UserChange> },
+    /// An existing registered user was brought up to date
+    Updated { changes: Vec<UserChange> },
+    /// The registered user already matched the record
+    Unchanged,
+    /// The record was rejected or a call failed; changes made before the failure are kept
+    Failed { error: String },
+}
+
+/// Result of importing one investor record
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RowResult {
+    /// 1-based position of the record in the input
+    pub row: usize,
+    #[serde(rename = "GAID")]
+    pub gaid: String,
+    pub registered_user: Option<i64>,
+    pub outcome: RowOutcome,
+    /// Differences from the record that were not applied
+    pub warnings: Vec<String>,
+}
+
+/// Per-row results of [`ApiClient::import_investors`](crate::ApiClient::import_investors)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OnboardingReport {
+    pub results: Vec<RowResult>,
+}
+
+impl OnboardingReport {
+    /// Returns the rows that created a registered user
+    #[must_use]
+    pub fn created(&self) -> Vec<&RowResult> {
+        self.filter(|outcome| matches!(outcome, RowOutcome::Created { .. }))
+    }
+
+    /// Returns the rows that changed an existing registered user
+    #[must_use]
+    pub fn updated(&self) -> Vec<&RowResult> {
+        self.filter(|outcome| matches!(outcome, RowOutcome::Updated { .. }))
+    }
+
+    /// Returns the rows that needed no change
+    #[must_use]
+    pub fn unchanged(&self) -> Vec<&RowResult> {
+        self.filter(|outcome| *outcome == RowOutcome::Unchanged)
+    }
+
+    /// Returns the rows that failed
+    #[must_use]
+    pub fn failed(&self) -> Vec<&RowResult> {
+        self.filter(|outcome| matches!(outcome, RowOutcome::Failed { .. }))
+    }
+
+    /// Returns `true` if no row failed
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.failed().is_empty()
+    }
+
+    fn filter(&self, predicate: impl Fn(&RowOutcome) -> bool) -> Vec<&RowResult> {
+        self.results
+            .iter()
+            .filter(|result| predicate(&result.outcome))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_csv_reads_quoted_fields_and_categories() {
+        let csv = "GAID,Name,is_company,categories\r\n\
+                   GA1,Alice,,Accredited; 7\r\n\
+                   \r\n\
+                   GA2,\"Acme, \"\"The\"\" Company\",yes,\n\
+                   GA3,\"Multi\nLine\",0,EU\n";
+        let records = InvestorRecord::from_csv(csv).unwrap();
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(
+            records[0],
+            InvestorRecord {
+                name: "Alice".to_string(),
+                gaid: "GA1".to_string(),
+                categories: vec![
+                    CategoryRef::Name("Accredited".to_string()),
+                    CategoryRef::Id(7)
+                ],
+                is_company: false,
+            }
+        );
+        assert_eq!(records[1].name, "Acme, \"The\" Company");
+        assert!(records[1].is_company);
+        assert!(records[1].categories.is_empty());
+        assert_eq!(records[2].name, "Multi\nLine");
+    }
+
+    #[test]
+    fn test_from_csv_reports_bad_lines() {
+        assert!(matches!(
+            InvestorRecord::from_csv("name,categories\nAlice,EU\n"),
+            Err(OnboardingError::MissingColumn("GAID"))
+        ));
+        assert!(matches!(
+            InvestorRecord::from_csv("name,GAID,is_company\nAlice,GA1,no\nBob,GA2,maybe\n"),
+            Err(OnboardingError::Csv { line: 3, .. })
+        ));
+        assert!(matches!(
+            InvestorRecord::from_csv("name,GAID\nAlice,\n"),
+            Err(OnboardingError::Csv { line: 2, .. })
+        ));
+        assert!(matches!(
+            InvestorRecord::from_csv("name,GAID\n\"Alice,GA1\n"),
+            Err(OnboardingError::Csv { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_from_json_accepts_category_ids_and_names() {
+        let records = InvestorRecord::from_json(
+            r#"[{"name": "Alice", "GAID": "GA1", "categories": [3, "EU"]},
+                {"name": "Acme", "GAID": "GA2", "is_company": true}]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            records[0].categories,
+            vec![CategoryRef::Id(3), CategoryRef::Name("EU".to_string())]
+        );
+        assert!(records[1].is_company);
+        assert!(InvestorRecord::from_json(r#"[{"name": "Alice"}]"#).is_err());
+    }
+}
@@ -0,0 +1,738 @@
+//! # Watch-only PSET Builder
+//!
+//! Builds blinded PSETs locally, without an Elements node wallet. The node path
+//! (`createrawtransaction` followed by `blindrawtransaction`) needs a loaded wallet that
+//! holds the blinding keys of every input. [`PsetBuilder`] instead takes the unblinded
+//! amounts and blinding factors of the inputs directly from their [`Unspent`] entries,
+//! so transactions can be built against a pruned node or an Esplora-style backend and
+//! handed to any [`Signer`](crate::signer::Signer) that understands PSETs.
+//!
+//! Each input must carry its `scriptpubkey`, and confidential inputs must carry their
+//! `amountblinder` and `assetblinder`. Inputs whose blinders are missing or all zero are
+//! treated as explicit outputs. Recipients and the change address must be confidential
+//! addresses, since their blinding public keys are what the outputs are blinded to.
+//!
+//! Besides payments, a PSET can destroy an amount of an asset
+//! ([`add_burn`](PsetBuilder::add_burn)) and reissue an asset whose reissuance token is
+//! among the inputs ([`add_reissuance`](PsetBuilder::add_reissuance)).
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use amp_rs::fee::FeeRate;
+//! use amp_rs::pset_builder::PsetBuilder;
+//! use amp_rs::{Amount, Network, Unspent};
+//!
+//! # fn example(utxos: Vec<Unspent>) -> Result<(), Box<dyn std::error::Error>> {
+//! let pset = PsetBuilder::new(Network::LiquidTestnet)
+//!     .add_inputs(utxos)
+//!     .add_recipient("tlq1qq...", "asset_id_hex", Amount::from_sat(1_000))
+//!     .change_address("tlq1qq...")
+//!     .fee_rate(FeeRate::MIN_RELAY)
+//!     .build()?;
+//! println!("{}", amp_rs::signer::pset::to_base64(&pset));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+
+use elements::confidential::{self, AssetBlindingFactor, ValueBlindingFactor};
+use elements::hashes::sha256;
+use elements::pset::{Input, Output, PartiallySignedTransaction};
+use elements::secp256k1_zkp::{self, Secp256k1};
+use elements::{Address, AssetId, OutPoint, Script, Sequence, TxOut, TxOutSecrets, Txid};
+use thiserror::Error;
+
+use crate::amount::Amount;
+use crate::fee::{self, FeeRate};
+use crate::model::Unspent;
+use crate::network::Network;
+
+/// Errors produced while building a PSET
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PsetBuilderError {
+    #[error("At least one input is required")]
+    NoInputs,
+    #[error("At least one recipient is required")]
+    NoRecipients,
+    #[error("Invalid input {index}: {reason}")]
+    InvalidInput { index: usize, reason: String },
+    #[error("Invalid recipient '{address}': {reason}")]
+    InvalidRecipient { address: String, reason: String },
+    #[error("Address '{0}' is not confidential")]
+    NotConfidential(String),
+    #[error("Insufficient funds for asset {asset}: need {needed}, have {available}")]
+    InsufficientFunds {
+        asset: String,
+        needed: Amount,
+        available: Amount,
+    },
+    #[error("A change address is required to return {amount} of asset {asset}")]
+    MissingChangeAddress { asset: String, amount: Amount },
+    #[error("Invalid reissuance of asset entropy '{entropy}': {reason}")]
+    InvalidReissuance { entropy: String, reason: String },
+    #[error("Blinding failed: {0}")]
+    Blinding(String),
+}
+
+/// How the builder determines the transaction fee
+#[derive(Debug, Clone, Copy)]
+enum FeeSource {
+    Absolute(Amount),
+    Rate(FeeRate),
+}
+
+#[derive(Debug, Clone)]
+struct Recipient {
+    address: String,
+    asset: String,
+    amount: Amount,
+}
+
+#[derive(Debug, Clone)]
+struct Burn {
+    asset: String,
+    amount: Amount,
+}
+
+#[derive(Debug, Clone)]
+struct Reissuance {
+    entropy: String,
+    amount: Amount,
+    address: String,
+}
+
+/// Builds blinded PSETs from UTXOs whose blinding factors are known
+///
+/// Outputs are ordered as recipients in the order they were added, then reissued
+/// amounts, then burns, then one change output per asset with a surplus (ordered by
+/// asset id), then the explicit fee output in the network's policy asset.
+#[derive(Debug, Clone)]
+pub struct PsetBuilder {
+    network: Network,
+    inputs: Vec<Unspent>,
+    recipients: Vec<Recipient>,
+    burns: Vec<Burn>,
+    reissuances: Vec<Reissuance>,
+    change_address: Option<String>,
+    fee: FeeSource,
+}
+
+impl PsetBuilder {
+    /// Creates a builder for `network` that pays the minimum relay fee rate
+    #[must_use]
+    pub const fn new(network: Network) -> Self {
+        Self {
+            network,
+            inputs: Vec::new(),
+            recipients: Vec::new(),
+            burns: Vec::new(),
+            reissuances: Vec::new(),
+            change_address: None,
+            fee: FeeSource::Rate(FeeRate::MIN_RELAY),
+        }
+    }
+
+    /// Adds a UTXO to spend
+    #[must_use]
+    pub fn add_input(mut self, utxo: Unspent) -> Self {
+        self.inputs.push(utxo);
+        self
+    }
+
+    /// Adds several UTXOs to spend, in order
+    #[must_use]
+    pub fn add_inputs(mut self, utxos: impl IntoIterator<Item = Unspent>) -> Self {
+        self.inputs.extend(utxos);
+        self
+    }
+
+    /// Pays `amount` of `asset_id` to the confidential `address`
+    #[must_use]
+    pub fn add_recipient(
+        mut self,
+        address: impl Into<String>,
+        asset_id: impl Into<String>,
+        amount: Amount,
+    ) -> Self {
+        self.recipients.push(Recipient {
+            address: address.into(),
+            asset: asset_id.into(),
+            amount,
+        });
+        self
+    }
+
+    /// Destroys `amount` of `asset_id` in an unspendable `OP_RETURN` output
+    #[must_use]
+    pub fn add_burn(mut self, asset_id: impl Into<String>, amount: Amount) -> Self {
+        self.burns.push(Burn {
+            asset: asset_id.into(),
+            amount,
+        });
+        self
+    }
+
+    /// Reissues `amount` of the asset with `asset_entropy` to the confidential `address`
+    ///
+    /// `asset_entropy` is the entropy the node's `listissuances` reports for the asset.
+    /// One of the inputs must spend the asset's reissuance token. The token must be
+    /// confidential, since its asset blinding factor is the reissuance nonce, and it is
+    /// returned to the change address.
+    #[must_use]
+    pub fn add_reissuance(
+        mut self,
+        asset_entropy: impl Into<String>,
+        amount: Amount,
+        address: impl Into<String>,
+    ) -> Self {
+        self.reissuances.push(Reissuance {
+            entropy: asset_entropy.into(),
+            amount,
+            address: address.into(),
+        });
+        self
+    }
+
+    /// Sets the confidential address that receives the surplus of every input asset
+    #[must_use]
+    pub fn change_address(mut self, address: impl Into<String>) -> Self {
+        self.change_address = Some(address.into());
+        self
+    }
+
+    /// Pays exactly `fee` in the policy asset
+    #[must_use]
+    pub const fn fee(mut self, fee: Amount) -> Self {
+        self.fee = FeeSource::Absolute(fee);
+        self
+    }
+
+    /// Pays a fee computed from `rate` and the estimated size of the transaction
+    #[must_use]
+    pub const fn fee_rate(mut self, rate: FeeRate) -> Self {
+        self.fee = FeeSource::Rate(rate);
+        self
+    }
+
+    /// Builds and blinds the PSET
+    ///
+    /// The returned PSET carries a `witness_utxo` for every input, rebuilt from the
+    /// input's amount, asset and blinders so that signers compute the correct sighash.
+    ///
+    /// # Errors
+    /// Returns an error if an input, recipient, burn or reissuance is malformed, an
+    /// address is not confidential or belongs to another network, the inputs do not
+    /// cover the recipients, burns and fee, a change output is needed but no change
+    /// address was set, or blinding fails
+    #[allow(clippy::too_many_lines)]
+    pub fn build(&self) -> Result<PartiallySignedTransaction, PsetBuilderError> {
+        if self.inputs.is_empty() {
+            return Err(PsetBuilderError::NoInputs);
+        }
+        if self.recipients.is_empty() && self.burns.is_empty() && self.reissuances.is_empty() {
+            return Err(PsetBuilderError::NoRecipients);
+        }
+
+        let secp = Secp256k1::new();
+        let policy_asset = parse_asset(self.network.policy_asset()).map_err(|reason| {
+            PsetBuilderError::Blinding(format!("Invalid policy asset: {reason}"))
+        })?;
+
+        let mut pset = PartiallySignedTransaction::new_v2();
+        let mut input_secrets = HashMap::new();
+        let mut available: BTreeMap<AssetId, Amount> = BTreeMap::new();
+        for (index, utxo) in self.inputs.iter().enumerate() {
+            let (input, secrets) = build_input(&secp, utxo)
+                .map_err(|reason| PsetBuilderError::InvalidInput { index, reason })?;
+            *available.entry(secrets.asset).or_insert(Amount::ZERO) += utxo.amount;
+            pset.add_input(input);
+            input_secrets.insert(index, secrets);
+        }
+
+        // Reissued amounts are paid like recipients, from the issuance on the token input
+        let mut recipients = self.recipients.clone();
+        for reissuance in &self.reissuances {
+            let asset = self.add_reissuance_input(&mut pset, &input_secrets, reissuance)?;
+            *available.entry(asset).or_insert(Amount::ZERO) += reissuance.amount;
+            recipients.push(Recipient {
+                address: reissuance.address.clone(),
+                asset: asset.to_string(),
+                amount: reissuance.amount,
+            });
+        }
+
+        let mut needed: BTreeMap<AssetId, Amount> = BTreeMap::new();
+        for recipient in &recipients {
+            let invalid = |reason: String| PsetBuilderError::InvalidRecipient {
+                address: recipient.address.clone(),
+                reason,
+            };
+            if !recipient.amount.is_positive() {
+                return Err(invalid(format!(
+                    "amount must be positive, got {}",
+                    recipient.amount
+                )));
+            }
+            let address = self.confidential_address(&recipient.address)?;
+            let asset = parse_asset(&recipient.asset).map_err(invalid)?;
+            *needed.entry(asset).or_insert(Amount::ZERO) += recipient.amount;
+            pset.add_output(blinded_output(&address, recipient.amount, asset));
+        }
+        for burn in &self.burns {
+            let invalid = |reason: String| PsetBuilderError::InvalidRecipient {
+                address: "OP_RETURN".to_string(),
+                reason,
+            };
+            if !burn.amount.is_positive() {
+                return Err(invalid(format!(
+                    "burn amount must be positive, got {}",
+                    burn.amount
+                )));
+            }
+            let asset = parse_asset(&burn.asset).map_err(invalid)?;
+            *needed.entry(asset).or_insert(Amount::ZERO) += burn.amount;
+            pset.add_output(Output::new_explicit(
+                Script::new_op_return(&[]),
+                to_u64(burn.amount),
+                asset,
+                None,
+            ));
+        }
+
+        // Assets with a surplus before the fee each get a change output
+        let mut surplus = BTreeMap::new();
+        for (asset, have) in &available {
+            let need = needed.get(asset).copied().unwrap_or(Amount::ZERO);
+            if *have > need {
+                surplus.insert(*asset, *have - need);
+            }
+        }
+        for (asset, need) in &needed {
+            let have = available.get(asset).copied().unwrap_or(Amount::ZERO);
+            if have < *need {
+                return Err(PsetBuilderError::InsufficientFunds {
+                    asset: asset.to_string(),
+                    needed: *need,
+                    available: have,
+                });
+            }
+        }
+
+        let fee = match self.fee {
+            FeeSource::Absolute(fee) => fee,
+            FeeSource::Rate(rate) => rate.fee_for_vsize(fee::estimate_vsize(
+                self.inputs.len(),
+                recipients.len() + self.burns.len() + surplus.len(),
+            )),
+        };
+        let policy_surplus = surplus.remove(&policy_asset).unwrap_or(Amount::ZERO);
+        if policy_surplus < fee {
+            return Err(PsetBuilderError::InsufficientFunds {
+                asset: policy_asset.to_string(),
+                needed: needed.get(&policy_asset).copied().unwrap_or(Amount::ZERO) + fee,
+                available: available
+                    .get(&policy_asset)
+                    .copied()
+                    .unwrap_or(Amount::ZERO),
+            });
+        }
+        if policy_surplus > fee {
+            surplus.insert(policy_asset, policy_surplus - fee);
+        }
+
+        if !surplus.is_empty() {
+            let change = match &self.change_address {
+                Some(change) => self.confidential_address(change)?,
+                None => {
+                    let (asset, amount) = surplus.iter().next().expect("surplus is not empty");
+                    return Err(PsetBuilderError::MissingChangeAddress {
+                        asset: asset.to_string(),
+                        amount: *amount,
+                    });
+                }
+            };
+            for (asset, amount) in &surplus {
+                pset.add_output(blinded_output(&change, *amount, *asset));
+            }
+        }
+
+        if fee.is_positive() {
+            pset.add_output(Output::from_txout(TxOut::new_fee(
+                to_u64(fee),
+                policy_asset,
+            )));
+        }
+
+        pset.blind_last(&mut rand::thread_rng(), &secp, &input_secrets)
+            .map_err(|e| PsetBuilderError::Blinding(e.to_string()))?;
+
+        Ok(pset)
+    }
+
+    /// Sets the issuance fields of the input spending the token of `reissuance` and
+    /// returns the reissued asset
+    fn add_reissuance_input(
+        &self,
+        pset: &mut PartiallySignedTransaction,
+        input_secrets: &HashMap<usize, TxOutSecrets>,
+        reissuance: &Reissuance,
+    ) -> Result<AssetId, PsetBuilderError> {
+        let invalid = |reason: String| PsetBuilderError::InvalidReissuance {
+            entropy: reissuance.entropy.clone(),
+            reason,
+        };
+        if !reissuance.amount.is_positive() {
+            return Err(invalid(format!(
+                "amount must be positive, got {}",
+                reissuance.amount
+            )));
+        }
+        let entropy = sha256::Midstate::from_str(&reissuance.entropy)
+            .map_err(|e| invalid(format!("invalid entropy: {e}")))?;
+        let asset = AssetId::from_entropy(entropy);
+        // The token id depends on whether the original issuance was blinded
+        let tokens = [
+            AssetId::reissuance_token_from_entropy(entropy, false),
+            AssetId::reissuance_token_from_entropy(entropy, true),
+        ];
+        let Some((index, secrets)) = (0..self.inputs.len()).find_map(|index| {
+            input_secrets
+                .get(&index)
+                .filter(|secrets| tokens.contains(&secrets.asset))
+                .map(|secrets| (index, secrets))
+        }) else {
+            return Err(invalid(format!(
+                "no input spends the reissuance token of asset {asset}"
+            )));
+        };
+        if secrets.asset_bf == AssetBlindingFactor::zero() {
+            return Err(PsetBuilderError::InvalidInput {
+                index,
+                reason: "the reissuance token must be confidential".to_string(),
+            });
+        }
+
+        let input = &mut pset.inputs_mut()[index];
+        input.issuance_value_amount = Some(to_u64(reissuance.amount));
+        input.issuance_blinding_nonce = Some(secrets.asset_bf.into_inner());
+        input.issuance_asset_entropy = Some(entropy.to_byte_array());
+        input.blinded_issuance = Some(0);
+        Ok(asset)
+    }
+
+    /// Parses `address` for this builder's network and requires a blinding key
+    fn confidential_address(&self, address: &str) -> Result<Address, PsetBuilderError> {
+        let parsed = self.network.validate_address(address).map_err(|e| {
+            PsetBuilderError::InvalidRecipient {
+                address: address.to_string(),
+                reason: e.to_string(),
+            }
+        })?;
+        if parsed.blinding_pubkey.is_none() {
+            return Err(PsetBuilderError::NotConfidential(address.to_string()));
+        }
+        Ok(parsed)
+    }
+}
+
+/// Creates an output to be blinded to `address` by the input at index 0
+fn blinded_output(address: &Address, amount: Amount, asset: AssetId) -> Output {
+    let blinding_key = address
+        .blinding_pubkey
+        .map(|inner| elements::bitcoin::PublicKey {
+            inner,
+            compressed: true,
+        });
+    let mut output =
+        Output::new_explicit(address.script_pubkey(), to_u64(amount), asset, blinding_key);
+    output.blinder_index = Some(0);
+    output
+}
+
+/// Converts a positive amount to satoshis; callers have already rejected negative amounts
+fn to_u64(amount: Amount) -> u64 {
+    u64::try_from(amount.to_sat()).unwrap_or_default()
+}
+
+/// Parses an asset id in the byte order used by the node's RPC
+fn parse_asset(asset: &str) -> Result<AssetId, String> {
+    AssetId::from_str(asset).map_err(|e| format!("invalid asset id '{asset}': {e}"))
+}
+
+/// Builds a PSET input and the secrets of the output it spends
+fn build_input(
+    secp: &Secp256k1<secp256k1_zkp::All>,
+    utxo: &Unspent,
+) -> Result<(Input, TxOutSecrets), String> {
+    let txid = Txid::from_str(&utxo.txid).map_err(|e| format!("invalid txid: {e}"))?;
+    let value = u64::try_from(utxo.amount.to_sat())
+        .map_err(|_| format!("negative amount {}", utxo.amount))?;
+    let asset = parse_asset(&utxo.asset)?;
+    let script_pubkey = utxo
+        .scriptpubkey
+        .as_deref()
+        .ok_or_else(|| "missing scriptpubkey".to_string())
+        .and_then(parse_script)?;
+    let asset_bf = match utxo.assetblinder.as_deref() {
+        Some(blinder) => AssetBlindingFactor::from_str(blinder)
+            .map_err(|e| format!("invalid asset blinder: {e}"))?,
+        None => AssetBlindingFactor::zero(),
+    };
+    let value_bf = match utxo.amountblinder.as_deref() {
+        Some(blinder) => ValueBlindingFactor::from_str(blinder)
+            .map_err(|e| format!("invalid amount blinder: {e}"))?,
+        None => ValueBlindingFactor::zero(),
+    };
+
+    // Rebuild the commitments of the spent output from its secrets. The nonce and
+    // proofs are not part of the sighash, so they can be left empty.
+    let is_confidential =
+        asset_bf != AssetBlindingFactor::zero() || value_bf != ValueBlindingFactor::zero();
+    let witness_utxo = if is_confidential {
+        TxOut {
+            asset: confidential::Asset::new_confidential(secp, asset, asset_bf),
+            value: confidential::Value::new_confidential_from_assetid(
+                secp, value, asset, value_bf, asset_bf,
+            ),
+            nonce: confidential::Nonce::Null,
+            script_pubkey,
+            witness: elements::TxOutWitness::default(),
+        }
+    } else {
+        TxOut {
+            asset: confidential::Asset::Explicit(asset),
+            value: confidential::Value::Explicit(value),
+            nonce: confidential::Nonce::Null,
+            script_pubkey,
+            witness: elements::TxOutWitness::default(),
+        }
+    };
+
+    let mut input = Input::from_prevout(OutPoint::new(txid, utxo.vout));
+    input.sequence = Some(Sequence(fee::SEQUENCE_RBF));
+    input.witness_utxo = Some(witness_utxo);
+    input.redeem_script = utxo.redeemscript.as_deref().map(parse_script).transpose()?;
+    input.witness_script = utxo
+        .witnessscript
+        .as_deref()
+        .map(parse_script)
+        .transpose()?;
+
+    Ok((input, TxOutSecrets::new(asset, asset_bf, value, value_bf)))
+}
+
+fn parse_script(script: &str) -> Result<Script, String> {
+    hex::decode(script)
+        .map(Script::from)
+        .map_err(|e| format!("invalid script hex '{script}': {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elements::hashes::Hash;
+    use elements::secp256k1_zkp::SecretKey;
+
+    const ASSET: &str = "6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d";
+
+    fn network() -> Network {
+        Network::LiquidTestnet
+    }
+
+    /// Returns a confidential testnet address and the secret key that unblinds its outputs
+    fn confidential_address(seed: u8) -> (String, SecretKey) {
+        let secp = Secp256k1::new();
+        let blinding_key = SecretKey::from_slice(&[seed; 32]).unwrap();
+        let spend_key = elements::bitcoin::PublicKey::new(
+            SecretKey::from_slice(&[seed.wrapping_add(100); 32])
+                .unwrap()
+                .public_key(&secp),
+        );
+        let address = Address::p2wpkh(
+            &spend_key,
+            Some(blinding_key.public_key(&secp)),
+            network().address_params(),
+        );
+        (address.to_string(), blinding_key)
+    }
+
+    /// Creates a confidential previous output paying `sats` of `asset`
+    fn confidential_utxo(vout: u32, asset: &str, sats: i64) -> (Unspent, TxOut) {
+        let secp = Secp256k1::new();
+        let mut rng = rand::thread_rng();
+        let asset_bf = AssetBlindingFactor::new(&mut rng);
+        let value_bf = ValueBlindingFactor::new(&mut rng);
+        let asset_id = AssetId::from_str(asset).unwrap();
+        let script_pubkey =
+            Script::from(hex::decode("0014".to_string() + &"22".repeat(20)).unwrap());
+        let txout = TxOut {
+            asset: confidential::Asset::new_confidential(&secp, asset_id, asset_bf),
+            value: confidential::Value::new_confidential_from_assetid(
+                &secp,
+                u64::try_from(sats).unwrap(),
+                asset_id,
+                value_bf,
+                asset_bf,
+            ),
+            nonce: confidential::Nonce::Null,
+            script_pubkey: script_pubkey.clone(),
+            witness: elements::TxOutWitness::default(),
+        };
+        let utxo = Unspent {
+            txid: "cc".repeat(32),
+            vout,
+            amount: Amount::from_sat(sats),
+            asset: asset.to_string(),
+            address: "address".to_string(),
+            spendable: true,
+            confirmations: Some(1),
+            scriptpubkey: Some(hex::encode(script_pubkey.as_bytes())),
+            redeemscript: None,
+            witnessscript: None,
+            amountblinder: Some(value_bf.to_string()),
+            assetblinder: Some(asset_bf.to_string()),
+        };
+        (utxo, txout)
+    }
+
+    #[test]
+    fn test_build_blinds_outputs_and_balances() {
+        let secp = Secp256k1::new();
+        let policy_asset = network().policy_asset().to_string();
+        let (asset_utxo, asset_txout) = confidential_utxo(0, ASSET, 10_000);
+        let (lbtc_utxo, lbtc_txout) = confidential_utxo(1, &policy_asset, 5_000);
+        let (recipient, recipient_key) = confidential_address(1);
+        let (change, _) = confidential_address(2);
+
+        let pset = PsetBuilder::new(network())
+            .add_inputs([asset_utxo, lbtc_utxo])
+            .add_recipient(&recipient, ASSET, Amount::from_sat(4_000))
+            .change_address(&change)
+            .fee(Amount::from_sat(300))
+            .build()
+            .unwrap();
+
+        // Recipient, asset change, policy asset change and fee
+        assert_eq!(pset.outputs().len(), 4);
+        let tx = pset.extract_tx().unwrap();
+        for output in &tx.output[..3] {
+            assert!(output.value.is_confidential());
+            assert!(output.asset.is_confidential());
+        }
+        assert!(tx.output[3].is_fee());
+        assert_eq!(tx.output[3].value, confidential::Value::Explicit(300));
+
+        // The recipient can unblind its output and the commitments balance
+        let secrets = tx.output[0].unblind(&secp, recipient_key).unwrap();
+        assert_eq!(secrets.value, 4_000);
+        assert_eq!(secrets.asset, AssetId::from_str(ASSET).unwrap());
+        tx.verify_tx_amt_proofs(&secp, &[asset_txout.clone(), lbtc_txout])
+            .unwrap();
+        assert_eq!(pset.inputs()[0].witness_utxo.as_ref(), Some(&asset_txout));
+    }
+
+    #[test]
+    fn test_build_burns_and_reissues() {
+        let secp = Secp256k1::new();
+        let policy_asset = network().policy_asset().to_string();
+        let entropy = AssetId::generate_asset_entropy(
+            OutPoint::new(Txid::from_str(&"aa".repeat(32)).unwrap(), 0),
+            elements::ContractHash::from_byte_array([0; 32]),
+        );
+        let asset = AssetId::from_entropy(entropy);
+        let token = AssetId::reissuance_token_from_entropy(entropy, false);
+        let (token_utxo, token_txout) = confidential_utxo(0, &token.to_string(), 1);
+        let (asset_utxo, asset_txout) = confidential_utxo(1, &asset.to_string(), 10_000);
+        let (lbtc_utxo, lbtc_txout) = confidential_utxo(2, &policy_asset, 5_000);
+        let (recipient, recipient_key) = confidential_address(4);
+        let (change, _) = confidential_address(5);
+
+        let pset = PsetBuilder::new(network())
+            .add_inputs([token_utxo.clone(), asset_utxo, lbtc_utxo])
+            .add_reissuance(entropy.to_string(), Amount::from_sat(7_000), &recipient)
+            .add_burn(asset.to_string(), Amount::from_sat(4_000))
+            .change_address(&change)
+            .fee(Amount::from_sat(300))
+            .build()
+            .unwrap();
+
+        // Reissued amount, burn, asset, token and policy asset change, and fee
+        let tx = pset.extract_tx().unwrap();
+        assert_eq!(tx.output.len(), 6);
+        let issuance = &tx.input[0].asset_issuance;
+        assert_eq!(issuance.amount, confidential::Value::Explicit(7_000));
+        assert_eq!(
+            issuance.asset_blinding_nonce,
+            AssetBlindingFactor::from_str(token_utxo.assetblinder.as_deref().unwrap())
+                .unwrap()
+                .into_inner()
+        );
+        let secrets = tx.output[0].unblind(&secp, recipient_key).unwrap();
+        assert_eq!((secrets.asset, secrets.value), (asset, 7_000));
+        assert!(tx.output[1].script_pubkey.is_provably_unspendable());
+        assert_eq!(tx.output[1].value, confidential::Value::Explicit(4_000));
+        tx.verify_tx_amt_proofs(&secp, &[token_txout, asset_txout, lbtc_txout])
+            .unwrap();
+
+        // Without the token among the inputs there is nothing to reissue with
+        let (lbtc_utxo, _) = confidential_utxo(0, &policy_asset, 5_000);
+        let err = PsetBuilder::new(network())
+            .add_input(lbtc_utxo)
+            .add_reissuance(entropy.to_string(), Amount::from_sat(7_000), &recipient)
+            .change_address(&change)
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, PsetBuilderError::InvalidReissuance { .. }));
+    }
+
+    #[test]
+    fn test_build_rejects_invalid_requests() {
+        let policy_asset = network().policy_asset().to_string();
+        let (recipient, _) = confidential_address(3);
+        let (utxo, _) = confidential_utxo(0, &policy_asset, 1_000);
+
+        let err = PsetBuilder::new(network())
+            .add_input(utxo.clone())
+            .add_recipient(&recipient, &policy_asset, Amount::from_sat(900))
+            .fee(Amount::from_sat(200))
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, PsetBuilderError::InsufficientFunds { .. }));
+
+        let err = PsetBuilder::new(network())
+            .add_input(utxo.clone())
+            .add_recipient(&recipient, &policy_asset, Amount::from_sat(500))
+            .fee(Amount::from_sat(200))
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, PsetBuilderError::MissingChangeAddress { .. }));
+
+        let unconfidential = Address::parse_with_params(&recipient, network().address_params())
+            .unwrap()
+            .to_unconfidential()
+            .to_string();
+        let err = PsetBuilder::new(network())
+            .add_input(utxo.clone())
+            .add_recipient(&unconfidential, &policy_asset, Amount::from_sat(800))
+            .fee(Amount::from_sat(200))
+            .build()
+            .unwrap_err();
+        assert_eq!(err, PsetBuilderError::NotConfidential(unconfidential));
+
+        let mut missing_script = utxo;
+        missing_script.scriptpubkey = None;
+        let err = PsetBuilder::new(network())
+            .add_input(missing_script)
+            .add_recipient(&recipient, &policy_asset, Amount::from_sat(800))
+            .fee(Amount::from_sat(200))
+            .build()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            PsetBuilderError::InvalidInput { index: 0, .. }
+        ));
+    }
+}
@@ -0,0 +1,816 @@
+//! # Holder Reconciliation
+//!
+//! The AMP API and the issuer's node each give one partial view of who holds an asset:
+//! ownerships per GAID, the asset's UTXOs with their registered user and blacklist flag,
+//! lost outputs, assignments, and the treasury UTXOs in the node wallet.
+//! [`reconcile`] joins these views per holder and lists every disagreement as a typed
+//! [`Mismatch`]:
+//!
+//! - assignments that have not been distributed
+//! - lost outputs, whose blinders AMP does not know
+//! - blacklisted UTXOs
+//! - holders, and the treasury, whose AMP ownership differs from the UTXOs AMP tracks
+//!   for them
+//! - treasury UTXOs AMP tracks that are missing from the wallet, and wallet UTXOs AMP
+//!   does not track
+//!
+//! Holders are matched by registered user id. GAIDs are resolved to registered users
+//! through the assignments and UTXOs that carry both; a GAID that cannot be resolved
+//! becomes a holder of its own. UTXOs without a registered user or GAID, and ownerships
+//! without a GAID, belong to the treasury.
+//!
+//! [`ApiClient::reconcile_asset`](crate::ApiClient::reconcile_asset) fetches all inputs
+//! and builds the report. Amounts are in the asset's base units, as in the AMP API. The
+//! report exports to JSON ([`ReconciliationReport::to_json`]) and CSV
+//! ([`ReconciliationReport::holders_csv`], [`ReconciliationReport::mismatches_csv`]).
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use amp_rs::{ApiClient, ElementsRpc};
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = ApiClient::new().await?;
+//! let node_rpc = ElementsRpc::from_env()?;
+//!
+//! let report = client
+//!     .reconcile_asset("asset-uuid", &node_rpc, "treasury_wallet")
+//!     .await?;
+//! for mismatch in &report.mismatches {
+//!     println!("{mismatch}");
+//! }
+//! std::fs::write("holders.csv", report.holders_csv())?;
+//! std::fs::write("mismatches.csv", report.mismatches_csv())?;
+//! std::fs::write("reconciliation.json", report.to_json()?)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::model::{AssetLostOutputs, AssetSummary, Assignment, Ownership, Unspent, Utxo};
+
+/// Everything [`reconcile`] joins, as returned by the AMP API and the node
+#[derive(Debug)]
+pub struct ReconciliationInput {
+    pub asset_uuid: String,
+    pub asset_id: String,
+    pub summary: AssetSummary,
+    pub ownerships: Vec<Ownership>,
+    pub utxos: Vec<Utxo>,
+    pub lost_outputs: AssetLostOutputs,
+    pub assignments: Vec<Assignment>,
+    /// UTXOs of the asset in the issuer's treasury wallet
+    pub wallet_utxos: Vec<Unspent>,
+}
+
+/// What AMP and the chain say one holder owns
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct HolderPosition {
+    pub registered_user: Option<i64>,
+    #[serde(rename = "GAID")]
+    pub gaid: Option<String>,
+    /// Owner name reported with the ownership
+    pub owner: Option<String>,
+    /// Amount AMP reports in the holder's ownerships
+    pub ownership: i64,
+    /// Amount in the holder's UTXOs that are not blacklisted
+    pub utxos: i64,
+    /// Amount in the holder's blacklisted UTXOs
+    pub blacklisted: i64,
+    /// Total of the holder's assignments
+    pub assigned: i64,
+    /// Part of `assigned` that has been distributed
+    pub distributed: i64,
+}
+
+impl HolderPosition {
+    /// Returns the assigned amount that has not been distributed yet
+    #[must_use]
+    pub const fn undistributed(&self) -> i64 {
+        self.assigned - self.distributed
+    }
+}
+
+/// The treasury as tracked by AMP and as held by the node wallet
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct TreasuryPosition {
+    /// Amount AMP reports in ownerships without a GAID, if it reports any
+    pub ownership: Option<i64>,
+    /// Amount in AMP UTXOs without a registered user or GAID
+    pub amp_amount: i64,
+    pub amp_utxo_count: usize,
+    /// Amount of the asset in the node wallet
+    pub wallet_amount: i64,
+    pub wallet_utxo_count: usize,
+}
+
+/// A disagreement between the views of AMP and the chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Mismatch {
+    /// An assignment that has been created but not distributed
+    UndistributedAssignment {
+        assignment_id: i64,
+        registered_user: i64,
+        amount: i64,
+        has_vested: bool,
+        ready_for_distribution: bool,
+    },
+    /// An output AMP cannot account for because its blinders are unknown
+    LostOutput {
+        txid: String,
+        vout: i64,
+        /// Whether the output holds the reissuance token rather than the asset
+        reissuance_token: bool,
+    },
+    /// A UTXO frozen by the issuer
+    BlacklistedUtxo {
+        txid: String,
+        vout: i64,
+        amount: i64,
+        registered_user: Option<i64>,
+        gaid: Option<String>,
+    },
+    /// AMP's ownership for a holder differs from the UTXOs AMP tracks for them
+    OwnershipMismatch {
+        registered_user: Option<i64>,
+        gaid: Option<String>,
+        ownership: i64,
+        utxos: i64,
+    },
+    /// AMP's ownership for the treasury differs from the treasury UTXOs AMP tracks
+    TreasuryOwnershipMismatch { ownership: i64, utxos: i64 },
+    /// A treasury UTXO tracked by AMP that the node wallet does not hold
+    MissingTreasuryUtxo {
+        txid: String,
+        vout: i64,
+        amount: i64,
+    },
+    /// A wallet UTXO of the asset that AMP does not track
+    UntrackedWalletUtxo {
+        txid: String,
+        vout: i64,
+        amount: i64,
+    },
+}
+
+impl Mismatch {
+    /// Returns the snake case name of the mismatch, as used in the exports
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::UndistributedAssignment { .. } => "undistributed_assignment",
+            Self::LostOutput { .. } => "lost_output",
+            Self::BlacklistedUtxo { .. } => "blacklisted_utxo",
+            Self::OwnershipMismatch { .. } => "ownership_mismatch",
+            Self::TreasuryOwnershipMismatch { .. } => "treasury_ownership_mismatch",
+            Self::MissingTreasuryUtxo { .. } => "missing_treasury_utxo",
+            Self::UntrackedWalletUtxo { .. } => "untracked_wallet_utxo",
+        }
+    }
+
+    /// Returns the registered user the mismatch concerns, if known
+    #[must_use]
+    pub const fn registered_user(&self) -> Option<i64> {
+        match self {
+            Self::UndistributedAssignment {
+                registered_user, ..
+            } => Some(*registered_user),
+            Self::BlacklistedUtxo {
+                registered_user, ..
+            }
+            | Self::OwnershipMismatch {
+                registered_user, ..
+            } => *registered_user,
+            _ => None,
+        }
+    }
+
+    fn gaid(&self) -> Option<&str> {
+        match self {
+            Self::BlacklistedUtxo { gaid, .. } | Self::OwnershipMismatch { gaid, .. } => {
+                gaid.as_deref()
+            }
+            _ => None,
+        }
+    }
+
+    fn outpoint(&self) -> Option<(&str, i64)> {
+        match self {
+            Self::LostOutput { txid, vout, .. }
+            | Self::BlacklistedUtxo { txid, vout, .. }
+            | Self::MissingTreasuryUtxo { txid, vout, .. }
+            | Self::UntrackedWalletUtxo { txid, vout, .. } => Some((txid, *vout)),
+            _ => None,
+        }
+    }
+
+    fn amount(&self) -> Option<i64> {
+        match self {
+            Self::UndistributedAssignment { amount, .. }
+            | Self::BlacklistedUtxo { amount, .. }
+            | Self::MissingTreasuryUtxo { amount, .. }
+            | Self::UntrackedWalletUtxo { amount, .. } => Some(*amount),
+            Self::OwnershipMismatch { ownership, .. }
+            | Self::TreasuryOwnershipMismatch { ownership, .. } => Some(*ownership),
+            Self::LostOutput { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UndistributedAssignment {
+                assignment_id,
+                registered_user,
+                amount,
+                has_vested,
+                ready_for_distribution,
+            } => write!(
+                f,
+                "Assignment {assignment_id} of {amount} to user {registered_user} is not distributed (vested: {has_vested}, ready: {ready_for_distribution})"
+            ),
+            Self::LostOutput {
+                txid,
+                vout,
+                reissuance_token,
+            } => {
+                let what = if *reissuance_token {
+                    "Reissuance token output"
+                } else {
+                    "Output"
+                };
+                write!(f, "{what} {txid}:{vout} is lost")
+            }
+            Self::BlacklistedUtxo {
+                txid, vout, amount, ..
+            } => write!(f, "UTXO {txid}:{vout} of {amount} is blacklisted"),
+            Self::OwnershipMismatch {
+                registered_user,
+                gaid,
+                ownership,
+                utxos,
+            } => write!(
+                f,
+                "Holder {} owns {ownership} according to AMP but holds {utxos} in UTXOs",
+                holder_label(*registered_user, gaid.as_deref())
+            ),
+            Self::TreasuryOwnershipMismatch { ownership, utxos } => write!(
+                f,
+                "Treasury owns {ownership} according to AMP but holds {utxos} in UTXOs"
+            ),
+            Self::MissingTreasuryUtxo { txid, vout, amount } => write!(
+                f,
+                "Treasury UTXO {txid}:{vout} of {amount} is not in the wallet"
+            ),
+            Self::UntrackedWalletUtxo { txid, vout, amount } => write!(
+                f,
+                "Wallet UTXO {txid}:{vout} of {amount} is not tracked by AMP"
+            ),
+        }
+    }
+}
+
+fn holder_label(registered_user: Option<i64>, gaid: Option<&str>) -> String {
+    match (registered_user, gaid) {
+        (Some(user), Some(gaid)) => format!("user {user} ({gaid})"),
+        (Some(user), None) => format!("user {user}"),
+        (None, Some(gaid)) => gaid.to_string(),
+        (None, None) => "unknown".to_string(),
+    }
+}
+
+/// Result of reconciling an asset's holders across AMP and the chain
+#[derive(Debug, Serialize)]
+pub struct ReconciliationReport {
+    pub asset_uuid: String,
+    pub asset_id: String,
+    pub generated_at: DateTime<Utc>,
+    /// The asset summary reported by AMP, for reference
+    pub summary: AssetSummary,
+    /// One position per holder, ordered by registered user and then GAID
+    pub holders: Vec<HolderPosition>,
+    pub treasury: TreasuryPosition,
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl ReconciliationReport {
+    /// Returns `true` if AMP and the chain agree and nothing is pending
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+
+    /// Returns the mismatches of one kind, see [`Mismatch::kind`]
+    #[must_use]
+    pub fn mismatches_of_kind(&self, kind: &str) -> Vec<&Mismatch> {
+        self.mismatches
+            .iter()
+            .filter(|mismatch| mismatch.kind() == kind)
+            .collect()
+    }
+
+    /// Serialises the full report as pretty-printed JSON
+    ///
+    /// # Errors
+    /// Returns an error if serialisation fails
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Exports the holder positions as CSV with a header row
+    #[must_use]
+    pub fn holders_csv(&self) -> String {
+        let mut csv = String::from(
+            "registered_user,gaid,owner,ownership,utxos,blacklisted,assigned,distributed,undistributed\n",
+        );
+        for holder in &self.holders {
+            push_row(
+                &mut csv,
+                &[
+                    optional(holder.registered_user),
+                    holder.gaid.clone().unwrap_or_default(),
+                    holder.owner.clone().unwrap_or_default(),
+                    holder.ownership.to_string(),
+                    holder.utxos.to_string(),
+                    holder.blacklisted.to_string(),
+                    holder.assigned.to_string(),
+                    holder.distributed.to_string(),
+                    holder.undistributed().to_string(),
+                ],
+            );
+        }
+        csv
+    }
+
+    /// Exports the mismatches as CSV with a header row
+    ///
+    /// Columns that do not apply to a mismatch are left empty; `description` holds the
+    /// full message.
+    #[must_use]
+    pub fn mismatches_csv(&self) -> String {
+        let mut csv = String::from("kind,registered_user,gaid,txid,vout,amount,description\n");
+        for mismatch in &self.mismatches {
+            let outpoint = mismatch.outpoint();
+            push_row(
+                &mut csv,
+                &[
+                    mismatch.kind().to_string(),
+                    optional(mismatch.registered_user()),
+                    mismatch.gaid().unwrap_or_default().to_string(),
+                    outpoint
+                        .map(|(txid, _)| txid.to_string())
+                        .unwrap_or_default(),
+                    optional(outpoint.map(|(_, vout)| vout)),
+                    optional(mismatch.amount()),
+                    mismatch.to_string(),
+                ],
+            );
+        }
+        csv
+    }
+}
+
+fn optional(value: Option<i64>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// Appends one CSV row, quoting fields as described in RFC 4180
+fn push_row(csv: &mut String, fields: &[String]) {
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            csv.push(',');
+        }
+        if field.contains([',', '"', '\n', '\r']) {
+            csv.push('"');
+            csv.push_str(&field.replace('"', "\"\""));
+            csv.push('"');
+        } else {
+            csv.push_str(field);
+        }
+    }
+    csv.push('\n');
+}
+
+/// Holders are keyed by registered user, falling back to the GAID
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum HolderKey {
+    User(i64),
+    Gaid(String),
+}
+
+fn position<'a>(
+    holders: &'a mut BTreeMap<HolderKey, HolderPosition>,
+    key: HolderKey,
+    registered_user: Option<i64>,
+    gaid: Option<&str>,
+) -> &'a mut HolderPosition {
+    let position = holders.entry(key).or_default();
+    position.registered_user = position.registered_user.or(registered_user);
+    if position.gaid.is_none() {
+        position.gaid = gaid.map(str::to_string);
+    }
+    position
+}
+
+/// Joins the views of AMP and the node wallet into a [`ReconciliationReport`]
+#[must_use]
+pub fn reconcile(input: ReconciliationInput) -> ReconciliationReport {
+    let ReconciliationInput {
+        asset_uuid,
+        asset_id,
+        summary,
+        ownerships,
+        utxos,
+        lost_outputs,
+        assignments,
+        wallet_utxos,
+    } = input;
+
+    let gaid_users: HashMap<&str, i64> = assignments
+        .iter()
+        .filter_map(|a| Some((a.gaid.as_deref()?, a.registered_user)))
+        .chain(
+            utxos
+                .iter()
+                .filter_map(|u| Some((u.gaid.as_deref()?, u.registered_user?))),
+        )
+        .collect();
+    let key_for = |user: Option<i64>, gaid: Option<&str>| {
+        user.or_else(|| gaid.and_then(|gaid| gaid_users.get(gaid).copied()))
+            .map(HolderKey::User)
+            .or_else(|| gaid.map(|gaid| HolderKey::Gaid(gaid.to_string())))
+    };
+
+    let mut holders: BTreeMap<HolderKey, HolderPosition> = BTreeMap::new();
+
+    let mut mismatches = Vec::new();
+    let mut treasury = TreasuryPosition::default();
+    let mut treasury_outpoints = HashMap::new();
+    let mut amp_outpoints = HashSet::new();
+
+    for ownership in &ownerships {
+        let gaid = ownership.gaid.as_deref();
+        let Some(key) = key_for(None, gaid) else {
+            *treasury.ownership.get_or_insert(0) += ownership.amount;
+            continue;
+        };
+        let user = match key {
+            HolderKey::User(user) => Some(user),
+            _ => None,
+        };
+        let position = position(&mut holders, key, user, gaid);
+        position.ownership += ownership.amount;
+        if position.owner.is_none() {
+            position.owner.clone_from(&ownership.owner);
+        }
+    }
+
+    for utxo in &utxos {
+        amp_outpoints.insert((utxo.txid.as_str(), utxo.vout));
+        if utxo.blacklisted {
+            mismatches.push(Mismatch::BlacklistedUtxo {
+                txid: utxo.txid.clone(),
+                vout: utxo.vout,
+                amount: utxo.amount,
+                registered_user: utxo.registered_user,
+                gaid: utxo.gaid.clone(),
+            });
+        }
+        let gaid = utxo.gaid.as_deref();
+        match key_for(utxo.registered_user, gaid) {
+            Some(key) => {
+                let position = position(&mut holders, key, utxo.registered_user, gaid);
+                if utxo.blacklisted {
+                    position.blacklisted += utxo.amount;
+                } else {
+                    position.utxos += utxo.amount;
+                }
+            }
+            None => {
+                treasury.amp_amount += utxo.amount;
+                treasury.amp_utxo_count += 1;
+                treasury_outpoints.insert((utxo.txid.as_str(), utxo.vout), utxo.amount);
+            }
+        }
+    }
+
+    for assignment in &assignments {
+        let key = HolderKey::User(assignment.registered_user);
+        let position = position(
+            &mut holders,
+            key,
+            Some(assignment.registered_user),
+            assignment.gaid.as_deref(),
+        );
+        position.assigned += assignment.amount;
+        if assignment.is_distributed {
+            position.distributed += assignment.amount;
+        } else {
+            mismatches.push(Mismatch::UndistributedAssignment {
+                assignment_id: assignment.id,
+                registered_user: assignment.registered_user,
+                amount: assignment.amount,
+                has_vested: assignment.has_vested,
+                ready_for_distribution: assignment.ready_for_distribution,
+            });
+        }
+    }
+
+    for (outputs, reissuance_token) in [
+        (&lost_outputs.lost_outputs, false),
+        (&lost_outputs.reissuance_lost_outputs, true),
+    ] {
+        mismatches.extend(outputs.iter().map(|outpoint| Mismatch::LostOutput {
+            txid: outpoint.txid.clone(),
+            vout: outpoint.vout,
+            reissuance_token,
+        }));
+    }
+
+    // Ownerships include blacklisted holdings, which stay with the holder
+    mismatches.extend(
+        holders
+            .values()
+            .filter(|h| h.ownership != h.utxos + h.blacklisted)
+            .map(|h| Mismatch::OwnershipMismatch {
+                registered_user: h.registered_user,
+                gaid: h.gaid.clone(),
+                ownership: h.ownership,
+                utxos: h.utxos + h.blacklisted,
+            }),
+    );
+    if let Some(ownership) = treasury.ownership {
+        if ownership != treasury.amp_amount {
+            mismatches.push(Mismatch::TreasuryOwnershipMismatch {
+                ownership,
+                utxos: treasury.amp_amount,
+            });
+        }
+    }
+
+    let mut wallet_outpoints = HashSet::new();
+    for utxo in &wallet_utxos {
+        let vout = i64::from(utxo.vout);
+        let amount = utxo.amount.to_sat();
+        treasury.wallet_amount += amount;
+        treasury.wallet_utxo_count += 1;
+        wallet_outpoints.insert((utxo.txid.as_str(), vout));
+        if !amp_outpoints.contains(&(utxo.txid.as_str(), vout)) {
+            mismatches.push(Mismatch::UntrackedWalletUtxo {
+                txid: utxo.txid.clone(),
+                vout,
+                amount,
+            });
+        }
+    }
+    let mut missing: Vec<_> = treasury_outpoints
+        .into_iter()
+        .filter(|(outpoint, _)| !wallet_outpoints.contains(outpoint))
+        .collect();
+    missing.sort_unstable();
+    mismatches.extend(missing.into_iter().map(|((txid, vout), amount)| {
+        Mismatch::MissingTreasuryUtxo {
+            txid: txid.to_string(),
+            vout,
+            amount,
+        }
+    }));
+
+    ReconciliationReport {
+        asset_uuid,
+        asset_id,
+        generated_at: Utc::now(),
+        summary,
+        holders: holders.into_values().collect(),
+        treasury,
+        mismatches,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::amount::Amount;
+    use crate::model::Outpoint;
+
+    fn utxo(txid: &str, amount: i64, user: Option<i64>, gaid: Option<&str>) -> Utxo {
+        Utxo {
+            txid: txid.repeat(32),
+            vout: 0,
+            asset: "asset".to_string(),
+            amount,
+            registered_user: user,
+            gaid: gaid.map(str::to_string),
+            blacklisted: false,
+        }
+    }
+
+    fn assignment(id: i64, user: i64, amount: i64, distributed: bool) -> Assignment {
+        Assignment {
+            id,
+            registered_user: user,
+            amount,
+            receiving_address: None,
+            distribution_uuid: None,
+            ready_for_distribution: true,
+            vesting_datetime: None,
+            vesting_timestamp: None,
+            has_vested: true,
+            is_distributed: distributed,
+            creator: 1,
+            gaid: None,
+            investor: None,
+        }
+    }
+
+    fn wallet_utxo(txid: &str, sats: i64) -> Unspent {
+        Unspent {
+            txid: txid.repeat(32),
+            vout: 0,
+            amount: Amount::from_sat(sats),
+            asset: "asset".to_string(),
+            address: "vjtreasury".to_string(),
+            spendable: true,
+            confirmations: Some(10),
+            scriptpubkey: None,
+            redeemscript: None,
+            witnessscript: None,
+            amountblinder: None,
+            assetblinder: None,
+        }
+    }
+
+    fn summary() -> AssetSummary {
+        serde_json::from_value(serde_json::json!({
+            "asset_id": "asset",
+            "reissuance_token_id": null,
+            "issued": 10_000,
+            "reissued": 0,
+            "assigned": 700,
+            "distributed": 500,
+            "burned": 0,
+            "blacklisted": 50,
+            "registered_users": 2,
+            "active_registered_users": 2,
+            "active_green_subaccounts": 1,
+            "reissuance_tokens": 0
+        }))
+        .unwrap()
+    }
+
+    fn input() -> ReconciliationInput {
+        let mut blacklisted = utxo("bb", 50, Some(2), Some("GA2"));
+        blacklisted.blacklisted = true;
+        let mut user_two = assignment(2, 2, 200, false);
+        user_two.gaid = Some("GA2".to_string());
+        ReconciliationInput {
+            asset_uuid: "uuid".to_string(),
+            asset_id: "asset".to_string(),
+            summary: summary(),
+            ownerships: vec![
+                Ownership {
+                    owner: Some("Alice".to_string()),
+                    amount: 500,
+                    gaid: Some("GA1".to_string()),
+                },
+                Ownership {
+                    owner: Some("Bob, Ltd".to_string()),
+                    amount: 50,
+                    gaid: Some("GA2".to_string()),
+                },
+                Ownership {
+                    owner: Some("Treasury".to_string()),
+                    amount: 9_500,
+                    gaid: None,
+                },
+            ],
+            utxos: vec![
+                utxo("aa", 400, Some(1), Some("GA1")),
+                blacklisted,
+                utxo("cc", 9_000, None, None),
+                utxo("dd", 500, None, None),
+            ],
+            lost_outputs: AssetLostOutputs {
+                lost_outputs: vec![Outpoint {
+                    txid: "ee".repeat(32),
+                    vout: 1,
+                }],
+                reissuance_lost_outputs: vec![],
+            },
+            assignments: vec![assignment(1, 1, 500, true), user_two],
+            wallet_utxos: vec![wallet_utxo("cc", 9_000), wallet_utxo("ff", 25)],
+        }
+    }
+
+    #[test]
+    fn test_reconcile_joins_holders_by_user_and_gaid() {
+        let report = reconcile(input());
+
+        assert_eq!(report.holders.len(), 2);
+        let alice = &report.holders[0];
+        assert_eq!(alice.registered_user, Some(1));
+        assert_eq!(alice.gaid.as_deref(), Some("GA1"));
+        assert_eq!(alice.owner.as_deref(), Some("Alice"));
+        assert_eq!(
+            (alice.ownership, alice.utxos, alice.distributed),
+            (500, 400, 500)
+        );
+        let bob = &report.holders[1];
+        assert_eq!(bob.registered_user, Some(2));
+        assert_eq!((bob.ownership, bob.utxos, bob.blacklisted), (50, 0, 50));
+        assert_eq!(bob.undistributed(), 200);
+
+        assert_eq!(
+            report.treasury,
+            TreasuryPosition {
+                ownership: Some(9_500),
+                amp_amount: 9_500,
+                amp_utxo_count: 2,
+                wallet_amount: 9_025,
+                wallet_utxo_count: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_reconcile_reports_each_mismatch_kind() {
+        let report = reconcile(input());
+        let kinds: Vec<&str> = report.mismatches.iter().map(Mismatch::kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "blacklisted_utxo",
+                "undistributed_assignment",
+                "lost_output",
+                "ownership_mismatch",
+                "untracked_wallet_utxo",
+                "missing_treasury_utxo",
+            ]
+        );
+        assert_eq!(
+            report.mismatches_of_kind("ownership_mismatch")[0],
+            &Mismatch::OwnershipMismatch {
+                registered_user: Some(1),
+                gaid: Some("GA1".to_string()),
+                ownership: 500,
+                utxos: 400,
+            }
+        );
+        assert!(!report.is_clean());
+
+        let mut input = input();
+        input.ownerships[2].amount = 9_000;
+        let report = reconcile(input);
+        assert_eq!(
+            report.mismatches_of_kind("treasury_ownership_mismatch"),
+            vec![&Mismatch::TreasuryOwnershipMismatch {
+                ownership: 9_000,
+                utxos: 9_500,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_reconcile_agreeing_views_are_clean() {
+        let mut input = input();
+        input.ownerships[0].amount = 400;
+        input.utxos.retain(|utxo| utxo.amount != 500);
+        input.ownerships[2].amount = 9_000;
+        input.lost_outputs.lost_outputs.clear();
+        input.assignments[1].is_distributed = true;
+        input.wallet_utxos.pop();
+        input.utxos[1].blacklisted = false;
+
+        let report = reconcile(input);
+        assert!(report.is_clean(), "{:?}", report.mismatches);
+        assert_eq!(report.treasury.ownership, Some(9_000));
+    }
+
+    #[test]
+    fn test_report_exports() {
+        let report = reconcile(input());
+
+        let holders = report.holders_csv();
+        let lines: Vec<&str> = holders.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[2], "2,GA2,\"Bob, Ltd\",50,0,50,200,0,200");
+
+        let mismatches = report.mismatches_csv();
+        assert_eq!(mismatches.lines().count(), 7);
+        assert!(mismatches
+            .lines()
+            .any(|line| line.starts_with(&format!("lost_output,,,{},1,,", "ee".repeat(32)))));
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json["treasury"]["amp_amount"], 9_500);
+        assert_eq!(json["holders"][1]["GAID"], "GA2");
+        assert_eq!(json["mismatches"][0]["kind"], "blacklisted_utxo");
+    }
+}
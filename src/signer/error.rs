@@ -82,6 +82,38 @@ pub enum SignerError {
     #[error("Network communication failed: {0}")]
     Network(#[from] reqwest::Error),
 
+    /// The signer does not support the requested Elements network
+    ///
+    /// Software signers refuse Liquid mainnet because their keys are not protected.
+    #[error("Unsupported network: {0}")]
+    UnsupportedNetwork(String),
+
+    /// The signer does not implement the requested operation
+    #[error("Unsupported signer operation: {0}")]
+    Unsupported(String),
+
+    /// An external signer did not respond in time
+    #[error("Signer timed out: {0}")]
+    Timeout(String),
+
+    /// Invalid account index or derivation path
+    #[error("Invalid derivation path: {0}")]
+    DerivationPath(String),
+
+    /// Encrypted mnemonic storage could not be sealed or opened
+    ///
+    /// Returned for a wrong passphrase, corrupted data or an unsupported format version.
+    #[error("Mnemonic encryption error: {0}")]
+    Encryption(String),
+
+    /// Invalid multisig configuration or descriptor
+    #[error("Invalid multisig configuration: {0}")]
+    Multisig(String),
+
+    /// Fewer cosigners signed than the multisig threshold requires
+    #[error("Multisig threshold not met: {collected} of {required} signatures collected")]
+    ThresholdNotMet { required: usize, collected: usize },
+
     /// JSON serialization and deserialization errors
     ///
     /// This variant captures JSON processing errors including:
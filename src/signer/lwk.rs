@@ -1,18 +1,24 @@
 use super::{Signer, SignerError};
 use crate::model::Unspent;
+use crate::network::Network;
 use async_trait::async_trait;
-use bip39::{Language, Mnemonic};
-use elements::bitcoin::bip32::{ChildNumber, DerivationPath, Xpriv};
-use elements::bitcoin::PublicKey;
+use elements::bitcoin::bip32::{ChildNumber, DerivationPath};
 use elements::encode::Decodable;
 use elements::pset::PartiallySignedTransaction;
 use elements::secp256k1_zkp::Secp256k1;
-use elements::{Address, AddressParams, TxOut};
+use elements_miniscript::psbt::PsbtExt;
+use elements_miniscript::{ConfidentialDescriptor, DescriptorPublicKey};
 use lwk_common::Signer as LwkSigner;
 use lwk_signer::SwSigner;
+use secrecy::{ExposeSecret, Secret};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
+use std::str::FromStr;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::encryption::{self, EncryptedEnvelope};
 
 /// Helper function to check if logging should be enabled during tests
 /// Only enables logging if --nocapture is passed to cargo test
@@ -92,15 +98,35 @@ macro_rules! cond_error {
 /// - **Validation**: Full BIP39 checksum validation
 ///
 ///
+/// ## Encrypted Storage
+///
+/// [`write_encrypted`](Self::write_encrypted) and [`read_encrypted`](Self::read_encrypted)
+/// store the same JSON sealed with XChaCha20-Poly1305 under a key derived from a
+/// passphrase with Argon2id. The file is a versioned envelope:
+///
+/// ```json
+/// {
+///   "version": 1,
+///   "kdf": "argon2id",
+///   "salt": "...",
+///   "nonce": "...",
+///   "ciphertext": "..."
+/// }
+/// ```
+///
+/// [`migrate_to_encrypted`](Self::migrate_to_encrypted) converts an existing plaintext
+/// file. Mnemonics held in memory are zeroized when the storage is dropped.
+///
 /// This struct handles persistent storage of mnemonic phrases in JSON format,
 /// supporting multiple mnemonics for different test scenarios.
-#[derive(Serialize, Deserialize, Debug, Clone)]
-struct MnemonicStorage {
+#[derive(Serialize, Deserialize, Debug, Clone, Default, Zeroize, ZeroizeOnDrop)]
+pub struct MnemonicStorage {
     mnemonic: Vec<String>,
 }
 
 impl MnemonicStorage {
     /// Create a new empty mnemonic storage
+    #[must_use]
     pub const fn new() -> Self {
         Self {
             mnemonic: Vec::new(),
@@ -503,6 +529,282 @@ impl MnemonicStorage {
     }
 }
 
+impl MnemonicStorage {
+    /// Read mnemonic storage from a passphrase-encrypted file
+    ///
+    /// A missing or empty file yields empty storage, as with
+    /// [`read_from_file_path`](Self::read_from_file_path). Decrypted data is zeroized
+    /// once parsed.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the encrypted storage file
+    /// * `passphrase` - Passphrase the file was encrypted with
+    ///
+    /// # Errors
+    ///
+    /// This function can return:
+    /// - `SignerError::FileIo` - File reading errors (except file not found)
+    /// - `SignerError::Encryption` - Wrong passphrase, corrupted data, unsupported format
+    ///   version, or a plaintext file that has not been migrated
+    /// - `SignerError::Serialization` - Malformed decrypted content
+    /// - `SignerError::InvalidMnemonic` - Mnemonic validation errors
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use amp_rs::signer::{MnemonicStorage, SignerError};
+    /// # use secrecy::Secret;
+    /// # fn main() -> Result<(), SignerError> {
+    /// let passphrase = Secret::new("correct horse battery staple".to_string());
+    /// let storage = MnemonicStorage::read_encrypted("mnemonic.enc.json", &passphrase)?;
+    /// println!("Loaded {} mnemonics", storage.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_encrypted<P: AsRef<Path>>(
+        path: P,
+        passphrase: &Secret<String>,
+    ) -> Result<Self, SignerError> {
+        let path = path.as_ref();
+
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                cond_debug!(
+                    "Encrypted mnemonic file {:?} does not exist, returning empty storage",
+                    path
+                );
+                return Ok(Self::new());
+            }
+            Err(e) => {
+                cond_error!("Failed to read encrypted mnemonic file {:?}: {}", path, e);
+                return Err(SignerError::FileIo(e));
+            }
+        };
+        if contents.trim().is_empty() {
+            return Ok(Self::new());
+        }
+
+        let envelope: EncryptedEnvelope = serde_json::from_str(&contents).map_err(|e| {
+            if serde_json::from_str::<Self>(&contents).is_ok() {
+                SignerError::Encryption(format!(
+                    "{} holds plaintext mnemonics; use MnemonicStorage::migrate_to_encrypted",
+                    path.display()
+                ))
+            } else {
+                SignerError::Serialization(e)
+            }
+        })?;
+        let plaintext = encryption::open(passphrase.expose_secret().as_bytes(), &envelope)
+            .map_err(|e| SignerError::Encryption(e.to_string()))?;
+
+        let storage: Self = serde_json::from_slice(&plaintext)?;
+        storage.validate()?;
+
+        cond_info!(
+            "Successfully loaded {} encrypted mnemonics from {:?}",
+            storage.len(),
+            path
+        );
+        Ok(storage)
+    }
+
+    /// Write mnemonic storage to a passphrase-encrypted file
+    ///
+    /// A fresh salt and nonce are used on every write. The file is written atomically
+    /// and, on Unix, with `0600` permissions.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path where the encrypted file should be written
+    /// * `passphrase` - Passphrase used to derive the encryption key
+    ///
+    /// # Errors
+    ///
+    /// This function can return:
+    /// - `SignerError::Encryption` - Key derivation or encryption errors
+    /// - `SignerError::Serialization` - JSON serialization errors
+    /// - `SignerError::FileIo` - File writing errors
+    pub fn write_encrypted<P: AsRef<Path>>(
+        &self,
+        path: P,
+        passphrase: &Secret<String>,
+    ) -> Result<(), SignerError> {
+        let path = path.as_ref();
+
+        let plaintext = Zeroizing::new(serde_json::to_vec(self)?);
+        let envelope = encryption::seal(passphrase.expose_secret().as_bytes(), &plaintext)
+            .map_err(|e| SignerError::Encryption(e.to_string()))?;
+        let contents = serde_json::to_string_pretty(&envelope)?;
+
+        let temp_path = path.with_extension("tmp");
+        write_private_file(&temp_path, contents.as_bytes())?;
+        fs::rename(&temp_path, path).map_err(|e| {
+            let _ = fs::remove_file(&temp_path);
+            SignerError::FileIo(e)
+        })?;
+
+        tracing::info!(
+            "Successfully wrote {} encrypted mnemonics to {:?}",
+            self.len(),
+            path
+        );
+        Ok(())
+    }
+
+    /// Encrypt a plaintext mnemonic file and remove the plaintext
+    ///
+    /// The encrypted file is written and read back before the plaintext file is
+    /// overwritten with zeros and deleted, so an interrupted migration never loses
+    /// mnemonics. `plaintext_path` and `encrypted_path` may be the same file.
+    ///
+    /// # Arguments
+    ///
+    /// * `plaintext_path` - Existing plaintext file, e.g. `mnemonic.local.json`
+    /// * `encrypted_path` - Path of the encrypted file to create
+    /// * `passphrase` - Passphrase used to derive the encryption key
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of migrated mnemonics
+    ///
+    /// # Errors
+    ///
+    /// Returns `SignerError::FileIo` if the plaintext file does not exist, and the errors
+    /// of [`read_from_file_path`](Self::read_from_file_path) and
+    /// [`write_encrypted`](Self::write_encrypted)
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use amp_rs::signer::{MnemonicStorage, SignerError};
+    /// # use secrecy::Secret;
+    /// # fn main() -> Result<(), SignerError> {
+    /// let passphrase = Secret::new("correct horse battery staple".to_string());
+    /// let migrated = MnemonicStorage::migrate_to_encrypted(
+    ///     "mnemonic.local.json",
+    ///     "mnemonic.enc.json",
+    ///     &passphrase,
+    /// )?;
+    /// println!("Encrypted {} mnemonics", migrated);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn migrate_to_encrypted<P: AsRef<Path>, Q: AsRef<Path>>(
+        plaintext_path: P,
+        encrypted_path: Q,
+        passphrase: &Secret<String>,
+    ) -> Result<usize, SignerError> {
+        let plaintext_path = plaintext_path.as_ref();
+        let encrypted_path = encrypted_path.as_ref();
+
+        if !plaintext_path.exists() {
+            return Err(SignerError::FileIo(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!(
+                    "Plaintext mnemonic file {} not found",
+                    plaintext_path.display()
+                ),
+            )));
+        }
+        let storage = Self::read_from_file_path(plaintext_path)?;
+
+        // Stage and verify the encrypted copy before touching the plaintext
+        let staging_path = encrypted_path.with_extension("migrating");
+        storage.write_encrypted(&staging_path, passphrase)?;
+        let verified = Self::read_encrypted(&staging_path, passphrase)?;
+        if verified.mnemonic != storage.mnemonic {
+            let _ = fs::remove_file(&staging_path);
+            return Err(SignerError::Encryption(
+                "Encrypted copy does not match the plaintext mnemonics".to_string(),
+            ));
+        }
+
+        // When the encrypted file replaces the plaintext in place, move the plaintext
+        // aside first so that it can still be wiped once the encrypted file is in place
+        let in_place = encrypted_path.exists()
+            && fs::canonicalize(plaintext_path)? == fs::canonicalize(encrypted_path)?;
+        let plaintext_copy = if in_place {
+            let aside = plaintext_path.with_extension("plaintext");
+            fs::rename(plaintext_path, &aside)?;
+            aside
+        } else {
+            plaintext_path.to_path_buf()
+        };
+
+        // Only wipe the plaintext once the encrypted copy is in place, so a failed rename
+        // never leaves the staging file as the only copy of the mnemonics
+        if let Err(e) = fs::rename(&staging_path, encrypted_path) {
+            if in_place {
+                fs::rename(&plaintext_copy, plaintext_path)?;
+            }
+            let _ = fs::remove_file(&staging_path);
+            return Err(e.into());
+        }
+        wipe_file(&plaintext_copy)?;
+
+        tracing::info!(
+            "Migrated {} mnemonics from {:?} to encrypted {:?}",
+            storage.len(),
+            plaintext_path,
+            encrypted_path
+        );
+        Ok(storage.len())
+    }
+}
+
+/// Writes a file readable only by its owner on Unix
+fn write_private_file(path: &Path, contents: &[u8]) -> Result<(), SignerError> {
+    use std::io::Write;
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options.open(path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    Ok(())
+}
+
+/// Overwrites a file with zeros before removing it
+fn wipe_file(path: &Path) -> Result<(), SignerError> {
+    use std::io::Write;
+
+    let len = usize::try_from(fs::metadata(path)?.len()).unwrap_or(0);
+    let mut file = fs::OpenOptions::new().write(true).open(path)?;
+    file.write_all(&vec![0u8; len])?;
+    file.sync_all()?;
+    drop(file);
+    fs::remove_file(path)?;
+    Ok(())
+}
+
+/// Chain of an account's addresses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyChain {
+    /// Receiving addresses (`.../0/*`)
+    External,
+    /// Change addresses (`.../1/*`)
+    Internal,
+}
+
+impl KeyChain {
+    /// Returns the derivation index of the chain
+    #[must_use]
+    pub const fn index(self) -> u32 {
+        match self {
+            Self::External => 0,
+            Self::Internal => 1,
+        }
+    }
+}
+
 /// Software-based transaction signer using Blockstream's Liquid Wallet Kit (LWK)
 ///
 /// `LwkSoftwareSigner` provides transaction signing capabilities for Elements/Liquid
@@ -514,9 +816,10 @@ impl MnemonicStorage {
 ///
 /// **THIS IMPLEMENTATION IS FOR TESTNET/REGTEST ONLY**
 ///
-/// - Mnemonic phrases are stored in **PLAIN TEXT** in `mnemonic.local.json`
+/// - Mnemonic phrases are stored in **PLAIN TEXT** in `mnemonic.local.json` unless
+///   [`generate_new_indexed_encrypted`](Self::generate_new_indexed_encrypted) is used
 /// - Private keys are held in **UNENCRYPTED MEMORY**
-/// - No password protection or encryption is provided
+/// - Encrypted storage protects mnemonics at rest only, not while the signer runs
 /// - Suitable ONLY for development, testing, and regtest environments
 ///
 /// **NEVER USE IN PRODUCTION OR WITH REAL FUNDS**
@@ -601,7 +904,7 @@ impl MnemonicStorage {
 ///
 /// All `LwkSoftwareSigner` instances are configured for testnet/regtest:
 /// - `is_testnet()` always returns `true`
-/// - Compatible with Elements regtest and Liquid testnet
+/// - Defaults to Liquid testnet; use `with_network()` for Elements regtest
 /// - Supports confidential transactions and Liquid-specific features
 ///
 /// ## Thread Safety
@@ -624,7 +927,11 @@ impl MnemonicStorage {
 pub struct LwkSoftwareSigner {
     signer: lwk_signer::SwSigner,
     mnemonic: String,
-    is_testnet: bool,
+    network: Network,
+    account: u32,
+    account_path: Option<DerivationPath>,
+    /// Whether an account was selected with `with_account` or `with_account_path`
+    account_selected: bool,
 }
 
 impl LwkSoftwareSigner {
@@ -707,7 +1014,10 @@ impl LwkSoftwareSigner {
         Ok(Self {
             signer,
             mnemonic: mnemonic_phrase.to_string(),
-            is_testnet: true,
+            network: Network::LiquidTestnet,
+            account: 0,
+            account_path: None,
+            account_selected: false,
         })
     }
 
@@ -842,6 +1152,54 @@ impl LwkSoftwareSigner {
         Ok((mnemonic, signer_instance))
     }
 
+    /// Get or create a signer from the mnemonic at `index` in an encrypted storage file
+    ///
+    /// Works like [`generate_new_indexed`](Self::generate_new_indexed), but reads and
+    /// writes `path` with [`MnemonicStorage::read_encrypted`] and
+    /// [`MnemonicStorage::write_encrypted`].
+    ///
+    /// # Errors
+    ///
+    /// This function can return:
+    /// - `SignerError::Encryption` - Wrong passphrase or corrupted storage file
+    /// - `SignerError::FileIo` - File reading or writing errors
+    /// - `SignerError::InvalidMnemonic` - Mnemonic validation errors
+    /// - `SignerError::Lwk` - Signer creation errors
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use amp_rs::signer::{LwkSoftwareSigner, SignerError};
+    /// # use secrecy::Secret;
+    /// # fn main() -> Result<(), SignerError> {
+    /// let passphrase = Secret::new("correct horse battery staple".to_string());
+    /// let (_, signer) =
+    ///     LwkSoftwareSigner::generate_new_indexed_encrypted(0, "mnemonic.enc.json", &passphrase)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn generate_new_indexed_encrypted<P: AsRef<Path>>(
+        index: usize,
+        path: P,
+        passphrase: &Secret<String>,
+    ) -> Result<(String, Self), SignerError> {
+        let path = path.as_ref();
+        let mut storage = MnemonicStorage::read_encrypted(path, passphrase)?;
+
+        let existing = storage.len();
+        let mnemonic = storage.get_or_generate_mnemonic_at_index(index)?;
+        if storage.len() != existing {
+            storage.write_encrypted(path, passphrase)?;
+        }
+
+        let signer_instance = Self::new(&mnemonic)?;
+        tracing::info!(
+            "Successfully created LwkSoftwareSigner with encrypted mnemonic at index {}",
+            index
+        );
+        Ok((mnemonic, signer_instance))
+    }
+
     /// Generate `WPkH` descriptor with Slip77 blinding for Liquid confidential addresses
     ///
     /// This method generates a single descriptor that covers both receive and change
@@ -880,15 +1238,16 @@ impl LwkSoftwareSigner {
 
     /// Generate P2SH-wrapped `WPkH` descriptor with Slip77 blinding for Liquid confidential addresses
     ///
-    /// This method generates a P2SH-wrapped segwit descriptor that matches the addresses
-    /// generated by our `derive_address` method. This ensures consistency between the
-    /// descriptor imported into Elements and the addresses we derive.
+    /// This method generates a P2SH-wrapped segwit descriptor for the signer's account that
+    /// matches the addresses generated by our `derive_address` method. This ensures
+    /// consistency between the descriptor imported into Elements and the addresses we derive.
+    /// Equivalent to [`get_account_descriptor`](Self::get_account_descriptor).
     ///
-    /// The descriptor format is: `ct(slip77(...),elsh(elwpkh(...)))`
+    /// The descriptor format is: `ct(slip77(...),elsh(wpkh(...)))`
     /// - `ct()` - Confidential transaction wrapper
     /// - `slip77()` - Blinding key derivation
     /// - `elsh()` - Elements script hash (P2SH wrapper)
-    /// - `elwpkh()` - Elements witness public key hash (segwit)
+    /// - `wpkh()` - Witness public key hash (segwit)
     ///
     /// # Returns
     ///
@@ -910,48 +1269,9 @@ impl LwkSoftwareSigner {
     /// # Ok(())
     /// # }
     /// ```
-    #[allow(clippy::cognitive_complexity)]
     pub fn get_p2sh_wpkh_slip77_descriptor(&self) -> Result<String, SignerError> {
-        tracing::debug!("Generating custom P2SH-wrapped WPkH Slip77 descriptor");
-
-        // First get the native segwit descriptor from LWK
-        let native_descriptor = self.signer.wpkh_slip77_descriptor().map_err(|e| {
-            tracing::error!("Failed to generate native descriptor: {}", e);
-            SignerError::Lwk(format!("Failed to generate native descriptor: {e}"))
-        })?;
-
-        tracing::debug!("Native descriptor: {}", native_descriptor);
-
-        // Convert the native segwit descriptor to P2SH-wrapped
-        // The native descriptor looks like: ct(slip77(...),elwpkh([...]/84h/1h/0h]tpub.../<0;1>/*))#checksum
-        // We need to convert it to: ct(slip77(...),elsh(elwpkh([...]/49h/1h/0h]tpub.../<0;1>/*)))#checksum
-
-        let p2sh_descriptor = if native_descriptor.contains("elwpkh(") {
-            // Replace the derivation path from 84h (native segwit) to 49h (P2SH-wrapped segwit)
-            let with_p2sh_path = native_descriptor.replace("/84h/1h/0h]", "/49h/1h/0h]");
-
-            // Wrap the elwpkh with elsh() for P2SH
-            let wrapped = with_p2sh_path.replace("elwpkh(", "elsh(elwpkh(");
-
-            // Add the closing parenthesis for elsh() before the checksum
-            if let Some(checksum_pos) = wrapped.rfind("))#") {
-                let mut result = wrapped;
-                result.insert(checksum_pos + 1, ')');
-                result
-            } else {
-                // If no checksum, just add the closing parenthesis
-                wrapped.replace("))", ")))")
-            }
-        } else {
-            return Err(SignerError::Lwk(
-                "Unexpected descriptor format - expected elwpkh".to_string(),
-            ));
-        };
-
-        tracing::info!("Successfully generated P2SH-wrapped WPkH Slip77 descriptor");
-        tracing::debug!("P2SH descriptor: {}", p2sh_descriptor);
-
-        Ok(p2sh_descriptor)
+        tracing::debug!("Generating P2SH-wrapped WPkH Slip77 descriptor");
+        self.get_account_descriptor()
     }
 
     /// Generate `WPkH` descriptors with Slip77 blinding for Liquid confidential addresses
@@ -990,12 +1310,38 @@ impl LwkSoftwareSigner {
         Ok((descriptor.clone(), descriptor))
     }
 
-    /// Derive a P2SH-wrapped segwit receiving address from the signer's mnemonic
+    /// Returns the signer's BIP87 multisig account key with its origin
     ///
-    /// This method derives a P2SH-wrapped segwit receiving address from the signer's mnemonic
-    /// using BIP49 derivation paths. The address is suitable for receiving confidential assets
-    /// and can be used as a treasury address for asset operations. This provides a more complex
-    /// testing scenario with blinded P2SH transactions.
+    /// The key has the form `[fingerprint/87h/1h/0h]tpub...` and is used as this
+    /// signer's key in a [`MultisigSigner`](super::MultisigSigner) descriptor.
+    ///
+    /// # Errors
+    /// Returns an error if the account key cannot be derived
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use amp_rs::signer::{LwkSoftwareSigner, SignerError};
+    /// # fn main() -> Result<(), SignerError> {
+    /// let (_, signer) = LwkSoftwareSigner::generate_new()?;
+    /// let key = signer.get_multisig_key_origin_xpub()?;
+    /// println!("Cosigner key: {}", key);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_multisig_key_origin_xpub(&self) -> Result<String, SignerError> {
+        self.signer
+            .keyorigin_xpub(lwk_common::Bip::Bip87, self.network.is_mainnet())
+            .map_err(|e| SignerError::Lwk(format!("Failed to derive multisig account key: {e}")))
+    }
+
+    /// Derive a P2SH-wrapped segwit receiving address
+    ///
+    /// By default the address is derived at the legacy path `m/49'/1776'/0'/0/<index>`
+    /// and blinded with its own key, so existing treasury addresses keep resolving to the
+    /// same address. Once an account is selected with [`with_account`](Self::with_account)
+    /// or [`with_account_path`](Self::with_account_path), this is equivalent to
+    /// [`derive_address_on_chain`](Self::derive_address_on_chain) on the external chain.
     ///
     /// # Arguments
     ///
@@ -1004,7 +1350,7 @@ impl LwkSoftwareSigner {
     /// # Returns
     ///
     /// Returns a `Result` containing:
-    /// - `Ok(String)` - Base58-encoded Liquid P2SH address (starts with 'v' on testnet)
+    /// - `Ok(String)` - Confidential Liquid P2SH address
     /// - `Err(SignerError)` - Address derivation error
     ///
     /// # Errors
@@ -1012,10 +1358,6 @@ impl LwkSoftwareSigner {
     /// This function can return:
     /// - `SignerError::Lwk` - Address derivation failures
     ///
-    /// # Panics
-    /// May panic when creating hardened child numbers from known valid indices.
-    /// This should never occur in practice as the indices are hardcoded constants.
-    ///
     /// # Example
     ///
     /// ```rust,no_run
@@ -1028,60 +1370,309 @@ impl LwkSoftwareSigner {
     /// # }
     /// ```
     pub fn derive_address(&self, index: Option<u32>) -> Result<String, SignerError> {
-        let derivation_index = index.unwrap_or(0);
-
-        tracing::debug!("Deriving address at index {} for testnet", derivation_index);
-
-        // Parse the mnemonic
-        let mnemonic = Mnemonic::parse_in(Language::English, &self.mnemonic)
-            .map_err(|e| SignerError::InvalidMnemonic(format!("Failed to parse mnemonic: {e}")))?;
-
-        // Create secp256k1 context
-        let secp = Secp256k1::new();
-
-        // Generate seed from mnemonic
-        let seed = mnemonic.to_seed("");
+        let index = index.unwrap_or(0);
+        if self.account_selected {
+            return self.derive_address_on_chain(KeyChain::External, index);
+        }
 
-        // Create master extended private key
-        let master_key = Xpriv::new_master(elements::bitcoin::Network::Regtest, &seed)
-            .map_err(|e| SignerError::Lwk(format!("Failed to create master key: {e}")))?;
+        tracing::debug!(
+            "Deriving legacy address at index {} for {}",
+            index,
+            self.network
+        );
 
-        // Derive using BIP49 path: m/49'/1776'/0'/0/index (BIP49 for P2SH-wrapped segwit, 1776 is Liquid's coin type)
+        // Legacy BIP49 path with Liquid's coin type: m/49'/1776'/0'/0/index
         let derivation_path = DerivationPath::from(vec![
-            ChildNumber::from_hardened_idx(49).unwrap(), // BIP49 for P2SH-wrapped segwit
-            ChildNumber::from_hardened_idx(1776).unwrap(), // Liquid coin type
-            ChildNumber::from_hardened_idx(0).unwrap(),
-            ChildNumber::from_normal_idx(0).unwrap(),
-            ChildNumber::from_normal_idx(derivation_index).unwrap(),
+            ChildNumber::Hardened { index: 49 },
+            ChildNumber::Hardened { index: 1776 },
+            ChildNumber::Hardened { index: 0 },
+            ChildNumber::Normal { index: 0 },
+            ChildNumber::from_normal_idx(index).map_err(|e| {
+                SignerError::DerivationPath(format!("Invalid address index {index}: {e}"))
+            })?,
         ]);
+        let public_key = self
+            .signer
+            .derive_xpub(&derivation_path)
+            .map_err(|e| SignerError::Lwk(format!("Failed to derive key: {e}")))?
+            .public_key;
+
+        // The derived key doubles as the address's blinding key
+        let address = elements::Address::p2shwpkh(
+            &elements::bitcoin::PublicKey::new(public_key),
+            Some(public_key),
+            self.network.address_params(),
+        );
 
-        let derived_key = master_key
-            .derive_priv(&secp, &derivation_path)
-            .map_err(|e| SignerError::Lwk(format!("Failed to derive key: {e}")))?;
+        let address_str = address.to_string();
+        tracing::info!(
+            "Successfully derived address at index {}: {}",
+            index,
+            address_str
+        );
 
-        // Get the public key and convert to bitcoin::PublicKey
-        let secp_public_key = derived_key.private_key.public_key(&secp);
-        let public_key = PublicKey::from(secp_public_key);
+        Ok(address_str)
+    }
 
-        // Create confidential address (using Liquid testnet parameters)
-        let address_params = &AddressParams::LIQUID_TESTNET;
-        // Generate a blinding key for confidential transactions
-        let blinding_key = derived_key.private_key;
-        let blinding_pubkey = blinding_key.public_key(&secp);
+    /// Derive a P2SH-wrapped segwit address on the external or internal (change) chain
+    ///
+    /// The address is derived at `<account path>/<chain>/<index>` and blinded with the
+    /// signer's SLIP-77 key, so it matches the addresses an Elements wallet derives from
+    /// [`get_account_descriptor`](Self::get_account_descriptor).
+    ///
+    /// # Errors
+    ///
+    /// Returns `SignerError::Lwk` if the account key, blinding key or address cannot be
+    /// derived
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use amp_rs::signer::{KeyChain, LwkSoftwareSigner, SignerError};
+    /// # fn main() -> Result<(), SignerError> {
+    /// let signer = LwkSoftwareSigner::new("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about")?
+    ///     .with_account(3)?;
+    /// let receive = signer.derive_address_on_chain(KeyChain::External, 0)?;
+    /// let change = signer.derive_address_on_chain(KeyChain::Internal, 0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn derive_address_on_chain(
+        &self,
+        chain: KeyChain,
+        index: u32,
+    ) -> Result<String, SignerError> {
+        tracing::debug!(
+            "Deriving {:?} address at index {} of account {} for {}",
+            chain,
+            index,
+            self.account_path(),
+            self.network
+        );
 
-        // Create P2SH-wrapped segwit address for more complex testing scenario
-        let address = Address::p2shwpkh(&public_key, Some(blinding_pubkey), address_params);
+        let chain_descriptor = self.chain_descriptor(chain)?;
+        let definite = chain_descriptor
+            .at_derivation_index(index)
+            .map_err(|e| SignerError::Lwk(format!("Failed to derive index {index}: {e}")))?;
+        let address = definite
+            .address(&Secp256k1::new(), self.network.address_params())
+            .map_err(|e| SignerError::Lwk(format!("Failed to derive address: {e}")))?;
 
         let address_str = address.to_string();
         tracing::info!(
             "Successfully derived address at index {}: {}",
-            derivation_index,
+            index,
             address_str
         );
 
         Ok(address_str)
     }
 
+    /// Select the BIP49 account used for descriptors, addresses and signing
+    ///
+    /// The account path becomes `m/49'/<coin type>'/<account>'`, so one seed can hold
+    /// separate accounts per asset or investor. Defaults to account 0. Selecting an
+    /// account also switches [`derive_address`](Self::derive_address) to the account's
+    /// SLIP-77 blinded addresses.
+    ///
+    /// # Errors
+    /// Returns `SignerError::DerivationPath` if `account` is not below 2^31
+    pub fn with_account(mut self, account: u32) -> Result<Self, SignerError> {
+        ChildNumber::from_hardened_idx(account).map_err(|e| {
+            SignerError::DerivationPath(format!("Invalid account index {account}: {e}"))
+        })?;
+        self.account = account;
+        self.account_path = None;
+        self.account_selected = true;
+        Ok(self)
+    }
+
+    /// Use a custom account derivation path instead of the BIP49 account path
+    ///
+    /// Addresses are derived below this path as `<path>/<chain>/<index>`, including those
+    /// returned by [`derive_address`](Self::derive_address).
+    ///
+    /// # Errors
+    /// Returns `SignerError::DerivationPath` if the path is empty
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use amp_rs::signer::{LwkSoftwareSigner, SignerError};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let signer = LwkSoftwareSigner::new("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about")?
+    ///     .with_account_path("m/49'/1'/42'".parse()?)?;
+    /// println!("Descriptor: {}", signer.get_account_descriptor()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_account_path(mut self, path: DerivationPath) -> Result<Self, SignerError> {
+        if path.is_empty() {
+            return Err(SignerError::DerivationPath(
+                "Account path must not be the master key".to_string(),
+            ));
+        }
+        self.account_path = Some(path);
+        self.account_selected = true;
+        Ok(self)
+    }
+
+    /// Returns the configured BIP49 account index
+    ///
+    /// Not used when a custom path is set with [`with_account_path`](Self::with_account_path).
+    #[must_use]
+    pub const fn account(&self) -> u32 {
+        self.account
+    }
+
+    /// Returns the derivation path of the signer's account
+    #[must_use]
+    pub fn account_path(&self) -> DerivationPath {
+        self.account_path.clone().unwrap_or_else(|| {
+            DerivationPath::from(vec![
+                ChildNumber::Hardened { index: 49 },
+                ChildNumber::Hardened {
+                    index: self.network.coin_type(),
+                },
+                ChildNumber::Hardened {
+                    index: self.account,
+                },
+            ])
+        })
+    }
+
+    /// Generate the confidential descriptor of the signer's account
+    ///
+    /// The descriptor has the form
+    /// `ct(slip77(...),elsh(wpkh([fingerprint/path]tpub.../<0;1>/*)))#checksum` and covers
+    /// both the external and internal chains.
+    ///
+    /// # Errors
+    /// Returns `SignerError::Lwk` if the account key or blinding key cannot be derived
+    pub fn get_account_descriptor(&self) -> Result<String, SignerError> {
+        Ok(self.account_descriptor()?.to_string())
+    }
+
+    /// Generate separate external and internal chain descriptors of the signer's account
+    ///
+    /// Suitable for [`ElementsRpc::import_descriptors`](crate::ElementsRpc::import_descriptors),
+    /// which imports the second descriptor as the wallet's change chain.
+    ///
+    /// # Errors
+    /// Returns `SignerError::Lwk` if the account key or blinding key cannot be derived
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use amp_rs::signer::LwkSoftwareSigner;
+    /// # use amp_rs::ElementsRpc;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let rpc = ElementsRpc::from_env()?;
+    /// let signer = LwkSoftwareSigner::new("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about")?
+    ///     .with_account(7)?;
+    /// let (external, internal) = signer.get_account_descriptors()?;
+    /// rpc.import_descriptors("investor_7", &external, &internal).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_account_descriptors(&self) -> Result<(String, String), SignerError> {
+        Ok((
+            self.chain_descriptor(KeyChain::External)?.to_string(),
+            self.chain_descriptor(KeyChain::Internal)?.to_string(),
+        ))
+    }
+
+    /// Builds the account's multipath confidential descriptor
+    fn account_descriptor(
+        &self,
+    ) -> Result<ConfidentialDescriptor<DescriptorPublicKey>, SignerError> {
+        let account_path = self.account_path();
+        let xpub = self
+            .signer
+            .derive_xpub(&account_path)
+            .map_err(|e| SignerError::Lwk(format!("Failed to derive account key: {e}")))?;
+        let blinding_key = self
+            .signer
+            .slip77_master_blinding_key()
+            .map_err(|e| SignerError::Lwk(format!("Failed to derive SLIP-77 blinding key: {e}")))?;
+
+        let origin: Vec<String> = std::iter::once(self.signer.fingerprint().to_string())
+            .chain(account_path.into_iter().map(ToString::to_string))
+            .collect();
+        let descriptor = format!(
+            "ct(slip77({blinding_key}),elsh(wpkh([{}]{xpub}/<0;1>/*)))",
+            origin.join("/")
+        );
+        ConfidentialDescriptor::from_str(&descriptor)
+            .map_err(|e| SignerError::Lwk(format!("Failed to build account descriptor: {e}")))
+    }
+
+    /// Builds the single-chain confidential descriptor for `chain`
+    fn chain_descriptor(
+        &self,
+        chain: KeyChain,
+    ) -> Result<ConfidentialDescriptor<DescriptorPublicKey>, SignerError> {
+        let account = self.account_descriptor()?;
+        let mut chains = account
+            .descriptor
+            .into_single_descriptors()
+            .map_err(|e| SignerError::Lwk(format!("Failed to split account descriptor: {e}")))?;
+        let index = chain.index() as usize;
+        if index >= chains.len() {
+            return Err(SignerError::Lwk(format!(
+                "Account descriptor has no chain {index}"
+            )));
+        }
+        Ok(ConfidentialDescriptor {
+            key: account.key,
+            descriptor: chains.swap_remove(index),
+        })
+    }
+
+    /// Adds witness scripts and key derivations for inputs spending this account
+    ///
+    /// LWK only signs inputs whose key derivations name its key, so inputs are matched by
+    /// script against the first [`DEFAULT_GAP_LIMIT`](super::DEFAULT_GAP_LIMIT) addresses of
+    /// both chains. Returns the number of inputs updated.
+    fn update_pset_for_account(
+        &self,
+        pset: &mut PartiallySignedTransaction,
+    ) -> Result<usize, SignerError> {
+        let mut scripts = HashMap::new();
+        for chain in [KeyChain::External, KeyChain::Internal] {
+            let chain_descriptor = self.chain_descriptor(chain)?;
+            for index in 0..super::DEFAULT_GAP_LIMIT {
+                let definite = chain_descriptor
+                    .descriptor
+                    .at_derivation_index(index)
+                    .map_err(|e| {
+                        SignerError::Lwk(format!("Failed to derive index {index}: {e}"))
+                    })?;
+                scripts.insert(definite.script_pubkey(), definite);
+            }
+        }
+
+        let mut updated = 0;
+        for index in 0..pset.inputs().len() {
+            let input = &pset.inputs()[index];
+            if !input.bip32_derivation.is_empty() {
+                continue;
+            }
+            let descriptor = input
+                .witness_utxo
+                .as_ref()
+                .and_then(|utxo| scripts.get(&utxo.script_pubkey));
+            if let Some(descriptor) = descriptor {
+                pset.update_input_with_descriptor(index, descriptor)
+                    .map_err(|e| {
+                        SignerError::Lwk(format!("Failed to update PSET input {index}: {e}"))
+                    })?;
+                updated += 1;
+            }
+        }
+        Ok(updated)
+    }
+
     /// Check if this signer is configured for testnet/regtest networks
     ///
     /// This method returns the network configuration of the signer. For `LwkSoftwareSigner`,
@@ -1117,7 +1708,45 @@ impl LwkSoftwareSigner {
     /// ```
     #[must_use]
     pub const fn is_testnet(&self) -> bool {
-        self.is_testnet
+        !self.network.is_mainnet()
+    }
+
+    /// Configure the network used for derived addresses and descriptors
+    ///
+    /// Signers default to Liquid testnet. Use this to derive Elements regtest addresses
+    /// for a local node.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SignerError::UnsupportedNetwork` for Liquid mainnet, which this signer never supports
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use amp_rs::signer::{LwkSoftwareSigner, SignerError};
+    /// # use amp_rs::network::{Network, ELEMENTS_REGTEST_POLICY_ASSET};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let (_, signer) = LwkSoftwareSigner::generate_new()?;
+    /// let signer = signer.with_network(Network::elements_regtest(ELEMENTS_REGTEST_POLICY_ASSET)?)?;
+    /// let address = signer.derive_address(Some(0))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_network(mut self, network: Network) -> Result<Self, SignerError> {
+        if network.is_mainnet() {
+            return Err(SignerError::UnsupportedNetwork(
+                "LwkSoftwareSigner is restricted to testnet and regtest networks".to_string(),
+            ));
+        }
+
+        self.network = network;
+        Ok(self)
+    }
+
+    /// Returns the network used for derived addresses and descriptors
+    #[must_use]
+    pub const fn network(&self) -> &Network {
+        &self.network
     }
 
     /// Create a signer from an Elements-exported private key (Elements-first approach)
@@ -1208,7 +1837,10 @@ impl LwkSoftwareSigner {
         Ok(Self {
             signer,
             mnemonic: format!("elements_private_key:{private_key_wif}"),
-            is_testnet: true,
+            network: Network::LiquidTestnet,
+            account: 0,
+            account_path: None,
+            account_selected: false,
         })
     }
 
@@ -1385,105 +2017,17 @@ impl LwkSoftwareSigner {
             ));
         }
 
-        // Validate UTXO count matches transaction inputs
-        if utxos.len() != unsigned_transaction.input.len() {
-            cond_error!(
-                "UTXO count ({}) does not match transaction input count ({})",
-                utxos.len(),
-                unsigned_transaction.input.len()
-            );
-            return Err(SignerError::InvalidTransaction(format!(
-                "UTXO count ({}) must match transaction input count ({})",
-                utxos.len(),
-                unsigned_transaction.input.len()
-            )));
-        }
-
-        // Convert to PartiallySignedTransaction for LWK signing
-        let mut pset = PartiallySignedTransaction::from_tx(unsigned_transaction.clone());
-
-        cond_debug!(
-            "Created PSET for signing with {} inputs",
-            pset.inputs().len()
-        );
-
-        // Add UTXO information to PSBT inputs
-        for (i, utxo) in utxos.iter().enumerate() {
-            // Verify the UTXO matches the transaction input
-            let tx_input = &unsigned_transaction.input[i];
-            if tx_input.previous_output.txid.to_string() != utxo.txid {
-                return Err(SignerError::InvalidTransaction(format!(
-                    "UTXO {} txid mismatch: expected {}, got {}",
-                    i, tx_input.previous_output.txid, utxo.txid
-                )));
-            }
-            if tx_input.previous_output.vout != utxo.vout {
-                return Err(SignerError::InvalidTransaction(format!(
-                    "UTXO {} vout mismatch: expected {}, got {}",
-                    i, tx_input.previous_output.vout, utxo.vout
-                )));
-            }
-
-            // Create TxOut from UTXO information
-            let value =
-                elements::confidential::Value::Explicit((utxo.amount * 100_000_000.0) as u64);
-            let asset = hex::decode(&utxo.asset).map_err(|e| {
-                SignerError::InvalidTransaction(format!("Invalid asset hex in UTXO {i}: {e}"))
-            })?;
-            let asset_commitment = if asset.len() == 32 {
-                let mut asset_bytes = [0u8; 32];
-                asset_bytes.copy_from_slice(&asset);
-                // Create AssetId from the raw bytes
-                let asset_id =
-                    elements::issuance::AssetId::from_slice(&asset_bytes).map_err(|e| {
-                        SignerError::InvalidTransaction(format!(
-                            "Invalid asset ID in UTXO {i}: {e}"
-                        ))
-                    })?;
-                elements::confidential::Asset::Explicit(asset_id)
-            } else {
-                return Err(SignerError::InvalidTransaction(format!(
-                    "Invalid asset length in UTXO {}: expected 32 bytes, got {}",
-                    i,
-                    asset.len()
-                )));
-            };
-
-            // Parse script pubkey if available
-            let script_pubkey = if let Some(ref spk) = utxo.scriptpubkey {
-                hex::decode(spk).map_err(|e| {
-                    SignerError::InvalidTransaction(format!(
-                        "Invalid scriptpubkey hex in UTXO {i}: {e}"
-                    ))
-                })?
-            } else {
-                // If no scriptpubkey provided, we can't properly construct the UTXO
-                return Err(SignerError::InvalidTransaction(format!(
-                    "Missing scriptpubkey for UTXO {i}"
-                )));
-            };
-
-            let tx_out = TxOut {
-                asset: asset_commitment,
-                value,
-                nonce: elements::confidential::Nonce::Null,
-                script_pubkey: elements::Script::from(script_pubkey),
-                witness: elements::TxOutWitness::default(),
-            };
-
-            // Add the UTXO to the PSBT input
-            if let Some(input) = pset.inputs_mut().get_mut(i) {
-                input.witness_utxo = Some(tx_out);
-                cond_debug!("Added UTXO {} to PSBT input {}", utxo.txid, i);
-            } else {
-                return Err(SignerError::InvalidTransaction(format!(
-                    "Failed to get PSBT input {i} for UTXO addition"
-                )));
-            }
-        }
+        // Convert to PartiallySignedTransaction with the spent outputs for LWK signing
+        let mut pset = super::pset::from_transaction(unsigned_transaction, utxos).map_err(|e| {
+            cond_error!("Failed to build PSET from UTXOs: {}", e);
+            e
+        })?;
 
         cond_debug!("Added {} UTXOs to PSBT inputs", utxos.len());
 
+        let account_inputs = self.update_pset_for_account(&mut pset)?;
+        cond_debug!("Matched {} inputs to the signer's account", account_inputs);
+
         // Use SwSigner to sign the transaction
         let signed_inputs = self.signer.sign(&mut pset).map_err(|e| {
             cond_error!(
@@ -1535,6 +2079,12 @@ impl LwkSoftwareSigner {
     }
 }
 
+impl Drop for LwkSoftwareSigner {
+    fn drop(&mut self) {
+        self.mnemonic.zeroize();
+    }
+}
+
 #[async_trait]
 impl Signer for LwkSoftwareSigner {
     #[allow(clippy::too_many_lines)]
@@ -1688,6 +2238,24 @@ impl Signer for LwkSoftwareSigner {
         Ok(signed_hex)
     }
 
+    async fn sign_pset(
+        &self,
+        mut pset: PartiallySignedTransaction,
+    ) -> Result<PartiallySignedTransaction, SignerError> {
+        self.update_pset_for_account(&mut pset)?;
+        let signed_inputs = self.signer.sign(&mut pset).map_err(|e| {
+            tracing::error!("LWK PSET signing failed: {}", e);
+            SignerError::Lwk(format!(
+                "PSET signing failed for {} inputs: {}",
+                pset.inputs().len(),
+                e
+            ))
+        })?;
+
+        tracing::info!("Signed {} PSET inputs", signed_inputs);
+        Ok(pset)
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -1713,6 +2281,32 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_lwk_signer_network_configuration() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let signer = LwkSoftwareSigner::new(mnemonic).unwrap();
+        assert_eq!(signer.network(), &Network::LiquidTestnet);
+        let testnet_address = signer.derive_address(Some(0)).unwrap();
+        assert!(Network::LiquidTestnet
+            .validate_address(&testnet_address)
+            .is_ok());
+
+        let regtest =
+            Network::elements_regtest(crate::network::ELEMENTS_REGTEST_POLICY_ASSET).unwrap();
+        let signer = signer.with_network(regtest.clone()).unwrap();
+        assert!(signer.is_testnet());
+        let regtest_address = signer.derive_address(Some(0)).unwrap();
+        assert!(regtest.validate_address(&regtest_address).is_ok());
+        assert!(Network::LiquidTestnet
+            .validate_address(&regtest_address)
+            .is_err());
+
+        let result = LwkSoftwareSigner::new(mnemonic)
+            .unwrap()
+            .with_network(Network::Liquid);
+        assert!(matches!(result, Err(SignerError::UnsupportedNetwork(_))));
+    }
+
     #[test]
     fn test_lwk_signer_generate_methods() {
         // Test generate_new method
@@ -3751,6 +4345,223 @@ mod tests {
         let _ = fs::remove_file(zero_path);
         let _ = fs::remove_file(extra_path);
     }
+
+    #[test]
+    fn test_derive_address_keeps_legacy_path_by_default() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let signer = LwkSoftwareSigner::new(mnemonic).unwrap();
+
+        // m/49'/1776'/0'/0/i blinded with the derived key, as derived before accounts
+        assert_eq!(
+            signer.derive_address(Some(0)).unwrap(),
+            "vjU2VEXNnHFHq5i2szWtGoK9SKZ8Wd2NKj2D4CqxTts3BWqSpRzQLmKA1UPctcN7j6kggHWdGrR19mmS"
+        );
+        assert_eq!(
+            signer.derive_address(Some(1)).unwrap(),
+            "vjTwCcC1Dx6XvYgeZnxSLR9TocWWeamVwbqmBCHop2EcMsCwQjSyXEDd36wu17kWqm8FMbLVajYSdTou"
+        );
+    }
+
+    #[test]
+    fn test_account_and_chain_derivation() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let signer = LwkSoftwareSigner::new(mnemonic).unwrap();
+        assert_eq!(signer.account_path().to_string(), "49'/1'/0'");
+
+        let external = signer
+            .derive_address_on_chain(KeyChain::External, 0)
+            .unwrap();
+        let internal = signer
+            .derive_address_on_chain(KeyChain::Internal, 0)
+            .unwrap();
+        assert_ne!(signer.derive_address(None).unwrap(), external);
+        assert_eq!(
+            LwkSoftwareSigner::new(mnemonic)
+                .unwrap()
+                .with_account(0)
+                .unwrap()
+                .derive_address(None)
+                .unwrap(),
+            external
+        );
+        assert_ne!(external, internal);
+        assert!(Network::LiquidTestnet.validate_address(&internal).is_ok());
+
+        let account = LwkSoftwareSigner::new(mnemonic)
+            .unwrap()
+            .with_account(3)
+            .unwrap();
+        assert_eq!(account.account(), 3);
+        assert_ne!(account.derive_address(None).unwrap(), external);
+        assert!(account
+            .get_account_descriptor()
+            .unwrap()
+            .contains("/49'/1'/3']"));
+
+        let (external_desc, internal_desc) = account.get_account_descriptors().unwrap();
+        assert!(external_desc.contains("/0/*"));
+        assert!(internal_desc.contains("/1/*"));
+
+        let custom = LwkSoftwareSigner::new(mnemonic)
+            .unwrap()
+            .with_account_path("m/49'/1'/3'".parse().unwrap())
+            .unwrap();
+        assert_eq!(
+            custom.get_account_descriptor().unwrap(),
+            account.get_account_descriptor().unwrap()
+        );
+
+        assert!(matches!(
+            LwkSoftwareSigner::new(mnemonic)
+                .unwrap()
+                .with_account(1 << 31),
+            Err(SignerError::DerivationPath(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sign_pset_signs_inputs_of_configured_account() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let signer = LwkSoftwareSigner::new(mnemonic)
+            .unwrap()
+            .with_account(2)
+            .unwrap();
+        let change: elements::Address = signer
+            .derive_address_on_chain(KeyChain::Internal, 5)
+            .unwrap()
+            .parse()
+            .unwrap();
+
+        let asset = elements::AssetId::from_slice(&[9u8; 32]).unwrap();
+        let mut pset = PartiallySignedTransaction::from_tx(elements::Transaction {
+            version: 2,
+            lock_time: elements::LockTime::ZERO,
+            input: vec![elements::TxIn {
+                previous_output: elements::OutPoint::new("ee".repeat(32).parse().unwrap(), 0),
+                is_pegin: false,
+                script_sig: elements::Script::new(),
+                sequence: elements::Sequence::ENABLE_RBF_NO_LOCKTIME,
+                asset_issuance: elements::AssetIssuance::default(),
+                witness: elements::TxInWitness::default(),
+            }],
+            output: vec![elements::TxOut::new_fee(1_000, asset)],
+        });
+        pset.inputs_mut()[0].witness_utxo = Some(elements::TxOut {
+            asset: elements::confidential::Asset::Explicit(asset),
+            value: elements::confidential::Value::Explicit(1_000),
+            nonce: elements::confidential::Nonce::Null,
+            script_pubkey: change.script_pubkey(),
+            witness: elements::TxOutWitness::default(),
+        });
+
+        let signed = signer.sign_pset(pset.clone()).await.unwrap();
+        assert_eq!(signed.inputs()[0].partial_sigs.len(), 1);
+
+        // The same seed on another account does not own the input
+        let other_account = LwkSoftwareSigner::new(mnemonic).unwrap();
+        let result = other_account.sign_pset(pset).await;
+        assert!(!matches!(result, Ok(p) if !p.inputs()[0].partial_sigs.is_empty()));
+    }
+
+    #[test]
+    fn test_encrypted_storage_round_trip() {
+        let path = "test_encrypted_round_trip.enc.json";
+        let passphrase = Secret::new("correct horse battery staple".to_string());
+        let mnemonics = vec![
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about".to_string(),
+            "legal winner thank year wave sausage worth useful legal winner thank yellow".to_string(),
+        ];
+
+        let storage = MnemonicStorage::with_mnemonics(mnemonics.clone()).unwrap();
+        storage.write_encrypted(path, &passphrase).unwrap();
+
+        let contents = fs::read_to_string(path).unwrap();
+        assert!(contents.contains("\"version\": 1"));
+        assert!(!contents.contains("abandon"));
+
+        let loaded = MnemonicStorage::read_encrypted(path, &passphrase).unwrap();
+        assert_eq!(loaded.mnemonic, mnemonics);
+
+        let wrong = Secret::new("wrong passphrase".to_string());
+        assert!(matches!(
+            MnemonicStorage::read_encrypted(path, &wrong),
+            Err(SignerError::Encryption(_))
+        ));
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_migrate_plaintext_storage_to_encrypted() {
+        let plaintext_path = "test_migrate_plaintext.json";
+        let encrypted_path = "test_migrate_plaintext.enc.json";
+        let passphrase = Secret::new("correct horse battery staple".to_string());
+
+        let storage = MnemonicStorage::with_mnemonics(vec![
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong".to_string(),
+        ])
+        .unwrap();
+        storage.write_to_file_path(plaintext_path).unwrap();
+
+        // Plaintext files are not read as encrypted storage
+        let err = MnemonicStorage::read_encrypted(plaintext_path, &passphrase).unwrap_err();
+        assert!(err.to_string().contains("migrate_to_encrypted"));
+
+        let migrated =
+            MnemonicStorage::migrate_to_encrypted(plaintext_path, encrypted_path, &passphrase)
+                .unwrap();
+        assert_eq!(migrated, 1);
+        assert!(!Path::new(plaintext_path).exists());
+
+        let loaded = MnemonicStorage::read_encrypted(encrypted_path, &passphrase).unwrap();
+        assert_eq!(loaded.mnemonic, storage.mnemonic);
+
+        // Signers can be created from the encrypted file, generating missing mnemonics
+        let (mnemonic, _) =
+            LwkSoftwareSigner::generate_new_indexed_encrypted(1, encrypted_path, &passphrase)
+                .unwrap();
+        let loaded = MnemonicStorage::read_encrypted(encrypted_path, &passphrase).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded.get_mnemonic(1), Some(&mnemonic));
+
+        let _ = fs::remove_file(encrypted_path);
+    }
+
+    #[test]
+    fn test_migrate_in_place_and_failed_rename_keep_mnemonics() {
+        let passphrase = Secret::new("correct horse battery staple".to_string());
+        let storage = MnemonicStorage::with_mnemonics(vec![
+            "legal winner thank year wave sausage worth useful legal winner thank yellow"
+                .to_string(),
+        ])
+        .unwrap();
+
+        // Migrating onto the plaintext file replaces it with the encrypted copy
+        let in_place_path = "test_migrate_in_place.json";
+        storage.write_to_file_path(in_place_path).unwrap();
+        MnemonicStorage::migrate_to_encrypted(in_place_path, in_place_path, &passphrase).unwrap();
+        let loaded = MnemonicStorage::read_encrypted(in_place_path, &passphrase).unwrap();
+        assert_eq!(loaded.mnemonic, storage.mnemonic);
+        assert!(!Path::new("test_migrate_in_place.plaintext").exists());
+        let _ = fs::remove_file(in_place_path);
+
+        // A target that cannot be replaced leaves the plaintext untouched
+        let plaintext_path = "test_migrate_failed_rename.json";
+        let blocked_path = "test_migrate_failed_rename.enc";
+        storage.write_to_file_path(plaintext_path).unwrap();
+        fs::create_dir_all(Path::new(blocked_path).join("occupied")).unwrap();
+
+        assert!(
+            MnemonicStorage::migrate_to_encrypted(plaintext_path, blocked_path, &passphrase)
+                .is_err()
+        );
+        let still_plaintext = MnemonicStorage::read_from_file_path(plaintext_path).unwrap();
+        assert_eq!(still_plaintext.mnemonic, storage.mnemonic);
+        assert!(!Path::new("test_migrate_failed_rename.migrating").exists());
+
+        let _ = fs::remove_file(plaintext_path);
+        let _ = fs::remove_dir_all(blocked_path);
+    }
 }
 #[test]
 fn test_conditional_logging_behavior() {
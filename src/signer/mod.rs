@@ -37,6 +37,10 @@
 //! - **Indexing**: Zero-based array indexing for consistent test identification
 //! - **Persistence**: Automatically created and updated when new mnemonics are generated
 //!
+//! [`MnemonicStorage::write_encrypted`] stores the same data encrypted under a passphrase
+//! (Argon2id and XChaCha20-Poly1305), and [`MnemonicStorage::migrate_to_encrypted`]
+//! converts an existing plaintext file.
+//!
 //! ## Usage Examples
 //!
 //! ### Basic Signer Creation
@@ -95,6 +99,49 @@
 //! }
 //! ```
 //!
+//! ### Accounts
+//!
+//! One seed can hold separate accounts, e.g. per asset or per investor. Each account has
+//! its own descriptor and external and internal (change) address chains.
+//!
+//! ```rust,no_run
+//! use amp_rs::signer::{KeyChain, LwkSoftwareSigner, SignerError};
+//!
+//! # fn main() -> Result<(), SignerError> {
+//! let (_, signer) = LwkSoftwareSigner::generate_new()?;
+//! let investor = signer.with_account(5)?;
+//! let descriptor = investor.get_account_descriptor()?;
+//! let change = investor.derive_address_on_chain(KeyChain::Internal, 0)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ### External Signers
+//!
+//! Hardware wallets and offline machines sign PSETs rather than raw transactions.
+//! [`PsetFileSigner`] exports the unsigned PSET as base64 to a directory or channel and
+//! waits for the signed PSET, which `ElementsRpc::sign_transaction_with_utxos` then
+//! finalizes on the node.
+//!
+//! ```rust,no_run
+//! use amp_rs::signer::PsetFileSigner;
+//!
+//! let (signer, mut requests) = PsetFileSigner::channel(1);
+//! tokio::spawn(async move {
+//!     while let Some(request) = requests.recv().await {
+//!         // Forward request.pset_base64() to the device and return its signed PSET
+//!         let signed = request.pset_base64().to_string();
+//!         let _ = request.respond(signed);
+//!     }
+//! });
+//! ```
+//!
+//! ### Multisig
+//!
+//! [`MultisigSigner`] combines several signers over a `wsh(multi(...))` descriptor and
+//! merges their partial signatures until the threshold is met. See the [`multisig`]
+//! module for an example.
+//!
 //! ### Error Handling
 //!
 //! ```rust,no_run
@@ -130,11 +177,20 @@
 
 pub mod error;
 pub mod lwk;
+pub mod multisig;
+pub mod pset;
+pub mod pset_file;
 
 pub use error::SignerError;
-pub use lwk::LwkSoftwareSigner;
+pub use lwk::{KeyChain, LwkSoftwareSigner, MnemonicStorage};
+pub use multisig::{Cosigner, MultisigSigner};
+pub use pset_file::{PsetFileSigner, PsetSigningRequest};
 
 use async_trait::async_trait;
+use elements::pset::PartiallySignedTransaction;
+
+/// Default number of addresses per chain scanned when matching PSET inputs to a wallet
+pub const DEFAULT_GAP_LIMIT: u32 = 100;
 
 /// Trait for transaction signing implementations
 ///
@@ -203,6 +259,30 @@ pub trait Signer: Send + Sync {
     /// ```
     async fn sign_transaction(&self, unsigned_tx: &str) -> Result<String, SignerError>;
 
+    /// Sign a partially signed Elements transaction (PSET)
+    ///
+    /// Adds this signer's signatures to the inputs it can sign and returns the updated
+    /// PSET. The PSET carries the outputs being spent, so signers do not need access to
+    /// the node. Inputs are not required to be finalized; callers finalize the PSET
+    /// once all signatures are present.
+    ///
+    /// The default implementation returns `SignerError::Unsupported`, in which case
+    /// callers fall back to [`sign_transaction`](Self::sign_transaction).
+    ///
+    /// # Errors
+    ///
+    /// - `SignerError::Unsupported` - The signer only signs raw transactions
+    /// - `SignerError::InvalidTransaction` - The PSET cannot be signed
+    /// - Implementation-specific errors for the signing backend
+    async fn sign_pset(
+        &self,
+        _pset: PartiallySignedTransaction,
+    ) -> Result<PartiallySignedTransaction, SignerError> {
+        Err(SignerError::Unsupported(
+            "PSET signing is not supported by this signer".to_string(),
+        ))
+    }
+
     /// Returns self as Any for downcasting to concrete types
     ///
     /// This method enables downcasting from the trait object to concrete implementations,
@@ -0,0 +1,577 @@
+//! # Multisig Signer
+//!
+//! [`MultisigSigner`] controls funds locked by a k-of-n `wsh(multi(...))` descriptor, such
+//! as a 2-of-3 treasury holding issuance and reissuance-token UTXOs. Each cosigner is an
+//! account key (`[fingerprint/path]xpub`) and, optionally, a [`Signer`] able to sign for it.
+//!
+//! Signing a PSET:
+//! 1. Inputs spending the multisig are matched against derived scripts and updated with
+//!    their witness script and key derivations
+//! 2. The PSET is handed to each cosigner's [`Signer::sign_pset`] in turn and the partial
+//!    signatures returned are merged, until every multisig input has `threshold` signatures
+//! 3. The multisig inputs are finalized
+//!
+//! The confidential descriptor uses an ELIP 151 blinding key derived from the descriptor
+//! itself, so every party derives the same addresses without sharing a blinding secret.
+//! [`MultisigSigner::descriptors`] returns descriptors for watch-only import with
+//! [`ElementsRpc::import_descriptors`](crate::ElementsRpc::import_descriptors).
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use amp_rs::signer::{Cosigner, LwkSoftwareSigner, MultisigSigner, SignerError};
+//! use amp_rs::network::Network;
+//! use std::sync::Arc;
+//!
+//! # fn main() -> Result<(), SignerError> {
+//! let (_, alice) = LwkSoftwareSigner::generate_new_indexed(0)?;
+//! let (_, bob) = LwkSoftwareSigner::generate_new_indexed(1)?;
+//! let carol_key = "[c0ffee00/87h/1h/0h]tpubD6NzVbkrYhZ4...";
+//!
+//! let treasury = MultisigSigner::new(
+//!     2,
+//!     vec![
+//!         Cosigner::from_lwk(Arc::new(alice))?,
+//!         Cosigner::from_lwk(Arc::new(bob))?,
+//!         Cosigner::watch_only(carol_key)?,
+//!     ],
+//!     Network::LiquidTestnet,
+//! )?;
+//! println!("Treasury address: {}", treasury.derive_address(0)?);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use elements::hashes::Hash;
+use elements::pset::PartiallySignedTransaction;
+use elements::secp256k1_zkp::Secp256k1;
+use elements::{BlockHash, Script};
+use elements_miniscript::psbt::PsbtExt;
+use elements_miniscript::{
+    ConfidentialDescriptor, DefiniteDescriptorKey, Descriptor, DescriptorPublicKey,
+};
+
+use super::{pset, LwkSoftwareSigner, Signer, SignerError, DEFAULT_GAP_LIMIT};
+use crate::network::Network;
+
+/// Maximum number of keys in a `multi` script
+pub const MAX_COSIGNERS: usize = 20;
+
+/// A multisig participant: an account key and, optionally, a signer holding its private key
+#[derive(Clone)]
+pub struct Cosigner {
+    key: String,
+    signer: Option<Arc<dyn Signer>>,
+}
+
+impl Cosigner {
+    /// Creates a cosigner from its account key and the signer that holds it
+    ///
+    /// `key` is an extended public key with its origin, e.g. `[fingerprint/87h/1h/0h]tpub...`.
+    ///
+    /// # Errors
+    /// Returns `SignerError::Multisig` if `key` is not an extended public key
+    pub fn new(key: &str, signer: Arc<dyn Signer>) -> Result<Self, SignerError> {
+        Ok(Self {
+            key: Self::parse_key(key)?,
+            signer: Some(signer),
+        })
+    }
+
+    /// Creates a cosigner whose signatures are produced elsewhere
+    ///
+    /// Its key is part of the descriptor, but it is never asked to sign.
+    ///
+    /// # Errors
+    /// Returns `SignerError::Multisig` if `key` is not an extended public key
+    pub fn watch_only(key: &str) -> Result<Self, SignerError> {
+        Ok(Self {
+            key: Self::parse_key(key)?,
+            signer: None,
+        })
+    }
+
+    /// Creates a cosigner from a software signer using its BIP87 account key
+    ///
+    /// # Errors
+    /// Returns an error if the account key cannot be derived
+    pub fn from_lwk(signer: Arc<LwkSoftwareSigner>) -> Result<Self, SignerError> {
+        let key = signer.get_multisig_key_origin_xpub()?;
+        Self::new(&key, signer)
+    }
+
+    /// Returns the cosigner's account key
+    #[must_use]
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Returns `true` if this cosigner cannot sign in this process
+    #[must_use]
+    pub const fn is_watch_only(&self) -> bool {
+        self.signer.is_none()
+    }
+
+    fn parse_key(key: &str) -> Result<String, SignerError> {
+        let key = key.trim();
+        match DescriptorPublicKey::from_str(key) {
+            Ok(DescriptorPublicKey::XPub(xpub)) if !xpub.derivation_path.is_empty() => {
+                Err(SignerError::Multisig(format!(
+                    "Cosigner key '{key}' must be an account key without a derivation suffix"
+                )))
+            }
+            Ok(DescriptorPublicKey::XPub(_)) => Ok(key.to_string()),
+            Ok(_) => Err(SignerError::Multisig(format!(
+                "Cosigner key '{key}' must be an extended public key"
+            ))),
+            Err(e) => Err(SignerError::Multisig(format!(
+                "Invalid cosigner key '{key}': {e}"
+            ))),
+        }
+    }
+}
+
+impl fmt::Debug for Cosigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cosigner")
+            .field("key", &self.key)
+            .field("watch_only", &self.is_watch_only())
+            .finish()
+    }
+}
+
+/// Threshold signer over a `wsh(multi(...))` descriptor
+#[derive(Debug, Clone)]
+pub struct MultisigSigner {
+    threshold: usize,
+    cosigners: Vec<Cosigner>,
+    network: Network,
+    descriptor: ConfidentialDescriptor<DescriptorPublicKey>,
+    gap_limit: u32,
+}
+
+impl MultisigSigner {
+    /// Creates a `threshold`-of-`cosigners.len()` multisig signer
+    ///
+    /// Key order is significant: the descriptor lists keys in the order given.
+    ///
+    /// # Errors
+    /// Returns `SignerError::Multisig` if the threshold is out of range, there are more than
+    /// [`MAX_COSIGNERS`] cosigners, a key is repeated, or the descriptor cannot be built
+    pub fn new(
+        threshold: usize,
+        cosigners: Vec<Cosigner>,
+        network: Network,
+    ) -> Result<Self, SignerError> {
+        if cosigners.is_empty() || cosigners.len() > MAX_COSIGNERS {
+            return Err(SignerError::Multisig(format!(
+                "Expected 1 to {MAX_COSIGNERS} cosigners, got {}",
+                cosigners.len()
+            )));
+        }
+        if threshold == 0 || threshold > cosigners.len() {
+            return Err(SignerError::Multisig(format!(
+                "Threshold must be between 1 and {}, got {threshold}",
+                cosigners.len()
+            )));
+        }
+        for (i, cosigner) in cosigners.iter().enumerate() {
+            if cosigners[..i].iter().any(|other| other.key == cosigner.key) {
+                return Err(SignerError::Multisig(format!(
+                    "Cosigner key '{}' is repeated",
+                    cosigner.key
+                )));
+            }
+        }
+
+        let keys: Vec<String> = cosigners
+            .iter()
+            .map(|cosigner| format!("{}/<0;1>/*", cosigner.key))
+            .collect();
+        let descriptor = Descriptor::<DescriptorPublicKey>::from_str(&format!(
+            "elwsh(multi({threshold},{}))",
+            keys.join(",")
+        ))
+        .map_err(|e| SignerError::Multisig(format!("Failed to build descriptor: {e}")))?;
+        let descriptor = ConfidentialDescriptor::with_elip151_descriptor_blinding_key(descriptor)
+            .map_err(|e| {
+            SignerError::Multisig(format!("Failed to derive blinding key: {e}"))
+        })?;
+
+        Ok(Self {
+            threshold,
+            cosigners,
+            network,
+            descriptor,
+            gap_limit: DEFAULT_GAP_LIMIT,
+        })
+    }
+
+    /// Sets how many addresses per chain are scanned when matching PSET inputs
+    #[must_use]
+    pub const fn with_gap_limit(mut self, gap_limit: u32) -> Self {
+        self.gap_limit = gap_limit;
+        self
+    }
+
+    /// Returns the number of signatures required
+    #[must_use]
+    pub const fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Returns the cosigners in descriptor order
+    #[must_use]
+    pub fn cosigners(&self) -> &[Cosigner] {
+        &self.cosigners
+    }
+
+    /// Returns the network used for derived addresses
+    #[must_use]
+    pub const fn network(&self) -> &Network {
+        &self.network
+    }
+
+    /// Returns the confidential descriptor covering both chains (`<0;1>/*`)
+    #[must_use]
+    pub fn descriptor(&self) -> String {
+        self.descriptor.to_string()
+    }
+
+    /// Returns the receive and change descriptors for watch-only import
+    ///
+    /// Both carry the blinding key of the combined descriptor, so the node derives the
+    /// same confidential addresses as [`derive_address`](Self::derive_address).
+    ///
+    /// # Errors
+    /// Returns `SignerError::Multisig` if the descriptor cannot be split
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use amp_rs::signer::MultisigSigner;
+    /// # use amp_rs::ElementsRpc;
+    /// # async fn example(treasury: &MultisigSigner) -> Result<(), Box<dyn std::error::Error>> {
+    /// let rpc = ElementsRpc::from_env()?;
+    /// let (receive, change) = treasury.descriptors()?;
+    /// rpc.create_descriptor_wallet("treasury").await?;
+    /// rpc.import_descriptors("treasury", &receive, &change).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn descriptors(&self) -> Result<(String, String), SignerError> {
+        let chains = self.chain_descriptors()?;
+        Ok((chains[0].to_string(), chains[1].to_string()))
+    }
+
+    /// Derives the confidential receive address at `index`
+    ///
+    /// # Errors
+    /// Returns `SignerError::Multisig` if the address cannot be derived
+    pub fn derive_address(&self, index: u32) -> Result<String, SignerError> {
+        let receive = &self.chain_descriptors()?[0];
+        let definite = receive
+            .at_derivation_index(index)
+            .map_err(|e| SignerError::Multisig(format!("Failed to derive index {index}: {e}")))?;
+        let address = definite
+            .address(&Secp256k1::new(), self.network.address_params())
+            .map_err(|e| SignerError::Multisig(format!("Failed to derive address: {e}")))?;
+        Ok(address.to_string())
+    }
+
+    /// Adds witness scripts and key derivations to the PSET inputs spending this multisig
+    ///
+    /// Inputs are matched by the script of their `witness_utxo` against the first
+    /// `gap_limit` addresses of each chain. Returns the indices of the matched inputs.
+    ///
+    /// # Errors
+    /// Returns `SignerError::Multisig` if no input spends this multisig
+    pub fn update_pset(
+        &self,
+        pset: &mut PartiallySignedTransaction,
+    ) -> Result<Vec<usize>, SignerError> {
+        let scripts = self.derived_scripts()?;
+
+        let mut owned = Vec::new();
+        for index in 0..pset.inputs().len() {
+            let descriptor = pset.inputs()[index]
+                .witness_utxo
+                .as_ref()
+                .and_then(|utxo| scripts.get(&utxo.script_pubkey));
+            if let Some(descriptor) = descriptor {
+                pset.update_input_with_descriptor(index, descriptor)
+                    .map_err(|e| {
+                        SignerError::Multisig(format!("Failed to update PSET input {index}: {e}"))
+                    })?;
+                owned.push(index);
+            }
+        }
+
+        if owned.is_empty() {
+            return Err(SignerError::Multisig(format!(
+                "No PSET input spends the first {} addresses of this multisig",
+                self.gap_limit
+            )));
+        }
+        Ok(owned)
+    }
+
+    /// Copies the partial signatures of `signed` for the given inputs into `combined`
+    ///
+    /// Only signatures by keys listed in the input's key derivations are taken. Returns
+    /// the number of signatures added.
+    ///
+    /// # Errors
+    /// Returns `SignerError::InvalidTransaction` if `signed` is not the same transaction
+    pub fn merge_signatures(
+        combined: &mut PartiallySignedTransaction,
+        signed: &PartiallySignedTransaction,
+        inputs: &[usize],
+    ) -> Result<usize, SignerError> {
+        pset::ensure_same_transaction(combined, signed)?;
+
+        let mut added = 0;
+        for &index in inputs {
+            let source = &signed.inputs()[index];
+            let target = &mut combined.inputs_mut()[index];
+            for (public_key, signature) in &source.partial_sigs {
+                if target.bip32_derivation.contains_key(public_key)
+                    && target
+                        .partial_sigs
+                        .insert(*public_key, signature.clone())
+                        .is_none()
+                {
+                    added += 1;
+                }
+            }
+        }
+        Ok(added)
+    }
+
+    /// Returns the lowest number of signatures collected on any of the given inputs
+    fn collected_signatures(pset: &PartiallySignedTransaction, inputs: &[usize]) -> usize {
+        inputs
+            .iter()
+            .map(|&index| pset.inputs()[index].partial_sigs.len())
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Returns the single-path receive and change descriptors
+    fn chain_descriptors(
+        &self,
+    ) -> Result<Vec<ConfidentialDescriptor<DescriptorPublicKey>>, SignerError> {
+        let chains = self
+            .descriptor
+            .descriptor
+            .clone()
+            .into_single_descriptors()
+            .map_err(|e| SignerError::Multisig(format!("Failed to split descriptor: {e}")))?;
+        Ok(chains
+            .into_iter()
+            .map(|descriptor| ConfidentialDescriptor {
+                key: self.descriptor.key.clone(),
+                descriptor,
+            })
+            .collect())
+    }
+
+    /// Maps the script pubkeys of the first `gap_limit` addresses to their descriptors
+    fn derived_scripts(
+        &self,
+    ) -> Result<HashMap<Script, Descriptor<DefiniteDescriptorKey>>, SignerError> {
+        let mut scripts = HashMap::new();
+        for chain in self.chain_descriptors()? {
+            for index in 0..self.gap_limit {
+                let definite = chain.descriptor.at_derivation_index(index).map_err(|e| {
+                    SignerError::Multisig(format!("Failed to derive index {index}: {e}"))
+                })?;
+                scripts.insert(definite.script_pubkey(), definite);
+            }
+        }
+        Ok(scripts)
+    }
+}
+
+#[async_trait]
+impl Signer for MultisigSigner {
+    /// Raw transactions do not carry the previous outputs multisig signing needs
+    async fn sign_transaction(&self, _unsigned_tx: &str) -> Result<String, SignerError> {
+        Err(SignerError::Unsupported(
+            "Multisig signing requires the previous outputs; use sign_pset".to_string(),
+        ))
+    }
+
+    async fn sign_pset(
+        &self,
+        mut pset: PartiallySignedTransaction,
+    ) -> Result<PartiallySignedTransaction, SignerError> {
+        let inputs = self.update_pset(&mut pset)?;
+
+        for cosigner in &self.cosigners {
+            if Self::collected_signatures(&pset, &inputs) >= self.threshold {
+                break;
+            }
+            let Some(signer) = &cosigner.signer else {
+                continue;
+            };
+
+            // A failing cosigner is not fatal while the others can still reach the threshold
+            match signer.sign_pset(pset.clone()).await {
+                Ok(signed) => {
+                    let added = Self::merge_signatures(&mut pset, &signed, &inputs)?;
+                    tracing::info!("Cosigner {} added {} signatures", cosigner.key, added);
+                }
+                Err(e) => {
+                    tracing::warn!("Cosigner {} did not sign: {}", cosigner.key, e);
+                }
+            }
+        }
+
+        let collected = Self::collected_signatures(&pset, &inputs);
+        if collected < self.threshold {
+            return Err(SignerError::ThresholdNotMet {
+                required: self.threshold,
+                collected,
+            });
+        }
+
+        let secp = Secp256k1::verification_only();
+        for &index in &inputs {
+            pset.finalize_inp_mut(&secp, index, BlockHash::all_zeros())
+                .map_err(|e| {
+                    SignerError::Multisig(format!("Failed to finalize PSET input {index}: {e}"))
+                })?;
+        }
+
+        tracing::info!(
+            "Finalized {} multisig inputs with {} signatures (threshold {})",
+            inputs.len(),
+            collected,
+            self.threshold
+        );
+        Ok(pset)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MNEMONICS: [&str; 3] = [
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong",
+        "legal winner thank year wave sausage worth useful legal winner thank yellow",
+    ];
+
+    fn cosigner(index: usize) -> Cosigner {
+        Cosigner::from_lwk(Arc::new(LwkSoftwareSigner::new(MNEMONICS[index]).unwrap())).unwrap()
+    }
+
+    fn watch_only(index: usize) -> Cosigner {
+        let signer = LwkSoftwareSigner::new(MNEMONICS[index]).unwrap();
+        Cosigner::watch_only(&signer.get_multisig_key_origin_xpub().unwrap()).unwrap()
+    }
+
+    /// Builds a PSET spending the multisig's first receive address
+    fn spending_pset(multisig: &MultisigSigner) -> PartiallySignedTransaction {
+        let asset = elements::AssetId::from_slice(&[7u8; 32]).unwrap();
+        let address: elements::Address = multisig.derive_address(0).unwrap().parse().unwrap();
+        let mut pset = PartiallySignedTransaction::from_tx(elements::Transaction {
+            version: 2,
+            lock_time: elements::LockTime::ZERO,
+            input: vec![elements::TxIn {
+                previous_output: elements::OutPoint::new("dd".repeat(32).parse().unwrap(), 0),
+                is_pegin: false,
+                script_sig: Script::new(),
+                sequence: elements::Sequence::ENABLE_RBF_NO_LOCKTIME,
+                asset_issuance: elements::AssetIssuance::default(),
+                witness: elements::TxInWitness::default(),
+            }],
+            output: vec![elements::TxOut::new_fee(1_000, asset)],
+        });
+        pset.inputs_mut()[0].witness_utxo = Some(elements::TxOut {
+            asset: elements::confidential::Asset::Explicit(asset),
+            value: elements::confidential::Value::Explicit(1_000),
+            nonce: elements::confidential::Nonce::Null,
+            script_pubkey: address.script_pubkey(),
+            witness: elements::TxOutWitness::default(),
+        });
+        pset
+    }
+
+    #[test]
+    fn test_multisig_configuration_and_descriptors() {
+        assert!(MultisigSigner::new(0, vec![cosigner(0)], Network::LiquidTestnet).is_err());
+        assert!(MultisigSigner::new(2, vec![cosigner(0)], Network::LiquidTestnet).is_err());
+        assert!(
+            MultisigSigner::new(1, vec![cosigner(0), cosigner(0)], Network::LiquidTestnet).is_err()
+        );
+        assert!(Cosigner::watch_only("not a key").is_err());
+
+        let multisig = MultisigSigner::new(
+            2,
+            vec![cosigner(0), cosigner(1), watch_only(2)],
+            Network::LiquidTestnet,
+        )
+        .unwrap();
+        assert!(multisig.descriptor().starts_with("ct("));
+        assert!(multisig.descriptor().contains(",elwsh(multi(2,"));
+
+        let (receive, change) = multisig.descriptors().unwrap();
+        assert!(receive.contains("/0/*"));
+        assert!(change.contains("/1/*"));
+        assert_ne!(
+            multisig.derive_address(0).unwrap(),
+            multisig.derive_address(1).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_two_of_three_signs_and_finalizes() {
+        let multisig = MultisigSigner::new(
+            2,
+            vec![cosigner(0), watch_only(1), cosigner(2)],
+            Network::LiquidTestnet,
+        )
+        .unwrap();
+
+        let signed = multisig.sign_pset(spending_pset(&multisig)).await.unwrap();
+        assert!(pset::is_finalized(&signed));
+        // Empty dummy element, two signatures and the witness script
+        let witness = signed.inputs()[0].final_script_witness.as_ref().unwrap();
+        assert_eq!(witness.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_threshold_not_met_without_enough_signers() {
+        let multisig = MultisigSigner::new(
+            2,
+            vec![cosigner(0), watch_only(1), watch_only(2)],
+            Network::LiquidTestnet,
+        )
+        .unwrap();
+
+        let err = multisig
+            .sign_pset(spending_pset(&multisig))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            SignerError::ThresholdNotMet {
+                required: 2,
+                collected: 1
+            }
+        ));
+    }
+}
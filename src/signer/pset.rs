@@ -0,0 +1,296 @@
+//! # PSET Helpers
+//!
+//! Partially Signed Elements Transactions (PSETs) carry an unsigned transaction together
+//! with the previous outputs it spends, so that a signer can produce signatures without
+//! access to the node. These helpers convert between raw transactions, PSETs and their
+//! base64 encoding, which is the format exchanged with external signers.
+
+use elements::hashes::Hash;
+use elements::pset::PartiallySignedTransaction;
+use elements::secp256k1_zkp::Secp256k1;
+use elements::{BlockHash, Transaction, TxOut};
+use elements_miniscript::psbt::PsbtExt;
+
+use super::SignerError;
+use crate::model::Unspent;
+
+/// Builds a PSET from an unsigned transaction and the UTXOs its inputs spend
+///
+/// `utxos` must be in input order. Each UTXO becomes the `witness_utxo` of its input.
+///
+/// # Errors
+/// Returns `SignerError::InvalidTransaction` if the UTXOs do not match the inputs or lack
+/// the asset, amount or script needed to describe the previous output
+pub fn from_transaction(
+    unsigned_transaction: Transaction,
+    utxos: &[Unspent],
+) -> Result<PartiallySignedTransaction, SignerError> {
+    if utxos.len() != unsigned_transaction.input.len() {
+        return Err(SignerError::InvalidTransaction(format!(
+            "UTXO count ({}) must match transaction input count ({})",
+            utxos.len(),
+            unsigned_transaction.input.len()
+        )));
+    }
+
+    let mut pset = PartiallySignedTransaction::from_tx(unsigned_transaction.clone());
+
+    for (i, utxo) in utxos.iter().enumerate() {
+        // Verify the UTXO matches the transaction input
+        let tx_input = &unsigned_transaction.input[i];
+        if tx_input.previous_output.txid.to_string() != utxo.txid {
+            return Err(SignerError::InvalidTransaction(format!(
+                "UTXO {} txid mismatch: expected {}, got {}",
+                i, tx_input.previous_output.txid, utxo.txid
+            )));
+        }
+        if tx_input.previous_output.vout != utxo.vout {
+            return Err(SignerError::InvalidTransaction(format!(
+                "UTXO {} vout mismatch: expected {}, got {}",
+                i, tx_input.previous_output.vout, utxo.vout
+            )));
+        }
+
+        // Create TxOut from UTXO information
+        let sats = u64::try_from(utxo.amount.to_sat()).map_err(|_| {
+            SignerError::InvalidTransaction(format!("UTXO {i} has negative amount {}", utxo.amount))
+        })?;
+        let value = elements::confidential::Value::Explicit(sats);
+        let asset = hex::decode(&utxo.asset).map_err(|e| {
+            SignerError::InvalidTransaction(format!("Invalid asset hex in UTXO {i}: {e}"))
+        })?;
+        let asset_commitment = if asset.len() == 32 {
+            let mut asset_bytes = [0u8; 32];
+            asset_bytes.copy_from_slice(&asset);
+            // Create AssetId from the raw bytes
+            let asset_id = elements::issuance::AssetId::from_slice(&asset_bytes).map_err(|e| {
+                SignerError::InvalidTransaction(format!("Invalid asset ID in UTXO {i}: {e}"))
+            })?;
+            elements::confidential::Asset::Explicit(asset_id)
+        } else {
+            return Err(SignerError::InvalidTransaction(format!(
+                "Invalid asset length in UTXO {}: expected 32 bytes, got {}",
+                i,
+                asset.len()
+            )));
+        };
+
+        // Without the script pubkey the previous output cannot be described
+        let script_pubkey = if let Some(ref spk) = utxo.scriptpubkey {
+            hex::decode(spk).map_err(|e| {
+                SignerError::InvalidTransaction(format!(
+                    "Invalid scriptpubkey hex in UTXO {i}: {e}"
+                ))
+            })?
+        } else {
+            return Err(SignerError::InvalidTransaction(format!(
+                "Missing scriptpubkey for UTXO {i}"
+            )));
+        };
+
+        let tx_out = TxOut {
+            asset: asset_commitment,
+            value,
+            nonce: elements::confidential::Nonce::Null,
+            script_pubkey: elements::Script::from(script_pubkey),
+            witness: elements::TxOutWitness::default(),
+        };
+
+        match pset.inputs_mut().get_mut(i) {
+            Some(input) => input.witness_utxo = Some(tx_out),
+            None => {
+                return Err(SignerError::InvalidTransaction(format!(
+                    "Failed to get PSET input {i} for UTXO addition"
+                )))
+            }
+        }
+    }
+
+    Ok(pset)
+}
+
+/// Encodes a PSET as base64
+#[must_use]
+pub fn to_base64(pset: &PartiallySignedTransaction) -> String {
+    pset.to_string()
+}
+
+/// Decodes a base64 PSET
+///
+/// # Errors
+/// Returns `SignerError::InvalidTransaction` if the input is not a valid base64 PSET
+pub fn from_base64(pset_base64: &str) -> Result<PartiallySignedTransaction, SignerError> {
+    pset_base64
+        .trim()
+        .parse()
+        .map_err(|e| SignerError::InvalidTransaction(format!("Invalid base64 PSET: {e}")))
+}
+
+/// Returns `true` if every input carries a non-empty final script sig or witness
+#[must_use]
+pub fn is_finalized(pset: &PartiallySignedTransaction) -> bool {
+    pset.inputs().iter().all(|input| {
+        input
+            .final_script_witness
+            .as_ref()
+            .is_some_and(|witness| !witness.is_empty())
+            || input
+                .final_script_sig
+                .as_ref()
+                .is_some_and(|script_sig| !script_sig.is_empty())
+    })
+}
+
+/// Finalizes the inputs a signer left unfinalized and extracts the signed transaction
+///
+/// Inputs are finalized from their partial signatures, without the node's
+/// `finalizepsbt`.
+///
+/// # Errors
+/// Returns `SignerError::InvalidTransaction` if an input lacks the signatures to finalize it
+pub fn finalize(mut pset: PartiallySignedTransaction) -> Result<Transaction, SignerError> {
+    if !is_finalized(&pset) {
+        let secp = Secp256k1::verification_only();
+        pset.finalize_mut(&secp, BlockHash::all_zeros())
+            .map_err(|errors| {
+                SignerError::InvalidTransaction(format!(
+                    "Failed to finalize PSET: {}",
+                    errors
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                ))
+            })?;
+    }
+    pset.extract_tx()
+        .map_err(|e| SignerError::InvalidTransaction(format!("Failed to extract transaction: {e}")))
+}
+
+/// Checks that a signed PSET spends the same inputs and pays the same outputs as the
+/// PSET that was sent out for signing
+///
+/// # Errors
+/// Returns `SignerError::InvalidTransaction` describing the first difference
+pub fn ensure_same_transaction(
+    original: &PartiallySignedTransaction,
+    signed: &PartiallySignedTransaction,
+) -> Result<(), SignerError> {
+    if original.inputs().len() != signed.inputs().len()
+        || original.outputs().len() != signed.outputs().len()
+    {
+        return Err(SignerError::InvalidTransaction(format!(
+            "Signed PSET has {} inputs and {} outputs, expected {} and {}",
+            signed.inputs().len(),
+            signed.outputs().len(),
+            original.inputs().len(),
+            original.outputs().len()
+        )));
+    }
+
+    for (i, (expected, actual)) in original.inputs().iter().zip(signed.inputs()).enumerate() {
+        if expected.previous_txid != actual.previous_txid
+            || expected.previous_output_index != actual.previous_output_index
+        {
+            return Err(SignerError::InvalidTransaction(format!(
+                "Signed PSET input {i} spends {}:{}, expected {}:{}",
+                actual.previous_txid,
+                actual.previous_output_index,
+                expected.previous_txid,
+                expected.previous_output_index
+            )));
+        }
+    }
+
+    for (i, (expected, actual)) in original.outputs().iter().zip(signed.outputs()).enumerate() {
+        if expected.script_pubkey != actual.script_pubkey
+            || expected.amount != actual.amount
+            || expected.asset != actual.asset
+            || expected.amount_comm != actual.amount_comm
+            || expected.asset_comm != actual.asset_comm
+        {
+            return Err(SignerError::InvalidTransaction(format!(
+                "Signed PSET output {i} differs from the output sent for signing"
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Amount;
+
+    const ASSET: &str = "144c654344aa716d6f3abcc1ca90e5641e4e2a7f633bc09fe3baf64585819a49";
+
+    fn unsigned_transaction(previous_txid: elements::Txid) -> Transaction {
+        Transaction {
+            version: 2,
+            lock_time: elements::LockTime::ZERO,
+            input: vec![elements::TxIn {
+                previous_output: elements::OutPoint::new(previous_txid, 1),
+                is_pegin: false,
+                script_sig: elements::Script::new(),
+                sequence: elements::Sequence::ENABLE_RBF_NO_LOCKTIME,
+                asset_issuance: elements::AssetIssuance::default(),
+                witness: elements::TxInWitness::default(),
+            }],
+            output: vec![TxOut::new_fee(
+                500,
+                elements::AssetId::from_slice(&hex::decode(ASSET).unwrap()).unwrap(),
+            )],
+        }
+    }
+
+    fn utxo(txid: &str, vout: u32) -> Unspent {
+        Unspent {
+            txid: txid.to_string(),
+            vout,
+            amount: Amount::from_sat(1_000),
+            asset: ASSET.to_string(),
+            address: "address".to_string(),
+            spendable: true,
+            confirmations: Some(1),
+            scriptpubkey: Some("0014".to_string() + &"11".repeat(20)),
+            redeemscript: None,
+            witnessscript: None,
+            amountblinder: None,
+            assetblinder: None,
+        }
+    }
+
+    #[test]
+    fn test_pset_round_trips_through_base64() {
+        let previous_txid: elements::Txid = "aa".repeat(32).parse().unwrap();
+        let pset = from_transaction(
+            unsigned_transaction(previous_txid),
+            &[utxo(&previous_txid.to_string(), 1)],
+        )
+        .unwrap();
+        assert!(pset.inputs()[0].witness_utxo.is_some());
+        assert!(!is_finalized(&pset));
+
+        let decoded = from_base64(&to_base64(&pset)).unwrap();
+        assert_eq!(decoded, pset);
+        ensure_same_transaction(&pset, &decoded).unwrap();
+
+        assert!(from_transaction(
+            unsigned_transaction(previous_txid),
+            &[utxo(&previous_txid.to_string(), 0)]
+        )
+        .is_err());
+        assert!(from_base64("not a pset").is_err());
+    }
+
+    #[test]
+    fn test_ensure_same_transaction_detects_changed_outputs() {
+        let previous_txid: elements::Txid = "bb".repeat(32).parse().unwrap();
+        let original = PartiallySignedTransaction::from_tx(unsigned_transaction(previous_txid));
+        let mut tampered = original.clone();
+        tampered.outputs_mut()[0].amount = Some(400);
+
+        assert!(ensure_same_transaction(&original, &tampered).is_err());
+    }
+}
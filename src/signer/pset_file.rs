@@ -0,0 +1,392 @@
+//! # External PSET Signer
+//!
+//! [`PsetFileSigner`] hands transactions to a signer outside this process, such as an
+//! offline machine or a hardware wallet, as base64 PSETs and waits for the signed PSET to
+//! come back. No key material is held by this process.
+//!
+//! Two transports are supported:
+//! - **Directory**: the unsigned PSET is written to `<dir>/<txid>.pset` and the signed
+//!   PSET is read from `<dir>/<txid>.signed.pset` once it appears
+//! - **Channel**: each request is sent as a [`PsetSigningRequest`] to an in-process
+//!   receiver, which replies with the signed PSET
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use amp_rs::signer::{PsetFileSigner, Signer};
+//! use std::time::Duration;
+//!
+//! # async fn example(unsigned_pset: elements::pset::PartiallySignedTransaction) -> Result<(), Box<dyn std::error::Error>> {
+//! // Exchange PSETs through a directory synchronised with the offline machine
+//! let signer = PsetFileSigner::new("/mnt/airgap/psets").with_timeout(Duration::from_secs(3600));
+//! let signed = signer.sign_pset(unsigned_pset).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use elements::pset::PartiallySignedTransaction;
+use tokio::sync::{mpsc, oneshot};
+
+use super::{pset, Signer, SignerError};
+
+/// Default interval between checks for a signed PSET file
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Default time to wait for the external signer
+pub const DEFAULT_SIGNING_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
+/// A PSET waiting to be signed by an external signer
+///
+/// Sent through the channel returned by [`PsetFileSigner::channel`]. Reply with
+/// [`respond`](Self::respond); dropping the request rejects it.
+#[derive(Debug)]
+pub struct PsetSigningRequest {
+    txid: String,
+    pset_base64: String,
+    reply: oneshot::Sender<String>,
+}
+
+impl PsetSigningRequest {
+    /// Returns the id of the unsigned transaction
+    #[must_use]
+    pub fn txid(&self) -> &str {
+        &self.txid
+    }
+
+    /// Returns the unsigned PSET as base64
+    #[must_use]
+    pub fn pset_base64(&self) -> &str {
+        &self.pset_base64
+    }
+
+    /// Sends the signed PSET (base64) back to the waiting signer
+    ///
+    /// # Errors
+    /// Returns an error if the signer stopped waiting
+    pub fn respond(self, signed_pset_base64: String) -> Result<(), SignerError> {
+        self.reply.send(signed_pset_base64).map_err(|_| {
+            SignerError::Timeout(format!(
+                "Signer for transaction {} is no longer waiting",
+                self.txid
+            ))
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Transport {
+    Directory(PathBuf),
+    Channel(mpsc::Sender<PsetSigningRequest>),
+}
+
+/// Signer that delegates signing to an external signer through PSET files or a channel
+#[derive(Debug, Clone)]
+pub struct PsetFileSigner {
+    transport: Transport,
+    poll_interval: Duration,
+    timeout: Duration,
+}
+
+impl PsetFileSigner {
+    /// Creates a signer that exchanges PSET files through `dir`
+    #[must_use]
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        Self {
+            transport: Transport::Directory(dir.as_ref().to_path_buf()),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            timeout: DEFAULT_SIGNING_TIMEOUT,
+        }
+    }
+
+    /// Creates a signer that sends signing requests through a channel
+    ///
+    /// Returns the signer and the receiver the external signer reads requests from.
+    #[must_use]
+    pub fn channel(buffer: usize) -> (Self, mpsc::Receiver<PsetSigningRequest>) {
+        let (sender, receiver) = mpsc::channel(buffer.max(1));
+        let signer = Self {
+            transport: Transport::Channel(sender),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            timeout: DEFAULT_SIGNING_TIMEOUT,
+        };
+        (signer, receiver)
+    }
+
+    /// Sets how often the directory is checked for a signed PSET
+    #[must_use]
+    pub const fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Sets how long to wait for the external signer
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns the exchange directory, if this signer uses files
+    #[must_use]
+    pub fn dir(&self) -> Option<&Path> {
+        match &self.transport {
+            Transport::Directory(dir) => Some(dir),
+            Transport::Channel(_) => None,
+        }
+    }
+
+    /// Sends the PSET to the external signer and waits for the signed PSET
+    async fn exchange(
+        &self,
+        unsigned: &PartiallySignedTransaction,
+    ) -> Result<PartiallySignedTransaction, SignerError> {
+        let txid = unsigned
+            .extract_tx()
+            .map_err(|e| SignerError::InvalidTransaction(format!("Invalid PSET: {e}")))?
+            .txid()
+            .to_string();
+        let pset_base64 = pset::to_base64(unsigned);
+
+        let signed_base64 = match &self.transport {
+            Transport::Directory(dir) => self.exchange_files(dir, &txid, &pset_base64).await?,
+            Transport::Channel(sender) => self.exchange_channel(sender, &txid, pset_base64).await?,
+        };
+
+        let signed = pset::from_base64(&signed_base64)?;
+        pset::ensure_same_transaction(unsigned, &signed)?;
+        tracing::info!("Received signed PSET for transaction {}", txid);
+        Ok(signed)
+    }
+
+    async fn exchange_files(
+        &self,
+        dir: &Path,
+        txid: &str,
+        pset_base64: &str,
+    ) -> Result<String, SignerError> {
+        let unsigned_path = dir.join(format!("{txid}.pset"));
+        let signed_path = dir.join(format!("{txid}.signed.pset"));
+
+        tokio::fs::create_dir_all(dir).await?;
+        // Write through a temporary file so the external signer never reads a partial PSET
+        let tmp_path = dir.join(format!("{txid}.pset.tmp"));
+        tokio::fs::write(&tmp_path, pset_base64).await?;
+        tokio::fs::rename(&tmp_path, &unsigned_path).await?;
+        tracing::info!(
+            "Wrote unsigned PSET to {}, waiting for {}",
+            unsigned_path.display(),
+            signed_path.display()
+        );
+
+        let deadline = tokio::time::Instant::now() + self.timeout;
+        loop {
+            match tokio::fs::read_to_string(&signed_path).await {
+                Ok(content) if pset::from_base64(&content).is_ok() => {
+                    let _ = tokio::fs::remove_file(&unsigned_path).await;
+                    let _ = tokio::fs::remove_file(&signed_path).await;
+                    return Ok(content);
+                }
+                Ok(_) => {
+                    tracing::debug!("Signed PSET {} is not readable yet", signed_path.display());
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(SignerError::FileIo(e)),
+            }
+
+            if tokio::time::Instant::now() >= deadline {
+                // Leave the unsigned PSET in place so a late signature can still be picked up
+                return Err(SignerError::Timeout(format!(
+                    "No signed PSET at {} after {:?}",
+                    signed_path.display(),
+                    self.timeout
+                )));
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    async fn exchange_channel(
+        &self,
+        sender: &mpsc::Sender<PsetSigningRequest>,
+        txid: &str,
+        pset_base64: String,
+    ) -> Result<String, SignerError> {
+        let (reply, response) = oneshot::channel();
+        let request = PsetSigningRequest {
+            txid: txid.to_string(),
+            pset_base64,
+            reply,
+        };
+
+        sender
+            .send(request)
+            .await
+            .map_err(|_| SignerError::Unsupported("PSET signing channel is closed".to_string()))?;
+
+        match tokio::time::timeout(self.timeout, response).await {
+            Ok(Ok(signed)) => Ok(signed),
+            Ok(Err(_)) => Err(SignerError::InvalidTransaction(format!(
+                "External signer rejected transaction {txid}"
+            ))),
+            Err(_) => Err(SignerError::Timeout(format!(
+                "No signed PSET for transaction {txid} after {:?}",
+                self.timeout
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl Signer for PsetFileSigner {
+    /// Sends the transaction to the external signer as a PSET without previous outputs
+    ///
+    /// The external signer must finalize the inputs, since raw transaction callers have no
+    /// way to finalize the PSET afterwards.
+    async fn sign_transaction(&self, unsigned_tx: &str) -> Result<String, SignerError> {
+        let tx_bytes = hex::decode(unsigned_tx.trim())?;
+        let transaction: elements::Transaction =
+            elements::encode::deserialize(&tx_bytes).map_err(|e| {
+                SignerError::InvalidTransaction(format!("Transaction deserialization failed: {e}"))
+            })?;
+
+        let signed = self
+            .exchange(&PartiallySignedTransaction::from_tx(transaction))
+            .await?;
+        if !pset::is_finalized(&signed) {
+            return Err(SignerError::InvalidTransaction(
+                "External signer returned a PSET with unfinalized inputs".to_string(),
+            ));
+        }
+
+        let signed_transaction = signed.extract_tx().map_err(|e| {
+            SignerError::InvalidTransaction(format!("Failed to extract signed transaction: {e}"))
+        })?;
+        Ok(hex::encode(elements::encode::serialize(
+            &signed_transaction,
+        )))
+    }
+
+    async fn sign_pset(
+        &self,
+        pset: PartiallySignedTransaction,
+    ) -> Result<PartiallySignedTransaction, SignerError> {
+        self.exchange(&pset).await
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unsigned_pset() -> PartiallySignedTransaction {
+        let asset = elements::AssetId::from_slice(&[7u8; 32]).expect("32-byte asset id is valid");
+        PartiallySignedTransaction::from_tx(elements::Transaction {
+            version: 2,
+            lock_time: elements::LockTime::ZERO,
+            input: vec![elements::TxIn {
+                previous_output: elements::OutPoint::new("cc".repeat(32).parse().unwrap(), 0),
+                is_pegin: false,
+                script_sig: elements::Script::new(),
+                sequence: elements::Sequence::ENABLE_RBF_NO_LOCKTIME,
+                asset_issuance: elements::AssetIssuance::default(),
+                witness: elements::TxInWitness::default(),
+            }],
+            output: vec![elements::TxOut::new_fee(500, asset)],
+        })
+    }
+
+    fn sign(pset_base64: &str) -> String {
+        let mut pset = pset::from_base64(pset_base64).unwrap();
+        pset.inputs_mut()[0].final_script_witness = Some(vec![vec![1u8; 71], vec![2u8; 33]]);
+        pset::to_base64(&pset)
+    }
+
+    #[tokio::test]
+    async fn test_channel_signer_returns_signed_pset() {
+        let (signer, mut requests) = PsetFileSigner::channel(1);
+        tokio::spawn(async move {
+            while let Some(request) = requests.recv().await {
+                let signed = sign(request.pset_base64());
+                request.respond(signed).unwrap();
+            }
+        });
+
+        let signed = signer.sign_pset(unsigned_pset()).await.unwrap();
+        assert!(pset::is_finalized(&signed));
+    }
+
+    #[tokio::test]
+    async fn test_directory_signer_waits_for_signed_file() {
+        let dir = std::env::temp_dir().join(format!("amp_rs_psets_{}", std::process::id()));
+        let signer = PsetFileSigner::new(&dir).with_poll_interval(Duration::from_millis(10));
+
+        let offline_dir = dir.clone();
+        let offline = tokio::spawn(async move {
+            loop {
+                let mut entries = match tokio::fs::read_dir(&offline_dir).await {
+                    Ok(entries) => entries,
+                    Err(_) => {
+                        tokio::time::sleep(Duration::from_millis(10)).await;
+                        continue;
+                    }
+                };
+                while let Some(entry) = entries.next_entry().await.unwrap() {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    if let Some(txid) = name.strip_suffix(".pset") {
+                        if !txid.ends_with(".signed") {
+                            let unsigned = tokio::fs::read_to_string(entry.path()).await.unwrap();
+                            let signed_path = offline_dir.join(format!("{txid}.signed.pset"));
+                            tokio::fs::write(signed_path, sign(&unsigned))
+                                .await
+                                .unwrap();
+                            return;
+                        }
+                    }
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        });
+
+        let signed = signer.sign_pset(unsigned_pset()).await.unwrap();
+        offline.await.unwrap();
+        assert!(pset::is_finalized(&signed));
+
+        // Both files are cleaned up after a successful exchange
+        let mut entries = tokio::fs::read_dir(&dir).await.unwrap();
+        assert!(entries.next_entry().await.unwrap().is_none());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_signer_rejects_tampered_pset_and_times_out() {
+        let (signer, mut requests) = PsetFileSigner::channel(1);
+        let signer = signer.with_timeout(Duration::from_millis(50));
+        tokio::spawn(async move {
+            let request = requests.recv().await.unwrap();
+            let mut tampered = pset::from_base64(request.pset_base64()).unwrap();
+            tampered.outputs_mut()[0].amount = Some(1);
+            request.respond(pset::to_base64(&tampered)).unwrap();
+
+            // Hold the second request without answering
+            let _pending = requests.recv().await;
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        });
+
+        assert!(matches!(
+            signer.sign_pset(unsigned_pset()).await,
+            Err(SignerError::InvalidTransaction(_))
+        ));
+        assert!(matches!(
+            signer.sign_pset(unsigned_pset()).await,
+            Err(SignerError::Timeout(_))
+        ));
+    }
+}
@@ -0,0 +1,432 @@
+//! # Token Persistence Backends
+//!
+//! [`TokenManager`](crate::client::TokenManager) keeps the current AMP token in memory and
+//! hands it to a [`TokenStore`] whenever it changes. The store decides where, and how, the
+//! token survives process restarts.
+//!
+//! Three implementations are provided:
+//! - [`InMemoryTokenStore`] - keeps the token in process memory only
+//! - [`FileTokenStore`] - plaintext JSON file at a configurable path, created with `0600`
+//!   permissions on Unix
+//! - [`EncryptedFileTokenStore`] - JSON file whose content is encrypted with a key derived
+//!   from a passphrase (Argon2id + XChaCha20-Poly1305)
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use amp_rs::ApiClient;
+//! # use amp_rs::token_store::EncryptedFileTokenStore;
+//! # use secrecy::Secret;
+//! # use std::sync::Arc;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let store = EncryptedFileTokenStore::new(
+//!     "/var/lib/my-service/amp-token.json",
+//!     Secret::new("store passphrase".to_string()),
+//! );
+//!
+//! let client = ApiClient::builder()
+//!     .credentials("issuer", Secret::new("password".to_string()))
+//!     .token_store(Arc::new(store))
+//!     .build()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use secrecy::{ExposeSecret, Secret};
+use tokio::sync::Mutex;
+
+use crate::client::TokenError;
+use crate::encryption::{self, EncryptedEnvelope};
+use crate::model::TokenData;
+
+/// Default token file used when no path is configured
+pub const DEFAULT_TOKEN_FILE: &str = "token.json";
+
+/// Storage backend for persisting AMP authentication tokens
+///
+/// Implementations must be thread-safe; a single store may be shared by several
+/// `TokenManager` instances.
+#[async_trait]
+pub trait TokenStore: Send + Sync + std::fmt::Debug {
+    /// Loads the persisted token
+    ///
+    /// Returns `Ok(None)` when no token is stored or the stored token has expired.
+    async fn load(&self) -> Result<Option<TokenData>, TokenError>;
+
+    /// Persists the token, replacing any previously stored token
+    async fn save(&self, token_data: &TokenData) -> Result<(), TokenError>;
+
+    /// Removes the persisted token
+    async fn clear(&self) -> Result<(), TokenError>;
+}
+
+/// Token store that keeps the token in process memory only
+#[derive(Debug, Default)]
+pub struct InMemoryTokenStore {
+    token_data: Mutex<Option<TokenData>>,
+}
+
+impl InMemoryTokenStore {
+    /// Creates an empty in-memory store
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl TokenStore for InMemoryTokenStore {
+    async fn load(&self) -> Result<Option<TokenData>, TokenError> {
+        let mut guard = self.token_data.lock().await;
+        if guard.as_ref().is_some_and(TokenData::is_expired) {
+            *guard = None;
+        }
+        Ok(guard.clone())
+    }
+
+    async fn save(&self, token_data: &TokenData) -> Result<(), TokenError> {
+        *self.token_data.lock().await = Some(token_data.clone());
+        Ok(())
+    }
+
+    async fn clear(&self) -> Result<(), TokenError> {
+        *self.token_data.lock().await = None;
+        Ok(())
+    }
+}
+
+/// Token store that writes the token as JSON to a file
+///
+/// On Unix the file is created with `0600` permissions so that only the owning user
+/// can read it.
+#[derive(Debug, Clone)]
+pub struct FileTokenStore {
+    path: PathBuf,
+}
+
+impl Default for FileTokenStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_TOKEN_FILE)
+    }
+}
+
+impl FileTokenStore {
+    /// Creates a file store at the given path
+    #[must_use]
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Returns the path of the token file
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[async_trait]
+impl TokenStore for FileTokenStore {
+    async fn load(&self) -> Result<Option<TokenData>, TokenError> {
+        let Some(content) = read_token_file(&self.path).await? else {
+            return Ok(None);
+        };
+
+        match serde_json::from_str::<TokenData>(&content) {
+            Ok(token_data) => discard_if_expired(&self.path, token_data).await,
+            Err(e) => {
+                tracing::warn!("Failed to parse token file, removing: {e}");
+                let _ = tokio::fs::remove_file(&self.path).await;
+                Err(TokenError::serialization(format!(
+                    "Failed to parse token file: {e}"
+                )))
+            }
+        }
+    }
+
+    async fn save(&self, token_data: &TokenData) -> Result<(), TokenError> {
+        let json = serde_json::to_string_pretty(token_data).map_err(|e| {
+            TokenError::serialization(format!("Failed to serialize token data: {e}"))
+        })?;
+        write_private_file(&self.path, json.as_bytes()).await?;
+        tracing::debug!("Token saved to disk: {}", self.path.display());
+        Ok(())
+    }
+
+    async fn clear(&self) -> Result<(), TokenError> {
+        remove_token_file(&self.path).await
+    }
+}
+
+/// Token store that encrypts the token before writing it to a file
+///
+/// The key is derived from the passphrase with Argon2id and a fresh random salt on
+/// every save, and the token is sealed with XChaCha20-Poly1305. The file carries an
+/// explicit format version. Like [`FileTokenStore`], the file is created with `0600`
+/// permissions on Unix.
+#[derive(Debug, Clone)]
+pub struct EncryptedFileTokenStore {
+    path: PathBuf,
+    passphrase: Secret<String>,
+}
+
+impl EncryptedFileTokenStore {
+    /// Creates an encrypted file store at the given path
+    #[must_use]
+    pub fn new<P: AsRef<Path>>(path: P, passphrase: Secret<String>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            passphrase,
+        }
+    }
+
+    /// Returns the path of the encrypted token file
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[async_trait]
+impl TokenStore for EncryptedFileTokenStore {
+    async fn load(&self) -> Result<Option<TokenData>, TokenError> {
+        let Some(content) = read_token_file(&self.path).await? else {
+            return Ok(None);
+        };
+
+        let envelope: EncryptedEnvelope = serde_json::from_str(&content).map_err(|e| {
+            TokenError::serialization(format!("Failed to parse encrypted token file: {e}"))
+        })?;
+        let plaintext = encryption::open(self.passphrase.expose_secret().as_bytes(), &envelope)
+            .map_err(|e| TokenError::storage(format!("Failed to decrypt token file: {e}")))?;
+        let token_data: TokenData = serde_json::from_slice(&plaintext).map_err(|e| {
+            TokenError::serialization(format!("Failed to parse decrypted token data: {e}"))
+        })?;
+
+        discard_if_expired(&self.path, token_data).await
+    }
+
+    async fn save(&self, token_data: &TokenData) -> Result<(), TokenError> {
+        let plaintext = zeroize::Zeroizing::new(serde_json::to_vec(token_data).map_err(|e| {
+            TokenError::serialization(format!("Failed to serialize token data: {e}"))
+        })?);
+        let envelope = encryption::seal(self.passphrase.expose_secret().as_bytes(), &plaintext)
+            .map_err(|e| TokenError::storage(format!("Failed to encrypt token data: {e}")))?;
+        let json = serde_json::to_string_pretty(&envelope).map_err(|e| {
+            TokenError::serialization(format!("Failed to serialize encrypted token: {e}"))
+        })?;
+        write_private_file(&self.path, json.as_bytes()).await?;
+        tracing::debug!("Encrypted token saved to disk: {}", self.path.display());
+        Ok(())
+    }
+
+    async fn clear(&self) -> Result<(), TokenError> {
+        remove_token_file(&self.path).await
+    }
+}
+
+/// Reads the token file, returning `None` if it does not exist
+async fn read_token_file(path: &Path) -> Result<Option<String>, TokenError> {
+    match tokio::fs::read_to_string(path).await {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            tracing::debug!("Token file does not exist: {}", path.display());
+            Ok(None)
+        }
+        Err(e) => {
+            tracing::warn!("Failed to read token file: {e}");
+            Err(TokenError::storage(format!(
+                "Failed to read token file: {e}"
+            )))
+        }
+    }
+}
+
+/// Removes the token file and returns `None` if the loaded token has expired
+async fn discard_if_expired(
+    path: &Path,
+    token_data: TokenData,
+) -> Result<Option<TokenData>, TokenError> {
+    if token_data.is_expired() {
+        tracing::info!("Token loaded from disk is expired, removing file");
+        let _ = tokio::fs::remove_file(path).await;
+        Ok(None)
+    } else {
+        tracing::info!("Valid token loaded from disk");
+        Ok(Some(token_data))
+    }
+}
+
+/// Writes `content` to `path`, restricting permissions to the owner on Unix
+async fn write_private_file(path: &Path, content: &[u8]) -> Result<(), TokenError> {
+    use tokio::io::AsyncWriteExt;
+
+    let mut options = tokio::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+
+    let mut file = options.open(path).await.map_err(|e| {
+        tracing::error!("Failed to write token file: {e}");
+        TokenError::storage(format!("Failed to write token file: {e}"))
+    })?;
+
+    // `mode` only applies when the file is created, so tighten pre-existing files too
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        tokio::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+            .await
+            .map_err(|e| {
+                TokenError::storage(format!("Failed to set token file permissions: {e}"))
+            })?;
+    }
+
+    file.write_all(content)
+        .await
+        .map_err(|e| TokenError::storage(format!("Failed to write token file: {e}")))?;
+    file.flush()
+        .await
+        .map_err(|e| TokenError::storage(format!("Failed to write token file: {e}")))
+}
+
+/// Removes the token file, treating a missing file as success
+async fn remove_token_file(path: &Path) -> Result<(), TokenError> {
+    match tokio::fs::remove_file(path).await {
+        Ok(()) => {
+            tracing::debug!("Token file removed from disk: {}", path.display());
+            Ok(())
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            tracing::debug!("Token file does not exist, nothing to remove");
+            Ok(())
+        }
+        Err(e) => {
+            tracing::warn!("Failed to remove token file: {e}");
+            Err(TokenError::storage(format!(
+                "Failed to remove token file: {e}"
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn token(value: &str) -> TokenData {
+        TokenData::new(value.to_string(), Utc::now() + Duration::hours(1))
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("amp_rs_{name}_{}.json", std::process::id()))
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store_round_trip() {
+        let store = InMemoryTokenStore::new();
+        assert!(store.load().await.unwrap().is_none());
+
+        store.save(&token("memory-token")).await.unwrap();
+        let loaded = store.load().await.unwrap().unwrap();
+        assert_eq!(loaded.token.expose_secret(), "memory-token");
+
+        store.clear().await.unwrap();
+        assert!(store.load().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store_discards_expired_token() {
+        let store = InMemoryTokenStore::new();
+        let expired = TokenData::new("old".to_string(), Utc::now() - Duration::minutes(1));
+        store.save(&expired).await.unwrap();
+        assert!(store.load().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_file_store_round_trip() {
+        let path = temp_path("token_store_file");
+        let store = FileTokenStore::new(&path);
+        assert!(store.load().await.unwrap().is_none());
+
+        store.save(&token("file-token")).await.unwrap();
+        let loaded = store.load().await.unwrap().unwrap();
+        assert_eq!(loaded.token.expose_secret(), "file-token");
+
+        store.clear().await.unwrap();
+        assert!(!path.exists());
+        assert!(store.load().await.unwrap().is_none());
+        // Clearing a missing file is not an error
+        store.clear().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_file_store_round_trip() {
+        let path = temp_path("token_store_encrypted");
+        let store = EncryptedFileTokenStore::new(&path, Secret::new("passphrase".to_string()));
+
+        store.save(&token("encrypted-token")).await.unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("encrypted-token"));
+        let envelope: EncryptedEnvelope = serde_json::from_str(&content).unwrap();
+        assert_eq!(envelope.version, encryption::ENVELOPE_VERSION);
+
+        let loaded = store.load().await.unwrap().unwrap();
+        assert_eq!(loaded.token.expose_secret(), "encrypted-token");
+
+        store.clear().await.unwrap();
+        assert!(store.load().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_file_store_wrong_passphrase_fails() {
+        let path = temp_path("token_store_wrong_passphrase");
+        EncryptedFileTokenStore::new(&path, Secret::new("right".to_string()))
+            .save(&token("secret-token"))
+            .await
+            .unwrap();
+
+        let wrong = EncryptedFileTokenStore::new(&path, Secret::new("wrong".to_string()));
+        let result = wrong.load().await;
+        assert!(matches!(result, Err(TokenError::Storage(_))));
+        // A failed decryption must not destroy the stored token
+        assert!(path.exists());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_file_stores_restrict_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let plain_path = temp_path("token_store_mode_plain");
+        let encrypted_path = temp_path("token_store_mode_encrypted");
+
+        // Pre-existing files with looser permissions are tightened on save
+        std::fs::write(&plain_path, "{}").unwrap();
+        std::fs::set_permissions(&plain_path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        FileTokenStore::new(&plain_path)
+            .save(&token("plain"))
+            .await
+            .unwrap();
+        EncryptedFileTokenStore::new(&encrypted_path, Secret::new("passphrase".to_string()))
+            .save(&token("encrypted"))
+            .await
+            .unwrap();
+
+        for path in [&plain_path, &encrypted_path] {
+            let mode = std::fs::metadata(path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600, "{}", path.display());
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
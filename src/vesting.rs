@@ -0,0 +1,535 @@
+//! # Vesting Schedules
+//!
+//! AMP assignments carry a `vesting_timestamp`: an assignment can only be distributed
+//! once it has vested. A [`VestingSchedule`] describes how a grant to one registered
+//! user vests over time and expands into one [`CreateAssetAssignmentRequest`] per
+//! tranche:
+//!
+//! - nothing vests before the cliff; at the cliff, everything accrued since the start
+//!   vests at once
+//! - after the cliff, the grant vests linearly in tranches every `interval_months`
+//!   (monthly by default) until `duration_months` after the start
+//!
+//! Tranche amounts are rounded down to whole satoshis and the remainder vests with the
+//! last tranche, so the tranches always add up to the grant. Dates on which nothing
+//! vests after rounding are left out.
+//!
+//! A [`VestingTracker`] reads the assignments of an asset back from the AMP API and
+//! reports, for a point in time, which tranches are still unvested, which have vested
+//! but are not yet marked `ready_for_distribution`, and which are ready.
+//! [`VestingTracker::next_distribution_batch`] turns the ready tranches into the
+//! assignments of the next distribution.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # use amp_rs::{Amount, ApiClient};
+//! # use amp_rs::vesting::VestingSchedule;
+//! # use chrono::{TimeZone, Utc};
+//! # use std::collections::HashMap;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = ApiClient::new().await?;
+//!
+//! // 4 years with a 1 year cliff, vesting monthly
+//! let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+//! let schedule = VestingSchedule::new(42, Amount::from_sat(4_800_000), start, 48).with_cliff(12);
+//! client.create_vesting_assignments("asset-uuid", &schedule).await?;
+//!
+//! // Later: distribute whatever has vested and is ready
+//! let tracker = client.vesting_tracker("asset-uuid").await?;
+//! let batch = tracker.next_distribution_batch(Utc::now(), &HashMap::new());
+//! println!(
+//!     "{} tranches ready, {} vested but not ready",
+//!     batch.assignments.len(),
+//!     tracker.awaiting_ready(Utc::now()).len()
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Months, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::amount::Amount;
+use crate::model::{AssetDistributionAssignment, Assignment, CreateAssetAssignmentRequest};
+
+/// Errors produced when expanding a vesting schedule
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum VestingError {
+    #[error("Vesting amount must be positive, got {0}")]
+    InvalidAmount(Amount),
+    #[error("Vesting duration must be at least one month")]
+    ZeroDuration,
+    #[error("Vesting interval must be at least one month")]
+    ZeroInterval,
+    #[error("Cliff of {cliff} months is longer than the vesting duration of {duration} months")]
+    CliffTooLong { cliff: u32, duration: u32 },
+    #[error("Vesting date {months} months after {start} is out of range")]
+    DateOutOfRange { start: DateTime<Utc>, months: u32 },
+}
+
+/// How a grant to one registered user vests over time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VestingSchedule {
+    /// AMP id of the registered user receiving the grant
+    pub registered_user: i64,
+    /// Total amount of the grant
+    pub total: Amount,
+    /// Start of the vesting period
+    pub start: DateTime<Utc>,
+    /// Months from the start until the grant is fully vested
+    pub duration_months: u32,
+    /// Months from the start before anything vests
+    pub cliff_months: u32,
+    /// Months between tranches after the cliff
+    pub interval_months: u32,
+}
+
+/// One vesting date of a schedule and the amount that vests on it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VestingTranche {
+    /// Position of the tranche in the schedule, starting at 0
+    pub index: usize,
+    pub vesting_time: DateTime<Utc>,
+    pub amount: Amount,
+}
+
+impl VestingSchedule {
+    /// Creates a schedule without a cliff that vests monthly over `duration_months`
+    #[must_use]
+    pub const fn new(
+        registered_user: i64,
+        total: Amount,
+        start: DateTime<Utc>,
+        duration_months: u32,
+    ) -> Self {
+        Self {
+            registered_user,
+            total,
+            start,
+            duration_months,
+            cliff_months: 0,
+            interval_months: 1,
+        }
+    }
+
+    /// Sets the cliff, in months from the start
+    #[must_use]
+    pub const fn with_cliff(mut self, cliff_months: u32) -> Self {
+        self.cliff_months = cliff_months;
+        self
+    }
+
+    /// Sets the number of months between tranches after the cliff
+    #[must_use]
+    pub const fn with_interval(mut self, interval_months: u32) -> Self {
+        self.interval_months = interval_months;
+        self
+    }
+
+    /// Expands the schedule into its tranches, in vesting order
+    ///
+    /// # Errors
+    /// Returns an error if the amount is not positive, the duration or interval is zero,
+    /// the cliff is longer than the duration, or a vesting date is out of range
+    pub fn tranches(&self) -> Result<Vec<VestingTranche>, VestingError> {
+        if !self.total.is_positive() {
+            return Err(VestingError::InvalidAmount(self.total));
+        }
+        if self.duration_months == 0 {
+            return Err(VestingError::ZeroDuration);
+        }
+        if self.interval_months == 0 {
+            return Err(VestingError::ZeroInterval);
+        }
+        if self.cliff_months > self.duration_months {
+            return Err(VestingError::CliffTooLong {
+                cliff: self.cliff_months,
+                duration: self.duration_months,
+            });
+        }
+
+        // Vesting months after the start: the cliff, every interval after it, and the end
+        let mut months: Vec<u32> = Vec::new();
+        if self.cliff_months > 0 {
+            months.push(self.cliff_months);
+        }
+        months.extend(
+            (1..)
+                .map(|step| step * self.interval_months)
+                .take_while(|month| *month < self.duration_months)
+                .filter(|month| *month > self.cliff_months),
+        );
+        if months.last() != Some(&self.duration_months) {
+            months.push(self.duration_months);
+        }
+
+        let mut tranches = Vec::with_capacity(months.len());
+        let mut vested = Amount::ZERO;
+        for month in months {
+            let cumulative = self.vested_after_months(month);
+            // Grants of a few satoshis do not vest something every month
+            if cumulative > vested {
+                tranches.push(VestingTranche {
+                    index: tranches.len(),
+                    vesting_time: self.date_after_months(month)?,
+                    amount: cumulative - vested,
+                });
+            }
+            vested = cumulative;
+        }
+
+        Ok(tranches)
+    }
+
+    /// Returns the amount vested at `at`
+    ///
+    /// # Errors
+    /// Returns an error if the schedule is invalid
+    pub fn vested_at(&self, at: DateTime<Utc>) -> Result<Amount, VestingError> {
+        Ok(self
+            .tranches()?
+            .into_iter()
+            .filter(|tranche| tranche.vesting_time <= at)
+            .map(|tranche| tranche.amount)
+            .sum())
+    }
+
+    /// Returns one assignment request per tranche
+    ///
+    /// The requests are not `ready_for_distribution`; tranches are marked ready once
+    /// they have vested (see [`VestingTracker::awaiting_ready`]).
+    ///
+    /// # Errors
+    /// Returns an error if the schedule is invalid
+    pub fn assignment_requests(&self) -> Result<Vec<CreateAssetAssignmentRequest>, VestingError> {
+        Ok(self
+            .tranches()?
+            .into_iter()
+            .map(|tranche| CreateAssetAssignmentRequest {
+                registered_user: self.registered_user,
+                amount: tranche.amount.to_sat(),
+                vesting_timestamp: Some(tranche.vesting_time.timestamp()),
+                ready_for_distribution: false,
+            })
+            .collect())
+    }
+
+    /// Amount vested `month` months after the start, rounded down
+    fn vested_after_months(&self, month: u32) -> Amount {
+        if month >= self.duration_months {
+            return self.total;
+        }
+        let vested =
+            i128::from(self.total.to_sat()) * i128::from(month) / i128::from(self.duration_months);
+        // The result lies between zero and the total, so it fits in an i64
+        Amount::from_sat(i64::try_from(vested).unwrap_or(i64::MAX))
+    }
+
+    fn date_after_months(&self, months: u32) -> Result<DateTime<Utc>, VestingError> {
+        self.start
+            .checked_add_months(Months::new(months))
+            .ok_or(VestingError::DateOutOfRange {
+                start: self.start,
+                months,
+            })
+    }
+}
+
+/// Where an assignment stands in its vesting and distribution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrancheStatus {
+    /// The vesting date has not been reached
+    Unvested,
+    /// Vested, but not marked `ready_for_distribution`
+    Vested,
+    /// Vested and ready for the next distribution
+    Ready,
+    /// Part of a distribution that is not confirmed yet
+    Distributing,
+    /// Distributed
+    Distributed,
+}
+
+impl TrancheStatus {
+    /// Returns the status of `assignment` at `now`
+    #[must_use]
+    pub fn of(assignment: &Assignment, now: DateTime<Utc>) -> Self {
+        if assignment.is_distributed {
+            Self::Distributed
+        } else if assignment.distribution_uuid.is_some() {
+            Self::Distributing
+        } else if !assignment.has_vested
+            && assignment
+                .vesting_timestamp
+                .is_some_and(|timestamp| timestamp > now.timestamp())
+        {
+            Self::Unvested
+        } else if assignment.ready_for_distribution {
+            Self::Ready
+        } else {
+            Self::Vested
+        }
+    }
+}
+
+/// Amounts of one registered user by [`TrancheStatus`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserVesting {
+    pub registered_user: i64,
+    pub unvested: Amount,
+    pub vested: Amount,
+    pub ready: Amount,
+    pub distributing: Amount,
+    pub distributed: Amount,
+}
+
+/// Assignments of the next distribution, built from the ready tranches
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NextDistributionBatch {
+    /// Assignments to pass to `distribute_asset`
+    pub assignments: Vec<AssetDistributionAssignment>,
+    /// AMP ids of the assignments included in the batch
+    pub assignment_ids: Vec<i64>,
+    /// AMP ids of ready assignments left out because no receiving address is known
+    pub missing_address: Vec<i64>,
+}
+
+/// Tracks the vesting of the assignments of an asset
+#[derive(Debug)]
+pub struct VestingTracker {
+    assignments: Vec<Assignment>,
+}
+
+impl VestingTracker {
+    /// Creates a tracker over assignments returned by the AMP API
+    #[must_use]
+    pub const fn new(assignments: Vec<Assignment>) -> Self {
+        Self { assignments }
+    }
+
+    /// Returns the tracked assignments
+    #[must_use]
+    pub fn assignments(&self) -> &[Assignment] {
+        &self.assignments
+    }
+
+    /// Returns the AMP ids of assignments with the given status at `now`
+    #[must_use]
+    pub fn with_status(&self, status: TrancheStatus, now: DateTime<Utc>) -> Vec<i64> {
+        self.assignments
+            .iter()
+            .filter(|assignment| TrancheStatus::of(assignment, now) == status)
+            .map(|assignment| assignment.id)
+            .collect()
+    }
+
+    /// Returns the AMP ids of vested assignments that still have to be marked
+    /// `ready_for_distribution`
+    #[must_use]
+    pub fn awaiting_ready(&self, now: DateTime<Utc>) -> Vec<i64> {
+        self.with_status(TrancheStatus::Vested, now)
+    }
+
+    /// Returns the amounts of every registered user by status, ordered by user id
+    #[must_use]
+    pub fn summary(&self, now: DateTime<Utc>) -> Vec<UserVesting> {
+        let mut users: BTreeMap<i64, UserVesting> = BTreeMap::new();
+        for assignment in &self.assignments {
+            let user = users
+                .entry(assignment.registered_user)
+                .or_insert_with(|| UserVesting {
+                    registered_user: assignment.registered_user,
+                    ..UserVesting::default()
+                });
+            let amount = Amount::from_sat(assignment.amount);
+            match TrancheStatus::of(assignment, now) {
+                TrancheStatus::Unvested => user.unvested += amount,
+                TrancheStatus::Vested => user.vested += amount,
+                TrancheStatus::Ready => user.ready += amount,
+                TrancheStatus::Distributing => user.distributing += amount,
+                TrancheStatus::Distributed => user.distributed += amount,
+            }
+        }
+        users.into_values().collect()
+    }
+
+    /// Builds the next distribution from the assignments that are ready at `now`
+    ///
+    /// Each assignment is paid to its `receiving_address`, or else to the address in
+    /// `addresses` for its registered user.
+    #[must_use]
+    pub fn next_distribution_batch(
+        &self,
+        now: DateTime<Utc>,
+        addresses: &HashMap<i64, String>,
+    ) -> NextDistributionBatch {
+        let mut batch = NextDistributionBatch::default();
+        for assignment in &self.assignments {
+            if TrancheStatus::of(assignment, now) != TrancheStatus::Ready {
+                continue;
+            }
+            let address = assignment
+                .receiving_address
+                .as_ref()
+                .or_else(|| addresses.get(&assignment.registered_user));
+            match address {
+                Some(address) => {
+                    batch.assignments.push(AssetDistributionAssignment {
+                        user_id: assignment.registered_user.to_string(),
+                        address: address.clone(),
+                        amount: Amount::from_sat(assignment.amount),
+                    });
+                    batch.assignment_ids.push(assignment.id);
+                }
+                None => batch.missing_address.push(assignment.id),
+            }
+        }
+        batch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 31, 12, 0, 0).unwrap()
+    }
+
+    fn assignment(id: i64, vesting_timestamp: i64, ready: bool) -> Assignment {
+        Assignment {
+            id,
+            registered_user: 7,
+            amount: 100,
+            receiving_address: None,
+            distribution_uuid: None,
+            ready_for_distribution: ready,
+            vesting_datetime: None,
+            vesting_timestamp: Some(vesting_timestamp),
+            has_vested: false,
+            is_distributed: false,
+            creator: 1,
+            gaid: None,
+            investor: None,
+        }
+    }
+
+    #[test]
+    fn test_schedule_with_cliff_expands_to_monthly_tranches() {
+        let schedule = VestingSchedule::new(7, Amount::from_sat(1000), start(), 48).with_cliff(12);
+        let tranches = schedule.tranches().unwrap();
+
+        // The cliff tranche and 36 monthly tranches after it
+        assert_eq!(tranches.len(), 37);
+        assert_eq!(tranches[0].amount, Amount::from_sat(250));
+        assert_eq!(
+            tranches[0].vesting_time,
+            Utc.with_ymd_and_hms(2026, 1, 31, 12, 0, 0).unwrap()
+        );
+        // Month ends are clamped to the end of shorter months
+        assert_eq!(
+            tranches[1].vesting_time,
+            Utc.with_ymd_and_hms(2026, 2, 28, 12, 0, 0).unwrap()
+        );
+        assert_eq!(
+            tranches.iter().map(|t| t.amount).sum::<Amount>(),
+            Amount::from_sat(1000)
+        );
+
+        let at_cliff = tranches[0].vesting_time;
+        assert_eq!(
+            schedule.vested_at(at_cliff - chrono::Duration::seconds(1)),
+            Ok(Amount::ZERO)
+        );
+        assert_eq!(schedule.vested_at(at_cliff), Ok(Amount::from_sat(250)));
+
+        let requests = schedule.assignment_requests().unwrap();
+        assert_eq!(requests.len(), 37);
+        assert_eq!(requests[0].vesting_timestamp, Some(at_cliff.timestamp()));
+        assert!(!requests[0].ready_for_distribution);
+    }
+
+    #[test]
+    fn test_schedule_rounding_and_validation() {
+        let tranches = VestingSchedule::new(7, Amount::from_sat(100), start(), 6)
+            .with_interval(4)
+            .tranches()
+            .unwrap();
+        let amounts: Vec<i64> = tranches.iter().map(|t| t.amount.to_sat()).collect();
+        assert_eq!(amounts, vec![66, 34]);
+
+        let tranches = VestingSchedule::new(7, Amount::from_sat(2), start(), 12)
+            .tranches()
+            .unwrap();
+        let months: Vec<(usize, u32)> = tranches
+            .iter()
+            .map(|t| (t.index, chrono::Datelike::month(&t.vesting_time)))
+            .collect();
+        assert_eq!(months, vec![(0, 7), (1, 1)]);
+
+        assert_eq!(
+            VestingSchedule::new(7, Amount::from_sat(100), start(), 12)
+                .with_cliff(13)
+                .tranches(),
+            Err(VestingError::CliffTooLong {
+                cliff: 13,
+                duration: 12
+            })
+        );
+        assert_eq!(
+            VestingSchedule::new(7, Amount::ZERO, start(), 12).tranches(),
+            Err(VestingError::InvalidAmount(Amount::ZERO))
+        );
+        assert_eq!(
+            VestingSchedule::new(7, Amount::from_sat(1), start(), 0).tranches(),
+            Err(VestingError::ZeroDuration)
+        );
+    }
+
+    #[test]
+    fn test_tracker_reports_status_and_next_batch() {
+        let now = start();
+        let past = now.timestamp() - 60;
+        let future = now.timestamp() + 60;
+
+        let mut with_address = assignment(1, past, true);
+        with_address.receiving_address = Some("vjaddress".to_string());
+        let mut distributing = assignment(5, past, true);
+        distributing.distribution_uuid = Some("distribution".to_string());
+        let mut other_user = assignment(6, past, true);
+        other_user.registered_user = 8;
+        let tracker = VestingTracker::new(vec![
+            with_address,
+            assignment(2, past, true),
+            assignment(3, past, false),
+            assignment(4, future, false),
+            distributing,
+            other_user,
+        ]);
+
+        assert_eq!(tracker.awaiting_ready(now), vec![3]);
+        assert_eq!(tracker.with_status(TrancheStatus::Unvested, now), vec![4]);
+
+        let summary = tracker.summary(now);
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary[0].ready, Amount::from_sat(200));
+        assert_eq!(summary[0].vested, Amount::from_sat(100));
+        assert_eq!(summary[0].unvested, Amount::from_sat(100));
+        assert_eq!(summary[0].distributing, Amount::from_sat(100));
+
+        let mut addresses = HashMap::new();
+        addresses.insert(7, "vjuser7".to_string());
+        let batch = tracker.next_distribution_batch(now, &addresses);
+        assert_eq!(batch.assignment_ids, vec![1, 2]);
+        assert_eq!(batch.assignments[0].address, "vjaddress");
+        assert_eq!(batch.assignments[1].address, "vjuser7");
+        assert_eq!(batch.missing_address, vec![6]);
+    }
+}
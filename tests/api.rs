@@ -1,5 +1,5 @@
 use amp_rs::mocks;
-use amp_rs::ApiClient;
+use amp_rs::{Amount, ApiClient};
 use httpmock::prelude::*;
 use secrecy::Secret;
 use serial_test::serial;
@@ -4979,7 +4979,7 @@ async fn test_reissue_request_mock() {
     let response = result.unwrap();
     assert_eq!(response.asset_uuid, "mock_asset_uuid");
     assert_eq!(response.asset_id, "mock_asset_id");
-    assert_eq!(response.amount, 10.0);
+    assert_eq!(response.amount, Amount::from_btc(10.0).unwrap());
     assert_eq!(response.command, "reissue");
     assert_eq!(response.reissuance_utxos.len(), 1);
     assert_eq!(response.reissuance_utxos[0].txid, "mock_reissuance_txid");
@@ -5226,7 +5226,7 @@ async fn test_burn_request_mock() {
     let burn_create = result.unwrap();
     assert_eq!(burn_create.asset_uuid, "mock_asset_uuid");
     assert_eq!(burn_create.asset_id, "mock_asset_id");
-    assert_eq!(burn_create.amount, 1_000_000.0);
+    assert_eq!(burn_create.amount, Amount::from_btc(1_000_000.0).unwrap());
     assert_eq!(burn_create.command, "destroyamount");
     assert_eq!(burn_create.utxos.len(), 2);
     assert_eq!(burn_create.utxos[0].txid, "mock_txid_1");
@@ -27,7 +27,7 @@
 //! - Proper cleanup to avoid test interference
 
 use amp_rs::signer::{LwkSoftwareSigner, Signer};
-use amp_rs::{ApiClient, ElementsRpc};
+use amp_rs::{Amount, ApiClient, ElementsRpc};
 use dotenvy;
 use serial_test::serial;
 use std::env;
@@ -1372,7 +1372,7 @@ async fn test_end_to_end_distribution_workflow() -> Result<(), Box<dyn std::erro
     let distribution_assignments = vec![amp_rs::model::AssetDistributionAssignment {
         user_id: user_id.to_string(),
         address: user_address.clone(),
-        amount: assignment_amount as f64 / 100_000_000.0, // Convert satoshis to BTC
+        amount: Amount::from_sat(assignment_amount),
     }];
 
     println!("✅ Assignment vector created");
@@ -1826,7 +1826,7 @@ async fn test_distribution_assignment_creation() -> Result<(), Box<dyn std::erro
         user_id: "123".to_string(),
         address: "vjTwqhz69nh7xHhtsHnx7mezsJV95EYHPqxshuoVXEMS5sqVzok57YVWYKDLcanqdSq54oTNhNM1NuTB"
             .to_string(),
-        amount: 0.001,
+        amount: Amount::from_btc(0.001).unwrap(),
     };
 
     println!("✅ Assignment created successfully");
@@ -1842,7 +1842,7 @@ async fn test_distribution_assignment_creation() -> Result<(), Box<dyn std::erro
             address:
                 "vjTwqhz69nh7xHhtsHnx7mezsJV95EYHPqxshuoVXEMS5sqVzok57YVWYKDLcanqdSq54oTNhNM1NuTB"
                     .to_string(),
-            amount: 0.002,
+            amount: Amount::from_btc(0.002).unwrap(),
         },
     ];
 
@@ -1915,7 +1915,7 @@ async fn test_network_failure_scenarios() -> Result<(), Box<dyn std::error::Erro
         user_id: "123".to_string(),
         address: "vjTwqhz69nh7xHhtsHnx7mezsJV95EYHPqxshuoVXEMS5sqVzok57YVWYKDLcanqdSq54oTNhNM1NuTB"
             .to_string(),
-        amount: 0.001,
+        amount: Amount::from_btc(0.001).unwrap(),
     }];
 
     let result = api_client
@@ -2103,7 +2103,7 @@ async fn test_signing_failure_scenarios() -> Result<(), Box<dyn std::error::Erro
         user_id: "123".to_string(),
         address: "vjTwqhz69nh7xHhtsHnx7mezsJV95EYHPqxshuoVXEMS5sqVzok57YVWYKDLcanqdSq54oTNhNM1NuTB"
             .to_string(),
-        amount: 0.001,
+        amount: Amount::from_btc(0.001).unwrap(),
     }];
 
     let result = api_client
@@ -2328,7 +2328,7 @@ async fn test_insufficient_utxos_and_invalid_addresses() -> Result<(), Box<dyn s
     let invalid_assignments = vec![amp_rs::model::AssetDistributionAssignment {
         user_id: "123".to_string(),
         address: "invalid_address_format".to_string(), // Invalid address
-        amount: 0.001,
+        amount: Amount::from_btc(0.001).unwrap(),
     }];
 
     let valid_rpc = ElementsRpc::new(
@@ -2365,7 +2365,7 @@ async fn test_insufficient_utxos_and_invalid_addresses() -> Result<(), Box<dyn s
     let empty_address_assignments = vec![amp_rs::model::AssetDistributionAssignment {
         user_id: "123".to_string(),
         address: "".to_string(), // Empty address
-        amount: 0.001,
+        amount: Amount::from_btc(0.001).unwrap(),
     }];
 
     let result = api_client
@@ -2397,7 +2397,7 @@ async fn test_insufficient_utxos_and_invalid_addresses() -> Result<(), Box<dyn s
         user_id: "123".to_string(),
         address: "vjTwqhz69nh7xHhtsHnx7mezsJV95EYHPqxshuoVXEMS5sqVzok57YVWYKDLcanqdSq54oTNhNM1NuTB"
             .to_string(),
-        amount: 0.0, // Zero amount
+        amount: Amount::from_btc(0.0).unwrap(), // Zero amount
     }];
 
     let result = api_client
@@ -2429,7 +2429,7 @@ async fn test_insufficient_utxos_and_invalid_addresses() -> Result<(), Box<dyn s
         user_id: "123".to_string(),
         address: "vjTwqhz69nh7xHhtsHnx7mezsJV95EYHPqxshuoVXEMS5sqVzok57YVWYKDLcanqdSq54oTNhNM1NuTB"
             .to_string(),
-        amount: -0.001, // Negative amount
+        amount: Amount::from_btc(-0.001).unwrap(), // Negative amount
     }];
 
     let result = api_client
@@ -2461,7 +2461,7 @@ async fn test_insufficient_utxos_and_invalid_addresses() -> Result<(), Box<dyn s
         user_id: "".to_string(), // Empty user ID
         address: "vjTwqhz69nh7xHhtsHnx7mezsJV95EYHPqxshuoVXEMS5sqVzok57YVWYKDLcanqdSq54oTNhNM1NuTB"
             .to_string(),
-        amount: 0.001,
+        amount: Amount::from_btc(0.001).unwrap(),
     }];
 
     let result = api_client
@@ -2549,7 +2549,7 @@ async fn test_duplicate_distribution_and_retry_scenarios() -> Result<(), Box<dyn
         user_id: "123".to_string(),
         address: "vjTwqhz69nh7xHhtsHnx7mezsJV95EYHPqxshuoVXEMS5sqVzok57YVWYKDLcanqdSq54oTNhNM1NuTB"
             .to_string(),
-        amount: 0.001,
+        amount: Amount::from_btc(0.001).unwrap(),
     }];
 
     let valid_rpc = ElementsRpc::new(
@@ -2736,14 +2736,14 @@ async fn test_comprehensive_error_scenario_integration() -> Result<(), Box<dyn s
 
     let invalid_assignments = vec![
         amp_rs::model::AssetDistributionAssignment {
-            user_id: "".to_string(),                // Empty user ID
-            address: "invalid_address".to_string(), // Invalid address
-            amount: -0.001,                         // Negative amount
+            user_id: "".to_string(),                   // Empty user ID
+            address: "invalid_address".to_string(),    // Invalid address
+            amount: Amount::from_btc(-0.001).unwrap(), // Negative amount
         },
         amp_rs::model::AssetDistributionAssignment {
             user_id: "123".to_string(),
-            address: "".to_string(), // Empty address
-            amount: 0.0,             // Zero amount
+            address: "".to_string(),                // Empty address
+            amount: Amount::from_btc(0.0).unwrap(), // Zero amount
         },
     ];
 
@@ -2792,7 +2792,7 @@ async fn test_comprehensive_error_scenario_integration() -> Result<(), Box<dyn s
         user_id: "123".to_string(),
         address: "vjTwqhz69nh7xHhtsHnx7mezsJV95EYHPqxshuoVXEMS5sqVzok57YVWYKDLcanqdSq54oTNhNM1NuTB"
             .to_string(),
-        amount: 0.001,
+        amount: Amount::from_btc(0.001).unwrap(),
     }];
 
     let result = api_client
@@ -2928,14 +2928,14 @@ async fn test_comprehensive_error_scenario_integration_fixed(
 
     let invalid_assignments = vec![
         amp_rs::model::AssetDistributionAssignment {
-            user_id: "".to_string(),                // Empty user ID
-            address: "invalid_address".to_string(), // Invalid address
-            amount: -0.001,                         // Negative amount
+            user_id: "".to_string(),                   // Empty user ID
+            address: "invalid_address".to_string(),    // Invalid address
+            amount: Amount::from_btc(-0.001).unwrap(), // Negative amount
         },
         amp_rs::model::AssetDistributionAssignment {
             user_id: "123".to_string(),
-            address: "".to_string(), // Empty address
-            amount: 0.0,             // Zero amount
+            address: "".to_string(),                // Empty address
+            amount: Amount::from_btc(0.0).unwrap(), // Zero amount
         },
     ];
 
@@ -2984,7 +2984,7 @@ async fn test_comprehensive_error_scenario_integration_fixed(
         user_id: "123".to_string(),
         address: "vjTwqhz69nh7xHhtsHnx7mezsJV95EYHPqxshuoVXEMS5sqVzok57YVWYKDLcanqdSq54oTNhNM1NuTB"
             .to_string(),
-        amount: 0.001,
+        amount: Amount::from_btc(0.001).unwrap(),
     }];
 
     let result = api_client
@@ -3146,8 +3146,8 @@ async fn test_fee_fix_with_existing_asset() -> Result<(), Box<dyn std::error::Er
 
     // Create a minimal assignment
     let assignments = vec![amp_rs::model::AssetDistributionAssignment {
-        user_id: "1352".to_string(), // Use existing test user
-        amount: 0.00000001,          // 1 satoshi
+        user_id: "1352".to_string(),                   // Use existing test user
+        amount: Amount::from_btc(0.00000001).unwrap(), // 1 satoshi
         address: "vjU7D4L6585envvv2Yf2ivk63d8dLihgZNzih3XAsYaUTzxYew6pVQecpgLj3PzRiWjJL3m8dADT5Fqp"
             .to_string(),
     }];
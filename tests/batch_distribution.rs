@@ -0,0 +1,275 @@
+use amp_rs::batch::{BatchPlan, BatchPlanner, BatchStatus};
+use amp_rs::distribution::{
+    CheckpointError, CheckpointStore, DistributionCheckpoint, InMemoryCheckpointStore,
+};
+use amp_rs::fee::FeeRate;
+use amp_rs::network::LIQUID_TESTNET_POLICY_ASSET;
+use amp_rs::signer::{LwkSoftwareSigner, Signer, SignerError};
+use amp_rs::{Amount, AssetDistributionAssignment};
+use async_trait::async_trait;
+use httpmock::prelude::*;
+use serde_json::json;
+use std::sync::Arc;
+
+mod common;
+
+use common::{client, node_rpc, rpc_result};
+
+const ASSET_UUID: &str = "550e8400-e29b-41d4-a716-446655440000";
+const WALLET: &str = "test_wallet";
+
+/// Signer that rejects every transaction as invalid, which passes signer validation
+#[derive(Debug)]
+struct RejectingSigner;
+
+#[async_trait]
+impl Signer for RejectingSigner {
+    async fn sign_transaction(&self, _unsigned_tx: &str) -> Result<String, SignerError> {
+        Err(SignerError::InvalidTransaction("not signing".to_string()))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Checkpoint store that cannot list its checkpoints, like a store shared across hosts
+#[derive(Debug, Default)]
+struct UnlistableStore(InMemoryCheckpointStore);
+
+#[async_trait]
+impl CheckpointStore for UnlistableStore {
+    async fn load(
+        &self,
+        distribution_uuid: &str,
+    ) -> Result<Option<DistributionCheckpoint>, CheckpointError> {
+        self.0.load(distribution_uuid).await
+    }
+
+    async fn save(&self, checkpoint: &DistributionCheckpoint) -> Result<(), CheckpointError> {
+        self.0.save(checkpoint).await
+    }
+
+    async fn remove(&self, distribution_uuid: &str) -> Result<(), CheckpointError> {
+        self.0.remove(distribution_uuid).await
+    }
+
+    async fn list(&self) -> Result<Vec<DistributionCheckpoint>, CheckpointError> {
+        Err(CheckpointError::Storage(
+            "listing is not supported".to_string(),
+        ))
+    }
+}
+
+fn plan() -> BatchPlan {
+    let signer = LwkSoftwareSigner::new(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+    )
+    .unwrap();
+    let assignments = (0..5)
+        .map(|i| AssetDistributionAssignment {
+            user_id: format!("user{i}"),
+            address: signer.derive_address(Some(i)).unwrap(),
+            amount: Amount::from_sat(1000),
+        })
+        .collect();
+    BatchPlanner::new()
+        .with_max_outputs(3)
+        .with_fee_rate(FeeRate::from_sat_per_vb(1))
+        .plan(assignments)
+        .unwrap()
+}
+
+/// Mocks a wallet holding one L-BTC UTXO of `amount`
+fn mock_wallet(server: &MockServer, amount: Amount) {
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"method": "loadwallet"}"#);
+        then.status(200)
+            .json_body(rpc_result(json!({"name": WALLET, "warning": ""})));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path(format!("/wallet/{WALLET}"))
+            .json_body_partial(r#"{"method": "listunspent"}"#);
+        then.status(200).json_body(rpc_result(json!([{
+            "txid": "aa".repeat(32),
+            "vout": 0,
+            "amount": amount.to_string(),
+            "asset": LIQUID_TESTNET_POLICY_ASSET,
+            "address": "vjchange",
+            "spendable": true,
+            "confirmations": 10
+        }])));
+    });
+}
+
+/// Mocks a Liquid testnet node that is up and synced
+fn mock_node_status(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"method": "getnetworkinfo"}"#);
+        then.status(200).json_body(rpc_result(json!({
+            "version": 230_000,
+            "subversion": "/Elements Core:23.0.0/",
+            "protocolversion": 70016,
+            "localservices": "0000000000000409",
+            "localrelay": true,
+            "timeoffset": 0,
+            "networkactive": true,
+            "connections": 0,
+            "networks": [],
+            "relayfee": 0.000_001,
+            "incrementalfee": 0.000_001,
+            "localaddresses": [],
+            "warnings": ""
+        })));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"method": "getblockchaininfo"}"#);
+        then.status(200).json_body(rpc_result(json!({
+            "chain": "liquidtestnet",
+            "blocks": 1000,
+            "headers": 1000,
+            "bestblockhash": "bb".repeat(32)
+        })));
+    });
+}
+
+#[tokio::test]
+async fn test_batches_are_not_started_without_fee_funds() {
+    let server = MockServer::start();
+    mock_wallet(&server, Amount::from_sat(100));
+    let create = server.mock(|when, then| {
+        when.path(format!("/assets/{ASSET_UUID}/distributions/create"));
+        then.status(200);
+    });
+
+    let error = client(&server)
+        .distribute_asset_in_batches(
+            ASSET_UUID,
+            plan(),
+            &node_rpc(&server),
+            WALLET,
+            &RejectingSigner,
+            None,
+        )
+        .await
+        .unwrap_err();
+
+    assert!(error.to_string().contains("Insufficient L-BTC"), "{error}");
+    create.assert_hits(0);
+}
+
+#[tokio::test]
+async fn test_failed_batch_stops_remaining_batches() {
+    let server = MockServer::start();
+    mock_wallet(&server, Amount::from_sat(1_000_000));
+    mock_node_status(&server);
+    let create = server.mock(|when, then| {
+        when.path(format!("/assets/{ASSET_UUID}/distributions/create"));
+        then.status(400).body("another distribution is in progress");
+    });
+
+    let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+    let report = client(&server)
+        .distribute_asset_in_batches(
+            ASSET_UUID,
+            plan(),
+            &node_rpc(&server),
+            WALLET,
+            &RejectingSigner,
+            Some(progress_tx),
+        )
+        .await
+        .unwrap();
+
+    create.assert_hits(1);
+    assert!(!report.is_complete());
+    assert_eq!(report.results.len(), 2);
+    assert!(matches!(
+        report.results[0].status,
+        BatchStatus::Failed {
+            distribution_uuid: None,
+            ..
+        }
+    ));
+    assert_eq!(report.results[1].status, BatchStatus::Skipped);
+    assert_eq!(report.skipped_assignments().len(), 2);
+
+    let first = progress_rx.recv().await.unwrap();
+    assert!(matches!(
+        first,
+        amp_rs::ProgressUpdate::Batch {
+            current: 1,
+            total: 2,
+            assignments: 3
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_failed_batch_reports_its_distribution() {
+    let server = MockServer::start();
+    mock_wallet(&server, Amount::from_sat(1_000_000));
+    mock_node_status(&server);
+    let plan = plan();
+    let first_batch = &plan.batches()[0];
+    let map_address_amount: serde_json::Map<String, serde_json::Value> = first_batch
+        .assignments
+        .iter()
+        .map(|assignment| (assignment.address.clone(), json!(0.00001)))
+        .collect();
+    let create = server.mock(|when, then| {
+        when.path(format!("/assets/{ASSET_UUID}/distributions/create"));
+        then.status(200).json_body(json!({
+            "distribution_uuid": "batch-distribution",
+            "map_address_amount": map_address_amount,
+            "map_address_asset": {},
+            "asset_id": "6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d"
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path(format!("/wallet/{WALLET}"))
+            .json_body_partial(r#"{"method": "sendmany"}"#);
+        then.status(500).json_body(json!({
+            "result": null,
+            "error": {"code": -6, "message": "Insufficient funds"},
+            "id": "amp-client"
+        }));
+    });
+
+    let client = common::builder(&server)
+        .checkpoint_store(Arc::new(UnlistableStore::default()))
+        .build()
+        .await
+        .unwrap();
+    let report = client
+        .distribute_asset_in_batches(
+            ASSET_UUID,
+            plan,
+            &node_rpc(&server),
+            WALLET,
+            &RejectingSigner,
+            None,
+        )
+        .await
+        .unwrap();
+
+    create.assert_hits(1);
+    assert!(matches!(
+        &report.results[0].status,
+        BatchStatus::Failed {
+            distribution_uuid: Some(uuid),
+            ..
+        } if uuid == "batch-distribution"
+    ));
+    // The failed batch must be resumed, not distributed again
+    assert_eq!(report.failed().len(), 1);
+    assert_eq!(report.skipped_assignments().len(), 2);
+}
@@ -0,0 +1,106 @@
+use chrono::{TimeZone, Utc};
+use httpmock::prelude::*;
+use serde_json::json;
+
+mod common;
+
+use common::{client, node_rpc, rpc_result};
+
+const ASSET_UUID: &str = "550e8400-e29b-41d4-a716-446655440000";
+const GENESIS_TIME: i64 = 1_700_000_000;
+const TIP: i64 = 9;
+
+fn block_hash(height: i64) -> String {
+    format!("{height:02x}").repeat(32)
+}
+
+/// Mocks a chain of `TIP + 1` blocks, one per minute from `GENESIS_TIME`
+fn mock_chain(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"method": "getblockchaininfo"}"#);
+        then.status(200).json_body(rpc_result(json!({
+            "chain": "liquidtestnet",
+            "blocks": TIP,
+            "headers": TIP,
+            "bestblockhash": block_hash(TIP)
+        })));
+    });
+    for height in 0..=TIP {
+        server.mock(|when, then| {
+            when.method(POST).path("/").json_body_partial(
+                json!({"method": "getblockhash", "params": [height]}).to_string(),
+            );
+            then.status(200)
+                .json_body(rpc_result(json!(block_hash(height))));
+        });
+        server.mock(|when, then| {
+            when.method(POST).path("/").json_body_partial(
+                json!({"method": "getblockheader", "params": [block_hash(height), true]})
+                    .to_string(),
+            );
+            then.status(200).json_body(rpc_result(json!({
+                "hash": block_hash(height),
+                "height": height,
+                "confirmations": TIP - height + 1,
+                "time": GENESIS_TIME + 60 * height,
+                "mediantime": GENESIS_TIME + 60 * height
+            })));
+        });
+    }
+}
+
+#[tokio::test]
+async fn test_height_at_finds_last_block_before_datetime() {
+    let server = MockServer::start();
+    mock_chain(&server);
+    let rpc = node_rpc(&server);
+    let at = |seconds: i64| Utc.timestamp_opt(GENESIS_TIME + seconds, 0).unwrap();
+
+    assert_eq!(rpc.height_at(at(0)).await.unwrap(), 0);
+    assert_eq!(rpc.height_at(at(60 * 4)).await.unwrap(), 4);
+    assert_eq!(rpc.height_at(at(60 * 4 + 59)).await.unwrap(), 4);
+    assert_eq!(rpc.height_at(at(60 * 100)).await.unwrap(), TIP);
+    assert!(rpc.height_at(at(-1)).await.is_err());
+}
+
+#[tokio::test]
+async fn test_cap_table_at_datetime_uses_ownerships_at_height() {
+    let server = MockServer::start();
+    mock_chain(&server);
+    let ownerships = server.mock(|when, then| {
+        when.method(GET)
+            .path(format!("/assets/{ASSET_UUID}/ownerships/6"));
+        then.status(200).json_body(json!([
+            {"owner": "Alice", "amount": 700, "GAID": "GA1"},
+            {"owner": "Bob", "amount": 300, "GAID": "GA2"}
+        ]));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path(format!("/assets/{ASSET_UUID}/ownerships/2"));
+        then.status(200)
+            .json_body(json!([{"owner": "Alice", "amount": 1000, "GAID": "GA1"}]));
+    });
+
+    let client = client(&server);
+    let record_date = Utc.timestamp_opt(GENESIS_TIME + 60 * 6 + 30, 0).unwrap();
+
+    let table = client
+        .cap_table_at(ASSET_UUID, record_date, &node_rpc(&server))
+        .await
+        .unwrap();
+    ownerships.assert();
+    assert_eq!(table.height, 6);
+    assert_eq!(table.record_time, Some(record_date));
+    assert_eq!(table.total(), 1_000);
+    assert_eq!(table.entries[0].holder(), "GA1");
+
+    let earlier = client.cap_table_at_height(ASSET_UUID, 2).await.unwrap();
+    let diff = earlier.diff(&table);
+    assert_eq!(diff.entered.len(), 1);
+    assert_eq!(diff.entered[0].holder(), "GA2");
+    assert_eq!(diff.changed[0].delta(), -300);
+    assert!(diff.exited.is_empty());
+}
@@ -1,5 +1,7 @@
 use amp_rs::client::{RetryClient, RetryConfig, TokenError};
+use amp_rs::ApiClient;
 use httpmock::prelude::*;
+use secrecy::Secret;
 use serial_test::serial;
 use std::env;
 use std::time::Duration as StdDuration;
@@ -582,3 +584,291 @@ async fn test_retry_client_exhausted_retries() {
 
     always_fail_mock.assert_hits(2); // Should be called max_attempts times
 }
+
+#[tokio::test]
+async fn test_api_client_builder_uses_explicit_credentials_per_client() {
+    let server = MockServer::start();
+    let base_url = url::Url::parse(&server.base_url()).unwrap();
+
+    let token_a_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/user/obtain_token")
+            .json_body(serde_json::json!({"username": "issuer_a", "password": "secret_a"}));
+        then.status(200)
+            .json_body(serde_json::json!({"token": "token_a"}));
+    });
+    let token_b_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/user/obtain_token")
+            .json_body(serde_json::json!({"username": "issuer_b", "password": "secret_b"}));
+        then.status(200)
+            .json_body(serde_json::json!({"token": "token_b"}));
+    });
+
+    let client_a = ApiClient::builder()
+        .base_url(base_url.clone())
+        .credentials("issuer_a", Secret::new("secret_a".to_string()))
+        .retry_config(RetryConfig::for_tests())
+        .build()
+        .await
+        .unwrap();
+    let client_b = ApiClient::builder()
+        .base_url(base_url)
+        .credentials("issuer_b", Secret::new("secret_b".to_string()))
+        .retry_config(RetryConfig::for_tests())
+        .build()
+        .await
+        .unwrap();
+
+    assert_eq!(client_a.get_token().await.unwrap(), "token_a");
+    assert_eq!(client_b.get_token().await.unwrap(), "token_b");
+    token_a_mock.assert_hits(1);
+    token_b_mock.assert_hits(1);
+}
+
+#[tokio::test]
+async fn test_token_managers_are_shared_per_account_and_reset_independently() {
+    let server = MockServer::start();
+    let base_url = url::Url::parse(&server.base_url()).unwrap();
+
+    let mut token_a_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/user/obtain_token")
+            .json_body(serde_json::json!({"username": "shared_a", "password": "secret_a"}));
+        then.status(200)
+            .json_body(serde_json::json!({"token": "token_a"}));
+    });
+    let token_b_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/user/obtain_token")
+            .json_body(serde_json::json!({"username": "shared_b", "password": "secret_b"}));
+        then.status(200)
+            .json_body(serde_json::json!({"token": "token_b"}));
+    });
+
+    let build_client = |username: &'static str, password: &'static str| {
+        ApiClient::builder()
+            .base_url(base_url.clone())
+            .credentials(username, Secret::new(password.to_string()))
+            .retry_config(RetryConfig::for_tests())
+            .build()
+    };
+
+    let client_a1 = build_client("shared_a", "secret_a").await.unwrap();
+    let client_a2 = build_client("shared_a", "secret_a").await.unwrap();
+    let client_b = build_client("shared_b", "secret_b").await.unwrap();
+
+    assert_eq!(client_a1.get_token().await.unwrap(), "token_a");
+    assert_eq!(client_a2.get_token().await.unwrap(), "token_a");
+    assert_eq!(client_b.get_token().await.unwrap(), "token_b");
+    token_a_mock.assert_hits(1);
+    token_b_mock.assert_hits(1);
+
+    // Resetting account A must not touch account B's token
+    let key_a = amp_rs::TokenAccountKey::new(&base_url, "shared_a");
+    assert!(amp_rs::client::TokenManager::reset_account(&key_a).await);
+    token_a_mock.delete();
+    let token_a_renewed_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/user/obtain_token")
+            .json_body(serde_json::json!({"username": "shared_a", "password": "secret_a"}));
+        then.status(200)
+            .json_body(serde_json::json!({"token": "token_a_renewed"}));
+    });
+
+    let client_a3 = build_client("shared_a", "secret_a").await.unwrap();
+    assert_eq!(client_a3.get_token().await.unwrap(), "token_a_renewed");
+    assert_eq!(client_b.get_token().await.unwrap(), "token_b");
+    token_a_renewed_mock.assert_hits(1);
+    token_b_mock.assert_hits(1);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_persisted_tokens_are_isolated_per_account() {
+    use amp_rs::token_store::{FileTokenStore, TokenStore};
+    use amp_rs::TokenAccountKey;
+    use secrecy::ExposeSecret;
+
+    let server = MockServer::start();
+    let base_url = url::Url::parse(&server.base_url()).unwrap();
+    let key_a = TokenAccountKey::new(&base_url, "persisted_a");
+    let key_b = TokenAccountKey::new(&base_url, "persisted_b");
+    assert_ne!(key_a.token_file(), key_b.token_file());
+    for key in [&key_a, &key_b] {
+        let _ = std::fs::remove_file(key.token_file());
+    }
+
+    for (username, token) in [("persisted_a", "token_a"), ("persisted_b", "token_b")] {
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/user/obtain_token")
+                .json_body(serde_json::json!({"username": username, "password": "secret"}));
+            then.status(200)
+                .json_body(serde_json::json!({ "token": token }));
+        });
+    }
+
+    // Live environments persist tokens without an explicit store
+    env::set_var("AMP_TESTS", "live");
+    env::set_var("AMP_TOKEN_PERSISTENCE", "true");
+
+    let build_client = |username: &'static str| {
+        ApiClient::builder()
+            .base_url(base_url.clone())
+            .credentials(username, Secret::new("secret".to_string()))
+            .retry_config(RetryConfig::for_tests())
+            .build()
+    };
+    let client_a = build_client("persisted_a").await.unwrap();
+    let client_b = build_client("persisted_b").await.unwrap();
+    assert_eq!(client_a.get_token().await.unwrap(), "token_a");
+    assert_eq!(client_b.get_token().await.unwrap(), "token_b");
+
+    let stored_token = |key: &TokenAccountKey| {
+        let store = FileTokenStore::new(key.token_file());
+        async move {
+            store
+                .load()
+                .await
+                .unwrap()
+                .map(|data| data.token.expose_secret().clone())
+        }
+    };
+    assert_eq!(stored_token(&key_a).await.as_deref(), Some("token_a"));
+    assert_eq!(stored_token(&key_b).await.as_deref(), Some("token_b"));
+
+    // Resetting account A removes only A's persisted token
+    assert!(amp_rs::client::TokenManager::reset_account(&key_a).await);
+    assert_eq!(stored_token(&key_a).await, None);
+    assert_eq!(stored_token(&key_b).await.as_deref(), Some("token_b"));
+
+    env::remove_var("AMP_TESTS");
+    env::remove_var("AMP_TOKEN_PERSISTENCE");
+    assert!(amp_rs::client::TokenManager::reset_account(&key_b).await);
+    let _ = std::fs::remove_file(key_b.token_file());
+}
+
+#[tokio::test]
+async fn test_api_client_builder_sends_user_agent() {
+    let server = MockServer::start();
+
+    let assets_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/assets")
+            .header("user-agent", "amp-rs-tests/1.0")
+            .header("authorization", "token builder_token");
+        then.status(200).json_body(serde_json::json!([]));
+    });
+
+    let client = ApiClient::builder()
+        .base_url(url::Url::parse(&server.base_url()).unwrap())
+        .token_strategy(Box::new(amp_rs::client::MockTokenStrategy::new(
+            "builder_token".to_string(),
+        )))
+        .user_agent("amp-rs-tests/1.0")
+        .timeout(StdDuration::from_secs(5))
+        .build()
+        .await
+        .unwrap();
+
+    let assets = client.get_assets().await.unwrap();
+    assert!(assets.is_empty());
+    assets_mock.assert();
+}
+
+fn activity_json(vout: i64) -> serde_json::Value {
+    serde_json::json!({
+        "type": "transfer",
+        "datetime": "2024-01-01T00:00:00Z",
+        "description": "",
+        "txid": "abc",
+        "vout": vout,
+        "blockheight": 100,
+        "asset_blinder": "",
+        "amount_blinder": "",
+        "registered_user": null,
+        "amount": 10
+    })
+}
+
+#[tokio::test]
+async fn test_stream_asset_activities_fetches_all_pages() {
+    use futures::TryStreamExt;
+
+    let server = MockServer::start();
+    let page_mocks: Vec<_> = [(0, vec![0, 1]), (2, vec![2, 3]), (4, vec![4])]
+        .into_iter()
+        .map(|(start, vouts)| {
+            server.mock(|when, then| {
+                when.method(GET)
+                    .path("/assets/asset-uuid/activities")
+                    .json_body_partial(
+                        serde_json::json!({"start": start, "count": 2, "sortorder": "asc"})
+                            .to_string(),
+                    );
+                then.status(200).json_body(serde_json::Value::Array(
+                    vouts.into_iter().map(activity_json).collect(),
+                ));
+            })
+        })
+        .collect();
+
+    let client = ApiClient::with_mock_token(
+        url::Url::parse(&server.base_url()).unwrap(),
+        "stream_token".to_string(),
+    )
+    .unwrap();
+    let filter = amp_rs::model::AssetActivityParams {
+        count: Some(2),
+        sortorder: Some("asc".to_string()),
+        ..Default::default()
+    };
+
+    let activities: Vec<_> = client
+        .stream_asset_activities("asset-uuid", filter)
+        .try_collect()
+        .await
+        .unwrap();
+
+    let vouts: Vec<i64> = activities.iter().map(|activity| activity.vout).collect();
+    assert_eq!(vouts, vec![0, 1, 2, 3, 4]);
+    for page_mock in &page_mocks {
+        page_mock.assert_hits(1);
+    }
+}
+
+#[tokio::test]
+async fn test_stream_asset_transactions_retries_server_errors() {
+    use futures::StreamExt;
+
+    let server = MockServer::start();
+    let txs_mock = server.mock(|when, then| {
+        when.method(GET).path("/assets/asset-uuid/txs");
+        then.status(503).body("unavailable");
+    });
+
+    let client = ApiClient::builder()
+        .base_url(url::Url::parse(&server.base_url()).unwrap())
+        .token_strategy(Box::new(amp_rs::client::MockTokenStrategy::new(
+            "stream_token".to_string(),
+        )))
+        .retry_config(
+            RetryConfig::for_tests()
+                .with_max_attempts(3)
+                .with_base_delay_ms(1)
+                .with_max_delay_ms(10),
+        )
+        .build()
+        .await
+        .unwrap();
+
+    let mut transactions = std::pin::pin!(client.stream_asset_transactions(
+        "asset-uuid",
+        amp_rs::model::AssetTransactionParams::default()
+    ));
+
+    let first = transactions.next().await.unwrap();
+    assert!(first.unwrap_err().to_string().contains("503"));
+    txs_mock.assert_hits(3);
+}
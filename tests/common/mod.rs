@@ -0,0 +1,37 @@
+//! Helpers shared by the integration tests that run against `httpmock` servers
+
+// Each test crate compiles its own copy and uses only some of the helpers
+#![allow(dead_code)]
+
+use amp_rs::{ApiClient, ApiClientBuilder, ElementsRpc, Network};
+use httpmock::MockServer;
+use serde_json::json;
+
+/// Returns a client of the mock AMP API at `server` that authenticates with a fixed token
+pub fn client(server: &MockServer) -> ApiClient {
+    ApiClient::with_mock_token(base_url(server), "mock_token".to_string()).unwrap()
+}
+
+/// Returns a builder for clients of the mock AMP API that need more configuration
+pub fn builder(server: &MockServer) -> ApiClientBuilder {
+    ApiClient::builder()
+        .base_url(base_url(server))
+        .token_strategy(Box::new(amp_rs::client::MockTokenStrategy::new(
+            "mock_token".to_string(),
+        )))
+}
+
+/// Returns an Elements RPC client of the mock node at `server`, on Liquid testnet
+pub fn node_rpc(server: &MockServer) -> ElementsRpc {
+    ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string())
+        .with_network(Network::LiquidTestnet)
+}
+
+/// Wraps `result` in a successful Elements RPC response
+pub fn rpc_result(result: serde_json::Value) -> serde_json::Value {
+    json!({"result": result, "error": null, "id": "amp-client"})
+}
+
+fn base_url(server: &MockServer) -> url::Url {
+    url::Url::parse(&server.base_url()).unwrap()
+}
@@ -0,0 +1,598 @@
+use amp_rs::chain::{EsploraBackend, FundingAddresses};
+use amp_rs::confirmation::{ConfirmedOperation, ReorgMonitor};
+use amp_rs::distribution::{
+    CheckpointError, CheckpointStore, DistributionCheckpoint, DistributionState,
+    InMemoryCheckpointStore,
+};
+use amp_rs::signer::{KeyChain, Signer, SignerError};
+use amp_rs::{Amount, AmpError, ApiClient, LwkSoftwareSigner, Network};
+use async_trait::async_trait;
+use httpmock::prelude::*;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+mod common;
+
+use common::{node_rpc, rpc_result};
+
+const ASSET_UUID: &str = "550e8400-e29b-41d4-a716-446655440000";
+const ASSET_ID: &str = "6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d";
+const DISTRIBUTION_UUID: &str = "dist-0001";
+const WALLET: &str = "test_wallet";
+const TXID: &str = "d0c5e7a5f6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3";
+
+/// Signer that must not be called when resuming past the signing step
+#[derive(Debug)]
+struct UnusedSigner;
+
+#[async_trait]
+impl Signer for UnusedSigner {
+    async fn sign_transaction(&self, _unsigned_tx: &str) -> Result<String, SignerError> {
+        panic!("the transaction is already signed");
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Store that fails to record any state after `Sending` while `failing` is set
+#[derive(Debug, Default)]
+struct SendFailingStore {
+    inner: InMemoryCheckpointStore,
+    failing: AtomicBool,
+}
+
+#[async_trait]
+impl CheckpointStore for SendFailingStore {
+    async fn load(
+        &self,
+        distribution_uuid: &str,
+    ) -> Result<Option<DistributionCheckpoint>, CheckpointError> {
+        self.inner.load(distribution_uuid).await
+    }
+
+    async fn save(&self, checkpoint: &DistributionCheckpoint) -> Result<(), CheckpointError> {
+        let sent = !matches!(
+            checkpoint.state,
+            DistributionState::Created | DistributionState::Sending { .. }
+        );
+        if sent && self.failing.load(Ordering::SeqCst) {
+            return Err(CheckpointError::Storage("disk full".to_string()));
+        }
+        self.inner.save(checkpoint).await
+    }
+
+    async fn remove(&self, distribution_uuid: &str) -> Result<(), CheckpointError> {
+        self.inner.remove(distribution_uuid).await
+    }
+
+    async fn list(&self) -> Result<Vec<DistributionCheckpoint>, CheckpointError> {
+        self.inner.list().await
+    }
+}
+
+fn checkpoint(state: DistributionState) -> DistributionCheckpoint {
+    let mut map_address_amount = HashMap::new();
+    map_address_amount.insert("address1".to_string(), Amount::from_sat(100_000));
+    let mut checkpoint = DistributionCheckpoint::new(
+        DISTRIBUTION_UUID.to_string(),
+        ASSET_UUID.to_string(),
+        ASSET_ID.to_string(),
+        WALLET.to_string(),
+        map_address_amount,
+    );
+    checkpoint.state = state;
+    checkpoint
+}
+
+async fn client(server: &MockServer, store: Arc<InMemoryCheckpointStore>) -> ApiClient {
+    common::builder(server)
+        .checkpoint_store(store)
+        .build()
+        .await
+        .unwrap()
+}
+
+/// Mocks the node calls made after broadcast: confirmation polling and change collection
+fn mock_confirmed_transaction(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"method": "loadwallet"}"#);
+        then.status(200)
+            .json_body(rpc_result(json!({"name": WALLET, "warning": ""})));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path(format!("/wallet/{WALLET}"))
+            .json_body_partial(r#"{"method": "gettransaction"}"#);
+        then.status(200).json_body(rpc_result(json!({
+            "txid": TXID,
+            "confirmations": 2,
+            "blockheight": 1000,
+            "hex": "00",
+            "details": []
+        })));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path(format!("/wallet/{WALLET}"))
+            .json_body_partial(r#"{"method": "listunspent"}"#);
+        then.status(200).json_body(rpc_result(json!([])));
+    });
+}
+
+fn mock_confirm_distribution(server: &MockServer) -> httpmock::Mock<'_> {
+    server.mock(|when, then| {
+        when.method(POST).path(format!(
+            "/assets/{ASSET_UUID}/distributions/{DISTRIBUTION_UUID}/confirm"
+        ));
+        then.status(200);
+    })
+}
+
+#[tokio::test]
+async fn test_resume_distribution_from_broadcast_confirms_once() {
+    let server = MockServer::start();
+    mock_confirmed_transaction(&server);
+    let confirm = mock_confirm_distribution(&server);
+
+    let store = Arc::new(InMemoryCheckpointStore::new());
+    store
+        .save(&checkpoint(DistributionState::Broadcast {
+            txid: TXID.to_string(),
+        }))
+        .await
+        .unwrap();
+    let client = client(&server, store.clone()).await;
+    let node_rpc = node_rpc(&server);
+
+    let txid = client
+        .resume_distribution(DISTRIBUTION_UUID, &node_rpc, &UnusedSigner)
+        .await
+        .unwrap();
+    assert_eq!(txid, TXID);
+    confirm.assert_hits(1);
+
+    let saved = store.load(DISTRIBUTION_UUID).await.unwrap().unwrap();
+    assert!(saved.state.is_complete());
+
+    // A confirmed distribution is not confirmed again
+    let txid = client
+        .resume_distribution(DISTRIBUTION_UUID, &node_rpc, &UnusedSigner)
+        .await
+        .unwrap();
+    assert_eq!(txid, TXID);
+    confirm.assert_hits(1);
+}
+
+#[tokio::test]
+async fn test_resume_distribution_from_created_sends_through_node_wallet() {
+    let server = MockServer::start();
+    mock_confirmed_transaction(&server);
+    let confirm = mock_confirm_distribution(&server);
+    let sendmany = server.mock(|when, then| {
+        when.method(POST)
+            .path(format!("/wallet/{WALLET}"))
+            .json_body_partial(r#"{"method": "sendmany"}"#);
+        then.status(200).json_body(rpc_result(json!(TXID)));
+    });
+    let send_raw = server.mock(|when, then| {
+        when.method(POST)
+            .json_body_partial(r#"{"method": "sendrawtransaction"}"#);
+        then.status(500);
+    });
+
+    let store = Arc::new(InMemoryCheckpointStore::new());
+    store
+        .save(&checkpoint(DistributionState::Created))
+        .await
+        .unwrap();
+    let client = client(&server, store.clone()).await;
+    let node_rpc = node_rpc(&server);
+
+    // The node wallet funds and signs, so the signer is never asked to sign
+    let txid = client
+        .resume_distribution(DISTRIBUTION_UUID, &node_rpc, &UnusedSigner)
+        .await
+        .unwrap();
+    assert_eq!(txid, TXID);
+    sendmany.assert_hits(1);
+    send_raw.assert_hits(0);
+    confirm.assert_hits(1);
+
+    let saved = store.load(DISTRIBUTION_UUID).await.unwrap().unwrap();
+    assert!(saved.state.is_complete());
+}
+
+#[tokio::test]
+async fn test_resume_distribution_after_sendmany_finds_the_sent_transaction() {
+    let server = MockServer::start();
+    mock_confirmed_transaction(&server);
+    let confirm = mock_confirm_distribution(&server);
+    let sendmany = server.mock(|when, then| {
+        when.method(POST)
+            .path(format!("/wallet/{WALLET}"))
+            .json_body_partial(r#"{"method": "sendmany"}"#);
+        then.status(200).json_body(rpc_result(json!(TXID)));
+    });
+    let now = chrono::Utc::now().timestamp();
+    let list_transactions = server.mock(|when, then| {
+        when.method(POST)
+            .path(format!("/wallet/{WALLET}"))
+            .json_body_partial(r#"{"method": "listtransactions"}"#);
+        then.status(200).json_body(rpc_result(json!([
+            {
+                "txid": "aa".repeat(32),
+                "address": "address1",
+                "category": "send",
+                "amount": -0.001,
+                "asset": ASSET_ID,
+                "time": now
+            },
+            {
+                "txid": TXID,
+                "address": "address1",
+                "category": "send",
+                "amount": -0.001,
+                "asset": ASSET_ID,
+                "comment": "AMP asset distribution",
+                "time": now
+            }
+        ])));
+    });
+
+    let store = Arc::new(SendFailingStore::default());
+    store
+        .save(&checkpoint(DistributionState::Created))
+        .await
+        .unwrap();
+    store.failing.store(true, Ordering::SeqCst);
+    let client = common::builder(&server)
+        .checkpoint_store(store.clone())
+        .build()
+        .await
+        .unwrap();
+    let node_rpc = node_rpc(&server);
+
+    // The node sends the transaction, but recording it fails
+    let err = client
+        .resume_distribution(DISTRIBUTION_UUID, &node_rpc, &UnusedSigner)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("disk full"));
+    sendmany.assert_hits(1);
+    let saved = store.load(DISTRIBUTION_UUID).await.unwrap().unwrap();
+    assert_eq!(saved.state.name(), "sending");
+
+    // The resume finds the sent transaction instead of sending the distribution again
+    store.failing.store(false, Ordering::SeqCst);
+    let txid = client
+        .resume_distribution(DISTRIBUTION_UUID, &node_rpc, &UnusedSigner)
+        .await
+        .unwrap();
+    assert_eq!(txid, TXID);
+    sendmany.assert_hits(1);
+    list_transactions.assert_hits(1);
+    confirm.assert_hits(1);
+    assert!(store
+        .load(DISTRIBUTION_UUID)
+        .await
+        .unwrap()
+        .unwrap()
+        .state
+        .is_complete());
+}
+
+#[tokio::test]
+async fn test_resume_distribution_from_signed_skips_known_transaction() {
+    let server = MockServer::start();
+    let broadcast = server.mock(|when, then| {
+        when.method(POST)
+            .json_body_partial(r#"{"method": "sendrawtransaction"}"#);
+        then.status(200).json_body(json!({
+            "result": null,
+            "error": {"code": -27, "message": "Transaction already in block chain"},
+            "id": "amp-client"
+        }));
+    });
+    mock_confirmed_transaction(&server);
+    let confirm = mock_confirm_distribution(&server);
+
+    let store = Arc::new(InMemoryCheckpointStore::new());
+    store
+        .save(&checkpoint(DistributionState::Signed {
+            signed_tx: "0200".to_string(),
+            txid: TXID.to_string(),
+        }))
+        .await
+        .unwrap();
+    let client = client(&server, store.clone()).await;
+    let node_rpc = node_rpc(&server);
+
+    let txid = client
+        .resume_distribution(DISTRIBUTION_UUID, &node_rpc, &UnusedSigner)
+        .await
+        .unwrap();
+    assert_eq!(txid, TXID);
+    broadcast.assert_hits(1);
+    confirm.assert_hits(1);
+    assert!(store
+        .load(DISTRIBUTION_UUID)
+        .await
+        .unwrap()
+        .unwrap()
+        .state
+        .is_complete());
+}
+
+#[tokio::test]
+async fn test_resume_distribution_broadcasts_through_chain_backend() {
+    let server = MockServer::start();
+    let esplora_server = MockServer::start();
+    let node_broadcast = server.mock(|when, then| {
+        when.method(POST)
+            .json_body_partial(r#"{"method": "sendrawtransaction"}"#);
+        then.status(500);
+    });
+    let esplora_broadcast = esplora_server.mock(|when, then| {
+        when.method(POST).path("/tx").body("0200");
+        then.status(200).body(TXID);
+    });
+    esplora_server.mock(|when, then| {
+        when.method(GET).path(format!("/tx/{TXID}/status"));
+        then.status(200)
+            .json_body(json!({"confirmed": true, "block_height": 1000}));
+    });
+    esplora_server.mock(|when, then| {
+        when.method(GET).path(format!("/tx/{TXID}/hex"));
+        then.status(200).body("0200");
+    });
+    esplora_server.mock(|when, then| {
+        when.method(GET).path("/blocks/tip/height");
+        then.status(200).body("1001");
+    });
+    mock_confirmed_transaction(&server);
+    let confirm = mock_confirm_distribution(&server);
+
+    let store = Arc::new(InMemoryCheckpointStore::new());
+    store
+        .save(&checkpoint(DistributionState::Signed {
+            signed_tx: "0200".to_string(),
+            txid: TXID.to_string(),
+        }))
+        .await
+        .unwrap();
+    let client = common::builder(&server)
+        .checkpoint_store(store.clone())
+        .chain_backend(Arc::new(EsploraBackend::new(esplora_server.base_url())))
+        .build()
+        .await
+        .unwrap();
+    assert!(client.chain_backend().is_some());
+    let node_rpc = node_rpc(&server);
+
+    let txid = client
+        .resume_distribution(DISTRIBUTION_UUID, &node_rpc, &UnusedSigner)
+        .await
+        .unwrap();
+    assert_eq!(txid, TXID);
+    esplora_broadcast.assert_hits(1);
+    node_broadcast.assert_hits(0);
+    confirm.assert_hits(1);
+}
+
+#[tokio::test]
+async fn test_distribution_from_funding_addresses_never_uses_the_node_wallet() {
+    let signer = LwkSoftwareSigner::new(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+    )
+    .unwrap()
+    .with_account(0)
+    .unwrap();
+    let funding_address = signer
+        .derive_address_on_chain(KeyChain::External, 0)
+        .unwrap();
+    let change_address = signer
+        .derive_address_on_chain(KeyChain::Internal, 0)
+        .unwrap();
+    let recipient = signer
+        .derive_address_on_chain(KeyChain::External, 1)
+        .unwrap();
+
+    let server = MockServer::start();
+    let esplora_server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"method": "estimatesmartfee"}"#);
+        then.status(200).json_body(rpc_result(
+            json!({"errors": ["Insufficient data"], "blocks": 0}),
+        ));
+    });
+    let node_wallet = server.mock(|when, then| {
+        when.method(POST).matches(|request| {
+            let body = String::from_utf8_lossy(request.body.as_deref().unwrap_or_default());
+            [
+                "sendmany",
+                "listunspent",
+                "createrawtransaction",
+                "sendrawtransaction",
+            ]
+            .iter()
+            .any(|method| body.contains(&format!(r#""method":"{method}""#)))
+        });
+        then.status(500);
+    });
+    mock_confirmed_transaction(&server);
+    let confirm = mock_confirm_distribution(&server);
+
+    esplora_server.mock(|when, then| {
+        when.method(GET).path("/blocks/tip/height");
+        then.status(200).body("1001");
+    });
+    let utxos = esplora_server.mock(|when, then| {
+        when.method(GET)
+            .path(format!("/address/{funding_address}/utxo"));
+        then.status(200).json_body(json!([
+            {
+                "txid": "aa".repeat(32),
+                "vout": 0,
+                "status": {"confirmed": true, "block_height": 990},
+                "value": 300_000,
+                "asset": ASSET_ID
+            },
+            {
+                "txid": "bb".repeat(32),
+                "vout": 1,
+                "status": {"confirmed": true, "block_height": 990},
+                "value": 50_000,
+                "asset": Network::LiquidTestnet.policy_asset()
+            }
+        ]));
+    });
+    let esplora_broadcast = esplora_server.mock(|when, then| {
+        when.method(POST).path("/tx");
+        then.status(200).body(TXID);
+    });
+    esplora_server.mock(|when, then| {
+        when.method(GET)
+            .path_matches(Regex::new("^/tx/[0-9a-f]{64}/status$").unwrap());
+        then.status(200)
+            .json_body(json!({"confirmed": true, "block_height": 1000}));
+    });
+    esplora_server.mock(|when, then| {
+        when.method(GET)
+            .path_matches(Regex::new("^/tx/[0-9a-f]{64}/hex$").unwrap());
+        then.status(200).body("0200");
+    });
+
+    let store = Arc::new(InMemoryCheckpointStore::new());
+    let mut map_address_amount = HashMap::new();
+    map_address_amount.insert(recipient, Amount::from_sat(100_000));
+    store
+        .save(&DistributionCheckpoint::new(
+            DISTRIBUTION_UUID.to_string(),
+            ASSET_UUID.to_string(),
+            ASSET_ID.to_string(),
+            WALLET.to_string(),
+            map_address_amount,
+        ))
+        .await
+        .unwrap();
+    let client = common::builder(&server)
+        .checkpoint_store(store.clone())
+        .chain_backend(Arc::new(EsploraBackend::new(esplora_server.base_url())))
+        .funding_addresses(FundingAddresses::new(vec![funding_address], change_address))
+        .build()
+        .await
+        .unwrap();
+    let node_rpc = node_rpc(&server);
+
+    let txid = client
+        .resume_distribution(DISTRIBUTION_UUID, &node_rpc, &signer)
+        .await
+        .unwrap();
+    assert_eq!(txid.len(), 64);
+    utxos.assert_hits(2);
+    esplora_broadcast.assert_hits(1);
+    node_wallet.assert_hits(0);
+    confirm.assert_hits(1);
+}
+
+#[tokio::test]
+async fn test_confirmed_distribution_reports_reorg_events() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"method": "loadwallet"}"#);
+        then.status(200)
+            .json_body(rpc_result(json!({"name": WALLET, "warning": ""})));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path(format!("/wallet/{WALLET}"))
+            .json_body_partial(r#"{"method": "listunspent"}"#);
+        then.status(200).json_body(rpc_result(json!([])));
+    });
+    let wallet_transaction = |blockhash: &str, confirmations: u32| {
+        server.mock(|when, then| {
+            when.method(POST)
+                .path(format!("/wallet/{WALLET}"))
+                .json_body_partial(r#"{"method": "gettransaction"}"#);
+            then.status(200).json_body(rpc_result(json!({
+                "txid": TXID,
+                "confirmations": confirmations,
+                "blockhash": blockhash,
+                "hex": "00",
+                "details": []
+            })));
+        })
+    };
+    let mut original = wallet_transaction("aa", 2);
+    mock_confirm_distribution(&server);
+
+    let store = Arc::new(InMemoryCheckpointStore::new());
+    store
+        .save(&checkpoint(DistributionState::Broadcast {
+            txid: TXID.to_string(),
+        }))
+        .await
+        .unwrap();
+    let (events_tx, mut events_rx) = tokio::sync::mpsc::unbounded_channel();
+    let client = common::builder(&server)
+        .checkpoint_store(store)
+        .reorg_monitor(
+            ReorgMonitor::new(3)
+                .with_poll_interval(std::time::Duration::from_millis(10))
+                .with_events(events_tx),
+        )
+        .build()
+        .await
+        .unwrap();
+    let node_rpc = node_rpc(&server);
+
+    client
+        .resume_distribution(DISTRIBUTION_UUID, &node_rpc, &UnusedSigner)
+        .await
+        .unwrap();
+
+    // The confirmed transaction is re-mined in another block
+    original.delete();
+    wallet_transaction("bb", 3);
+
+    let event = tokio::time::timeout(std::time::Duration::from_secs(5), events_rx.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        event.operation,
+        ConfirmedOperation::Distribution {
+            asset_uuid: ASSET_UUID.to_string(),
+            distribution_uuid: DISTRIBUTION_UUID.to_string(),
+        }
+    );
+    assert_eq!(event.txid, TXID);
+    assert_eq!(event.previous_blockhash, "aa");
+    assert_eq!(event.current_blockhash.as_deref(), Some("bb"));
+    assert!(!event.rebroadcast);
+}
+
+#[tokio::test]
+async fn test_resume_distribution_without_checkpoint_fails() {
+    let server = MockServer::start();
+    let client = client(&server, Arc::new(InMemoryCheckpointStore::new())).await;
+    let node_rpc = node_rpc(&server);
+
+    let err = client
+        .resume_distribution("unknown-distribution", &node_rpc, &UnusedSigner)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AmpError::Validation(_)));
+    assert!(err.to_string().contains("No checkpoint found"));
+    assert!(err.to_string().contains("persistent checkpoint store"));
+}
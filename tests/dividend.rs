@@ -0,0 +1,134 @@
+use amp_rs::dividend::{DividendCalculator, ExclusionReason};
+use amp_rs::Amount;
+use httpmock::prelude::*;
+use serde_json::json;
+
+mod common;
+
+use common::client;
+
+const SOURCE_UUID: &str = "550e8400-e29b-41d4-a716-446655440000";
+const TARGET_UUID: &str = "660e8400-e29b-41d4-a716-446655440000";
+
+fn registered_user(id: i64, gaid: &str) -> serde_json::Value {
+    json!({
+        "id": id,
+        "GAID": gaid,
+        "is_company": false,
+        "name": format!("User {id}"),
+        "categories": [],
+        "creator": 1
+    })
+}
+
+fn assignment(id: i64, user: i64, amount: i64) -> serde_json::Value {
+    json!({
+        "id": id,
+        "registered_user": user,
+        "amount": amount,
+        "receiving_address": null,
+        "distribution_uuid": null,
+        "ready_for_distribution": true,
+        "vesting_datetime": null,
+        "vesting_timestamp": null,
+        "has_vested": true,
+        "is_distributed": false,
+        "creator": 1,
+        "GAID": null
+    })
+}
+
+#[tokio::test]
+async fn test_dividend_assignments_follow_source_holdings() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET)
+            .path(format!("/assets/{SOURCE_UUID}/ownerships/1200"));
+        then.status(200).json_body(json!([
+            {"owner": "Alice", "amount": 1, "GAID": "GA1"},
+            {"owner": "Bob", "amount": 1, "GAID": "GA2"},
+            {"owner": "Alice", "amount": 1, "GAID": "GA3"},
+            {"owner": "Carol", "amount": 5, "GAID": "GA4"},
+            {"owner": "Issuer", "amount": 92, "GAID": null}
+        ]));
+    });
+    // GA3 is a second GAID of user 1, so it is not the GAID listed for the user
+    for (gaid, user) in [("GA1", 1), ("GA2", 2), ("GA3", 1)] {
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/gaids/{gaid}/registered_user"));
+            then.status(200)
+                .json_body(registered_user(user, &format!("GA{user}")));
+        });
+    }
+    server.mock(|when, then| {
+        when.method(GET).path("/gaids/GA4/registered_user");
+        then.status(404).body("Not found");
+    });
+    let create: Vec<_> = [(10, 1, 667), (11, 2, 333)]
+        .into_iter()
+        .map(|(id, user, amount)| {
+            server.mock(|when, then| {
+                when.method(POST)
+                    .path(format!("/assets/{TARGET_UUID}/assignments/create"))
+                    .json_body(json!({"assignments": [
+                        {"registered_user": user, "amount": amount, "ready_for_distribution": true}
+                    ]}));
+                then.status(200)
+                    .json_body(json!([assignment(id, user, amount)]));
+            })
+        })
+        .collect();
+
+    let client = client(&server);
+    let calculator =
+        DividendCalculator::new(Amount::from_sat(1_000)).with_ready_for_distribution(true);
+
+    let plan = client
+        .plan_dividend(SOURCE_UUID, 1_200, &calculator)
+        .await
+        .unwrap();
+    assert_eq!(plan.record_height, 1_200);
+    assert_eq!(plan.allocated(), Amount::from_sat(1_000));
+    assert_eq!(plan.excluded.len(), 2);
+    assert!(plan
+        .excluded
+        .iter()
+        .all(|holder| holder.reason == ExclusionReason::NotRegistered));
+    assert!(plan.excluded.iter().any(|holder| holder.holder == "GA4"));
+
+    let assignments = client
+        .create_dividend_assignments(TARGET_UUID, &plan)
+        .await
+        .unwrap();
+    for mock in &create {
+        mock.assert();
+    }
+    assert_eq!(assignments.len(), 2);
+}
+
+#[tokio::test]
+async fn test_failed_gaid_lookup_fails_the_plan() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET)
+            .path(format!("/assets/{SOURCE_UUID}/ownerships/1200"));
+        then.status(200)
+            .json_body(json!([{"owner": "Alice", "amount": 1, "GAID": "GA1"}]));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/gaids/GA1/registered_user");
+        then.status(500).body("internal error");
+    });
+
+    // Only a GAID AMP does not know is excluded as unregistered; other errors abort
+    let error = client(&server)
+        .plan_dividend(
+            SOURCE_UUID,
+            1_200,
+            &DividendCalculator::new(Amount::from_sat(1_000)),
+        )
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("internal error"), "{error}");
+}
@@ -0,0 +1,333 @@
+use amp_rs::coin_selection::BranchAndBound;
+use amp_rs::distribution::DistributionFunding;
+use amp_rs::dry_run::{DryRunOperation, OutputKind};
+use amp_rs::fee::FeeRate;
+use amp_rs::network::LIQUID_TESTNET_POLICY_ASSET;
+use amp_rs::{Amount, AssetDistributionAssignment, LwkSoftwareSigner};
+use httpmock::prelude::*;
+use serde_json::json;
+use std::sync::Arc;
+
+mod common;
+
+use common::{client, node_rpc, rpc_result};
+
+const ASSET_UUID: &str = "550e8400-e29b-41d4-a716-446655440000";
+const ASSET_ID: &str = "6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d";
+const TOKEN_ID: &str = "1f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d";
+const WALLET: &str = "test_wallet";
+const ADDRESS: &str =
+    "vjTwqhz69nh7xHhtsHnx7mezsJV95EYHPqxshuoVXEMS5sqVzok57YVWYKDLcanqdSq54oTNhNM1NuTB";
+
+fn utxo(txid_byte: &str, asset: &str, amount: &str) -> serde_json::Value {
+    json!({
+        "txid": txid_byte.repeat(32),
+        "vout": 0,
+        "amount": amount,
+        "asset": asset,
+        "address": "vjwallet",
+        "spendable": true,
+        "confirmations": 10
+    })
+}
+
+/// Mocks the AMP asset, its assignments and a node wallet holding the asset, its
+/// reissuance token and L-BTC
+fn mock_environment(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(GET).path(format!("/assets/{ASSET_UUID}"));
+        then.status(200).json_body(json!({
+            "name": "Test Asset",
+            "asset_uuid": ASSET_UUID,
+            "issuer": 1,
+            "asset_id": ASSET_ID,
+            "reissuance_token_id": TOKEN_ID,
+            "requirements": [],
+            "ticker": "TST",
+            "precision": 8,
+            "domain": null,
+            "pubkey": null,
+            "is_registered": true,
+            "is_authorized": true,
+            "is_locked": false,
+            "issuer_authorization_endpoint": null,
+            "transfer_restricted": true
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path(format!("/assets/{ASSET_UUID}/assignments"));
+        then.status(200).json_body(json!([{
+            "id": 7,
+            "registered_user": 1,
+            "amount": 100,
+            "receiving_address": null,
+            "distribution_uuid": "open-distribution",
+            "ready_for_distribution": true,
+            "vesting_datetime": null,
+            "vesting_timestamp": null,
+            "has_vested": true,
+            "is_distributed": false,
+            "creator": 1,
+            "GAID": null
+        }]));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"method": "getnetworkinfo"}"#);
+        then.status(200).json_body(rpc_result(json!({
+            "version": 230_000,
+            "subversion": "/Elements Core:23.0.0/",
+            "protocolversion": 70016,
+            "localservices": "0000000000000409",
+            "localrelay": true,
+            "timeoffset": 0,
+            "networkactive": true,
+            "connections": 0,
+            "networks": [],
+            "relayfee": 0.000_001,
+            "incrementalfee": 0.000_001,
+            "localaddresses": [],
+            "warnings": ""
+        })));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"method": "getblockchaininfo"}"#);
+        then.status(200).json_body(rpc_result(json!({
+            "chain": "liquidtestnet",
+            "blocks": 1000,
+            "headers": 1000,
+            "bestblockhash": "bb".repeat(32)
+        })));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"method": "estimatesmartfee"}"#);
+        then.status(200).json_body(rpc_result(
+            json!({"errors": ["Insufficient data"], "blocks": 0}),
+        ));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"method": "loadwallet"}"#);
+        then.status(200)
+            .json_body(rpc_result(json!({"name": WALLET, "warning": ""})));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path(format!("/wallet/{WALLET}"))
+            .json_body_partial(r#"{"method": "listunspent"}"#);
+        then.status(200).json_body(rpc_result(json!([
+            utxo("aa", ASSET_ID, "5.00000000"),
+            utxo("bb", TOKEN_ID, "1.00000000"),
+            utxo("cc", LIQUID_TESTNET_POLICY_ASSET, "0.01000000"),
+        ])));
+    });
+}
+
+#[tokio::test]
+async fn test_distribution_dry_run_plans_without_side_effects() {
+    let server = MockServer::start();
+    mock_environment(&server);
+    let create = server.mock(|when, then| {
+        when.path(format!("/assets/{ASSET_UUID}/distributions/create"));
+        then.status(200);
+    });
+    let state_changing_rpc = server.mock(|when, then| {
+        when.method(POST).matches(|request| {
+            let body = String::from_utf8_lossy(request.body.as_deref().unwrap_or_default());
+            [
+                "getnewaddress",
+                "importaddress",
+                "createrawtransaction",
+                "sendrawtransaction",
+                "sendmany",
+            ]
+            .iter()
+            .any(|method| body.contains(method))
+        });
+        then.status(500);
+    });
+
+    let assignments = vec![
+        AssetDistributionAssignment {
+            user_id: "user1".to_string(),
+            address: ADDRESS.to_string(),
+            amount: Amount::from_btc_str("1").unwrap(),
+        },
+        AssetDistributionAssignment {
+            user_id: "user2".to_string(),
+            address: ADDRESS.to_string(),
+            amount: Amount::from_btc_str("0.5").unwrap(),
+        },
+    ];
+    let plan = client(&server)
+        .distribute_asset_dry_run(ASSET_UUID, &assignments, &node_rpc(&server), WALLET)
+        .await
+        .unwrap();
+
+    create.assert_hits(0);
+    state_changing_rpc.assert_hits(0);
+
+    assert_eq!(plan.operation, DryRunOperation::Distribution);
+    assert_eq!(plan.asset_id, ASSET_ID);
+    assert_eq!(plan.amount, Amount::from_btc_str("1.5").unwrap());
+    assert_eq!(plan.inputs.len(), 2);
+    assert_eq!(plan.fee_rate, FeeRate::MIN_RELAY);
+
+    let recipients: Vec<_> = plan
+        .outputs
+        .iter()
+        .filter(|output| output.kind == OutputKind::Recipient)
+        .collect();
+    assert_eq!(
+        recipients.len(),
+        1,
+        "assignments to one address share an output"
+    );
+    assert_eq!(recipients[0].amount, Amount::from_btc_str("1.5").unwrap());
+    assert_eq!(
+        plan.change_in(ASSET_ID),
+        Amount::from_btc_str("3.5").unwrap()
+    );
+    assert_eq!(
+        plan.change_in(LIQUID_TESTNET_POLICY_ASSET) + plan.fee,
+        Amount::from_btc_str("0.01").unwrap()
+    );
+
+    let paths: Vec<&str> = plan.amp_calls.iter().map(|c| c.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        vec![
+            format!("/assets/{ASSET_UUID}/distributions/create"),
+            format!("/assets/{ASSET_UUID}/distributions/{{distribution_uuid}}/confirm"),
+        ]
+    );
+    assert!(plan.node_calls.iter().any(|call| call.method == "sendmany"));
+    assert!(plan.estimated, "sendmany selects its own inputs");
+    assert_eq!(plan.warnings.len(), 1);
+    assert!(plan.warnings[0].contains("open-distribution"));
+}
+
+#[tokio::test]
+async fn test_reissue_and_burn_dry_runs() {
+    let server = MockServer::start();
+    mock_environment(&server);
+    let requests = server.mock(|when, then| {
+        when.method(POST).path_contains("-request");
+        then.status(200);
+    });
+    let client = client(&server);
+    let node_rpc = node_rpc(&server);
+
+    let reissue = client
+        .reissue_asset_dry_run(ASSET_UUID, 1_000, &node_rpc, WALLET)
+        .await
+        .unwrap();
+    assert_eq!(reissue.operation, DryRunOperation::Reissuance);
+    assert_eq!(reissue.inputs[0].asset, TOKEN_ID);
+    assert_eq!(reissue.outputs[0].kind, OutputKind::Reissuance);
+    assert_eq!(reissue.outputs[0].amount, Amount::from_sat(1_000));
+    assert_eq!(
+        reissue.change_in(TOKEN_ID),
+        Amount::from_btc_str("1").unwrap()
+    );
+    assert_eq!(reissue.node_calls[0].method, "reissueasset");
+
+    let burn = client
+        .burn_asset_dry_run(ASSET_UUID, 100_000_000, &node_rpc, WALLET)
+        .await
+        .unwrap();
+    assert_eq!(burn.operation, DryRunOperation::Burn);
+    assert_eq!(burn.outputs[0].kind, OutputKind::Burn);
+    assert_eq!(burn.change_in(ASSET_ID), Amount::from_btc_str("4").unwrap());
+    assert_eq!(burn.node_calls[0].method, "destroyamount");
+
+    assert!(client
+        .burn_asset_dry_run(ASSET_UUID, 0, &node_rpc, WALLET)
+        .await
+        .is_err());
+    requests.assert_hits(0);
+}
+
+#[tokio::test]
+async fn test_distribution_rejects_wrong_network_address_before_creating() {
+    let server = MockServer::start();
+    mock_environment(&server);
+    let create = server.mock(|when, then| {
+        when.path(format!("/assets/{ASSET_UUID}/distributions/create"));
+        then.status(200);
+    });
+
+    // A Liquid mainnet address on a Liquid testnet node
+    let secp = elements::secp256k1_zkp::Secp256k1::new();
+    let public_key = elements::secp256k1_zkp::SecretKey::from_slice(&[1u8; 32])
+        .unwrap()
+        .public_key(&secp);
+    let mainnet_address = elements::Address::p2wpkh(
+        &elements::bitcoin::PublicKey::new(public_key),
+        Some(public_key),
+        &elements::AddressParams::LIQUID,
+    );
+    let assignments = vec![AssetDistributionAssignment {
+        user_id: "user1".to_string(),
+        address: mainnet_address.to_string(),
+        amount: Amount::from_btc_str("1").unwrap(),
+    }];
+    let (_, signer) = LwkSoftwareSigner::generate_new().unwrap();
+
+    let error = client(&server)
+        .distribute_asset(ASSET_UUID, assignments, &node_rpc(&server), WALLET, &signer)
+        .await
+        .unwrap_err();
+
+    assert!(error.to_string().contains("Assignment address validation"));
+    create.assert_hits(0);
+}
+
+#[tokio::test]
+async fn test_distribution_dry_run_uses_the_configured_coin_selector() {
+    let server = MockServer::start();
+    mock_environment(&server);
+    let node_rpc = node_rpc(&server);
+    let assignments = |btc: &str| {
+        vec![AssetDistributionAssignment {
+            user_id: "user1".to_string(),
+            address: ADDRESS.to_string(),
+            amount: Amount::from_btc_str(btc).unwrap(),
+        }]
+    };
+
+    // The issued asset cannot pay fees, so even one base unit of excess is change
+    let plan = client(&server)
+        .distribute_asset_dry_run(ASSET_UUID, &assignments("4.99999999"), &node_rpc, WALLET)
+        .await
+        .unwrap();
+    assert_eq!(plan.change_in(ASSET_ID), Amount::from_sat(1));
+    assert_eq!(plan.warnings.len(), 1);
+
+    let changeless = common::builder(&server)
+        .coin_selector(Arc::new(BranchAndBound::new(Amount::ZERO)))
+        .distribution_funding(DistributionFunding::Signer)
+        .build()
+        .await
+        .unwrap();
+    assert!(changeless
+        .distribute_asset_dry_run(ASSET_UUID, &assignments("4.99999999"), &node_rpc, WALLET)
+        .await
+        .is_err());
+    let plan = changeless
+        .distribute_asset_dry_run(ASSET_UUID, &assignments("5"), &node_rpc, WALLET)
+        .await
+        .unwrap();
+    assert_eq!(plan.change_in(ASSET_ID), Amount::ZERO);
+    assert!(
+        !plan.estimated,
+        "signer-funded distributions spend the planned inputs"
+    );
+}
@@ -1,5 +1,5 @@
 // Integration tests for ElementsRpc wallet-specific methods
-use amp_rs::{ElementsRpc, TxInput};
+use amp_rs::{Amount, ElementsRpc, TxInput};
 use httpmock::prelude::*;
 use serde_json::json;
 use std::collections::HashMap;
@@ -32,7 +32,10 @@ async fn test_create_raw_transaction_single_input_output() {
     }];
 
     let mut outputs = HashMap::new();
-    outputs.insert("recipient_address".to_string(), 100.0);
+    outputs.insert(
+        "recipient_address".to_string(),
+        Amount::from_btc(100.0).unwrap(),
+    );
 
     let mut assets = HashMap::new();
     assets.insert("recipient_address".to_string(), "asset_id".to_string());
@@ -76,8 +79,8 @@ async fn test_create_raw_transaction_multiple_inputs_outputs() {
     ];
 
     let mut outputs = HashMap::new();
-    outputs.insert("address1".to_string(), 50.0);
-    outputs.insert("address2".to_string(), 30.0);
+    outputs.insert("address1".to_string(), Amount::from_btc(50.0).unwrap());
+    outputs.insert("address2".to_string(), Amount::from_btc(30.0).unwrap());
 
     let mut assets = HashMap::new();
     assets.insert("address1".to_string(), "asset1".to_string());
@@ -0,0 +1,129 @@
+use amp_rs::environment::{Change, EnvironmentSpec};
+use httpmock::prelude::*;
+use serde_json::json;
+
+mod common;
+
+use common::client;
+
+const ASSET_UUID: &str = "550e8400-e29b-41d4-a716-446655440000";
+
+const SPEC: &str = r#"{
+    "categories": [{"name": "EU", "description": "EU investors",
+                    "assets": ["550e8400-e29b-41d4-a716-446655440000"]}],
+    "registered_users": [{"name": "Bob", "GAID": "GAbob", "categories": ["EU"]}],
+    "managers": [{"username": "ops", "assets": ["550e8400-e29b-41d4-a716-446655440000"]}],
+    "treasury_addresses": {"550e8400-e29b-41d4-a716-446655440000": ["vjtreasury"]}
+}"#;
+
+fn category(id: i64, name: &str) -> serde_json::Value {
+    json!({
+        "id": id,
+        "name": name,
+        "description": "EU investors",
+        "registered_users": [],
+        "assets": []
+    })
+}
+
+/// Mocks an account with one manager and nothing else
+fn mock_live_account(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(GET).path("/categories");
+        then.status(200).json_body(json!([]));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/registered_users");
+        then.status(200).json_body(json!([]));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/managers");
+        then.status(200).json_body(json!([
+            {"username": "ops", "id": 5, "is_locked": false, "assets": []}
+        ]));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path(format!("/assets/{ASSET_UUID}/treasury-addresses"));
+        then.status(200).json_body(json!([]));
+    });
+}
+
+#[tokio::test]
+async fn test_apply_resolves_objects_created_by_the_plan() {
+    let server = MockServer::start();
+    mock_live_account(&server);
+    let mocks = [
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/categories/add")
+                .json_body(json!({"name": "EU", "description": "EU investors"}));
+            then.status(201).json_body(category(20, "EU"));
+        }),
+        server.mock(|when, then| {
+            when.method(PUT)
+                .path(format!("/categories/20/assets/{ASSET_UUID}/add"));
+            then.status(200).json_body(category(20, "EU"));
+        }),
+        server.mock(|when, then| {
+            when.method(POST).path("/registered_users/add");
+            then.status(201).json_body(json!({
+                "id": 30, "GAID": "GAbob", "is_company": false, "name": "Bob",
+                "categories": [], "creator": 1
+            }));
+        }),
+        server.mock(|when, then| {
+            when.method(PUT)
+                .path("/categories/20/registered_users/30/add");
+            then.status(200).json_body(category(20, "EU"));
+        }),
+        server.mock(|when, then| {
+            when.method(PUT)
+                .path(format!("/managers/5/assets/{ASSET_UUID}/add"));
+            then.status(200);
+        }),
+        server.mock(|when, then| {
+            when.method(POST)
+                .path(format!("/assets/{ASSET_UUID}/treasury-addresses/add"))
+                .json_body(json!(["vjtreasury"]));
+            then.status(200);
+        }),
+    ];
+
+    let client = client(&server);
+    let spec = EnvironmentSpec::from_json(SPEC).unwrap();
+    let plan = client.plan_environment(&spec).await.unwrap();
+    assert_eq!(plan.changes.len(), 6);
+
+    let report = client.apply_environment(&plan).await;
+    assert!(report.is_complete(), "{:?}", report.failure);
+    assert_eq!(report.applied, plan.changes);
+    for mock in &mocks {
+        mock.assert();
+    }
+}
+
+#[tokio::test]
+async fn test_apply_stops_at_first_failure() {
+    let server = MockServer::start();
+    mock_live_account(&server);
+    server.mock(|when, then| {
+        when.method(POST).path("/categories/add");
+        then.status(400).body("category limit reached");
+    });
+    let add_user = server.mock(|when, then| {
+        when.method(POST).path("/registered_users/add");
+        then.status(201);
+    });
+
+    let client = client(&server);
+    let spec = EnvironmentSpec::from_json(SPEC).unwrap();
+    let plan = client.plan_environment(&spec).await.unwrap();
+    let report = client.apply_environment(&plan).await;
+
+    assert!(report.applied.is_empty());
+    let failure = report.failure.unwrap();
+    assert!(matches!(failure.change, Change::CreateCategory { .. }));
+    assert!(failure.error.contains("category limit reached"));
+    add_user.assert_hits(0);
+}
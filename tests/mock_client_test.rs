@@ -3,7 +3,7 @@
 use amp_rs::model::{
     Asset, AssetTransaction, AssetTransactionParams, CreateAssetAssignmentRequest,
 };
-use amp_rs::MockApiClient;
+use amp_rs::{Amount, MockApiClient};
 use secrecy::ExposeSecret;
 
 #[tokio::test]
@@ -155,7 +155,7 @@ async fn test_create_distribution() {
         user_id: "1".to_string(),
         address: "vjU2i2EM2viGEzSywpStMPkTX9U9QSDsLSN63kJJYVpxKJZuxaph8v5r5Jf11aqnfBVdjSbrvcJ2pw26"
             .to_string(),
-        amount: 100.0,
+        amount: Amount::from_btc(100.0).unwrap(),
     }];
 
     let response = client
@@ -173,7 +173,7 @@ async fn test_burn_request() {
     let asset_uuid = assets[0].asset_uuid.clone();
 
     let response = client.burn_request(&asset_uuid, 100_000).await.unwrap();
-    assert_eq!(response.amount, 100_000.0);
+    assert_eq!(response.amount, Amount::from_sat(100_000));
     assert!(!response.asset_id.is_empty());
 }
 
@@ -0,0 +1,240 @@
+use amp_rs::onboarding::{InvestorRecord, RowOutcome, UserChange};
+use httpmock::prelude::*;
+use serde_json::json;
+
+mod common;
+
+use common::client;
+
+const CSV: &str = "name,GAID,categories,is_company
+New Investor,GAnew,Accredited,false
+Renamed Investor,GAsecond,Accredited,false
+Same Investor,GAsame,,true
+Bad Investor,GAbad,,false
+Broken Investor,GAbroken,,false
+";
+
+fn user(
+    id: i64,
+    name: &str,
+    gaid: &str,
+    categories: &[i64],
+    is_company: bool,
+) -> serde_json::Value {
+    json!({
+        "id": id,
+        "GAID": gaid,
+        "is_company": is_company,
+        "name": name,
+        "categories": categories,
+        "creator": 1
+    })
+}
+
+fn category() -> serde_json::Value {
+    json!({
+        "id": 7,
+        "name": "Accredited",
+        "description": null,
+        "registered_users": [],
+        "assets": []
+    })
+}
+
+fn mock_validate(server: &MockServer, gaid: &str, is_valid: bool) {
+    server.mock(|when, then| {
+        when.method(GET).path(format!("/gaids/{gaid}/validate"));
+        then.status(200).json_body(json!({
+            "is_valid": is_valid,
+            "error": if is_valid { None } else { Some("checksum mismatch") }
+        }));
+    });
+}
+
+fn mock_lookup(server: &MockServer, gaid: &str, user: Option<serde_json::Value>) {
+    server.mock(|when, then| {
+        when.method(GET)
+            .path(format!("/gaids/{gaid}/registered_user"));
+        match user {
+            Some(user) => then.status(200).json_body(user),
+            None => then.status(404).body("Not found"),
+        };
+    });
+}
+
+fn mock_categories(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(GET).path("/categories");
+        then.status(200).json_body(json!([category()]));
+    });
+}
+
+#[tokio::test]
+async fn test_import_investors_applies_only_needed_changes() {
+    let server = MockServer::start();
+    mock_categories(&server);
+    for gaid in ["GAnew", "GAsecond", "GAsame", "GAbroken"] {
+        mock_validate(&server, gaid, true);
+    }
+    mock_validate(&server, "GAbad", false);
+    mock_lookup(&server, "GAnew", None);
+    // Known by a second GAID, with an outdated name
+    mock_lookup(
+        &server,
+        "GAsecond",
+        Some(user(2, "Old Name", "GAfirst", &[7], false)),
+    );
+    mock_lookup(
+        &server,
+        "GAsame",
+        Some(user(3, "Same Investor", "GAsame", &[], false)),
+    );
+
+    let add = server.mock(|when, then| {
+        when.method(POST)
+            .path("/registered_users/add")
+            .json_body(json!({"name": "New Investor", "GAID": "GAnew", "is_company": false}));
+        then.status(201)
+            .json_body(user(1, "New Investor", "GAnew", &[], false));
+    });
+    let add_to_category = server.mock(|when, then| {
+        when.method(PUT)
+            .path("/categories/7/registered_users/1/add");
+        then.status(200).json_body(category());
+    });
+    let rename = server.mock(|when, then| {
+        when.method(PUT)
+            .path("/registered_users/2/edit")
+            .json_body(json!({"name": "Renamed Investor"}));
+        then.status(200)
+            .json_body(user(2, "Renamed Investor", "GAfirst", &[7], false));
+    });
+    let set_default = server.mock(|when, then| {
+        when.method(POST)
+            .path("/registered_users/2/gaids/set-default");
+        then.status(200);
+    });
+    // Lookup failures other than 404 fail the row instead of creating a duplicate user
+    server.mock(|when, then| {
+        when.method(GET).path("/gaids/GAbroken/registered_user");
+        then.status(500).body("Internal error");
+    });
+
+    let investors = InvestorRecord::from_csv(CSV).unwrap();
+
+    let report = client(&server).import_investors(&investors).await.unwrap();
+
+    add.assert();
+    add_to_category.assert();
+    rename.assert();
+    set_default.assert_hits(0);
+    assert_eq!(report.results.len(), 5);
+    assert_eq!(
+        report.results[0].outcome,
+        RowOutcome::Created {
+            changes: vec![UserChange::AddedToCategory { category_id: 7 }]
+        }
+    );
+    assert_eq!(report.results[0].registered_user, Some(1));
+    assert_eq!(
+        report.results[1].outcome,
+        RowOutcome::Updated {
+            changes: vec![UserChange::Renamed {
+                from: "Old Name".to_string(),
+                to: "Renamed Investor".to_string()
+            }]
+        }
+    );
+    assert_eq!(report.results[1].warnings.len(), 1);
+    assert!(report.results[1].warnings[0].contains("GAfirst"));
+    assert_eq!(report.results[2].outcome, RowOutcome::Unchanged);
+    assert_eq!(report.results[2].warnings.len(), 1);
+    assert!(matches!(
+        &report.results[3].outcome,
+        RowOutcome::Failed { error } if error.contains("checksum mismatch")
+    ));
+    assert_eq!(report.results[3].row, 4);
+    assert!(matches!(
+        &report.results[4].outcome,
+        RowOutcome::Failed { error } if error.contains("500")
+    ));
+    assert!(!report.is_success());
+}
+
+#[tokio::test]
+async fn test_failed_category_add_fails_only_its_row() {
+    let server = MockServer::start();
+    mock_categories(&server);
+    for gaid in ["GAnew", "GAsame"] {
+        mock_validate(&server, gaid, true);
+    }
+    mock_lookup(&server, "GAnew", None);
+    mock_lookup(
+        &server,
+        "GAsame",
+        Some(user(3, "Same Investor", "GAsame", &[7], false)),
+    );
+    let add = server.mock(|when, then| {
+        when.method(POST).path("/registered_users/add");
+        then.status(201)
+            .json_body(user(1, "New Investor", "GAnew", &[], false));
+    });
+    server.mock(|when, then| {
+        when.method(PUT)
+            .path("/categories/7/registered_users/1/add");
+        then.status(500).body("Internal error");
+    });
+    let investors = InvestorRecord::from_csv(
+        "name,GAID,categories,is_company
+New Investor,GAnew,Accredited,false
+Same Investor,GAsame,Accredited,false
+",
+    )
+    .unwrap();
+
+    let report = client(&server).import_investors(&investors).await.unwrap();
+
+    add.assert();
+    // The user was created before the failure, so a rerun finds it and only adds the category
+    assert!(matches!(
+        &report.results[0].outcome,
+        RowOutcome::Failed { error } if error.contains("500")
+    ));
+    assert_eq!(report.results[0].registered_user, Some(1));
+    assert_eq!(report.results[1].outcome, RowOutcome::Unchanged);
+}
+
+#[tokio::test]
+async fn test_unknown_gaid_validation_fails_the_row() {
+    let server = MockServer::start();
+    mock_categories(&server);
+    server.mock(|when, then| {
+        when.method(GET).path("/gaids/GAnew/validate");
+        then.status(404).body("Not found");
+    });
+    let lookup = server.mock(|when, then| {
+        when.method(GET).path("/gaids/GAnew/registered_user");
+        then.status(404).body("Not found");
+    });
+    let add = server.mock(|when, then| {
+        when.method(POST).path("/registered_users/add");
+        then.status(201)
+            .json_body(user(1, "New Investor", "GAnew", &[], false));
+    });
+    let investors = InvestorRecord::from_csv(
+        "name,GAID,categories,is_company
+New Investor,GAnew,,false
+",
+    )
+    .unwrap();
+
+    let report = client(&server).import_investors(&investors).await.unwrap();
+
+    assert!(matches!(
+        &report.results[0].outcome,
+        RowOutcome::Failed { error } if error.contains("404")
+    ));
+    assert_eq!(report.results[0].registered_user, None);
+    lookup.assert_hits(0);
+    add.assert_hits(0);
+}
@@ -0,0 +1,179 @@
+use amp_rs::reconciliation::Mismatch;
+use httpmock::prelude::*;
+use serde_json::json;
+
+mod common;
+
+use common::{client, node_rpc, rpc_result};
+
+const ASSET_UUID: &str = "550e8400-e29b-41d4-a716-446655440000";
+const ASSET_ID: &str = "6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d";
+const WALLET: &str = "treasury_wallet";
+
+fn mock_get(server: &MockServer, path: &str, body: serde_json::Value) {
+    server.mock(|when, then| {
+        when.method(GET).path(format!("/assets/{ASSET_UUID}{path}"));
+        then.status(200).json_body(body);
+    });
+}
+
+/// Mocks the asset and its summary
+fn mock_asset(server: &MockServer) {
+    mock_get(
+        server,
+        "",
+        json!({
+            "name": "Test Asset",
+            "asset_uuid": ASSET_UUID,
+            "issuer": 1,
+            "asset_id": ASSET_ID,
+            "reissuance_token_id": null,
+            "requirements": [],
+            "ticker": "TST",
+            "precision": 8,
+            "domain": null,
+            "pubkey": null,
+            "is_registered": true,
+            "is_authorized": true,
+            "is_locked": false,
+            "issuer_authorization_endpoint": null,
+            "transfer_restricted": true
+        }),
+    );
+    mock_get(
+        server,
+        "/summary",
+        json!({
+            "asset_id": ASSET_ID,
+            "reissuance_token_id": null,
+            "issued": 1_000,
+            "reissued": 0,
+            "assigned": 300,
+            "distributed": 300,
+            "burned": 0,
+            "blacklisted": 0,
+            "registered_users": 1,
+            "active_registered_users": 1,
+            "active_green_subaccounts": 1,
+            "reissuance_tokens": 0
+        }),
+    );
+}
+
+#[tokio::test]
+async fn test_reconcile_asset_joins_amp_and_wallet() {
+    let server = MockServer::start();
+    mock_asset(&server);
+    mock_get(
+        &server,
+        "/ownerships",
+        json!([
+            {"owner": "Alice", "amount": 300, "GAID": "GA1"},
+            {"owner": "Treasury", "amount": 700, "GAID": null}
+        ]),
+    );
+    mock_get(
+        &server,
+        "/utxos",
+        json!([
+            {
+                "txid": "aa".repeat(32), "vout": 0, "asset": ASSET_ID, "amount": 300,
+                "registered_user": 1, "gaid": "GA1", "blacklisted": false
+            },
+            {
+                "txid": "bb".repeat(32), "vout": 1, "asset": ASSET_ID, "amount": 700,
+                "registered_user": null, "gaid": null, "blacklisted": false
+            }
+        ]),
+    );
+    mock_get(
+        &server,
+        "/lost-outputs",
+        json!({"lost_outputs": [], "reissuance_lost_outputs": []}),
+    );
+    mock_get(
+        &server,
+        "/assignments",
+        json!([{
+            "id": 3,
+            "registered_user": 1,
+            "amount": 300,
+            "receiving_address": null,
+            "distribution_uuid": "done",
+            "ready_for_distribution": true,
+            "vesting_datetime": null,
+            "vesting_timestamp": null,
+            "has_vested": true,
+            "is_distributed": true,
+            "creator": 1,
+            "GAID": "GA1"
+        }]),
+    );
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"method": "loadwallet"}"#);
+        then.status(200)
+            .json_body(rpc_result(json!({"name": WALLET, "warning": ""})));
+    });
+    let listunspent = server.mock(|when, then| {
+        when.method(POST)
+            .path(format!("/wallet/{WALLET}"))
+            .json_body_partial(r#"{"method": "listunspent"}"#)
+            .body_contains(ASSET_ID);
+        then.status(200).json_body(rpc_result(json!([{
+            "txid": "bb".repeat(32),
+            "vout": 1,
+            "amount": "0.00000700",
+            "asset": ASSET_ID,
+            "address": "vjtreasury",
+            "spendable": true,
+            "confirmations": 10
+        }])));
+    });
+
+    let report = client(&server)
+        .reconcile_asset(ASSET_UUID, &node_rpc(&server), WALLET)
+        .await
+        .unwrap();
+
+    listunspent.assert();
+    assert!(report.is_clean(), "{:?}", report.mismatches);
+    assert_eq!(report.asset_id, ASSET_ID);
+    assert_eq!(report.holders.len(), 1);
+    assert_eq!(report.holders[0].registered_user, Some(1));
+    assert_eq!(report.holders[0].ownership, 300);
+    assert_eq!(report.holders[0].utxos, 300);
+    assert_eq!(report.treasury.ownership, Some(700));
+    assert_eq!(report.treasury.amp_amount, 700);
+    assert_eq!(report.treasury.wallet_amount, 700);
+    assert_eq!(
+        report.mismatches_of_kind("lost_output"),
+        Vec::<&Mismatch>::new()
+    );
+    assert_eq!(report.holders_csv().lines().count(), 2);
+}
+
+#[tokio::test]
+async fn test_reconcile_asset_stops_at_amp_error() {
+    let server = MockServer::start();
+    mock_asset(&server);
+    server.mock(|when, then| {
+        when.method(GET)
+            .path(format!("/assets/{ASSET_UUID}/ownerships"));
+        then.status(500).body("internal error");
+    });
+    let listunspent = server.mock(|when, then| {
+        when.method(POST)
+            .json_body_partial(r#"{"method": "listunspent"}"#);
+        then.status(200).json_body(rpc_result(json!([])));
+    });
+
+    let error = client(&server)
+        .reconcile_asset(ASSET_UUID, &node_rpc(&server), WALLET)
+        .await
+        .unwrap_err();
+
+    assert!(error.to_string().contains("internal error"), "{error}");
+    listunspent.assert_hits(0);
+}
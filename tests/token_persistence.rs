@@ -107,3 +107,106 @@ fn test_token_persistence_environment_detection() {
         env::set_var("AMP_PASSWORD", val);
     }
 }
+
+fn temp_token_path(name: &str) -> std::path::PathBuf {
+    env::temp_dir().join(format!("amp_rs_{}_{}.json", name, std::process::id()))
+}
+
+#[tokio::test]
+async fn test_file_token_store_round_trip_with_private_permissions() {
+    use amp_rs::model::TokenData;
+    use amp_rs::{FileTokenStore, TokenStore};
+    use chrono::{Duration, Utc};
+    use secrecy::ExposeSecret;
+
+    let path = temp_token_path("file_store");
+    let store = FileTokenStore::new(&path);
+
+    assert!(store.load().await.unwrap().is_none());
+
+    let token_data = TokenData::new("file_token".to_string(), Utc::now() + Duration::hours(1));
+    store.save(&token_data).await.unwrap();
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    let loaded = store.load().await.unwrap().expect("token should be stored");
+    assert_eq!(loaded.token.expose_secret(), "file_token");
+
+    store.clear().await.unwrap();
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn test_file_token_store_discards_expired_token() {
+    use amp_rs::model::TokenData;
+    use amp_rs::{FileTokenStore, TokenStore};
+    use chrono::{Duration, Utc};
+
+    let path = temp_token_path("expired_store");
+    let store = FileTokenStore::new(&path);
+
+    let token_data = TokenData::new("old_token".to_string(), Utc::now() - Duration::hours(1));
+    store.save(&token_data).await.unwrap();
+
+    assert!(store.load().await.unwrap().is_none());
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn test_encrypted_file_token_store_does_not_write_plaintext() {
+    use amp_rs::model::TokenData;
+    use amp_rs::{EncryptedFileTokenStore, TokenStore};
+    use chrono::{Duration, Utc};
+    use secrecy::{ExposeSecret, Secret};
+
+    let path = temp_token_path("encrypted_store");
+    let store = EncryptedFileTokenStore::new(&path, Secret::new("passphrase".to_string()));
+
+    let token_data = TokenData::new(
+        "encrypted_token_value".to_string(),
+        Utc::now() + Duration::hours(1),
+    );
+    store.save(&token_data).await.unwrap();
+
+    let raw = std::fs::read_to_string(&path).unwrap();
+    assert!(!raw.contains("encrypted_token_value"));
+    assert!(raw.contains("\"version\": 1"));
+
+    let loaded = store.load().await.unwrap().expect("token should be stored");
+    assert_eq!(loaded.token.expose_secret(), "encrypted_token_value");
+
+    let wrong_store = EncryptedFileTokenStore::new(&path, Secret::new("wrong".to_string()));
+    assert!(wrong_store.load().await.is_err());
+
+    store.clear().await.unwrap();
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn test_token_manager_uses_configured_token_store() {
+    use amp_rs::client::{RetryConfig, TokenManager};
+    use amp_rs::model::TokenData;
+    use amp_rs::{InMemoryTokenStore, TokenStore};
+    use chrono::{Duration, Utc};
+    use std::sync::Arc;
+
+    let store = Arc::new(InMemoryTokenStore::new());
+    let token_data = TokenData::new("stored_token".to_string(), Utc::now() + Duration::hours(1));
+    store.save(&token_data).await.unwrap();
+
+    let base_url = url::Url::parse("http://localhost:1/api").unwrap();
+    let manager = TokenManager::with_token_store(RetryConfig::for_tests(), base_url, store.clone())
+        .await
+        .unwrap();
+
+    // The token comes from the store, so no network request is made
+    assert_eq!(manager.get_token().await.unwrap(), "stored_token");
+
+    manager.clear_token().await.unwrap();
+    assert!(store.load().await.unwrap().is_none());
+}
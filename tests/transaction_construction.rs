@@ -1,5 +1,8 @@
+use amp_rs::coin_selection::{BranchAndBound, LargestFirst};
+use amp_rs::fee::{FeeRate, FeeStrategy};
+use amp_rs::network::Network;
 use amp_rs::signer::{Signer, SignerError};
-use amp_rs::{AmpError, ElementsRpc, TxInput, Unspent};
+use amp_rs::{Amount, AmpError, ElementsRpc, TxInput, Unspent};
 use async_trait::async_trait;
 use httpmock::prelude::*;
 
@@ -109,7 +112,7 @@ fn create_mock_utxos(asset_id: &str, amounts: Vec<f64>) -> Vec<Unspent> {
         .map(|(i, amount)| Unspent {
             txid: format!("txid_{:03}", i),
             vout: i as u32,
-            amount,
+            amount: Amount::from_btc(amount).unwrap(),
             asset: asset_id.to_string(),
             address: format!("address_{}", i),
             spendable: true,
@@ -123,15 +126,43 @@ fn create_mock_utxos(asset_id: &str, amounts: Vec<f64>) -> Vec<Unspent> {
         .collect()
 }
 
+/// L-BTC asset ID on Liquid testnet
+const LBTC_ASSET_ID: &str = "144c654344aa716d6f3abcc1ca90e5641e4e2a7f633bc09fe3baf64585819a49";
+
+/// Helper function to create an L-BTC UTXO for paying fees
+fn create_mock_lbtc_utxo(txid: &str, amount: Amount) -> Unspent {
+    Unspent {
+        txid: txid.to_string(),
+        vout: 0,
+        amount,
+        asset: LBTC_ASSET_ID.to_string(),
+        address: "lbtc_address".to_string(),
+        spendable: true,
+        confirmations: Some(6),
+        scriptpubkey: None,
+        redeemscript: None,
+        witnessscript: None,
+        amountblinder: None,
+        assetblinder: None,
+    }
+}
+
 /// Helper function to create mock RPC response for listunspent
+///
+/// The wallet also holds 0.001 L-BTC for paying fees.
 fn create_listunspent_mock(
     server: &MockServer,
     _wallet_name: &str,
     _asset_id: &str,
-    utxos: Vec<Unspent>,
+    mut utxos: Vec<Unspent>,
 ) {
     use httpmock::Method::POST;
 
+    utxos.push(create_mock_lbtc_utxo(
+        "lbtc_txid",
+        Amount::from_sat(100_000),
+    ));
+
     // Specific mock for createrawtransaction - return transaction hex (must come first)
     server.mock(|when, then| {
         when.method(POST)
@@ -227,14 +258,19 @@ async fn test_utxo_selection_sufficient_funds_single_utxo() {
 
     // Test selecting UTXOs for 100.0 + 1.0 fee = 101.0 total
     let result = rpc
-        .select_utxos_for_amount("test_wallet", asset_id, 100.0, 1.0)
+        .select_utxos_for_amount(
+            "test_wallet",
+            asset_id,
+            Amount::from_btc(100.0).unwrap(),
+            Amount::from_btc(1.0).unwrap(),
+        )
         .await;
 
     match result {
         Ok((selected_utxos, total_amount)) => {
             assert_eq!(selected_utxos.len(), 1);
-            assert_eq!(total_amount, 150.0);
-            assert_eq!(selected_utxos[0].amount, 150.0);
+            assert_eq!(total_amount, Amount::from_btc(150.0).unwrap());
+            assert_eq!(selected_utxos[0].amount, Amount::from_btc(150.0).unwrap());
         }
         Err(e) => {
             println!("Error: {}", e);
@@ -256,7 +292,12 @@ async fn test_utxo_selection_sufficient_funds_multiple_utxos() {
 
     // Test selecting UTXos for 120.0 + 1.0 fee = 121.0 total
     let result = rpc
-        .select_utxos_for_amount("test_wallet", asset_id, 120.0, 1.0)
+        .select_utxos_for_amount(
+            "test_wallet",
+            asset_id,
+            Amount::from_btc(120.0).unwrap(),
+            Amount::from_btc(1.0).unwrap(),
+        )
         .await;
 
     assert!(result.is_ok());
@@ -265,7 +306,7 @@ async fn test_utxo_selection_sufficient_funds_multiple_utxos() {
     // Should select largest UTXOs first: 50.0 + 40.0 + 30.0 = 120.0 (sufficient)
     // or 50.0 + 40.0 + 30.0 + 25.0 = 145.0 depending on algorithm
     assert!(selected_utxos.len() >= 3);
-    assert!(total_amount >= 121.0);
+    assert!(total_amount >= Amount::from_btc(121.0).unwrap());
 
     // Verify UTXOs are sorted by amount (largest first)
     for i in 1..selected_utxos.len() {
@@ -286,7 +327,12 @@ async fn test_utxo_selection_insufficient_funds() {
 
     // Try to select UTXOs for 100.0 + 1.0 fee = 101.0 total, but only have 18.0
     let result = rpc
-        .select_utxos_for_amount("test_wallet", asset_id, 100.0, 1.0)
+        .select_utxos_for_amount(
+            "test_wallet",
+            asset_id,
+            Amount::from_btc(100.0).unwrap(),
+            Amount::from_btc(1.0).unwrap(),
+        )
         .await;
 
     assert!(result.is_err());
@@ -310,7 +356,12 @@ async fn test_utxo_selection_no_spendable_utxos() {
     let rpc = ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string());
 
     let result = rpc
-        .select_utxos_for_amount("test_wallet", asset_id, 50.0, 1.0)
+        .select_utxos_for_amount(
+            "test_wallet",
+            asset_id,
+            Amount::from_btc(50.0).unwrap(),
+            Amount::from_btc(1.0).unwrap(),
+        )
         .await;
 
     assert!(result.is_err());
@@ -331,13 +382,18 @@ async fn test_utxo_selection_exact_amount_needed() {
 
     // Need exactly 101.0 (100.0 + 1.0 fee), have 51.0 + 50.0 = 101.0
     let result = rpc
-        .select_utxos_for_amount("test_wallet", asset_id, 100.0, 1.0)
+        .select_utxos_for_amount(
+            "test_wallet",
+            asset_id,
+            Amount::from_btc(100.0).unwrap(),
+            Amount::from_btc(1.0).unwrap(),
+        )
         .await;
 
     assert!(result.is_ok());
     let (selected_utxos, total_amount) = result.unwrap();
     assert_eq!(selected_utxos.len(), 2);
-    assert_eq!(total_amount, 101.0);
+    assert_eq!(total_amount, Amount::from_btc(101.0).unwrap());
 }
 
 #[tokio::test]
@@ -353,17 +409,22 @@ async fn test_utxo_selection_algorithm_largest_first() {
 
     // Need 120.0 + 1.0 fee = 121.0 total
     let result = rpc
-        .select_utxos_for_amount("test_wallet", asset_id, 120.0, 1.0)
+        .select_utxos_for_amount(
+            "test_wallet",
+            asset_id,
+            Amount::from_btc(120.0).unwrap(),
+            Amount::from_btc(1.0).unwrap(),
+        )
         .await;
 
     assert!(result.is_ok());
     let (selected_utxos, total_amount) = result.unwrap();
 
     // Should select 100.0 + 75.0 = 175.0 (largest first algorithm)
-    assert!(total_amount >= 121.0);
+    assert!(total_amount >= Amount::from_btc(121.0).unwrap());
 
     // Verify first UTXO is the largest available
-    assert_eq!(selected_utxos[0].amount, 100.0);
+    assert_eq!(selected_utxos[0].amount, Amount::from_btc(100.0).unwrap());
 
     // Verify UTXOs are in descending order by amount
     for i in 1..selected_utxos.len() {
@@ -371,6 +432,33 @@ async fn test_utxo_selection_algorithm_largest_first() {
     }
 }
 
+#[tokio::test]
+async fn test_utxo_selection_with_branch_and_bound_selector() {
+    let server = MockServer::start();
+    let asset_id = "6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d";
+
+    let utxos = create_mock_utxos(asset_id, vec![100.0, 60.0, 40.0, 21.0]);
+    create_listunspent_mock(&server, "test_wallet", asset_id, utxos);
+
+    let rpc = ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string());
+
+    // Largest first would pick 100.0 + 60.0; 100.0 + 21.0 covers 121.0 without change
+    let (selected_utxos, total_amount) = rpc
+        .select_utxos_with_selector(
+            "test_wallet",
+            asset_id,
+            Amount::from_btc(120.0).unwrap(),
+            Amount::from_btc(1.0).unwrap(),
+            &BranchAndBound::default(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(total_amount, Amount::from_btc(121.0).unwrap());
+    assert_eq!(selected_utxos.len(), 2);
+    assert_eq!(selected_utxos[1].amount, Amount::from_btc(21.0).unwrap());
+}
+
 #[tokio::test]
 async fn test_transaction_construction_with_mock_signer_success() {
     let server = MockServer::start();
@@ -382,7 +470,7 @@ async fn test_transaction_construction_with_mock_signer_success() {
 
     // Setup transaction creation mock
     let mut address_amounts = HashMap::new();
-    address_amounts.insert("recipient1".to_string(), 100.0);
+    address_amounts.insert("recipient1".to_string(), Amount::from_btc(100.0).unwrap());
 
     let mut expected_outputs = HashMap::new();
     expected_outputs.insert("recipient1".to_string(), 100.0);
@@ -398,7 +486,8 @@ async fn test_transaction_construction_with_mock_signer_success() {
         sequence: None,
     }];
 
-    let rpc = ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string());
+    let rpc = ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string())
+        .with_network(Network::LiquidTestnet);
 
     // Test transaction construction
     let result = rpc
@@ -407,7 +496,7 @@ async fn test_transaction_construction_with_mock_signer_success() {
             asset_id,
             address_amounts,
             "address_0", // change address
-            1.0,         // fee
+            FeeStrategy::Rate(FeeRate::from_sat_per_vb(1)),
         )
         .await;
 
@@ -419,8 +508,8 @@ async fn test_transaction_construction_with_mock_signer_success() {
 
     // Verify transaction was built
     assert!(!raw_tx.is_empty());
-    assert_eq!(selected_utxos.len(), 1);
-    assert_eq!(change_amount, 50.0);
+    assert_eq!(selected_utxos.len(), 2); // Asset input plus L-BTC fee input
+    assert_eq!(change_amount, Amount::from_btc(50.0).unwrap());
 }
 
 #[tokio::test]
@@ -570,7 +659,7 @@ async fn test_liquid_specific_transaction_format() {
     let mut address_amounts = HashMap::new();
     address_amounts.insert(
         "lq1qq2xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq".to_string(),
-        50.0,
+        Amount::from_btc(50.0).unwrap(),
     );
 
     let mut expected_outputs = HashMap::new();
@@ -593,7 +682,8 @@ async fn test_liquid_specific_transaction_format() {
         sequence: None,
     }];
 
-    let rpc = ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string());
+    let rpc = ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string())
+        .with_network(Network::LiquidTestnet);
 
     let result = rpc
         .build_distribution_transaction(
@@ -601,7 +691,7 @@ async fn test_liquid_specific_transaction_format() {
             asset_id,
             address_amounts,
             "address_0", // change address
-            1.0,         // fee
+            FeeStrategy::Rate(FeeRate::from_sat_per_vb(1)),
         )
         .await;
 
@@ -611,9 +701,9 @@ async fn test_liquid_specific_transaction_format() {
     // Verify Liquid-specific transaction structure
     assert!(!raw_tx.is_empty());
     assert!(raw_tx.starts_with("02")); // Liquid transaction version
-    assert_eq!(selected_utxos.len(), 1);
+    assert_eq!(selected_utxos.len(), 2); // Asset input plus L-BTC fee input
     assert_eq!(selected_utxos[0].asset, asset_id); // Verify asset ID is preserved
-    assert_eq!(change_amount, 50.0);
+    assert_eq!(change_amount, Amount::from_btc(50.0).unwrap());
 }
 
 #[tokio::test]
@@ -627,8 +717,8 @@ async fn test_transaction_construction_with_multiple_outputs() {
 
     // Setup transaction creation mock with multiple outputs
     let mut address_amounts = HashMap::new();
-    address_amounts.insert("recipient1".to_string(), 50.0);
-    address_amounts.insert("recipient2".to_string(), 75.0);
+    address_amounts.insert("recipient1".to_string(), Amount::from_btc(50.0).unwrap());
+    address_amounts.insert("recipient2".to_string(), Amount::from_btc(75.0).unwrap());
 
     let mut expected_outputs = HashMap::new();
     expected_outputs.insert("recipient1".to_string(), 50.0);
@@ -646,7 +736,8 @@ async fn test_transaction_construction_with_multiple_outputs() {
         sequence: None,
     }];
 
-    let rpc = ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string());
+    let rpc = ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string())
+        .with_network(Network::LiquidTestnet);
 
     let result = rpc
         .build_distribution_transaction(
@@ -654,7 +745,7 @@ async fn test_transaction_construction_with_multiple_outputs() {
             asset_id,
             address_amounts,
             "address_0", // change address
-            2.0,         // fee
+            FeeStrategy::Rate(FeeRate::from_sat_per_vb(1)),
         )
         .await;
 
@@ -663,8 +754,8 @@ async fn test_transaction_construction_with_multiple_outputs() {
 
     // Verify transaction with multiple outputs
     assert!(!raw_tx.is_empty());
-    assert_eq!(selected_utxos.len(), 1);
-    assert_eq!(change_amount, 75.0);
+    assert_eq!(selected_utxos.len(), 2); // Asset input plus L-BTC fee input
+    assert_eq!(change_amount, Amount::from_btc(75.0).unwrap());
 }
 
 #[tokio::test]
@@ -678,7 +769,7 @@ async fn test_transaction_construction_no_change_needed() {
 
     // Setup transaction creation mock with no change output
     let mut address_amounts = HashMap::new();
-    address_amounts.insert("recipient1".to_string(), 100.0);
+    address_amounts.insert("recipient1".to_string(), Amount::from_btc(100.0).unwrap());
 
     let mut expected_outputs = HashMap::new();
     expected_outputs.insert("recipient1".to_string(), 100.0);
@@ -693,7 +784,8 @@ async fn test_transaction_construction_no_change_needed() {
         sequence: None,
     }];
 
-    let rpc = ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string());
+    let rpc = ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string())
+        .with_network(Network::LiquidTestnet);
 
     let result = rpc
         .build_distribution_transaction(
@@ -701,7 +793,7 @@ async fn test_transaction_construction_no_change_needed() {
             asset_id,
             address_amounts,
             "address_0", // change address
-            1.0,         // fee
+            FeeStrategy::Rate(FeeRate::from_sat_per_vb(1)),
         )
         .await;
 
@@ -710,8 +802,8 @@ async fn test_transaction_construction_no_change_needed() {
 
     // Verify transaction with no change
     assert!(!raw_tx.is_empty());
-    assert_eq!(selected_utxos.len(), 1);
-    assert_eq!(change_amount, 1.0); // Change is 101 - 100 = 1.0
+    assert_eq!(selected_utxos.len(), 2); // Asset input plus L-BTC fee input
+    assert_eq!(change_amount, Amount::from_btc(1.0).unwrap()); // Change is 101 - 100 = Amount::from_btc(1.0).unwrap()
 }
 
 #[tokio::test]
@@ -725,7 +817,7 @@ async fn test_transaction_construction_dust_change_handling() {
 
     // Setup transaction creation mock - change amount is 0.4 which is above dust threshold
     let mut address_amounts = HashMap::new();
-    address_amounts.insert("recipient1".to_string(), 100.0);
+    address_amounts.insert("recipient1".to_string(), Amount::from_btc(100.0).unwrap());
 
     let mut expected_outputs = HashMap::new();
     expected_outputs.insert("recipient1".to_string(), 100.0);
@@ -741,7 +833,8 @@ async fn test_transaction_construction_dust_change_handling() {
         sequence: None,
     }];
 
-    let rpc = ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string());
+    let rpc = ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string())
+        .with_network(Network::LiquidTestnet);
 
     let result = rpc
         .build_distribution_transaction(
@@ -749,7 +842,7 @@ async fn test_transaction_construction_dust_change_handling() {
             asset_id,
             address_amounts,
             "address_0", // change address
-            0.1,         // small fee
+            FeeStrategy::Rate(FeeRate::from_sat_per_vb(1)),
         )
         .await;
 
@@ -758,8 +851,8 @@ async fn test_transaction_construction_dust_change_handling() {
 
     // Verify dust change handling
     assert!(!raw_tx.is_empty());
-    assert_eq!(selected_utxos.len(), 1);
-    assert_eq!(change_amount, 0.5); // 100.5 - 100.0 = 0.5
+    assert_eq!(selected_utxos.len(), 2); // Asset input plus L-BTC fee input
+    assert_eq!(change_amount, Amount::from_btc(0.5).unwrap()); // Amount::from_btc(100.5).unwrap() - Amount::from_btc(100.0).unwrap() = Amount::from_btc(0.5).unwrap()
 }
 
 #[tokio::test]
@@ -779,7 +872,7 @@ async fn test_transaction_construction_zero_amount_distribution() {
             asset_id,
             address_amounts,
             "change_address", // change address
-            1.0,
+            FeeStrategy::Rate(FeeRate::from_sat_per_vb(1)),
         )
         .await;
 
@@ -873,12 +966,17 @@ async fn test_utxo_selection_edge_cases() {
     let rpc = ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string());
 
     let result = rpc
-        .select_utxos_for_amount("test_wallet", asset_id, 100.0, 0.0)
+        .select_utxos_for_amount(
+            "test_wallet",
+            asset_id,
+            Amount::from_btc(100.0).unwrap(),
+            Amount::from_btc(0.0).unwrap(),
+        )
         .await;
     assert!(result.is_ok());
     let (selected_utxos, total_amount) = result.unwrap();
     assert_eq!(selected_utxos.len(), 1);
-    assert_eq!(total_amount, 100.0);
+    assert_eq!(total_amount, Amount::from_btc(100.0).unwrap());
 }
 
 #[tokio::test]
@@ -896,14 +994,19 @@ async fn test_utxo_selection_with_confirmations_filter() {
     let rpc = ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string());
 
     let result = rpc
-        .select_utxos_for_amount("test_wallet", asset_id, 75.0, 1.0)
+        .select_utxos_for_amount(
+            "test_wallet",
+            asset_id,
+            Amount::from_btc(75.0).unwrap(),
+            Amount::from_btc(1.0).unwrap(),
+        )
         .await;
 
     assert!(result.is_ok());
     let (selected_utxos, total_amount) = result.unwrap();
 
     // Should select confirmed UTXOs preferentially
-    assert!(total_amount >= 76.0);
+    assert!(total_amount >= Amount::from_btc(76.0).unwrap());
     for utxo in &selected_utxos {
         // All selected UTXOs should be spendable
         assert!(utxo.spendable);
@@ -1143,9 +1246,9 @@ async fn test_change_data_collection_success_with_multiple_outputs() {
     }
 
     // Verify specific amounts
-    let amounts: Vec<f64> = change_data.iter().map(|u| u.amount).collect();
-    assert!(amounts.contains(&25.5));
-    assert!(amounts.contains(&10.0));
+    let amounts: Vec<Amount> = change_data.iter().map(|u| u.amount).collect();
+    assert!(amounts.contains(&Amount::from_btc(25.5).unwrap()));
+    assert!(amounts.contains(&Amount::from_btc(10.0).unwrap()));
 }
 
 #[tokio::test]
@@ -1301,7 +1404,7 @@ async fn test_change_data_collection_filters_unspendable() {
 
     // Should only return spendable UTXOs
     assert_eq!(change_data.len(), 1);
-    assert_eq!(change_data[0].amount, 25.0);
+    assert_eq!(change_data[0].amount, Amount::from_btc(25.0).unwrap());
     assert!(change_data[0].spendable);
     assert_eq!(change_data[0].address, "spendable_address");
 }
@@ -1381,7 +1484,7 @@ async fn test_change_data_collection_filters_wrong_asset() {
 
     // Should only return UTXOs with the correct asset ID
     assert_eq!(change_data.len(), 1);
-    assert_eq!(change_data[0].amount, 25.0);
+    assert_eq!(change_data[0].amount, Amount::from_btc(25.0).unwrap());
     assert_eq!(change_data[0].asset, asset_id);
     assert_eq!(change_data[0].address, "correct_asset_address");
 }
@@ -1496,7 +1599,7 @@ async fn test_change_data_formatting_for_api() {
     // Verify all fields are properly formatted for API submission
     assert_eq!(change_utxo.txid, txid);
     assert_eq!(change_utxo.vout, 1);
-    assert_eq!(change_utxo.amount, 42.75);
+    assert_eq!(change_utxo.amount, Amount::from_btc(42.75).unwrap());
     assert_eq!(change_utxo.asset, asset_id);
     assert_eq!(
         change_utxo.address,
@@ -1582,7 +1685,7 @@ async fn test_collect_change_data_integration() {
     all_utxos.push(Unspent {
         txid: "other_txid_123".to_string(),
         vout: 0,
-        amount: 75.0,
+        amount: Amount::from_btc(75.0).unwrap(),
         asset: asset_id.to_string(),
         address: "lq1qq1xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq".to_string(),
         spendable: true,
@@ -1602,7 +1705,7 @@ async fn test_collect_change_data_integration() {
     all_utxos.push(Unspent {
         txid: distribution_txid.to_string(),
         vout: 1,
-        amount: 25.5,
+        amount: Amount::from_btc(25.5).unwrap(),
         asset: asset_id.to_string(),
         address: "lq1qq2xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq".to_string(),
         spendable: true,
@@ -1621,7 +1724,7 @@ async fn test_collect_change_data_integration() {
     all_utxos.push(Unspent {
         txid: distribution_txid.to_string(),
         vout: 2,
-        amount: 10.0,
+        amount: Amount::from_btc(10.0).unwrap(),
         asset: asset_id.to_string(),
         address: "lq1qq3xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq".to_string(),
         spendable: true,
@@ -1641,7 +1744,7 @@ async fn test_collect_change_data_integration() {
     all_utxos.push(Unspent {
         txid: "different_txid_456".to_string(),
         vout: 0,
-        amount: 50.0,
+        amount: Amount::from_btc(50.0).unwrap(),
         asset: asset_id.to_string(),
         address: "lq1qq4xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq".to_string(),
         spendable: true,
@@ -1682,14 +1785,14 @@ async fn test_collect_change_data_integration() {
 
     // Verify specific change UTXOs
     let change_utxo_1 = change_utxos.iter().find(|u| u.vout == 1).unwrap();
-    assert_eq!(change_utxo_1.amount, 25.5);
+    assert_eq!(change_utxo_1.amount, Amount::from_btc(25.5).unwrap());
     assert_eq!(
         change_utxo_1.address,
         "lq1qq2xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq"
     );
 
     let change_utxo_2 = change_utxos.iter().find(|u| u.vout == 2).unwrap();
-    assert_eq!(change_utxo_2.amount, 10.0);
+    assert_eq!(change_utxo_2.amount, Amount::from_btc(10.0).unwrap());
     assert_eq!(
         change_utxo_2.address,
         "lq1qq3xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq"
@@ -1707,7 +1810,7 @@ async fn test_collect_change_data_no_change_scenario() {
         Unspent {
             txid: "other_txid_123".to_string(),
             vout: 0,
-            amount: 75.0,
+            amount: Amount::from_btc(75.0).unwrap(),
             asset: asset_id.to_string(),
             address: "lq1qq1xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq".to_string(),
             spendable: true,
@@ -1725,7 +1828,7 @@ async fn test_collect_change_data_no_change_scenario() {
         Unspent {
             txid: "different_txid_456".to_string(),
             vout: 0,
-            amount: 50.0,
+            amount: Amount::from_btc(50.0).unwrap(),
             asset: asset_id.to_string(),
             address: "lq1qq2xvpcvfup5j8zscjq05u2wxxjcyewk7979f9lq".to_string(),
             spendable: true,
@@ -1771,7 +1874,7 @@ async fn test_collect_change_data_workflow_integration() {
         Unspent {
             txid: distribution_txid.to_string(),
             vout: 2, // Change output
-            amount: 48.0,
+            amount: Amount::from_btc(48.0).unwrap(),
             asset: asset_id.to_string(),
             address: "change_address".to_string(),
             spendable: true,
@@ -1790,7 +1893,7 @@ async fn test_collect_change_data_workflow_integration() {
         Unspent {
             txid: "unrelated_txid".to_string(),
             vout: 0,
-            amount: 25.0,
+            amount: Amount::from_btc(25.0).unwrap(),
             asset: asset_id.to_string(),
             address: "other_address".to_string(),
             spendable: true,
@@ -1824,9 +1927,331 @@ async fn test_collect_change_data_workflow_integration() {
     assert_eq!(change_utxos.len(), 1);
     assert_eq!(change_utxos[0].txid, distribution_txid);
     assert_eq!(change_utxos[0].vout, 2);
-    assert_eq!(change_utxos[0].amount, 48.0);
+    assert_eq!(change_utxos[0].amount, Amount::from_btc(48.0).unwrap());
     assert_eq!(change_utxos[0].address, "change_address");
     assert!(change_utxos[0].spendable);
 
     // This change data would then be used in the distribution confirmation API call
 }
+
+#[tokio::test]
+async fn test_build_distribution_transaction_pays_fee_from_estimated_vsize() {
+    let server = MockServer::start();
+    let asset_id = "6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d";
+
+    // Inputs signal replace-by-fee and the fee is an explicit L-BTC output
+    let create_mock = server.mock(|when, then| {
+        when.method(POST)
+            .body_contains("createrawtransaction")
+            .body_contains("\"sequence\":4294967293")
+            .body_contains("\"fee\":");
+        then.status(200).json_body(json!({
+            "jsonrpc": "1.0",
+            "id": "amp-client",
+            "result": "0200000000",
+            "error": null
+        }));
+    });
+    let utxos = create_mock_utxos(asset_id, vec![150.0]);
+    create_listunspent_mock(&server, "test_wallet", asset_id, utxos);
+
+    let rpc = ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string())
+        .with_network(Network::LiquidTestnet);
+
+    let mut address_amounts = HashMap::new();
+    address_amounts.insert("recipient1".to_string(), Amount::from_btc(60.0).unwrap());
+    address_amounts.insert("recipient2".to_string(), Amount::from_btc(40.0).unwrap());
+
+    let fee_rate = FeeRate::from_sat_per_vb(1);
+    let transaction = rpc
+        .build_distribution_transaction_with_selector(
+            "test_wallet",
+            asset_id,
+            address_amounts,
+            "change_address",
+            FeeStrategy::Rate(fee_rate),
+            &LargestFirst,
+        )
+        .await
+        .unwrap();
+
+    create_mock.assert();
+    // Two recipients, asset change and L-BTC change are blinded
+    assert_eq!(transaction.vsize, amp_rs::fee::estimate_vsize(2, 4));
+    assert_eq!(transaction.fee, fee_rate.fee_for_vsize(transaction.vsize));
+    assert_eq!(transaction.fee_rate, fee_rate);
+    assert_eq!(transaction.fee_inputs.len(), 1);
+    assert_eq!(
+        transaction.fee_change,
+        Amount::from_sat(100_000) - transaction.fee
+    );
+    assert_eq!(transaction.asset_change, Amount::from_btc(50.0).unwrap());
+}
+
+#[tokio::test]
+async fn test_build_distribution_transaction_fails_without_lbtc_for_fee() {
+    let server = MockServer::start();
+    let asset_id = "6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d";
+
+    let utxos = create_mock_utxos(asset_id, vec![150.0]);
+    create_listunspent_mock(&server, "test_wallet", asset_id, utxos);
+
+    let rpc = ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string())
+        .with_network(Network::LiquidTestnet);
+
+    let mut address_amounts = HashMap::new();
+    address_amounts.insert("recipient1".to_string(), Amount::from_btc(100.0).unwrap());
+
+    // 1000 sat/vB needs far more than the 0.001 L-BTC the wallet holds
+    let error = rpc
+        .build_distribution_transaction(
+            "test_wallet",
+            asset_id,
+            address_amounts,
+            "change_address",
+            FeeStrategy::Rate(FeeRate::from_sat_per_vb(1000)),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(error.to_string().contains("Insufficient L-BTC for fees"));
+}
+
+#[tokio::test]
+async fn test_bump_distribution_fee_replaces_with_higher_fee() {
+    let server = MockServer::start();
+    let asset_id = "6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d";
+
+    let utxos = create_mock_utxos(asset_id, vec![150.0]);
+    create_listunspent_mock(&server, "test_wallet", asset_id, utxos);
+
+    let rpc = ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string())
+        .with_network(Network::LiquidTestnet);
+
+    let mut address_amounts = HashMap::new();
+    address_amounts.insert("recipient1".to_string(), Amount::from_btc(100.0).unwrap());
+
+    let original = rpc
+        .build_distribution_transaction_with_selector(
+            "test_wallet",
+            asset_id,
+            address_amounts.clone(),
+            "change_address",
+            FeeStrategy::Rate(FeeRate::MIN_RELAY),
+            &LargestFirst,
+        )
+        .await
+        .unwrap();
+
+    let replacement = rpc
+        .bump_distribution_fee(
+            "test_wallet",
+            asset_id,
+            address_amounts.clone(),
+            "change_address",
+            &original,
+            FeeStrategy::Rate(FeeRate::from_sat_per_vb(2)),
+        )
+        .await
+        .unwrap();
+
+    // The replacement conflicts with the original by spending the same inputs
+    assert_eq!(
+        replacement.asset_inputs[0].txid,
+        original.asset_inputs[0].txid
+    );
+    assert_eq!(replacement.fee_inputs[0].txid, original.fee_inputs[0].txid);
+    assert!(replacement.fee > original.fee);
+    assert_eq!(
+        replacement.fee + replacement.fee_change,
+        original.fee + original.fee_change
+    );
+
+    let error = rpc
+        .bump_distribution_fee(
+            "test_wallet",
+            asset_id,
+            address_amounts,
+            "change_address",
+            &replacement,
+            FeeStrategy::Rate(FeeRate::from_sat_per_vb(1)),
+        )
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("must be higher"));
+}
+
+#[tokio::test]
+async fn test_estimate_fee_rate_uses_estimatesmartfee() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .json_body_partial(r#"{"method": "estimatesmartfee", "params": [2]}"#);
+        then.status(200).json_body(json!({
+            "result": {"feerate": 0.00002, "blocks": 2},
+            "error": null,
+            "id": "amp-client"
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .json_body_partial(r#"{"method": "estimatesmartfee", "params": [6]}"#);
+        then.status(200).json_body(json!({
+            "result": {"errors": ["Insufficient data or no feerate found"], "blocks": 0},
+            "error": null,
+            "id": "amp-client"
+        }));
+    });
+
+    let rpc = ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string());
+
+    assert_eq!(
+        rpc.estimate_fee_rate(FeeStrategy::ConfTarget(2))
+            .await
+            .unwrap(),
+        FeeRate::from_sat_per_vb(2)
+    );
+    // Liquid nodes often have no estimate; fall back to the minimum relay fee rate
+    assert_eq!(
+        rpc.estimate_fee_rate(FeeStrategy::default()).await.unwrap(),
+        FeeRate::MIN_RELAY
+    );
+    assert_eq!(
+        rpc.estimate_fee_rate(FeeStrategy::Rate(FeeRate::from_sat_per_kvb(10)))
+            .await
+            .unwrap(),
+        FeeRate::MIN_RELAY
+    );
+}
+
+fn mock_blockchain_info<'a>(server: &'a MockServer, chain: &str) -> httpmock::Mock<'a> {
+    server.mock(|when, then| {
+        when.method(POST)
+            .json_body_partial(r#"{"method": "getblockchaininfo"}"#);
+        then.status(200).json_body(json!({
+            "result": {
+                "chain": chain,
+                "blocks": 100,
+                "headers": 100,
+                "bestblockhash": "00".repeat(32)
+            },
+            "error": null,
+            "id": "amp-client"
+        }));
+    })
+}
+
+#[tokio::test]
+async fn test_network_detected_from_regtest_node_once() {
+    let server = MockServer::start();
+    let blockchain_info = mock_blockchain_info(&server, "elementsregtest");
+    let policy_asset = "ab".repeat(32);
+    server.mock(|when, then| {
+        when.method(POST)
+            .json_body_partial(r#"{"method": "dumpassetlabels"}"#);
+        then.status(200).json_body(json!({
+            "result": {"bitcoin": policy_asset},
+            "error": null,
+            "id": "amp-client"
+        }));
+    });
+
+    let rpc = ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string());
+
+    let network = rpc.network().await.unwrap();
+    assert_eq!(network, Network::elements_regtest(&policy_asset).unwrap());
+    assert_eq!(network.policy_asset(), policy_asset);
+
+    // The detected network is cached
+    assert_eq!(rpc.network().await.unwrap(), network);
+    blockchain_info.assert_hits(1);
+}
+
+#[tokio::test]
+async fn test_network_configured_explicitly_skips_detection() {
+    let server = MockServer::start();
+    let blockchain_info = mock_blockchain_info(&server, "liquidtestnet");
+
+    let rpc = ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string())
+        .with_network(Network::Liquid);
+
+    assert_eq!(rpc.network().await.unwrap(), Network::Liquid);
+    blockchain_info.assert_hits(0);
+}
+
+#[tokio::test]
+async fn test_network_detection_rejects_unknown_chain() {
+    let server = MockServer::start();
+    mock_blockchain_info(&server, "signet");
+
+    let rpc = ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string());
+
+    let err = rpc.network().await.unwrap_err();
+    assert!(err.to_string().contains("Unknown Elements chain 'signet'"));
+}
+
+#[tokio::test]
+async fn test_sign_transaction_with_utxos_finalizes_external_pset() {
+    use amp_rs::signer::PsetFileSigner;
+    use elements::pset::PartiallySignedTransaction;
+
+    let server = MockServer::start();
+    let finalize = server.mock(|when, then| {
+        when.method(POST)
+            .path("/")
+            .json_body_partial(r#"{"method": "finalizepsbt"}"#);
+        then.status(200).json_body(json!({
+            "result": {"hex": "02000000signed", "complete": true},
+            "error": null,
+            "id": "amp-client"
+        }));
+    });
+    let rpc = ElementsRpc::new(server.url("/"), "user".to_string(), "pass".to_string());
+
+    let asset_id = "144c654344aa716d6f3abcc1ca90e5641e4e2a7f633bc09fe3baf64585819a49";
+    let previous_txid: elements::Txid = "aa".repeat(32).parse().unwrap();
+    let transaction = elements::Transaction {
+        version: 2,
+        lock_time: elements::LockTime::ZERO,
+        input: vec![elements::TxIn {
+            previous_output: elements::OutPoint::new(previous_txid, 0),
+            is_pegin: false,
+            script_sig: elements::Script::new(),
+            sequence: elements::Sequence::ENABLE_RBF_NO_LOCKTIME,
+            asset_issuance: elements::AssetIssuance::default(),
+            witness: elements::TxInWitness::default(),
+        }],
+        output: vec![elements::TxOut::new_fee(500, asset_id.parse().unwrap())],
+    };
+    let unsigned_tx_hex = hex::encode(elements::encode::serialize(&transaction));
+    let mut utxo = create_mock_lbtc_utxo(&previous_txid.to_string(), Amount::from_sat(1_000));
+    utxo.vout = 0;
+    utxo.asset = asset_id.to_string();
+    utxo.scriptpubkey = Some(format!("0014{}", "11".repeat(20)));
+
+    // The external signer receives the previous output and adds a partial signature
+    let (signer, mut requests) = PsetFileSigner::channel(1);
+    tokio::spawn(async move {
+        let request = requests.recv().await.unwrap();
+        let mut pset: PartiallySignedTransaction = request.pset_base64().parse().unwrap();
+        assert!(pset.inputs()[0].witness_utxo.is_some());
+        pset.inputs_mut()[0].final_script_witness = Some(vec![vec![1u8; 71], vec![2u8; 33]]);
+        request.respond(pset.to_string()).unwrap();
+    });
+
+    let signed_tx_hex = rpc
+        .sign_transaction_with_utxos(&unsigned_tx_hex, &[utxo.clone()], &signer)
+        .await
+        .unwrap();
+    assert_eq!(signed_tx_hex, "02000000signed");
+    finalize.assert_hits(1);
+
+    // Signers without PSET support still go through sign_transaction
+    let fallback = MockSigner::new_success();
+    let result = rpc
+        .sign_transaction_with_utxos(&unsigned_tx_hex, &[utxo], &fallback)
+        .await;
+    assert!(result.is_ok());
+    assert_eq!(fallback.call_count(), 1);
+    finalize.assert_hits(1);
+}